@@ -3,9 +3,9 @@ use forge_testsuite::Runner;
 use foundry_evm::executor::EvmError;
 use ismp::{host::StateMachine, router};
 use ismp_solidity_abi::{evm_host::HostParams, shared_types::PostRequest};
-use pallet_ismp_host_executive::EvmHostParamsAbi;
+use pallet_ismp_host_executive::{EvmHostAction, EvmHostParamsAbi, FrozenStatus};
 use pallet_ismp_relayer::withdrawal::WithdrawalParams;
-use primitive_types::{H160, U256};
+use primitive_types::{H160, H256, U256};
 use std::{env, path::PathBuf};
 
 #[tokio::test(flavor = "multi_thread")]
@@ -182,3 +182,89 @@ async fn test_host_manager_set_host_params() -> Result<(), anyhow::Error> {
 
 	Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_host_manager_set_frozen_state() -> Result<(), anyhow::Error> {
+	let base_dir = env::current_dir()?.parent().unwrap().display().to_string();
+	let mut runner = Runner::new(PathBuf::from(&base_dir));
+	let mut contract = runner.deploy("HostManagerTest").await;
+
+	// create post request object
+	let post = router::PostRequest {
+		source: StateMachine::Kusama(2000),
+		dest: StateMachine::Evm(1),
+		nonce: 0,
+		from: contract.runner.sender.as_bytes().to_vec(),
+		to: vec![],
+		timeout_timestamp: 100,
+		body: EvmHostAction::SetFrozenState(FrozenStatus::Outgoing).abi_encode(),
+	};
+
+	let request: PostRequest = post.clone().into();
+
+	// execute the test
+	contract
+		.call::<_, ()>("HostManagerSetFrozenState", (request.into_token(),))
+		.await?;
+
+	// governance can't freeze incoming messages, it would be unable to unfreeze the host
+	for status in [FrozenStatus::Incoming, FrozenStatus::All] {
+		let request: PostRequest = router::PostRequest {
+			body: EvmHostAction::SetFrozenState(status).abi_encode(),
+			..post.clone()
+		}
+		.into();
+		let EvmError::Execution(error) = contract
+			.call::<_, ()>("HostManagerOnAccept", (request.into_token(),))
+			.await
+			.unwrap_err()
+		else {
+			panic!("Call should revert")
+		};
+		assert_eq!(error.reason.as_str(), "InvalidFrozenState()");
+	}
+
+	Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_host_manager_set_consensus_client() -> Result<(), anyhow::Error> {
+	let base_dir = env::current_dir()?.parent().unwrap().display().to_string();
+	let mut runner = Runner::new(PathBuf::from(&base_dir));
+	let mut contract = runner.deploy("HostManagerTest").await;
+	let value = contract.call::<_, HostParams>("hostParamsInternal", ()).await?;
+	let code_hash = contract.call::<_, [u8; 32]>("codeHash", (value.consensus_client,)).await?;
+
+	let action = |code_hash| EvmHostAction::SetConsensusClient {
+		address: H160(value.consensus_client.0),
+		code_hash,
+	};
+	let post = router::PostRequest {
+		source: StateMachine::Kusama(2000),
+		dest: StateMachine::Evm(1),
+		nonce: 0,
+		from: contract.runner.sender.as_bytes().to_vec(),
+		to: vec![],
+		timeout_timestamp: 100,
+		body: action(H256::random()).abi_encode(),
+	};
+
+	// the deployed code must match the supplied code hash
+	let request: PostRequest = post.clone().into();
+	let EvmError::Execution(error) = contract
+		.call::<_, ()>("HostManagerOnAccept", (request.into_token(),))
+		.await
+		.unwrap_err()
+	else {
+		panic!("Call should revert")
+	};
+	assert_eq!(error.reason.as_str(), "CodeHashMismatch()");
+
+	let request: PostRequest =
+		router::PostRequest { body: action(H256(code_hash)).abi_encode(), ..post }.into();
+	contract
+		.call::<_, ()>("HostManagerSetConsensusClient", (request.into_token(),))
+		.await?;
+
+	Ok(())
+}
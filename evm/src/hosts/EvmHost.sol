@@ -854,12 +854,14 @@ abstract contract EvmHost is IIsmpHost, IHostManager, Context {
     }
 
     /**
-     * @dev set the new state of the bridge
+     * @dev set the new state of the bridge. Can also be called by the host manager through cross-chain governance.
      * @param newState new state
      */
     function setFrozenState(FrozenStatus newState) external {
         address caller = _msgSender();
-        if (caller != _hostParams.admin && caller != _hostParams.handler) revert UnauthorizedAction();
+        if (caller != _hostParams.admin && caller != _hostParams.handler && caller != _hostParams.hostManager) {
+            revert UnauthorizedAction();
+        }
 
         _frozen = newState;
 
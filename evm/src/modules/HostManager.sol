@@ -17,12 +17,12 @@ pragma solidity ^0.8.17;
 import {Bytes} from "@polytope-labs/solidity-merkle-trees/trie/Bytes.sol";
 import {PostRequest, PostResponse, GetRequest, GetResponse} from "@polytope-labs/ismp-solidity/Message.sol";
 import {StateMachine} from "@polytope-labs/ismp-solidity/StateMachine.sol";
-import {IIsmpHost} from "@polytope-labs/ismp-solidity/IIsmpHost.sol";
+import {IIsmpHost, FrozenStatus} from "@polytope-labs/ismp-solidity/IIsmpHost.sol";
 import {BaseIsmpModule, IncomingPostRequest, IIsmpModule} from "@polytope-labs/ismp-solidity/IIsmpModule.sol";
 
 import {ERC165} from "openzeppelin/utils/introspection/ERC165.sol";
 
-import {HostParams, IHostManager, WithdrawParams} from "../hosts/EvmHost.sol";
+import {HostParams, IHostManager, WithdrawParams, EvmHost} from "../hosts/EvmHost.sol";
 
 /// Host manager params
 struct HostManagerParams {
@@ -32,19 +32,30 @@ struct HostManagerParams {
     address host;
 }
 
+/// Replaces one of the contracts the host depends on
+struct ContractUpgrade {
+    /// The address of the new contract
+    address implementation;
+    /// The expected code hash of the new contract
+    bytes32 codeHash;
+}
+
 /**
  * @title The ISMP HostManager.
  * @author Polytope Labs (hello@polytope.technology)
  *
- * @notice Allows cross-chain governance actions
- * for updating the ISMP Host parameters or withdrawing bridge revenue.
+ * @notice Allows cross-chain governance actions for updating the ISMP Host parameters,
+ * withdrawing bridge revenue, freezing the host or upgrading its consensus client and handler.
  */
 contract HostManager is BaseIsmpModule, ERC165 {
     using Bytes for bytes;
 
     enum OnAcceptActions {
         Withdraw,
-        SetHostParam
+        SetHostParam,
+        SetFrozenState,
+        SetConsensusClient,
+        SetHandler
     }
 
     HostManagerParams private _params;
@@ -52,6 +63,12 @@ contract HostManager is BaseIsmpModule, ERC165 {
     // @dev Action is unauthorized
     error UnauthorizedAction();
 
+    // @dev The code deployed at the upgraded contract's address does not match the expected code hash
+    error CodeHashMismatch();
+
+    // @dev Freezing incoming messages would also block the request that unfreezes the host
+    error InvalidFrozenState();
+
     // @dev restricts call to the provided `caller`
     modifier restrict(address caller) {
         if (msg.sender != caller) revert UnauthorizedAction();
@@ -99,6 +116,25 @@ contract HostManager is BaseIsmpModule, ERC165 {
         } else if (action == OnAcceptActions.SetHostParam) {
             HostParams memory hostParams = abi.decode(request.body[1:], (HostParams));
             IHostManager(_params.host).updateHostParams(hostParams);
+        } else if (action == OnAcceptActions.SetFrozenState) {
+            FrozenStatus status = abi.decode(request.body[1:], (FrozenStatus));
+            // Only the admin can freeze incoming messages, since governance couldn't reach the host afterwards
+            if (status == FrozenStatus.Incoming || status == FrozenStatus.All) revert InvalidFrozenState();
+            EvmHost(payable(_params.host)).setFrozenState(status);
+        } else if (action == OnAcceptActions.SetConsensusClient) {
+            ContractUpgrade memory upgrade = abi.decode(request.body[1:], (ContractUpgrade));
+            if (upgrade.implementation.codehash != upgrade.codeHash) revert CodeHashMismatch();
+
+            HostParams memory hostParams = EvmHost(payable(_params.host)).hostParams();
+            hostParams.consensusClient = upgrade.implementation;
+            IHostManager(_params.host).updateHostParams(hostParams);
+        } else if (action == OnAcceptActions.SetHandler) {
+            ContractUpgrade memory upgrade = abi.decode(request.body[1:], (ContractUpgrade));
+            if (upgrade.implementation.codehash != upgrade.codeHash) revert CodeHashMismatch();
+
+            HostParams memory hostParams = EvmHost(payable(_params.host)).hostParams();
+            hostParams.handler = upgrade.implementation;
+            IHostManager(_params.host).updateHostParams(hostParams);
         }
     }
 }
@@ -19,7 +19,8 @@ import "forge-std/Test.sol";
 import {BaseTest} from "./BaseTest.sol";
 import {PostRequest} from "@polytope-labs/ismp-solidity/Message.sol";
 import {IncomingPostRequest} from "@polytope-labs/ismp-solidity/IIsmpModule.sol";
-import {HostManagerParams, HostManager} from "../src/modules/HostManager.sol";
+import {FrozenStatus} from "@polytope-labs/ismp-solidity/IIsmpHost.sol";
+import {HostManagerParams, HostManager, ContractUpgrade} from "../src/modules/HostManager.sol";
 import {HostParams, EvmHost} from "../src/hosts/EvmHost.sol";
 
 contract HostManagerTest is BaseTest {
@@ -45,6 +46,24 @@ contract HostManagerTest is BaseTest {
         require(host.hostParams().challengePeriod == params.challengePeriod, "Failed to process request");
     }
 
+    function HostManagerSetFrozenState(PostRequest calldata request) public {
+        vm.startPrank(address(host));
+
+        HostManager(payable(host.hostParams().hostManager)).onAccept(IncomingPostRequest(request, tx.origin));
+        FrozenStatus status = abi.decode(request.body[1:], (FrozenStatus));
+
+        require(host.frozen() == status, "Failed to process request");
+    }
+
+    function HostManagerSetConsensusClient(PostRequest calldata request) public {
+        vm.startPrank(address(host));
+
+        HostManager(payable(host.hostParams().hostManager)).onAccept(IncomingPostRequest(request, tx.origin));
+        ContractUpgrade memory upgrade = abi.decode(request.body[1:], (ContractUpgrade));
+
+        require(host.hostParams().consensusClient == upgrade.implementation, "Failed to process request");
+    }
+
     function testCannotSetInvalidAddresses() public {
         HostParams memory params = host.hostParams();
 
@@ -110,4 +129,8 @@ contract HostManagerTest is BaseTest {
     function hostParamsInternal() public view returns (HostParams memory) {
         return host.hostParams();
     }
+
+    function codeHash(address account) public view returns (bytes32) {
+        return account.codehash;
+    }
 }
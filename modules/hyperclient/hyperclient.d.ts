@@ -28,6 +28,8 @@ interface IConfig {
   hyperbridge: IHyperbridgeConfig;
   // Indexer url
   indexer: string;
+  // configuration objects for any other chains this client should track messages for
+  chains?: IChainConfig[];
 }
 
 interface IChainConfig {
@@ -72,6 +74,40 @@ interface IPostResponse {
   response: string;
   // Timestamp at which this response expires in seconds.
   timeoutTimestamp: bigint;
+  // Height at which this response was emitted on the source
+  height: bigint;
+}
+
+interface IGetRequest {
+  // The source state machine of this request.
+  source: string;
+  // The destination state machine of this request.
+  dest: string;
+  // Module Id of the sending module
+  from: string;
+  // The nonce of this request on the source chain
+  nonce: bigint;
+  // Raw storage keys to be read from the destination chain.
+  keys: string[];
+  // Height at which to read the state of the destination chain.
+  height: bigint;
+  // Timestamp which this request expires in seconds.
+  timeoutTimestamp: bigint;
+  // Height at which this request was emitted on the source
+  txHeight: bigint;
+}
+
+interface IFeeEstimate {
+  // Protocol fee charged by the host on the source chain, denominated in its fee token
+  protocol_fee: `0x{string}`;
+  // Estimated cost of delivering and executing the request on the destination chain,
+  // denominated in the native token of the destination chain
+  execution_cost: `0x{string}`;
+  // The relayer fee to attach to the request, the execution cost quoted in the fee token
+  relayer_fee: `0x{string}`;
+  // The total amount of the fee token needed to dispatch the request, the protocol fee plus
+  // the relayer fee
+  recommended_fee: `0x{string}`;
 }
 
 type MessageStatus =
@@ -274,6 +310,49 @@ export class HyperClient {
   timeout_post_request(
     request: IPostRequest,
   ): Promise<ReadableStream<TimeoutStatusWithMeta>>;
+  /**
+   * Return the status of a post response as a `ReadableStream` that yields
+   * `MessageStatusWithMeta`
+   * @param {IPostResponse} response
+   * @returns {Promise<ReadableStream<MessageStatusWithMeta>>}
+   */
+  response_status_stream(
+    response: IPostResponse,
+  ): Promise<ReadableStream<MessageStatusWithMeta>>;
+  /**
+   * Given a post response that has timed out returns a `ReadableStream` that yields a
+   * `TimeoutStatus` This function will not check if the response has timed out, only call it
+   * when you receive a `MesssageStatus::TimeOut` from `query_response_status` or
+   * `response_status_stream`. The stream ends when once it yields a `TimeoutMessage`
+   * @param {IPostResponse} response
+   * @returns {Promise<ReadableStream<TimeoutStatusWithMeta>>}
+   */
+  timeout_post_response(
+    response: IPostResponse,
+  ): Promise<ReadableStream<TimeoutStatusWithMeta>>;
+  /**
+   * Estimates the fees to attach to a post request and returns a `FeeEstimate`. The protocol
+   * fee, relayer fee and recommended fee are denominated in the fee token of the source chain,
+   * while the execution cost is denominated in the native token of the destination chain.
+   * @param {IPostRequest} request
+   * @returns {Promise<IFeeEstimate>}
+   */
+  estimate_fee(request: IPostRequest): Promise<IFeeEstimate>;
+  /**
+   * Queries the status of a get request and returns `MessageStatusWithMetadata`
+   * @param {IGetRequest} request
+   * @returns {Promise<MessageStatus>}
+   */
+  query_get_request_status(request: IGetRequest): Promise<MessageStatus>;
+  /**
+   * Return the status of a get request as a `ReadableStream` that yields
+   * `MessageStatusWithMeta`
+   * @param {IGetRequest} request
+   * @returns {Promise<ReadableStream<MessageStatusWithMeta>>}
+   */
+  get_request_status_stream(
+    request: IGetRequest,
+  ): Promise<ReadableStream<MessageStatusWithMeta>>;
   /**
    * @returns {string | undefined}
    */
@@ -169,6 +169,50 @@ impl Client for AnyClient {
 		}
 	}
 
+	async fn post_response_handled_stream(
+		&self,
+		commitment: sp_core::H256,
+		initial_height: u64,
+	) -> Result<
+		crate::types::BoxStream<
+			crate::providers::interface::WithMetadata<
+				ismp_solidity_abi::evm_host::PostResponseHandledFilter,
+			>,
+		>,
+		anyhow::Error,
+	> {
+		match self {
+			AnyClient::Evm(inner) =>
+				inner.post_response_handled_stream(commitment, initial_height).await,
+			AnyClient::BlakeSubstrateChain(inner) =>
+				inner.post_response_handled_stream(commitment, initial_height).await,
+			AnyClient::KeccakSubstrateChain(inner) =>
+				inner.post_response_handled_stream(commitment, initial_height).await,
+		}
+	}
+
+	async fn get_request_handled_stream(
+		&self,
+		commitment: sp_core::H256,
+		initial_height: u64,
+	) -> Result<
+		crate::types::BoxStream<
+			crate::providers::interface::WithMetadata<
+				ismp_solidity_abi::evm_host::GetRequestHandledFilter,
+			>,
+		>,
+		anyhow::Error,
+	> {
+		match self {
+			AnyClient::Evm(inner) =>
+				inner.get_request_handled_stream(commitment, initial_height).await,
+			AnyClient::BlakeSubstrateChain(inner) =>
+				inner.get_request_handled_stream(commitment, initial_height).await,
+			AnyClient::KeccakSubstrateChain(inner) =>
+				inner.get_request_handled_stream(commitment, initial_height).await,
+		}
+	}
+
 	async fn query_latest_state_machine_height(
 		&self,
 		state_machine: ismp::consensus::StateMachineId,
@@ -288,4 +332,36 @@ impl Client for AnyClient {
 			AnyClient::KeccakSubstrateChain(inner) => inner.query_challenge_period(id).await,
 		}
 	}
+
+	async fn estimate_request_execution_cost(
+		&self,
+		post: ismp::router::PostRequest,
+	) -> Result<primitive_types::U256, anyhow::Error> {
+		match self {
+			AnyClient::Evm(inner) => inner.estimate_request_execution_cost(post).await,
+			AnyClient::BlakeSubstrateChain(inner) =>
+				inner.estimate_request_execution_cost(post).await,
+			AnyClient::KeccakSubstrateChain(inner) =>
+				inner.estimate_request_execution_cost(post).await,
+		}
+	}
+
+	async fn quote_fee_token(
+		&self,
+		amount: primitive_types::U256,
+	) -> Result<primitive_types::U256, anyhow::Error> {
+		match self {
+			AnyClient::Evm(inner) => inner.quote_fee_token(amount).await,
+			AnyClient::BlakeSubstrateChain(inner) => inner.quote_fee_token(amount).await,
+			AnyClient::KeccakSubstrateChain(inner) => inner.quote_fee_token(amount).await,
+		}
+	}
+
+	async fn fee_token_decimals(&self) -> Result<u8, anyhow::Error> {
+		match self {
+			AnyClient::Evm(inner) => inner.fee_token_decimals().await,
+			AnyClient::BlakeSubstrateChain(inner) => inner.fee_token_decimals().await,
+			AnyClient::KeccakSubstrateChain(inner) => inner.fee_token_decimals().await,
+		}
+	}
 }
@@ -0,0 +1,129 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command line front-end for the hyperclient
+
+use anyhow::anyhow;
+use clap::{Args, Parser, Subcommand};
+use futures::StreamExt;
+use hyperclient::{
+	interfaces::JsClientConfig,
+	providers::interface::Client,
+	types::{ClientConfig, MessageStatusWithMetadata, TimeoutStatus},
+	HyperClient,
+};
+use ismp::host::StateMachine;
+use sp_core::H256;
+
+/// Tracks ISMP requests through hyperbridge.
+#[derive(Parser, Debug)]
+#[command(name = "hyperclient")]
+struct Cli {
+	/// Path to a json config file, this uses the same format as the javascript `IConfig`
+	#[arg(short, long)]
+	config: String,
+
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Track a post request from its source chain to its destination, printing every status
+	/// transition. If the request times out, the calldata for the timeout message is printed.
+	Track(TrackCmd),
+}
+
+#[derive(Args, Debug)]
+struct TrackCmd {
+	/// The source chain of the request, eg `EVM-97`
+	#[arg(long)]
+	source: StateMachine,
+
+	/// Commitment of the post request
+	#[arg(long)]
+	commitment: H256,
+
+	/// Block on the source chain to start searching for the request from
+	#[arg(long)]
+	from_block: u64,
+
+	/// Block on the source chain to stop searching for the request at, defaults to the latest
+	/// block
+	#[arg(long)]
+	to_block: Option<u64>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+	let cli = Cli::parse();
+	let config = std::fs::read_to_string(&cli.config)?;
+	let config: ClientConfig = json::from_str::<JsClientConfig>(&config)?.try_into()?;
+	let client = HyperClient::new(config).await?;
+
+	match cli.command {
+		Command::Track(cmd) => cmd.run(&client).await,
+	}
+}
+
+impl TrackCmd {
+	async fn run(&self, client: &HyperClient) -> Result<(), anyhow::Error> {
+		let to_block = match self.to_block {
+			Some(height) => height,
+			None => client.client_for(self.source).await?.query_latest_block_height().await?,
+		};
+		let (post, height) = client
+			.find_post_request(self.source, self.commitment, self.from_block..=to_block)
+			.await?
+			.ok_or_else(|| {
+				anyhow!(
+					"Request {:?} not found in blocks {}..={to_block}",
+					self.commitment,
+					self.from_block
+				)
+			})?;
+		println!("Found request {:?} at height {height}", self.commitment);
+
+		let mut stream = client.post_request_status_stream(post.clone(), height).await?;
+		let mut timed_out = false;
+		while let Some(status) = stream.next().await {
+			let status = status?;
+			println!("{}", json::to_string(&status)?);
+			match status {
+				MessageStatusWithMetadata::DestinationDelivered { .. } => break,
+				MessageStatusWithMetadata::Timeout => {
+					timed_out = true;
+					break;
+				},
+				_ => {},
+			}
+		}
+
+		if !timed_out {
+			return Ok(());
+		}
+
+		let mut stream = client.post_request_timeout_stream(post).await?;
+		while let Some(status) = stream.next().await {
+			let status = status?;
+			println!("{}", json::to_string(&status)?);
+			if let TimeoutStatus::TimeoutMessage { .. } = status {
+				break;
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -266,7 +266,7 @@ pub async fn query_request_status_from_indexer(
 
 						let calldata = match request {
 							Request::Post(post) => {
-								let dest_client = hyperclient.dest.clone();
+								let dest_client = hyperclient.client_for(post.dest).await?;
 								let hyperbridge = hyperclient.hyperbridge.clone();
 								encode_request_call_data(
 									&hyperbridge,
@@ -411,7 +411,7 @@ pub async fn query_response_status_from_indexer(
 
 						let calldata = match response {
 							Response::Post(post) => {
-								let dest_client = hyperclient.dest.clone();
+								let dest_client = hyperclient.client_for(post.dest_chain()).await?;
 								let hyperbridge = &hyperclient.hyperbridge;
 								encode_response_call_data(
 									hyperbridge,
@@ -14,11 +14,12 @@
 // limitations under the License.
 
 use crate::types::{ChainConfig, ClientConfig, EvmConfig, HashAlgorithm, SubstrateConfig};
+use alloc::collections::BTreeMap;
 use anyhow::anyhow;
 use core::str::FromStr;
 use ismp::{
 	host::StateMachine,
-	router::{PostRequest, PostResponse},
+	router::{GetRequest, PostRequest, PostResponse},
 };
 use primitive_types::H160;
 use serde::{Deserialize, Serialize};
@@ -43,6 +44,9 @@ pub struct JsClientConfig {
 	pub dest: JsChainConfig,
 	pub hyperbridge: JsHyperbridgeConfig,
 	pub indexer: String,
+	/// Any additional chains this client should be able to track messages for
+	#[serde(default)]
+	pub chains: Vec<JsChainConfig>,
 }
 
 impl TryFrom<JsClientConfig> for ClientConfig {
@@ -50,11 +54,12 @@ impl TryFrom<JsClientConfig> for ClientConfig {
 
 	fn try_from(value: JsClientConfig) -> Result<Self, Self::Error> {
 		let to_config = |val: &JsChainConfig| {
+			let state_machine =
+				StateMachine::from_str(&val.state_machine).map_err(|e| anyhow!("{e:?}"))?;
 			if !val.host_address.is_empty() {
 				let conf = EvmConfig {
 					rpc_url: val.rpc_url.clone(),
-					state_machine: StateMachine::from_str(&val.state_machine)
-						.map_err(|e| anyhow!("{e:?}"))?,
+					state_machine,
 					host_address: {
 						let address = from_hex(&val.host_address)?;
 						if address.len() != 20 {
@@ -72,7 +77,7 @@ impl TryFrom<JsClientConfig> for ClientConfig {
 					},
 				};
 
-				Ok::<_, anyhow::Error>(ChainConfig::Evm(conf))
+				Ok::<_, anyhow::Error>((state_machine, ChainConfig::Evm(conf)))
 			} else {
 				let conf = SubstrateConfig {
 					rpc_url: val.rpc_url.clone(),
@@ -87,7 +92,7 @@ impl TryFrom<JsClientConfig> for ClientConfig {
 					hash_algo: HashAlgorithm::Keccak,
 				};
 
-				Ok(ChainConfig::Substrate(conf))
+				Ok((state_machine, ChainConfig::Substrate(conf)))
 			}
 		};
 
@@ -107,11 +112,14 @@ impl TryFrom<JsClientConfig> for ClientConfig {
 			Ok::<ChainConfig, Self::Error>(ChainConfig::Substrate(conf))
 		};
 
-		let source_config = to_config(&value.source)?;
-		let dest_config = to_config(&value.dest)?;
+		let chains = [&value.source, &value.dest]
+			.into_iter()
+			.chain(value.chains.iter())
+			.map(to_config)
+			.collect::<Result<BTreeMap<_, _>, _>>()?;
 		let hyperbridge = to_hyperbridge_config(&value.hyperbridge)?;
 
-		Ok(ClientConfig { source: source_config, dest: dest_config, hyperbridge, indexer })
+		Ok(ClientConfig { chains, hyperbridge, indexer })
 	}
 }
 
@@ -177,6 +185,9 @@ pub struct JsPostResponse {
 	/// Timestamp at which this response expires in seconds.
 	#[serde(rename = "timeoutTimestamp")]
 	pub timeout_timestamp: u64,
+	/// Height at which this response was emitted on the source chain
+	#[serde(default)]
+	pub height: u64,
 }
 
 impl TryFrom<JsPostResponse> for PostResponse {
@@ -193,18 +204,74 @@ impl TryFrom<JsPostResponse> for PostResponse {
 	}
 }
 
+#[derive(Clone, Eq, PartialEq, Default, Deserialize, Serialize)]
+pub struct JsGet {
+	/// The source state machine of this request.
+	pub source: String,
+	/// The destination state machine of this request.
+	pub dest: String,
+	/// The nonce of this request on the source chain
+	pub nonce: u64,
+	/// Module Id of the sending module
+	pub from: String,
+	/// Raw storage keys to be read from the destination chain
+	pub keys: Vec<String>,
+	/// Height at which to read the state of the destination chain
+	pub height: u64,
+	/// Timestamp which this request expires in seconds.
+	#[serde(rename = "timeoutTimestamp")]
+	pub timeout_timestamp: u64,
+	/// Height at which this request was emitted on the source chain
+	#[serde(rename = "txHeight")]
+	pub tx_height: u64,
+}
+
+impl TryFrom<JsGet> for GetRequest {
+	type Error = anyhow::Error;
+
+	fn try_from(value: JsGet) -> Result<Self, Self::Error> {
+		let source = if value.source.starts_with("0x") {
+			let string = String::from_utf8(from_hex(&value.source)?)?;
+			StateMachine::from_str(&string).map_err(|e| anyhow!("{e:?}"))?
+		} else {
+			StateMachine::from_str(&value.source).map_err(|e| anyhow!("{e:?}"))?
+		};
+
+		let dest = if value.dest.starts_with("0x") {
+			let string = String::from_utf8(from_hex(&value.dest)?)?;
+			StateMachine::from_str(&string).map_err(|e| anyhow!("{e:?}"))?
+		} else {
+			StateMachine::from_str(&value.dest).map_err(|e| anyhow!("{e:?}"))?
+		};
+
+		let get = GetRequest {
+			source,
+			dest,
+			nonce: value.nonce,
+			from: from_hex(&value.from)?,
+			keys: value.keys.iter().map(|key| from_hex(key)).collect::<Result<_, _>>()?,
+			height: value.height,
+			timeout_timestamp: value.timeout_timestamp,
+		};
+		Ok(get)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
-		interfaces::{JsChainConfig, JsClientConfig, JsHyperbridgeConfig, JsPost, JsPostResponse},
+		interfaces::{
+			JsChainConfig, JsClientConfig, JsGet, JsHyperbridgeConfig, JsPost, JsPostResponse,
+		},
 		types::{ChainConfig, ClientConfig, EvmConfig, HashAlgorithm, SubstrateConfig},
 	};
 	use ethers::prelude::H160;
 	use hex_literal::hex;
 	use ismp::{
 		host::StateMachine,
-		router::{PostRequest, PostResponse},
+		router::{GetRequest, PostRequest, PostResponse},
 	};
+	use std::collections::BTreeMap;
 	const OP_HOST: H160 = H160(hex!("1B58A47e61Ca7604b634CBB00b4e275cCd7c9E95"));
 	const BSC_HOST: H160 = H160(hex!("022DDE07A21d8c553978b006D93CDe68ac83e677"));
 
@@ -230,8 +297,10 @@ mod tests {
 			hash_algo: HashAlgorithm::Keccak,
 		};
 		let config = ClientConfig {
-			source: ChainConfig::Evm(source_chain.clone()),
-			dest: ChainConfig::Evm(dest_chain.clone()),
+			chains: BTreeMap::from([
+				(source_chain.state_machine, ChainConfig::Evm(source_chain.clone())),
+				(dest_chain.state_machine, ChainConfig::Evm(dest_chain.clone())),
+			]),
 			hyperbridge: ChainConfig::Substrate(hyperbrige_config),
 			indexer: Some("http://localhost:3000/".to_string()),
 		};
@@ -257,6 +326,7 @@ mod tests {
 			dest: js_dest,
 			hyperbridge: js_hyperbridge,
 			indexer: "http://localhost:3000/".to_string(),
+			chains: vec![],
 		};
 
 		assert_eq!(config, js_client_conf.try_into().unwrap());
@@ -291,8 +361,35 @@ mod tests {
 			},
 			response: vec![80; 256],
 			timeout_timestamp: 4_500_000,
+			height: 0,
 		};
 
 		assert_eq!(post_response, js_post_response.try_into().unwrap())
 	}
+
+	#[test]
+	fn test_get_conversion() {
+		let get = GetRequest {
+			source: StateMachine::Evm(97),
+			dest: StateMachine::Kusama(2000),
+			nonce: 100,
+			from: vec![30; 20],
+			keys: vec![vec![20; 32], vec![40; 52]],
+			height: 1_000,
+			timeout_timestamp: 1_600_000,
+		};
+
+		let js_get = JsGet {
+			source: "EVM-97".to_string(),
+			dest: "KUSAMA-2000".to_string(),
+			nonce: 100,
+			from: hex::encode(vec![30; 20]),
+			keys: vec![hex::encode(vec![20; 32]), hex::encode(vec![40; 52])],
+			height: 1_000,
+			timeout_timestamp: 1_600_000,
+			tx_height: 0,
+		};
+
+		assert_eq!(get, js_get.try_into().unwrap())
+	}
 }
@@ -21,16 +21,23 @@ use crate::{
 		interface::{wait_for_challenge_period, Client, Query, RequestOrResponse},
 		substrate::SubstrateClient,
 	},
-	types::{BoxStream, MessageStatusWithMetadata, PostStreamState, TimeoutStatus},
+	types::{
+		BoxStream, EventMetadata, FeeEstimate, GetStreamState, MessageStatusWithMetadata,
+		PostStreamState, TimeoutStatus,
+	},
 	HyperClient, Keccak256,
 };
 use anyhow::anyhow;
-use ethers::prelude::H160;
+use ethers::prelude::{H160, U256};
 use futures::{stream, StreamExt};
 use ismp::{
 	consensus::StateMachineHeight,
-	messaging::{hash_request, Message, Proof, RequestMessage, ResponseMessage, TimeoutMessage},
-	router::{PostRequest, PostResponse, Request, Response},
+	host::StateMachine,
+	messaging::{
+		hash_request, hash_response, Message, Proof, RequestMessage, ResponseMessage,
+		TimeoutMessage,
+	},
+	router::{GetRequest, PostRequest, PostResponse, Request, Response},
 };
 use sp_core::H256;
 use subxt_utils::Hyperbridge;
@@ -45,13 +52,7 @@ pub async fn query_request_status_internal(
 	client: &HyperClient,
 	post: PostRequest,
 ) -> Result<MessageStatusWithMetadata, anyhow::Error> {
-	let dest_client = if post.dest == client.dest.state_machine_id().state_id {
-		&client.dest
-	} else if post.dest == client.source.state_machine_id().state_id {
-		&client.source
-	} else {
-		Err(anyhow!("Unknown client for {}", post.source))?
-	};
+	let dest_client = client.client_for(post.dest).await?;
 	let destination_current_timestamp = dest_client.query_timestamp().await?;
 	let req = Request::Post(post.clone());
 	let hash = hash_request::<Keccak256>(&req);
@@ -94,14 +95,7 @@ pub async fn query_response_status_internal(
 	hyperclient: &HyperClient,
 	post_response: PostResponse,
 ) -> Result<MessageStatusWithMetadata, anyhow::Error> {
-	let dest_client = if post_response.dest_chain() == hyperclient.dest.state_machine_id().state_id
-	{
-		&hyperclient.dest
-	} else if post_response.dest_chain() == hyperclient.source.state_machine_id().state_id {
-		&hyperclient.source
-	} else {
-		Err(anyhow!("Unknown client for {}", post_response.dest_chain()))?
-	};
+	let dest_client = hyperclient.client_for(post_response.dest_chain()).await?;
 	let response_destination_timeout = dest_client.query_timestamp().await?;
 	let res = Response::Post(post_response.clone());
 	let req_hash = hash_request::<Keccak256>(&res.request());
@@ -139,6 +133,83 @@ pub async fn query_response_status_internal(
 	Ok(MessageStatusWithMetadata::Pending)
 }
 
+/// `query_get_request_status_internal` returns the status of a get request. Get requests are
+/// handled by the state coprocessor on hyperbridge and their responses are delivered back to the
+/// source chain.
+pub async fn query_get_request_status_internal(
+	client: &HyperClient,
+	get: GetRequest,
+) -> Result<MessageStatusWithMetadata, anyhow::Error> {
+	let source_client = client.client_for(get.source).await?;
+	let source_current_timestamp = source_client.query_timestamp().await?;
+	let hash = hash_request::<Keccak256>(&Request::Get(get.clone()));
+	let relayer_address = source_client.query_response_receipt(hash).await?;
+	if relayer_address != H160::zero() {
+		// The get response has been delivered back to the source chain
+		return Ok(MessageStatusWithMetadata::DestinationDelivered { meta: Default::default() });
+	}
+
+	// Get requests can only be timed out on the source chain
+	if source_current_timestamp.as_secs() >= get.timeout().as_secs() {
+		return Ok(MessageStatusWithMetadata::Timeout);
+	}
+
+	let relayer = client.hyperbridge.query_request_receipt(hash).await?;
+	if relayer != H160::zero() {
+		// The request has been handled by the state coprocessor
+		return Ok(MessageStatusWithMetadata::HyperbridgeDelivered { meta: Default::default() });
+	}
+
+	Ok(MessageStatusWithMetadata::Pending)
+}
+
+/// `estimate_fee_internal` estimates the fees to attach to a post request. The protocol fee is
+/// queried from hyperbridge, which tracks the host parameters of all connected chains, while the
+/// execution cost is estimated on the destination chain and converted to the decimals of the
+/// source chain's fee token.
+pub async fn estimate_fee_internal(
+	client: &HyperClient,
+	post: PostRequest,
+) -> Result<FeeEstimate, anyhow::Error> {
+	let source_client = client.client_for(post.source).await?;
+	let dest_client = client.client_for(post.dest).await?;
+	let protocol_fee = client.hyperbridge.query_protocol_fee(post.clone()).await?;
+	let execution_cost = dest_client.estimate_request_execution_cost(post).await?;
+	let relayer_fee = convert_decimals(
+		dest_client.quote_fee_token(execution_cost).await?,
+		dest_client.fee_token_decimals().await?,
+		source_client.fee_token_decimals().await?,
+	);
+
+	Ok(FeeEstimate {
+		protocol_fee,
+		execution_cost,
+		relayer_fee,
+		recommended_fee: protocol_fee + relayer_fee,
+	})
+}
+
+/// Converts an amount of a token with `from` decimals to the same value in a token with `to`
+/// decimals, rounding up so that fees are never underestimated.
+pub fn convert_decimals(amount: U256, from: u8, to: u8) -> U256 {
+	let ten = U256::from(10u8);
+	if to >= from {
+		ten.checked_pow((to - from).into())
+			.and_then(|factor| amount.checked_mul(factor))
+			.unwrap_or(U256::MAX)
+	} else {
+		let Some(divisor) = ten.checked_pow((from - to).into()) else {
+			return U256::from(!amount.is_zero() as u8)
+		};
+		let (quotient, remainder) = amount.div_mod(divisor);
+		if remainder.is_zero() {
+			quotient
+		} else {
+			quotient + 1
+		}
+	}
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TimeoutStreamState {
 	Pending,
@@ -152,6 +223,216 @@ pub enum TimeoutStreamState {
 	End,
 }
 
+/// A post request or a post response. Both are delivered to their destination through
+/// hyperbridge and are timed out in the same way, so their status and timeout streams share a
+/// single implementation.
+#[derive(Clone)]
+enum PostMessage {
+	Request(PostRequest),
+	Response(PostResponse),
+}
+
+impl PostMessage {
+	/// The chain this message was dispatched from
+	fn source(&self) -> StateMachine {
+		match self {
+			PostMessage::Request(post) => post.source,
+			PostMessage::Response(response) => response.source_chain(),
+		}
+	}
+
+	/// The chain this message is delivered to
+	fn dest(&self) -> StateMachine {
+		match self {
+			PostMessage::Request(post) => post.dest,
+			PostMessage::Response(response) => response.dest_chain(),
+		}
+	}
+
+	fn timeout_timestamp(&self) -> u64 {
+		match self {
+			PostMessage::Request(post) => post.timeout_timestamp,
+			PostMessage::Response(response) => response.timeout_timestamp,
+		}
+	}
+
+	/// Receipts for both requests and responses are keyed by the request commitment
+	fn receipt_key(&self) -> H256 {
+		match self {
+			PostMessage::Request(post) => hash_request::<Keccak256>(&Request::Post(post.clone())),
+			PostMessage::Response(response) => hash_request::<Keccak256>(&response.request()),
+		}
+	}
+
+	/// The commitment of the message itself
+	fn commitment(&self) -> H256 {
+		match self {
+			PostMessage::Request(post) => hash_request::<Keccak256>(&Request::Post(post.clone())),
+			PostMessage::Response(response) =>
+				hash_response::<Keccak256>(&Response::Post(response.clone())),
+		}
+	}
+
+	/// Query the relayer that delivered this message on the given chain
+	async fn query_receipt<C: Client>(&self, client: &C) -> Result<H160, anyhow::Error> {
+		match self {
+			PostMessage::Request(_) => client.query_request_receipt(self.receipt_key()).await,
+			PostMessage::Response(_) => client.query_response_receipt(self.receipt_key()).await,
+		}
+	}
+
+	/// Storage key of the receipt of this message on the given chain
+	fn receipt_full_key<C: Client>(&self, client: &C) -> Vec<u8> {
+		match self {
+			PostMessage::Request(_) => client.request_receipt_full_key(self.receipt_key()),
+			PostMessage::Response(_) => client.response_receipt_full_key(self.receipt_key()),
+		}
+	}
+
+	async fn query_status_from_indexer(
+		&self,
+		hyperclient: &HyperClient,
+	) -> Option<MessageStatusWithMetadata> {
+		let status = match self {
+			PostMessage::Request(post) =>
+				query_request_status_from_indexer(Request::Post(post.clone()), hyperclient).await,
+			PostMessage::Response(response) =>
+				query_response_status_from_indexer(Response::Post(response.clone()), hyperclient)
+					.await,
+		};
+
+		status.ok().flatten()
+	}
+
+	fn as_item(&self) -> RequestOrResponse {
+		match self {
+			PostMessage::Request(post) => RequestOrResponse::Request(post.clone()),
+			PostMessage::Response(response) => RequestOrResponse::Response(response.clone()),
+		}
+	}
+
+	/// Returns true if the event is the dispatch of this message
+	fn is_dispatched_by(&self, event: &Event) -> bool {
+		match (self, event) {
+			(PostMessage::Request(post), Event::PostRequest(dispatched)) =>
+				post.source == dispatched.source && post.nonce == dispatched.nonce,
+			(PostMessage::Response(response), Event::PostResponse(dispatched)) =>
+				response == dispatched,
+			_ => false,
+		}
+	}
+
+	/// Returns true if the event is the delivery of this message
+	fn is_handled_by(&self, event: &Event) -> bool {
+		match (self, event) {
+			(PostMessage::Request(_), Event::PostRequestHandled(handled)) |
+			(PostMessage::Response(_), Event::PostResponseHandled(handled)) =>
+				handled.commitment == self.commitment(),
+			_ => false,
+		}
+	}
+
+	/// Stream of the deliveries of this message on its destination
+	async fn handled_stream(
+		&self,
+		dest_client: &AnyClient,
+		initial_height: u64,
+	) -> Result<BoxStream<EventMetadata>, anyhow::Error> {
+		let commitment = self.commitment();
+		let stream: BoxStream<EventMetadata> = match self {
+			PostMessage::Request(_) => Box::pin(
+				dest_client
+					.post_request_handled_stream(commitment, initial_height)
+					.await?
+					.map(|event| event.map(|event| event.meta)),
+			),
+			PostMessage::Response(_) => Box::pin(
+				dest_client
+					.post_response_handled_stream(commitment, initial_height)
+					.await?
+					.map(|event| event.map(|event| event.meta)),
+			),
+		};
+
+		Ok(stream)
+	}
+
+	/// Encodes the calldata for delivering this message to its destination, once the challenge
+	/// period for the hyperbridge height has elapsed
+	async fn encode_message_and_wait_for_challenge_period(
+		&self,
+		hyperbridge: &SubstrateClient<Hyperbridge>,
+		dest_client: &AnyClient,
+		height: u64,
+	) -> Result<Vec<u8>, anyhow::Error> {
+		match self {
+			PostMessage::Request(post) =>
+				encode_request_message_and_wait_for_challenge_period(
+					hyperbridge,
+					dest_client,
+					post.clone(),
+					self.commitment(),
+					height,
+				)
+				.await,
+			PostMessage::Response(response) =>
+				encode_response_message_and_wait_for_challenge_period(
+					hyperbridge,
+					dest_client,
+					response.clone(),
+					self.commitment(),
+					height,
+				)
+				.await,
+		}
+	}
+
+	fn timeout_message(&self, timeout_proof: Proof) -> Message {
+		match self {
+			PostMessage::Request(post) => Message::Timeout(TimeoutMessage::Post {
+				requests: vec![Request::Post(post.clone())],
+				timeout_proof,
+			}),
+			PostMessage::Response(response) => Message::Timeout(TimeoutMessage::PostResponse {
+				responses: vec![response.clone()],
+				timeout_proof,
+			}),
+		}
+	}
+}
+
+/// Position of a state in the lifecycle of a post request or response
+fn post_stream_progress(state: PostStreamState) -> u8 {
+	match state {
+		PostStreamState::Pending => 0,
+		PostStreamState::SourceFinalized(_) => 1,
+		PostStreamState::HyperbridgeDelivered(_) => 2,
+		PostStreamState::HyperbridgeFinalized(_) => 3,
+		PostStreamState::DestinationDelivered | PostStreamState::End => 4,
+	}
+}
+
+/// Returns the state to advance the stream to, if the indexer has already seen the message go
+/// past the current state
+fn indexed_stream_state(
+	status: &MessageStatusWithMetadata,
+	current: PostStreamState,
+) -> Option<PostStreamState> {
+	let next = match status {
+		MessageStatusWithMetadata::SourceFinalized { finalized_height, .. } =>
+			PostStreamState::SourceFinalized(*finalized_height),
+		MessageStatusWithMetadata::HyperbridgeDelivered { meta } =>
+			PostStreamState::HyperbridgeDelivered(meta.block_number),
+		MessageStatusWithMetadata::HyperbridgeFinalized { finalized_height, .. } =>
+			PostStreamState::HyperbridgeFinalized(*finalized_height),
+		MessageStatusWithMetadata::DestinationDelivered { .. } =>
+			PostStreamState::DestinationDelivered,
+		_ => return None,
+	};
+
+	(post_stream_progress(next) > post_stream_progress(current)).then_some(next)
+}
+
 /// Handles the timeout process internally and yields the encoded transaction data to be submitted
 /// to the source chain This future does not check the request timeout status, only call it after
 /// you have confirmed the request timeout status using `query_request_status`
@@ -159,34 +440,41 @@ pub async fn timeout_request_stream(
 	hyperclient: &HyperClient,
 	post: PostRequest,
 ) -> Result<BoxStream<TimeoutStatus>, anyhow::Error> {
-	let source_client = if post.source == hyperclient.dest.state_machine_id().state_id {
-		hyperclient.dest.clone()
-	} else if post.source == hyperclient.source.state_machine_id().state_id {
-		hyperclient.source.clone()
-	} else {
-		Err(anyhow!("Unknown client for source: {}", post.source))?
-	};
-	let dest_client = if post.dest == hyperclient.dest.state_machine_id().state_id {
-		hyperclient.dest.clone()
-	} else if post.dest == hyperclient.source.state_machine_id().state_id {
-		hyperclient.source.clone()
-	} else {
-		Err(anyhow!("Unknown client for dest: {}", post.dest))?
-	};
+	timeout_stream(hyperclient, PostMessage::Request(post)).await
+}
+
+/// Handles the timeout process for a post response and yields the encoded transaction data to be
+/// submitted to the source chain of the response. This future does not check the response timeout
+/// status, only call it after you have confirmed the response timeout status using
+/// `query_response_status`
+pub async fn timeout_response_stream(
+	hyperclient: &HyperClient,
+	response: PostResponse,
+) -> Result<BoxStream<TimeoutStatus>, anyhow::Error> {
+	timeout_stream(hyperclient, PostMessage::Response(response)).await
+}
+
+/// Proves the non-delivery of a timed out post request or response on its destination to
+/// hyperbridge, and then proves the timeout on hyperbridge to the source chain.
+async fn timeout_stream(
+	hyperclient: &HyperClient,
+	message: PostMessage,
+) -> Result<BoxStream<TimeoutStatus>, anyhow::Error> {
+	let source_client = hyperclient.client_for(message.source()).await?;
+	let dest_client = hyperclient.client_for(message.dest()).await?;
 	let hyperbridge_client = hyperclient.hyperbridge.clone();
 
 	let stream = stream::unfold(TimeoutStreamState::Pending, move |state| {
 		let dest_client = dest_client.clone();
 		let hyperbridge_client = hyperbridge_client.clone();
 		let source_client = source_client.clone();
-		let req = Request::Post(post.clone());
-		let hash = hash_request::<Keccak256>(&req);
+		let message = message.clone();
 
 		async move {
 			let lambda = || async {
 				match state {
 					TimeoutStreamState::Pending => {
-						let relayer = hyperbridge_client.query_request_receipt(hash).await?;
+						let relayer = message.query_receipt(&hyperbridge_client).await?;
 						if relayer != H160::zero() {
 							let height = hyperbridge_client
 								.query_latest_state_machine_height(dest_client.state_machine_id())
@@ -199,7 +487,7 @@ pub async fn timeout_request_stream(
 								})
 								.await?;
 
-							if state_commitment.timestamp > post.timeout_timestamp {
+							if state_commitment.timestamp > message.timeout_timestamp() {
 								// early return if the destination has already finalized the height
 								return Ok(Some((
 									Ok(TimeoutStatus::DestinationFinalized {
@@ -223,7 +511,7 @@ pub async fn timeout_request_stream(
 										let commitment = hyperbridge_client
 											.query_state_machine_commitment(state_machine_height)
 											.await?;
-										if commitment.timestamp > post.timeout_timestamp {
+										if commitment.timestamp > message.timeout_timestamp() {
 											valid_proof_height = Some(ev);
 											break;
 										}
@@ -254,17 +542,14 @@ pub async fn timeout_request_stream(
 						}
 					},
 					TimeoutStreamState::DestinationFinalized(proof_height) => {
-						let storage_key = dest_client.request_receipt_full_key(hash);
+						let storage_key = message.receipt_full_key(&dest_client);
 						let proof =
 							dest_client.query_state_proof(proof_height, vec![storage_key]).await?;
 						let height = StateMachineHeight {
 							id: dest_client.state_machine_id(),
 							height: proof_height,
 						};
-						let message = Message::Timeout(TimeoutMessage::Post {
-							requests: vec![req.clone()],
-							timeout_proof: Proof { height, proof },
-						});
+						let timeout = message.timeout_message(Proof { height, proof });
 						let challenge_period = hyperbridge_client
 							.query_challenge_period(
 								dest_client.state_machine_id().consensus_state_id,
@@ -278,7 +563,7 @@ pub async fn timeout_request_stream(
 							challenge_period,
 						)
 						.await?;
-						let meta = hyperbridge_client.submit(message).await?;
+						let meta = hyperbridge_client.submit(timeout).await?;
 						Ok(Some((
 							Ok(TimeoutStatus::HyperbridgeTimedout { meta }),
 							TimeoutStreamState::HyperbridgeTimedout(meta.block_number),
@@ -294,7 +579,7 @@ pub async fn timeout_request_stream(
 						if latest_hyperbridge_height >= hyperbridge_height {
 							let latest_height = source_client.query_latest_block_height().await?;
 							let meta = source_client
-								.query_ismp_event((latest_height - 500)..=latest_height)
+								.query_ismp_event(latest_height.saturating_sub(500)..=latest_height)
 								.await?
 								.into_iter()
 								.find_map(|event| match event.event {
@@ -336,7 +621,7 @@ pub async fn timeout_request_stream(
 									let commitment = source_client
 										.query_state_machine_commitment(state_machine_height)
 										.await?;
-									if commitment.timestamp > post.timeout_timestamp &&
+									if commitment.timestamp > message.timeout_timestamp() &&
 										ev.event.latest_height >= hyperbridge_height
 									{
 										valid_proof_height = Some(ev);
@@ -359,7 +644,7 @@ pub async fn timeout_request_stream(
 						}))
 					},
 					TimeoutStreamState::HyperbridgeFinalized(proof_height) => {
-						let storage_key = hyperbridge_client.request_receipt_full_key(hash);
+						let storage_key = message.receipt_full_key(&hyperbridge_client);
 						let proof = hyperbridge_client
 							.query_state_proof(proof_height, vec![storage_key])
 							.await?;
@@ -367,10 +652,7 @@ pub async fn timeout_request_stream(
 							id: hyperbridge_client.state_machine,
 							height: proof_height,
 						};
-						let message = Message::Timeout(TimeoutMessage::Post {
-							requests: vec![req],
-							timeout_proof: Proof { height, proof },
-						});
+						let timeout = message.timeout_message(Proof { height, proof });
 						let challenge_period = source_client
 							.query_challenge_period(
 								hyperbridge_client.state_machine_id().consensus_state_id,
@@ -380,7 +662,7 @@ pub async fn timeout_request_stream(
 							source_client.query_state_machine_update_time(height).await?;
 						wait_for_challenge_period(&source_client, update_time, challenge_period)
 							.await?;
-						let calldata = source_client.encode(message)?;
+						let calldata = source_client.encode(timeout)?;
 
 						Ok(Some((
 							Ok(TimeoutStatus::TimeoutMessage { calldata: calldata.into() }),
@@ -403,103 +685,67 @@ pub async fn timeout_request_stream(
 	Ok(Box::pin(stream))
 }
 
-/// returns the query stream for a post
+/// returns the query stream for a post request
 pub async fn request_status_stream(
 	hyperclient: &HyperClient,
 	post: PostRequest,
 	post_request_height: u64,
 ) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
-	let source_client = if post.source == hyperclient.dest.state_machine_id().state_id {
-		hyperclient.dest.clone()
-	} else if post.source == hyperclient.source.state_machine_id().state_id {
-		hyperclient.source.clone()
-	} else {
-		Err(anyhow!("Unknown client for source: {}", post.source))?
-	};
-	let dest_client = if post.dest == hyperclient.dest.state_machine_id().state_id {
-		hyperclient.dest.clone()
-	} else if post.dest == hyperclient.source.state_machine_id().state_id {
-		hyperclient.source.clone()
-	} else {
-		Err(anyhow!("Unknown client for dest: {}", post.dest))?
-	};
+	message_status_stream(hyperclient, PostMessage::Request(post), post_request_height).await
+}
+
+/// returns the query stream for a post response
+pub async fn response_status_stream(
+	hyperclient: &HyperClient,
+	response: PostResponse,
+	post_response_height: u64,
+) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+	message_status_stream(hyperclient, PostMessage::Response(response), post_response_height).await
+}
+
+/// Tracks a post request or response from its source, through hyperbridge, to its destination.
+/// `message_height` is the height at which the message was dispatched on its source.
+async fn message_status_stream(
+	hyperclient: &HyperClient,
+	message: PostMessage,
+	message_height: u64,
+) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+	let source_client = hyperclient.client_for(message.source()).await?;
+	let dest_client = hyperclient.client_for(message.dest()).await?;
 	let hyperbridge_client = hyperclient.hyperbridge.clone();
 	let hyperclient_clone = hyperclient.clone();
 
-	let stream = stream::unfold(PostStreamState::Pending, move |post_request_status| {
+	let stream = stream::unfold(PostStreamState::Pending, move |post_status| {
 		let dest_client = dest_client.clone();
 		let hyperbridge_client = hyperbridge_client.clone();
 		let source_client = source_client.clone();
-		let req = Request::Post(post.clone());
-		let hash = hash_request::<Keccak256>(&req);
-		let post = post.clone();
+		let message = message.clone();
 		let hyperclient_clone = hyperclient_clone.clone();
 		async move {
 			let lambda = || async {
-				match post_request_status {
+				if matches!(
+					post_status,
+					PostStreamState::DestinationDelivered | PostStreamState::End
+				) {
+					return Ok::<Option<(Result<_, anyhow::Error>, PostStreamState)>, anyhow::Error>(
+						None,
+					);
+				}
+
+				if let Some(status) = message.query_status_from_indexer(&hyperclient_clone).await {
+					if let Some(next) = indexed_stream_state(&status, post_status) {
+						return Ok(Some((Ok(status), next)));
+					}
+				}
+
+				match post_status {
 					PostStreamState::Pending => {
 						let destination_current_timestamp = dest_client.query_timestamp().await?;
-						let relayer_address = dest_client.query_request_receipt(hash).await?;
-
-						if let Some(ref msg_status) =
-							query_request_status_from_indexer(req.clone(), &hyperclient_clone)
-								.await
-								.ok()
-								.flatten()
-						{
-							match msg_status {
-								MessageStatusWithMetadata::SourceFinalized {
-									finalized_height,
-									..
-								} => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::SourceFinalized(*finalized_height),
-									)));
-								},
-								MessageStatusWithMetadata::HyperbridgeDelivered { meta } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::HyperbridgeDelivered(meta.block_number),
-									)));
-								},
-								MessageStatusWithMetadata::HyperbridgeFinalized {
-									finalized_height,
-									..
-								} => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::HyperbridgeFinalized(*finalized_height),
-									)));
-								},
-								MessageStatusWithMetadata::DestinationDelivered { .. } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::DestinationDelivered,
-									)));
-								},
-								_ => {},
-							}
-						}
+						let relayer = message.query_receipt(&dest_client).await?;
 
-						if relayer_address != H160::zero() {
+						if relayer != H160::zero() {
 							// This means the message has gotten to the destination chain
-							return Ok::<
-								Option<(Result<_, anyhow::Error>, PostStreamState)>,
-								anyhow::Error,
-							>(Some((
+							return Ok(Some((
 								Ok(MessageStatusWithMetadata::DestinationDelivered {
 									meta: Default::default(),
 								}),
@@ -507,7 +753,7 @@ pub async fn request_status_stream(
 							)));
 						}
 
-						if destination_current_timestamp.as_secs() >= post.timeout_timestamp {
+						if destination_current_timestamp.as_secs() >= message.timeout_timestamp() {
 							// Checking to see if the message has timed-out
 							return Ok(Some((
 								Ok(MessageStatusWithMetadata::Timeout),
@@ -517,14 +763,11 @@ pub async fn request_status_stream(
 
 						let hyperbridge_current_timestamp =
 							hyperbridge_client.query_timestamp().await?;
-						let relayer = hyperbridge_client.query_request_receipt(hash).await?;
+						let relayer = message.query_receipt(&hyperbridge_client).await?;
 
 						if relayer != H160::zero() {
-							// This means the message has gotten to the destination chain
-							return Ok::<
-								Option<(Result<_, anyhow::Error>, PostStreamState)>,
-								anyhow::Error,
-							>(Some((
+							// This means the message has gotten to hyperbridge
+							return Ok(Some((
 								Ok(MessageStatusWithMetadata::HyperbridgeDelivered {
 									meta: Default::default(),
 								}),
@@ -534,7 +777,7 @@ pub async fn request_status_stream(
 							)));
 						}
 
-						if hyperbridge_current_timestamp.as_secs() >= post.timeout_timestamp {
+						if hyperbridge_current_timestamp.as_secs() >= message.timeout_timestamp() {
 							// Checking to see if the message has timed-out
 							return Ok(Some((
 								Ok(MessageStatusWithMetadata::Timeout),
@@ -549,11 +792,9 @@ pub async fn request_status_stream(
 						while let Some(item) = state_machine_updated_stream.next().await {
 							match item {
 								Ok(state_machine_update) => {
-									if state_machine_update.event.latest_height >=
-										post_request_height && state_machine_update
-										.event
-										.state_machine_id
-										.state_id == post.source
+									if state_machine_update.event.latest_height >= message_height &&
+										state_machine_update.event.state_machine_id.state_id ==
+											message.source()
 									{
 										return Ok(Some((
 											Ok(MessageStatusWithMetadata::SourceFinalized {
@@ -575,7 +816,7 @@ pub async fn request_status_stream(
 											PostStreamState::Pending,
 											e
 										)),
-										post_request_status,
+										post_status,
 									))),
 							};
 						}
@@ -584,63 +825,18 @@ pub async fn request_status_stream(
 					},
 
 					PostStreamState::SourceFinalized(finalized_height) => {
-						let relayer = hyperbridge_client.query_request_receipt(hash).await?;
-
-						if let Some(ref msg_status) =
-							query_request_status_from_indexer(req.clone(), &hyperclient_clone)
-								.await
-								.ok()
-								.flatten()
-						{
-							match msg_status {
-								MessageStatusWithMetadata::HyperbridgeDelivered { meta } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::HyperbridgeDelivered(meta.block_number),
-									)));
-								},
-								MessageStatusWithMetadata::HyperbridgeFinalized {
-									finalized_height,
-									..
-								} => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::HyperbridgeFinalized(*finalized_height),
-									)));
-								},
-								MessageStatusWithMetadata::DestinationDelivered { .. } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::DestinationDelivered,
-									)));
-								},
-								_ => {},
-							}
-						}
+						let relayer = message.query_receipt(&hyperbridge_client).await?;
 
 						if relayer != H160::zero() {
 							let latest_height =
 								hyperbridge_client.query_latest_block_height().await?;
 
-							let meta = dest_client
+							let meta = hyperbridge_client
 								.query_ismp_event(finalized_height..=latest_height)
 								.await?
 								.into_iter()
-								.find_map(|event| match event.event {
-									Event::PostRequest(post_event)
-										if post.source == post_event.source &&
-											post.nonce == post_event.nonce =>
-										Some(event.meta),
-									_ => None,
+								.find_map(|event| {
+									message.is_dispatched_by(&event.event).then_some(event.meta)
 								});
 
 							return Ok(Some((
@@ -654,10 +850,7 @@ pub async fn request_status_stream(
 						}
 
 						let mut stream = hyperbridge_client
-							.ismp_events_stream(
-								RequestOrResponse::Request(post.clone()),
-								finalized_height,
-							)
+							.ismp_events_stream(message.as_item(), finalized_height)
 							.await?;
 						while let Some(event) = stream.next().await {
 							match event {
@@ -681,41 +874,9 @@ pub async fn request_status_stream(
 					},
 
 					PostStreamState::HyperbridgeDelivered(height) => {
-						let res = dest_client.query_request_receipt(hash).await?;
-
-						if let Some(ref msg_status) =
-							query_request_status_from_indexer(req.clone(), &hyperclient_clone)
-								.await
-								.ok()
-								.flatten()
-						{
-							match msg_status {
-								MessageStatusWithMetadata::HyperbridgeFinalized {
-									finalized_height,
-									..
-								} => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::HyperbridgeFinalized(*finalized_height),
-									)));
-								},
-								MessageStatusWithMetadata::DestinationDelivered { .. } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::DestinationDelivered,
-									)));
-								},
-								_ => {},
-							}
-						}
+						let relayer = message.query_receipt(&dest_client).await?;
 
-						if res != H160::zero() {
+						if relayer != H160::zero() {
 							return Ok(Some((
 								Ok(MessageStatusWithMetadata::DestinationDelivered {
 									meta: Default::default(),
@@ -733,7 +894,7 @@ pub async fn request_status_stream(
 						if latest_hyperbridge_height >= height {
 							let latest_height = dest_client.query_latest_block_height().await?;
 							let meta = dest_client
-								.query_ismp_event((latest_height - 500)..=latest_height)
+								.query_ismp_event(latest_height.saturating_sub(500)..=latest_height)
 								.await?
 								.into_iter()
 								.find_map(|event| match event.event {
@@ -744,12 +905,10 @@ pub async fn request_status_stream(
 								});
 
 							let Some((meta, update)) = meta else {
-								let calldata =
-									encode_request_message_and_wait_for_challenge_period(
+								let calldata = message
+									.encode_message_and_wait_for_challenge_period(
 										&hyperbridge_client,
 										&dest_client,
-										post.clone(),
-										hash,
 										latest_hyperbridge_height,
 									)
 									.await?;
@@ -763,14 +922,13 @@ pub async fn request_status_stream(
 								)));
 							};
 
-							let calldata = encode_request_message_and_wait_for_challenge_period(
-								&hyperbridge_client,
-								&dest_client,
-								post.clone(),
-								hash,
-								update.latest_height,
-							)
-							.await?;
+							let calldata = message
+								.encode_message_and_wait_for_challenge_period(
+									&hyperbridge_client,
+									&dest_client,
+									update.latest_height,
+								)
+								.await?;
 
 							return Ok(Some((
 								Ok(MessageStatusWithMetadata::HyperbridgeFinalized {
@@ -791,12 +949,10 @@ pub async fn request_status_stream(
 							match update {
 								Ok(event) =>
 									if event.event.latest_height >= height {
-										let calldata =
-											encode_request_message_and_wait_for_challenge_period(
+										let calldata = message
+											.encode_message_and_wait_for_challenge_period(
 												&hyperbridge_client,
 												&dest_client,
-												post.clone(),
-												hash,
 												event.event.latest_height,
 											)
 											.await?;
@@ -820,7 +976,7 @@ pub async fn request_status_stream(
 											PostStreamState::HyperbridgeDelivered(height),
 											e
 										)),
-										post_request_status,
+										post_status,
 									))),
 							}
 						}
@@ -828,40 +984,16 @@ pub async fn request_status_stream(
 					},
 
 					PostStreamState::HyperbridgeFinalized(finalized_height) => {
-						let res = dest_client.query_request_receipt(hash).await?;
-
-						if let Some(msg_status) =
-							query_request_status_from_indexer(req.clone(), &hyperclient_clone)
-								.await
-								.ok()
-								.flatten()
-						{
-							match &msg_status {
-								MessageStatusWithMetadata::DestinationDelivered { .. } => {
-									return Ok::<
-										Option<(Result<_, anyhow::Error>, PostStreamState)>,
-										anyhow::Error,
-									>(Some((
-										Ok(msg_status.clone()),
-										PostStreamState::DestinationDelivered,
-									)));
-								},
-								_ => {},
-							}
-						}
-						let request_commitment =
-							hash_request::<Keccak256>(&Request::Post(post.clone()));
-						if res != H160::zero() {
+						let relayer = message.query_receipt(&dest_client).await?;
+
+						if relayer != H160::zero() {
 							let latest_height = dest_client.query_latest_block_height().await?;
 							let meta = dest_client
 								.query_ismp_event(finalized_height..=latest_height)
 								.await?
 								.into_iter()
-								.find_map(|event| match event.event {
-									Event::PostRequestHandled(handled)
-										if handled.commitment == request_commitment =>
-										Some(event.meta),
-									_ => None,
+								.find_map(|event| {
+									message.is_handled_by(&event.event).then_some(event.meta)
 								})
 								.unwrap_or_default();
 							return Ok(Some((
@@ -869,34 +1001,236 @@ pub async fn request_status_stream(
 								PostStreamState::DestinationDelivered,
 							)));
 						}
+
+						let mut stream =
+							message.handled_stream(&dest_client, finalized_height).await?;
+						while let Some(meta) = stream.next().await {
+							match meta {
+								Ok(meta) =>
+									return Ok(Some((
+										Ok(MessageStatusWithMetadata::DestinationDelivered {
+											meta,
+										}),
+										PostStreamState::DestinationDelivered,
+									))),
+								Err(e) => tracing::info!(
+									"Encountered an error waiting for message delivery to destination {e:?}",
+								),
+							}
+						}
+
+						Ok(None)
+					},
+
+					PostStreamState::DestinationDelivered | PostStreamState::End => Ok(None),
+				}
+			};
+
+			// terminate the stream once an error is encountered
+			lambda().await.unwrap_or_else(|e| {
+				Some((Err(anyhow!("Encountered an error in stream {e:?}")), PostStreamState::End))
+			})
+		}
+	});
+
+	Ok(Box::pin(stream))
+}
+
+/// returns the query stream for a get request
+pub async fn get_request_status_stream(
+	hyperclient: &HyperClient,
+	get: GetRequest,
+	get_request_height: u64,
+) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+	let source_client = hyperclient.client_for(get.source).await?;
+	let hyperbridge_client = hyperclient.hyperbridge.clone();
+
+	let stream = stream::unfold(GetStreamState::Pending, move |get_request_status| {
+		let hyperbridge_client = hyperbridge_client.clone();
+		let source_client = source_client.clone();
+		let hash = hash_request::<Keccak256>(&Request::Get(get.clone()));
+		let get = get.clone();
+		async move {
+			let lambda = || async {
+				match get_request_status {
+					GetStreamState::Pending => {
+						let source_current_timestamp = source_client.query_timestamp().await?;
+						let relayer_address = source_client.query_response_receipt(hash).await?;
+
+						if relayer_address != H160::zero() {
+							// This means the response has gotten back to the source chain
+							return Ok::<
+								Option<(Result<_, anyhow::Error>, GetStreamState)>,
+								anyhow::Error,
+							>(Some((
+								Ok(MessageStatusWithMetadata::DestinationDelivered {
+									meta: Default::default(),
+								}),
+								GetStreamState::End,
+							)));
+						}
+
+						if source_current_timestamp.as_secs() >= get.timeout().as_secs() {
+							// Checking to see if the request has timed-out
+							return Ok(Some((
+								Ok(MessageStatusWithMetadata::Timeout),
+								GetStreamState::End,
+							)));
+						}
+
+						let relayer = hyperbridge_client.query_request_receipt(hash).await?;
+
+						if relayer != H160::zero() {
+							// This means the request has been handled by the state coprocessor
+							return Ok(Some((
+								Ok(MessageStatusWithMetadata::HyperbridgeDelivered {
+									meta: Default::default(),
+								}),
+								GetStreamState::HyperbridgeDelivered(
+									hyperbridge_client.query_latest_block_height().await?,
+								),
+							)));
+						}
+
+						let mut state_machine_updated_stream = hyperbridge_client
+							.state_machine_update_notification(source_client.state_machine_id())
+							.await?;
+
+						while let Some(item) = state_machine_updated_stream.next().await {
+							match item {
+								Ok(state_machine_update) => {
+									if state_machine_update.event.latest_height >=
+										get_request_height && state_machine_update
+										.event
+										.state_machine_id
+										.state_id == get.source
+									{
+										return Ok(Some((
+											Ok(MessageStatusWithMetadata::SourceFinalized {
+												finalized_height: state_machine_update
+													.event
+													.latest_height,
+												meta: state_machine_update.meta,
+											}),
+											GetStreamState::SourceFinalized(
+												state_machine_update.meta.block_number,
+											),
+										)));
+									}
+								},
+								Err(e) =>
+									return Ok(Some((
+										Err(anyhow!(
+											"Encountered an error {:?}: in {:?}",
+											GetStreamState::Pending,
+											e
+										)),
+										get_request_status,
+									))),
+							};
+						}
+
+						Ok(None)
+					},
+
+					GetStreamState::SourceFinalized(finalized_height) => {
+						let relayer = hyperbridge_client.query_request_receipt(hash).await?;
+
+						if relayer != H160::zero() {
+							let latest_height =
+								hyperbridge_client.query_latest_block_height().await?;
+
+							let meta = hyperbridge_client
+								.query_ismp_event(finalized_height..=latest_height)
+								.await?
+								.into_iter()
+								.find_map(|event| match event.event {
+									Event::GetResponse(response) if response.get == get =>
+										Some(event.meta),
+									_ => None,
+								});
+
+							return Ok(Some((
+								Ok(MessageStatusWithMetadata::HyperbridgeDelivered {
+									meta: meta.unwrap_or_default(),
+								}),
+								GetStreamState::HyperbridgeDelivered(
+									meta.map(|m| m.block_number).unwrap_or(latest_height),
+								),
+							)));
+						}
+
+						let mut stream = hyperbridge_client
+							.ismp_events_stream(
+								RequestOrResponse::GetRequest(get.clone()),
+								finalized_height,
+							)
+							.await?;
+						while let Some(event) = stream.next().await {
+							match event {
+								Ok(event) => {
+									return Ok(Some((
+										Ok(MessageStatusWithMetadata::HyperbridgeDelivered {
+											meta: event.meta.clone(),
+										}),
+										GetStreamState::HyperbridgeDelivered(
+											event.meta.block_number,
+										),
+									)));
+								},
+								Err(e) => tracing::info!(
+									"Encountered waiting for get request on hyperbridge: {e:?}"
+								),
+							}
+						}
+
+						Ok(None)
+					},
+
+					GetStreamState::HyperbridgeDelivered(_) => {
+						// fetch the height before checking the receipt so the delivery can't be
+						// missed by the event stream
+						let latest_height = source_client.query_latest_block_height().await?;
+						let relayer = source_client.query_response_receipt(hash).await?;
+
+						if relayer != H160::zero() {
+							return Ok(Some((
+								Ok(MessageStatusWithMetadata::DestinationDelivered {
+									meta: Default::default(),
+								}),
+								GetStreamState::DestinationDelivered,
+							)));
+						}
+
 						let mut stream =
-							dest_client.post_request_handled_stream(hash, finalized_height).await?;
+							source_client.get_request_handled_stream(hash, latest_height).await?;
 
 						while let Some(event) = stream.next().await {
 							match event {
-                                Ok(event) => return Ok(Some((
-                                    Ok(MessageStatusWithMetadata::DestinationDelivered {
-                                        meta: event.meta,
-                                    }),
-                                    PostStreamState::DestinationDelivered,
-                                ))),
-                                Err(e) =>  tracing::info!(
-                                    "Encountered an error waiting for message delivery to destination {e:?}",
-                                ),
-                            }
+								Ok(event) =>
+									return Ok(Some((
+										Ok(MessageStatusWithMetadata::DestinationDelivered {
+											meta: event.meta,
+										}),
+										GetStreamState::DestinationDelivered,
+									))),
+								Err(e) => tracing::info!(
+									"Encountered an error waiting for get response delivery to source {e:?}",
+								),
+							}
 						}
 
 						Ok(None)
 					},
 
-					PostStreamState::DestinationDelivered | PostStreamState::End =>
-						Ok::<Option<(Result<_, _>, PostStreamState)>, anyhow::Error>(None),
+					GetStreamState::DestinationDelivered | GetStreamState::End =>
+						Ok::<Option<(Result<_, _>, GetStreamState)>, anyhow::Error>(None),
 				}
 			};
 
 			// terminate the stream once an error is encountered
 			lambda().await.unwrap_or_else(|e| {
-				Some((Err(anyhow!("Encountered an error in stream {e:?}")), PostStreamState::End))
+				Some((Err(anyhow!("Encountered an error in stream {e:?}")), GetStreamState::End))
 			})
 		}
 	});
@@ -945,6 +1279,27 @@ pub async fn request_timeout_stream(
 	Box::pin(stream)
 }
 
+/// Returns the chain whose timestamp a request's timeout is checked against. Post requests time
+/// out on their destination, while get requests time out on their source chain, which is where
+/// their response is delivered.
+pub fn request_timeout_chain(request: &Request) -> StateMachine {
+	match request {
+		Request::Post(post) => post.dest,
+		Request::Get(get) => get.source,
+	}
+}
+
+/// Returns a stream that yields [`MessageStatusWithMetadata::Timeout`] once the request has timed
+/// out on the chain returned by [`request_timeout_chain`]
+pub async fn request_timed_out_stream(
+	hyperclient: &HyperClient,
+	request: &Request,
+) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+	let client = hyperclient.client_for(request_timeout_chain(request)).await?;
+
+	Ok(request_timeout_stream(request.timeout().as_secs(), client).await)
+}
+
 pub async fn encode_request_call_data(
 	hyperbridge: &SubstrateClient<Hyperbridge>,
 	dest_client: &AnyClient,
@@ -1017,3 +1372,189 @@ pub async fn encode_request_message_and_wait_for_challenge_period(
 
 	Ok(calldata)
 }
+
+// Encodes the call data for the response but waits for the challenge period before yielding
+pub async fn encode_response_message_and_wait_for_challenge_period(
+	hyperbridge: &SubstrateClient<Hyperbridge>,
+	dest_client: &AnyClient,
+	post_response: PostResponse,
+	commitment: H256,
+	height: u64,
+) -> Result<Vec<u8>, anyhow::Error> {
+	let calldata =
+		encode_response_call_data(hyperbridge, dest_client, post_response, commitment, height)
+			.await?;
+	let proof_height = StateMachineHeight { id: hyperbridge.state_machine, height };
+	let challenge_period = dest_client
+		.query_challenge_period(hyperbridge.state_machine_id().consensus_state_id)
+		.await?;
+	let update_time = dest_client.query_state_machine_update_time(proof_height).await?;
+	wait_for_challenge_period(dest_client, update_time, challenge_period).await?;
+
+	Ok(calldata)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Bytes;
+	use ismp::events::RequestResponseHandled;
+
+	fn post(nonce: u64) -> PostRequest {
+		PostRequest {
+			source: StateMachine::Evm(97),
+			dest: StateMachine::Evm(11155111),
+			nonce,
+			from: vec![1; 20],
+			to: vec![2; 20],
+			timeout_timestamp: 1_000,
+			body: vec![3; 32],
+		}
+	}
+
+	fn response() -> PostResponse {
+		PostResponse { post: post(0), response: vec![4; 32], timeout_timestamp: 2_000 }
+	}
+
+	fn meta(block_number: u64) -> EventMetadata {
+		EventMetadata { block_number, ..Default::default() }
+	}
+
+	fn handled(commitment: H256) -> RequestResponseHandled {
+		RequestResponseHandled { commitment, relayer: vec![5; 20] }
+	}
+
+	#[test]
+	fn should_route_responses_back_to_the_source_of_their_request() {
+		let request = PostMessage::Request(post(0));
+		assert_eq!(request.source(), StateMachine::Evm(97));
+		assert_eq!(request.dest(), StateMachine::Evm(11155111));
+		assert_eq!(request.timeout_timestamp(), 1_000);
+
+		let message = PostMessage::Response(response());
+		assert_eq!(message.source(), StateMachine::Evm(11155111));
+		assert_eq!(message.dest(), StateMachine::Evm(97));
+		assert_eq!(message.timeout_timestamp(), 2_000);
+
+		// receipts of both are keyed by the request commitment
+		assert_eq!(message.receipt_key(), request.commitment());
+		assert_eq!(request.receipt_key(), request.commitment());
+		assert_eq!(message.commitment(), hash_response::<Keccak256>(&Response::Post(response())));
+		assert_ne!(message.commitment(), request.commitment());
+	}
+
+	#[test]
+	fn should_match_the_dispatch_and_delivery_events_of_the_message() {
+		let request = PostMessage::Request(post(0));
+		assert!(request.is_dispatched_by(&Event::PostRequest(post(0))));
+		assert!(!request.is_dispatched_by(&Event::PostRequest(post(1))));
+		assert!(!request.is_dispatched_by(&Event::PostResponse(response())));
+		assert!(request.is_handled_by(&Event::PostRequestHandled(handled(request.commitment()))));
+		assert!(!request.is_handled_by(&Event::PostResponseHandled(handled(request.commitment()))));
+		assert!(!request.is_handled_by(&Event::PostRequestHandled(handled(H256::zero()))));
+
+		let message = PostMessage::Response(response());
+		assert!(message.is_dispatched_by(&Event::PostResponse(response())));
+		assert!(!message.is_dispatched_by(&Event::PostRequest(post(0))));
+		assert!(message.is_handled_by(&Event::PostResponseHandled(handled(message.commitment()))));
+		// responses are not matched by the delivery of their request
+		assert!(!message.is_handled_by(&Event::PostResponseHandled(handled(request.commitment()))));
+	}
+
+	#[test]
+	fn should_time_out_the_message_itself() {
+		let proof = Proof {
+			height: StateMachineHeight {
+				id: ismp::consensus::StateMachineId {
+					state_id: StateMachine::Kusama(4009),
+					consensus_state_id: *b"PARA",
+				},
+				height: 10,
+			},
+			proof: vec![],
+		};
+
+		let Message::Timeout(TimeoutMessage::Post { requests, .. }) =
+			PostMessage::Request(post(0)).timeout_message(proof.clone())
+		else {
+			panic!("Expected a post request timeout")
+		};
+		assert_eq!(requests, vec![Request::Post(post(0))]);
+
+		let Message::Timeout(TimeoutMessage::PostResponse { responses, timeout_proof }) =
+			PostMessage::Response(response()).timeout_message(proof.clone())
+		else {
+			panic!("Expected a post response timeout")
+		};
+		assert_eq!(responses, vec![response()]);
+		assert_eq!(timeout_proof, proof);
+	}
+
+	#[test]
+	fn should_only_advance_the_stream_to_later_indexed_states() {
+		let source_finalized =
+			MessageStatusWithMetadata::SourceFinalized { finalized_height: 5, meta: meta(10) };
+		let delivered = MessageStatusWithMetadata::HyperbridgeDelivered { meta: meta(12) };
+		let finalized = MessageStatusWithMetadata::HyperbridgeFinalized {
+			finalized_height: 12,
+			meta: meta(20),
+			calldata: Bytes(vec![]),
+		};
+		let destination = MessageStatusWithMetadata::DestinationDelivered { meta: meta(21) };
+
+		assert_eq!(
+			indexed_stream_state(&source_finalized, PostStreamState::Pending),
+			Some(PostStreamState::SourceFinalized(5))
+		);
+		assert_eq!(
+			indexed_stream_state(&delivered, PostStreamState::Pending),
+			Some(PostStreamState::HyperbridgeDelivered(12))
+		);
+		assert_eq!(
+			indexed_stream_state(&finalized, PostStreamState::HyperbridgeDelivered(12)),
+			Some(PostStreamState::HyperbridgeFinalized(12))
+		);
+		assert_eq!(
+			indexed_stream_state(&destination, PostStreamState::SourceFinalized(5)),
+			Some(PostStreamState::DestinationDelivered)
+		);
+
+		// the indexer lagging behind the stream doesn't move it back
+		assert_eq!(
+			indexed_stream_state(&source_finalized, PostStreamState::SourceFinalized(5)),
+			None
+		);
+		assert_eq!(
+			indexed_stream_state(&delivered, PostStreamState::HyperbridgeFinalized(20)),
+			None
+		);
+		assert_eq!(indexed_stream_state(&destination, PostStreamState::End), None);
+
+		// statuses that aren't stream states are left to the stream
+		for status in [
+			MessageStatusWithMetadata::Pending,
+			MessageStatusWithMetadata::Timeout,
+			MessageStatusWithMetadata::Error { description: "error".into() },
+		] {
+			assert_eq!(indexed_stream_state(&status, PostStreamState::Pending), None);
+		}
+	}
+
+	#[test]
+	fn should_order_post_stream_states() {
+		let states = [
+			PostStreamState::Pending,
+			PostStreamState::SourceFinalized(1),
+			PostStreamState::HyperbridgeDelivered(1),
+			PostStreamState::HyperbridgeFinalized(1),
+			PostStreamState::DestinationDelivered,
+		];
+		for pair in states.windows(2) {
+			assert!(post_stream_progress(pair[0]) < post_stream_progress(pair[1]));
+		}
+		assert_eq!(
+			post_stream_progress(PostStreamState::End),
+			post_stream_progress(PostStreamState::DestinationDelivered)
+		);
+	}
+}
@@ -31,13 +31,18 @@ extern crate core;
 use crate::types::ClientConfig;
 
 use crate::{
-	interfaces::{JsClientConfig, JsPost, JsPostResponse},
+	interfaces::{JsClientConfig, JsGet, JsPost, JsPostResponse},
 	providers::substrate::SubstrateClient,
 	types::{MessageStatusWithMetadata, TimeoutStatus},
 };
+use alloc::{collections::BTreeMap, sync::Arc};
+use anyhow::anyhow;
 use ethers::{types::H256, utils::keccak256};
-use futures::StreamExt;
-use ismp::router::{PostRequest, PostResponse};
+use futures::{lock::Mutex, StreamExt};
+use ismp::{
+	host::StateMachine,
+	router::{GetRequest, PostRequest, PostResponse, Request},
+};
 use subxt_utils::Hyperbridge;
 use wasm_bindgen::prelude::*;
 use wasm_streams::ReadableStream;
@@ -47,6 +52,9 @@ pub mod testing;
 
 pub mod indexing;
 
+#[cfg(feature = "std")]
+pub mod native;
+
 #[cfg(test)]
 mod tests;
 
@@ -61,6 +69,8 @@ interface IConfig {
     hyperbridge: IHyperbridgeConfig;
 	// Indexer url
 	indexer: string;
+    // configuration objects for any other chains this client should track messages for
+    chains?: IChainConfig[];
 }
 
 interface IChainConfig {
@@ -100,6 +110,25 @@ interface IPostRequest {
     height: bigint;
 }
 
+interface IGetRequest {
+    // The source state machine of this request.
+    source: string;
+    // The destination state machine of this request.
+    dest: string;
+    // Module Id of the sending module
+    from: string;
+    // The nonce of this request on the source chain
+    nonce: bigint;
+    // Raw storage keys to be read from the destination chain.
+    keys: string[];
+    // Height at which to read the state of the destination chain.
+    height: bigint;
+    // Timestamp which this request expires in seconds.
+    timeoutTimestamp: bigint;
+    // Height at which this request was emitted on the source
+    txHeight: bigint;
+}
+
 interface IPostResponse {
     // The request that triggered this response.
     post: IPostRequest;
@@ -107,6 +136,22 @@ interface IPostResponse {
     response: Uint8Array;
     // Timestamp at which this response expires in seconds.
     timeout_timestamp: bigint;
+    // Height at which this response was emitted on the source
+    height: bigint;
+}
+
+interface IFeeEstimate {
+    // Protocol fee charged by the host on the source chain, denominated in its fee token
+    protocol_fee: `0x{string}`;
+    // Estimated cost of delivering and executing the request on the destination chain,
+    // denominated in the native token of the destination chain
+    execution_cost: `0x{string}`;
+    // The relayer fee to attach to the request, the execution cost quoted in the fee token of
+    // the destination chain and converted to the decimals of the source chain's fee token
+    relayer_fee: `0x{string}`;
+    // The total amount of the fee token needed to dispatch the request, the protocol fee plus
+    // the relayer fee
+    recommended_fee: `0x{string}`;
 }
 
 type MessageStatus =
@@ -277,6 +322,9 @@ extern "C" {
 
 	#[wasm_bindgen(typescript_type = "IPostResponse")]
 	pub type IPostResponse;
+
+	#[wasm_bindgen(typescript_type = "IGetRequest")]
+	pub type IGetRequest;
 }
 
 /// The hyperclient, allows the clients of hyperbridge to manage their in-flight ISMP requests
@@ -285,9 +333,10 @@ extern "C" {
 #[derive(Clone)]
 pub struct HyperClient {
 	#[wasm_bindgen(skip)]
-	pub source: AnyClient,
+	pub config: ClientConfig,
+	/// Clients for the chains in the config, connected to on first use
 	#[wasm_bindgen(skip)]
-	pub dest: AnyClient,
+	pub clients: Arc<Mutex<BTreeMap<StateMachine, AnyClient>>>,
 	#[wasm_bindgen(skip)]
 	pub hyperbridge: SubstrateClient<Hyperbridge>,
 	#[wasm_bindgen(skip)]
@@ -297,17 +346,47 @@ pub struct HyperClient {
 impl HyperClient {
 	/// Initialize the Hyperclient
 	pub async fn new(config: ClientConfig) -> Result<Self, anyhow::Error> {
-		tracing::info!("Connecting to source");
-		let source = config.source_chain().await?;
-
-		tracing::info!("Connecting to dest");
-		let dest = config.dest_chain().await?;
-
 		tracing::info!("Connecting to hyperbridge");
 		let hyperbridge = config.hyperbridge_client().await?;
 
 		tracing::info!("Connected to hyperbridge");
-		Ok(Self { source, dest, hyperbridge, indexer: config.indexer.clone() })
+		Ok(Self {
+			indexer: config.indexer.clone(),
+			config,
+			clients: Default::default(),
+			hyperbridge,
+		})
+	}
+
+	/// Returns the client for the given state machine, connecting to it if this is the first
+	/// time it is used.
+	pub async fn client_for(
+		&self,
+		state_machine: StateMachine,
+	) -> Result<AnyClient, anyhow::Error> {
+		if let Some(client) = self.clients.lock().await.get(&state_machine) {
+			return Ok(client.clone());
+		}
+
+		let config = self
+			.config
+			.chains
+			.get(&state_machine)
+			.ok_or_else(|| anyhow!("No config provided for {state_machine}"))?;
+		tracing::info!("Connecting to {state_machine}");
+		let client = config.into_client().await?;
+		if client.state_machine_id().state_id != state_machine {
+			Err(anyhow!(
+				"Config for {state_machine} connects to {}",
+				client.state_machine_id().state_id
+			))?
+		}
+
+		// the lock isn't held while connecting, so keep the first client if another task
+		// connected to this chain in the meantime
+		let client = self.clients.lock().await.entry(state_machine).or_insert(client).clone();
+
+		Ok(client)
 	}
 }
 
@@ -337,11 +416,7 @@ impl HyperClient {
 		};
 
 		lambda().await.map_err(|err: anyhow::Error| {
-			JsError::new(&format!(
-				"Failed to query request status for {:?}->{:?}: {err:?}",
-				self.source.state_machine_id().state_id,
-				self.dest.state_machine_id().state_id,
-			))
+			JsError::new(&format!("Failed to query request status: {err:?}"))
 		})
 	}
 
@@ -355,11 +430,7 @@ impl HyperClient {
 		};
 
 		lambda().await.map_err(|err: anyhow::Error| {
-			JsError::new(&format!(
-				"Failed to query response status for {:?}->{:?}: {err:?}",
-				self.source.state_machine_id().state_id,
-				self.dest.state_machine_id().state_id,
-			))
+			JsError::new(&format!("Failed to query response status: {err:?}"))
 		})
 	}
 
@@ -376,8 +447,7 @@ impl HyperClient {
 
 			// Obtaining the request stream and the timeout stream
 			let timed_out =
-				internals::request_timeout_stream(post.timeout_timestamp, self.source.clone())
-					.await;
+				internals::request_timed_out_stream(&self, &Request::Post(post.clone())).await?;
 
 			let request_status = internals::request_status_stream(&self, post, height).await?;
 
@@ -402,6 +472,114 @@ impl HyperClient {
 		})
 	}
 
+	/// Return the status of a post response as a `ReadableStream` that yields
+	/// `MessageStatusWithMeta`
+	pub async fn response_status_stream(
+		&self,
+		response: IPostResponse,
+	) -> Result<wasm_streams::readable::sys::ReadableStream, JsError> {
+		let lambda = || async move {
+			let response =
+				serde_wasm_bindgen::from_value::<JsPostResponse>(response.into()).unwrap();
+			let height = response.height;
+			let response: PostResponse = response.try_into()?;
+			let dest_client = self.client_for(response.dest_chain()).await?;
+
+			// Obtaining the response stream and the timeout stream
+			let timed_out =
+				internals::request_timeout_stream(response.timeout_timestamp, dest_client).await;
+
+			let response_status =
+				internals::response_status_stream(&self, response, height).await?;
+
+			let stream = futures::stream::select(response_status, timed_out).map(|res| {
+				res.map(|status| serde_wasm_bindgen::to_value(&status).expect("Infallible"))
+					.map_err(|e| {
+						serde_wasm_bindgen::to_value(&MessageStatusWithMetadata::Error {
+							description: alloc::format!("{e:?}"),
+						})
+						.expect("Infallible")
+					})
+			});
+
+			// Wrapping the main stream in a readable stream
+			let js_stream = ReadableStream::from_stream(stream);
+
+			Ok(js_stream.into_raw())
+		};
+
+		lambda().await.map_err(|err: anyhow::Error| {
+			JsError::new(&format!("Failed to create response status stream: {err:?}"))
+		})
+	}
+
+	/// Estimates the fees to attach to a post request and returns a `FeeEstimate`. The protocol
+	/// fee, relayer fee and recommended fee are denominated in the fee token of the source chain,
+	/// while the execution cost is denominated in the native token of the destination chain.
+	pub async fn estimate_fee(&self, request: IPostRequest) -> Result<JsValue, JsError> {
+		let lambda = || async move {
+			let post = serde_wasm_bindgen::from_value::<JsPost>(request.into()).unwrap();
+			let post: PostRequest = post.try_into()?;
+			let estimate = internals::estimate_fee_internal(&self, post).await?;
+			Ok(serde_wasm_bindgen::to_value(&estimate).expect("Infallible"))
+		};
+
+		lambda()
+			.await
+			.map_err(|err: anyhow::Error| JsError::new(&format!("Failed to estimate fee: {err:?}")))
+	}
+
+	/// Queries the status of a get request and returns `MessageStatusWithMetadata`
+	pub async fn query_get_request_status(&self, request: IGetRequest) -> Result<JsValue, JsError> {
+		let lambda = || async move {
+			let get = serde_wasm_bindgen::from_value::<JsGet>(request.into()).unwrap();
+			let get: GetRequest = get.try_into()?;
+			let status = internals::query_get_request_status_internal(&self, get).await?;
+			Ok(serde_wasm_bindgen::to_value(&status).expect("Infallible"))
+		};
+
+		lambda().await.map_err(|err: anyhow::Error| {
+			JsError::new(&format!("Failed to query get request status: {err:?}"))
+		})
+	}
+
+	/// Return the status of a get request as a `ReadableStream` that yields
+	/// `MessageStatusWithMeta`
+	#[wasm_bindgen(js_name = get_request_status_stream)]
+	pub async fn js_get_request_status_stream(
+		&self,
+		request: IGetRequest,
+	) -> Result<wasm_streams::readable::sys::ReadableStream, JsError> {
+		let lambda = || async move {
+			let get = serde_wasm_bindgen::from_value::<JsGet>(request.into()).unwrap();
+			let height = get.tx_height;
+			let get: GetRequest = get.try_into()?;
+			let timed_out =
+				internals::request_timed_out_stream(&self, &Request::Get(get.clone())).await?;
+
+			let request_status = internals::get_request_status_stream(&self, get, height).await?;
+
+			let stream = futures::stream::select(request_status, timed_out).map(|res| {
+				res.map(|status| serde_wasm_bindgen::to_value(&status).expect("Infallible"))
+					.map_err(|e| {
+						serde_wasm_bindgen::to_value(&MessageStatusWithMetadata::Error {
+							description: alloc::format!("{e:?}"),
+						})
+						.expect("Infallible")
+					})
+			});
+
+			// Wrapping the main stream in a readable stream
+			let js_stream = ReadableStream::from_stream(stream);
+
+			Ok(js_stream.into_raw())
+		};
+
+		lambda().await.map_err(|err: anyhow::Error| {
+			JsError::new(&format!("Failed to create get request status stream: {err:?}"))
+		})
+	}
+
 	/// Given a post request that has timed out returns a `ReadableStream` that yields a
 	/// `TimeoutStatus` This function will not check if the request has timed out, only call it
 	/// when you receive a `MesssageStatus::TimeOut` from `query_request_status` or
@@ -434,6 +612,39 @@ impl HyperClient {
 		})
 	}
 
+	/// Given a post response that has timed out returns a `ReadableStream` that yields a
+	/// `TimeoutStatus` This function will not check if the response has timed out, only call it
+	/// when you receive a `MesssageStatus::TimeOut` from `query_response_status` or
+	/// `response_status_stream`. The stream ends when once it yields a `TimeoutMessage`
+	pub async fn timeout_post_response(
+		&self,
+		response: IPostResponse,
+	) -> Result<wasm_streams::readable::sys::ReadableStream, JsError> {
+		let lambda = || async move {
+			let response =
+				serde_wasm_bindgen::from_value::<JsPostResponse>(response.into()).unwrap();
+			let response: PostResponse = response.try_into()?;
+
+			let stream = internals::timeout_response_stream(&self, response).await?.map(|value| {
+				value
+					.map(|status| serde_wasm_bindgen::to_value(&status).expect("Infallible"))
+					.map_err(|e| {
+						serde_wasm_bindgen::to_value(&TimeoutStatus::Error {
+							description: alloc::format!("{e:?}"),
+						})
+						.expect("Infallible")
+					})
+			});
+
+			let js_stream = ReadableStream::from_stream(stream);
+			Ok(js_stream.into_raw())
+		};
+
+		lambda().await.map_err(|err: anyhow::Error| {
+			JsError::new(&format!("Failed to create post response timeout stream: {err:?}"))
+		})
+	}
+
 	pub fn get_indexer_url(&self) -> Option<String> {
 		self.indexer.clone()
 	}
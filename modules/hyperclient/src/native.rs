@@ -0,0 +1,170 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Native rust API for the hyperclient. This mirrors the javascript API but accepts and yields
+//! the typed ISMP messages instead of their javascript representations.
+
+use crate::{
+	internals,
+	providers::interface::Client,
+	types::{BoxStream, FeeEstimate, MessageStatusWithMetadata, TimeoutStatus},
+	HyperClient, Keccak256,
+};
+use ismp::{
+	events::Event,
+	host::StateMachine,
+	messaging::hash_request,
+	router::{GetRequest, PostRequest, PostResponse, Request},
+};
+use sp_core::H256;
+use std::ops::RangeInclusive;
+
+/// Maximum number of blocks to query events for in a single call.
+const EVENT_QUERY_BATCH_SIZE: u64 = 1000;
+
+impl HyperClient {
+	/// Returns the status of a post request
+	pub async fn post_request_status(
+		&self,
+		post: PostRequest,
+	) -> Result<MessageStatusWithMetadata, anyhow::Error> {
+		internals::query_request_status_internal(self, post).await
+	}
+
+	/// Returns a stream that yields the status of a post request, the stream ends once the request
+	/// has been delivered or has timed out. `height` is the block height at which the request was
+	/// emitted on its source chain.
+	pub async fn post_request_status_stream(
+		&self,
+		post: PostRequest,
+		height: u64,
+	) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+		let timed_out =
+			internals::request_timed_out_stream(self, &Request::Post(post.clone())).await?;
+		let request_status = internals::request_status_stream(self, post, height).await?;
+
+		Ok(Box::pin(futures::stream::select(request_status, timed_out)))
+	}
+
+	/// Returns a stream that drives the timeout of a post request, the stream ends once it yields
+	/// a `TimeoutMessage` which holds the calldata to be submitted to the source chain. This does
+	/// not check that the request has timed out.
+	pub async fn post_request_timeout_stream(
+		&self,
+		post: PostRequest,
+	) -> Result<BoxStream<TimeoutStatus>, anyhow::Error> {
+		internals::timeout_request_stream(self, post).await
+	}
+
+	/// Returns the status of a post response
+	pub async fn post_response_status(
+		&self,
+		response: PostResponse,
+	) -> Result<MessageStatusWithMetadata, anyhow::Error> {
+		internals::query_response_status_internal(self, response).await
+	}
+
+	/// Returns a stream that yields the status of a post response, the stream ends once the
+	/// response has been delivered or has timed out. `height` is the block height at which the
+	/// response was emitted on its source chain.
+	pub async fn post_response_status_stream(
+		&self,
+		response: PostResponse,
+		height: u64,
+	) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+		let timed_out = internals::request_timeout_stream(
+			response.timeout_timestamp,
+			self.client_for(response.dest_chain()).await?,
+		)
+		.await;
+		let response_status = internals::response_status_stream(self, response, height).await?;
+
+		Ok(Box::pin(futures::stream::select(response_status, timed_out)))
+	}
+
+	/// Returns a stream that drives the timeout of a post response, the stream ends once it yields
+	/// a `TimeoutMessage` which holds the calldata to be submitted to the source chain of the
+	/// response. This does not check that the response has timed out.
+	pub async fn post_response_timeout_stream(
+		&self,
+		response: PostResponse,
+	) -> Result<BoxStream<TimeoutStatus>, anyhow::Error> {
+		internals::timeout_response_stream(self, response).await
+	}
+
+	/// Returns the status of a get request
+	pub async fn get_request_status(
+		&self,
+		get: GetRequest,
+	) -> Result<MessageStatusWithMetadata, anyhow::Error> {
+		internals::query_get_request_status_internal(self, get).await
+	}
+
+	/// Returns a stream that yields the status of a get request, the stream ends once the get
+	/// response has been delivered to the source chain or the request has timed out. `height` is
+	/// the block height at which the request was emitted on its source chain.
+	pub async fn get_request_status_stream(
+		&self,
+		get: GetRequest,
+		height: u64,
+	) -> Result<BoxStream<MessageStatusWithMetadata>, anyhow::Error> {
+		let timed_out =
+			internals::request_timed_out_stream(self, &Request::Get(get.clone())).await?;
+		let request_status = internals::get_request_status_stream(self, get, height).await?;
+
+		Ok(Box::pin(futures::stream::select(request_status, timed_out)))
+	}
+
+	/// Estimates the fees to attach to a post request before it is dispatched. The protocol fee,
+	/// relayer fee and recommended fee are denominated in the fee token of the source chain, while
+	/// the execution cost is denominated in the native token of the destination chain.
+	pub async fn estimate_fee(&self, post: PostRequest) -> Result<FeeEstimate, anyhow::Error> {
+		internals::estimate_fee_internal(self, post).await
+	}
+
+	/// Searches the given source chain within the block range for the post request with the
+	/// provided commitment. Returns the request along with the height at which it was emitted.
+	pub async fn find_post_request(
+		&self,
+		source: StateMachine,
+		commitment: H256,
+		range: RangeInclusive<u64>,
+	) -> Result<Option<(PostRequest, u64)>, anyhow::Error> {
+		let source_client = self.client_for(source).await?;
+		let mut start = *range.start();
+		while start <= *range.end() {
+			let end = (start + EVENT_QUERY_BATCH_SIZE - 1).min(*range.end());
+			let found =
+				source_client
+					.query_ismp_event(start..=end)
+					.await?
+					.into_iter()
+					.find_map(|event| match event.event {
+						Event::PostRequest(post)
+							if hash_request::<Keccak256>(&Request::Post(post.clone())) ==
+								commitment =>
+							Some((post, event.meta.block_number)),
+						_ => None,
+					});
+
+			if found.is_some() {
+				return Ok(found);
+			}
+			start = end + 1;
+		}
+
+		Ok(None)
+	}
+}
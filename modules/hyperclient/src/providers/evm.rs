@@ -33,6 +33,8 @@ use crate::{
 use anyhow::{anyhow, Context, Error};
 use core::time::Duration;
 use ethers::{
+	abi::parse_abi,
+	contract::{Contract, LogMeta},
 	prelude::{ProviderExt, H160, H256, U256},
 	providers::{Http, Provider},
 	utils::keccak256,
@@ -43,14 +45,20 @@ use ismp::{
 	events::{Event, StateMachineUpdated},
 	host::StateMachine,
 	messaging::{Message, ResponseMessage, TimeoutMessage},
-	router::{Request, RequestResponse, Response},
+	router::{PostRequest, Request, RequestResponse, Response},
 };
 use ismp_solidity_abi::{
-	evm_host::{EvmHost, EvmHostEvents, PostRequestHandledFilter},
+	erc20::ERC20,
+	evm_host::{
+		EvmHost, EvmHostEvents, GetRequestHandledFilter, PostRequestHandledFilter,
+		PostResponseHandledFilter,
+	},
 	handler::{
 		Handler, PostRequestLeaf, PostRequestMessage, PostRequestTimeoutMessage, PostResponseLeaf,
 		PostResponseMessage, PostResponseTimeoutMessage, Proof,
 	},
+	host_manager::HostManager,
+	shared_types::IncomingPostRequest,
 };
 use mmr_primitives::mmr_position_to_k_index;
 use pallet_ismp::mmr::{LeafIndexAndPos, Proof as MmrProof};
@@ -58,6 +66,23 @@ use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
 
 use super::interface::Query;
 
+/// Gas used by the handler and host to deliver a single request, on top of the gas used by the
+/// destination module's `onAccept` and the calldata of the request. Relayers measure the full cost
+/// by tracing the handler call (see `estimate_gas` in tesseract's evm provider), which needs the
+/// request's proof and so isn't possible before the request is dispatched. This allowance covers
+/// verifying the request's inclusion in the hyperbridge state commitment, storing the request
+/// receipt and the host's fee accounting, as observed when relaying a single request.
+const REQUEST_DELIVERY_BASE_GAS: u64 = 200_000;
+
+/// Gas charged for each non-zero byte of calldata, requests are delivered with their full body
+const CALLDATA_GAS_PER_BYTE: u64 = 16;
+
+/// The Uniswap V2 router functions used by the host to swap the native token for its fee token
+const UNISWAP_V2_ROUTER_ABI: &[&str] = &[
+	"function WETH() external pure returns (address)",
+	"function getAmountsOut(uint256 amountIn, address[] path) external view returns (uint256[])",
+];
+
 #[derive(Debug, Clone)]
 pub struct EvmClient {
 	// A WS rpc url of the EVM chain
@@ -119,6 +144,90 @@ impl EvmClient {
 	pub fn response_receipt_key(&self, key: H256) -> H256 {
 		derive_map_key(key.0.to_vec(), RESPONSE_RECEIPTS_SLOT)
 	}
+
+	/// Polls the host for new blocks every 12 seconds, yielding the latest handled event in them
+	/// for the given commitment. `handled` selects the kind of handled event along with the
+	/// commitment it was emitted for.
+	fn handled_stream<E: Clone + 'static>(
+		&self,
+		commitment: H256,
+		initial_height: u64,
+		handled: fn(EvmHostEvents) -> Option<([u8; 32], E)>,
+	) -> BoxStream<WithMetadata<E>> {
+		let client = self.clone();
+		let stream =
+			stream::unfold((initial_height, client), move |(latest_height, client)| async move {
+				let state_machine = client.state_machine;
+				wasmtimer::tokio::sleep(Duration::from_secs(12)).await;
+				let block_number = match client.client.get_block_number().await {
+					Ok(number) => number.low_u64(),
+					Err(err) =>
+						return Some((
+							Err(err).context(format!(
+                            "Error encountered fetching latest block number for {state_machine:?}"
+                        )),
+							(latest_height, client),
+						)),
+				};
+
+				// in case we get old heights, best to ignore them
+				if block_number < latest_height {
+					return Some((Ok(None), (block_number, client)));
+				}
+
+				let contract = EvmHost::new(client.host_address, client.client.clone());
+				let results = match contract
+					.events()
+					.address(client.host_address.into())
+					.from_block(latest_height)
+					.to_block(block_number)
+					.query_with_meta()
+					.await
+				{
+					Ok(events) => events,
+					Err(err) =>
+						return Some((
+							Err(err)
+								.context(format!("Failed to query events on {state_machine:?}")),
+							(latest_height, client),
+						)),
+				};
+
+				let event = latest_handled_event(results, commitment, handled);
+				Some((Ok(event), (block_number + 1, client)))
+			})
+			.filter_map(|item| async move {
+				match item {
+					Ok(None) => None,
+					Ok(Some(event)) => Some(Ok(event)),
+					Err(err) => Some(Err(err)),
+				}
+			});
+
+		Box::pin(stream)
+	}
+}
+
+/// Returns the last of the events selected by `handled` that was emitted for the given commitment
+fn latest_handled_event<E>(
+	events: Vec<(EvmHostEvents, LogMeta)>,
+	commitment: H256,
+	handled: fn(EvmHostEvents) -> Option<([u8; 32], E)>,
+) -> Option<WithMetadata<E>> {
+	events
+		.into_iter()
+		.filter_map(|(event, meta)| {
+			let (handled_commitment, event) = handled(event)?;
+			(handled_commitment == commitment.0).then(|| WithMetadata {
+				meta: EventMetadata {
+					block_hash: meta.block_hash,
+					transaction_hash: meta.transaction_hash,
+					block_number: meta.block_number.as_u64(),
+				},
+				event,
+			})
+		})
+		.last()
 }
 
 fn derive_map_key(mut key: Vec<u8>, slot: u64) -> H256 {
@@ -275,78 +384,32 @@ impl Client for EvmClient {
 		commitment: H256,
 		initial_height: u64,
 	) -> Result<BoxStream<WithMetadata<PostRequestHandledFilter>>, Error> {
-		let client = self.clone();
-		let stream =
-			stream::unfold((initial_height, client), move |(latest_height, client)| async move {
-				let state_machine = client.state_machine;
-				wasmtimer::tokio::sleep(Duration::from_secs(12)).await;
-				let block_number = match client.client.get_block_number().await {
-					Ok(number) => number.low_u64(),
-					Err(err) =>
-						return Some((
-							Err(err).context(format!(
-                            "Error encountered fetching latest block number for {state_machine:?}"
-                        )),
-							(latest_height, client),
-						)),
-				};
-
-				// in case we get old heights, best to ignore them
-				if block_number < latest_height {
-					return Some((Ok(None), (block_number, client)));
-				}
-
-				let contract = EvmHost::new(client.host_address, client.client.clone());
-				let results = match contract
-					.events()
-					.address(client.host_address.into())
-					.from_block(latest_height)
-					.to_block(block_number)
-					.query_with_meta()
-					.await
-				{
-					Ok(events) => events,
-					Err(err) =>
-						return Some((
-							Err(err)
-								.context(format!("Failed to query events on {state_machine:?}")),
-							(latest_height, client),
-						)),
-				};
-
-				let events = results
-					.into_iter()
-					.filter_map(|(ev, meta)| match ev {
-						EvmHostEvents::PostRequestHandledFilter(filter) => {
-							if filter.commitment == commitment.0 {
-								return Some(WithMetadata {
-									meta: EventMetadata {
-										block_hash: meta.block_hash,
-										transaction_hash: meta.transaction_hash,
-										block_number: meta.block_number.as_u64(),
-									},
-									event: filter,
-								});
-							}
-
-							None
-						},
-						_ => None,
-					})
-					.collect::<Vec<_>>();
+		Ok(self.handled_stream(commitment, initial_height, |event| match event {
+			EvmHostEvents::PostRequestHandledFilter(filter) => Some((filter.commitment, filter)),
+			_ => None,
+		}))
+	}
 
-				// we only want the highest event
-				Some((Ok(events.last().cloned()), (block_number + 1, client)))
-			})
-			.filter_map(|item| async move {
-				match item {
-					Ok(None) => None,
-					Ok(Some(event)) => Some(Ok(event)),
-					Err(err) => Some(Err(err)),
-				}
-			});
+	async fn post_response_handled_stream(
+		&self,
+		commitment: H256,
+		initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<PostResponseHandledFilter>>, Error> {
+		Ok(self.handled_stream(commitment, initial_height, |event| match event {
+			EvmHostEvents::PostResponseHandledFilter(filter) => Some((filter.commitment, filter)),
+			_ => None,
+		}))
+	}
 
-		Ok(Box::pin(stream))
+	async fn get_request_handled_stream(
+		&self,
+		commitment: H256,
+		initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<GetRequestHandledFilter>>, Error> {
+		Ok(self.handled_stream(commitment, initial_height, |event| match event {
+			EvmHostEvents::GetRequestHandledFilter(filter) => Some((filter.commitment, filter)),
+			_ => None,
+		}))
 	}
 
 	async fn query_latest_state_machine_height(
@@ -676,4 +739,108 @@ impl Client for EvmClient {
 		let value = contract.challenge_period().call().await?;
 		Ok(Duration::from_secs(value.low_u64()))
 	}
+
+	async fn estimate_request_execution_cost(&self, post: PostRequest) -> Result<U256, Error> {
+		if post.to.len() != 20 {
+			Err(anyhow!("Expected a 20 byte destination module address, found {:?}", post.to))?
+		}
+		// every ismp module exposes `onAccept`, so the HostManager bindings can call any of them
+		let module = HostManager::new(H160::from_slice(&post.to), self.client.clone());
+		let call =
+			module.on_accept(IncomingPostRequest { request: post.into(), relayer: H160::zero() });
+		let calldata_gas = call.tx.data().map(|data| data.len() as u64).unwrap_or_default() *
+			CALLDATA_GAS_PER_BYTE;
+		let gas = call.from(self.host_address).estimate_gas().await?;
+		let gas_price = self.client.get_gas_price().await?;
+
+		Ok((gas + U256::from(REQUEST_DELIVERY_BASE_GAS + calldata_gas)) * gas_price)
+	}
+
+	async fn quote_fee_token(&self, amount: U256) -> Result<U256, Error> {
+		if amount.is_zero() {
+			return Ok(U256::zero());
+		}
+
+		// quote the same swap the host performs when fees are paid in the native token
+		let host = EvmHost::new(self.host_address, self.client.clone());
+		let fee_token = host.fee_token().call().await?;
+		let router = Contract::new(
+			host.uniswap_v2_router().call().await?,
+			parse_abi(UNISWAP_V2_ROUTER_ABI)?,
+			self.client.clone(),
+		);
+		let weth: H160 = router.method("WETH", ())?.call().await?;
+		let amounts: Vec<U256> =
+			router.method("getAmountsOut", (amount, vec![weth, fee_token]))?.call().await?;
+
+		amounts
+			.last()
+			.copied()
+			.ok_or_else(|| anyhow!("Uniswap router returned no amounts"))
+	}
+
+	async fn fee_token_decimals(&self) -> Result<u8, Error> {
+		let host = EvmHost::new(self.host_address, self.client.clone());
+		let fee_token = ERC20::new(host.fee_token().call().await?, self.client.clone());
+		Ok(fee_token.decimals().call().await?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethers::prelude::U64;
+
+	fn meta(block_number: u64) -> LogMeta {
+		LogMeta {
+			address: Default::default(),
+			block_number: U64::from(block_number),
+			block_hash: H256::repeat_byte(block_number as u8),
+			transaction_hash: H256::repeat_byte(block_number as u8 + 1),
+			transaction_index: Default::default(),
+			log_index: Default::default(),
+		}
+	}
+
+	fn get_request_handled(event: EvmHostEvents) -> Option<([u8; 32], GetRequestHandledFilter)> {
+		match event {
+			EvmHostEvents::GetRequestHandledFilter(filter) => Some((filter.commitment, filter)),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn should_select_the_latest_handled_event_for_the_commitment() {
+		let commitment = H256::repeat_byte(1);
+		let handled = |commitment: H256, relayer: u8| GetRequestHandledFilter {
+			commitment: commitment.0,
+			relayer: H160::repeat_byte(relayer),
+		};
+		let events = vec![
+			(EvmHostEvents::GetRequestHandledFilter(handled(commitment, 1)), meta(10)),
+			// other kinds of handled events for the same commitment are ignored
+			(
+				EvmHostEvents::PostRequestHandledFilter(PostRequestHandledFilter {
+					commitment: commitment.0,
+					relayer: H160::repeat_byte(2),
+				}),
+				meta(11),
+			),
+			(EvmHostEvents::GetRequestHandledFilter(handled(commitment, 3)), meta(12)),
+			(EvmHostEvents::GetRequestHandledFilter(handled(H256::repeat_byte(2), 4)), meta(13)),
+		];
+
+		let event = latest_handled_event(events.clone(), commitment, get_request_handled).unwrap();
+		assert_eq!(event.event, handled(commitment, 3));
+		assert_eq!(
+			event.meta,
+			EventMetadata {
+				block_hash: H256::repeat_byte(12),
+				transaction_hash: H256::repeat_byte(13),
+				block_number: 12,
+			}
+		);
+
+		assert!(latest_handled_event(events, H256::repeat_byte(3), get_request_handled).is_none());
+	}
 }
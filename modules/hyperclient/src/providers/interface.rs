@@ -17,15 +17,20 @@
 
 use crate::types::{BoxStream, EventMetadata};
 use core::time::Duration;
-use ethers::{prelude::H256, types::H160};
+use ethers::{
+	prelude::H256,
+	types::{H160, U256},
+};
 use ismp::{
 	consensus::{ConsensusStateId, StateCommitment, StateMachineHeight, StateMachineId},
 	events::{Event, StateMachineUpdated},
 	host::StateMachine,
 	messaging::Message,
-	router::{PostRequest, PostResponse},
+	router::{GetRequest, PostRequest, PostResponse},
+};
+use ismp_solidity_abi::evm_host::{
+	GetRequestHandledFilter, PostRequestHandledFilter, PostResponseHandledFilter,
 };
-use ismp_solidity_abi::evm_host::PostRequestHandledFilter;
 use serde::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 
@@ -33,6 +38,8 @@ use std::ops::RangeInclusive;
 pub enum RequestOrResponse {
 	Request(PostRequest),
 	Response(PostResponse),
+	/// A get request, matched against the get response produced for it by the state coprocessor
+	GetRequest(GetRequest),
 }
 
 /// Provides an interface for accessing new events and ISMP data on the chain which must be
@@ -114,6 +121,20 @@ pub trait Client: Clone + Send + Sync + 'static {
 		initial_height: u64,
 	) -> Result<BoxStream<WithMetadata<PostRequestHandledFilter>>, anyhow::Error>;
 
+	// Returns a stream of the PostResponseHandled on the ISMP host of this chain
+	async fn post_response_handled_stream(
+		&self,
+		commitment: H256,
+		initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<PostResponseHandledFilter>>, anyhow::Error>;
+
+	// Returns a stream of the GetRequestHandled on the ISMP host of this chain
+	async fn get_request_handled_stream(
+		&self,
+		commitment: H256,
+		initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<GetRequestHandledFilter>>, anyhow::Error>;
+
 	/// Query the latest height of the given state machine
 	async fn query_latest_state_machine_height(
 		&self,
@@ -162,6 +183,20 @@ pub trait Client: Clone + Send + Sync + 'static {
 	/// Query the challenge period for client
 	async fn query_challenge_period(&self, id: ConsensusStateId)
 		-> Result<Duration, anyhow::Error>;
+
+	/// Estimate the cost of delivering and executing the request on this chain, denominated in
+	/// the native token of this chain
+	async fn estimate_request_execution_cost(
+		&self,
+		post: PostRequest,
+	) -> Result<U256, anyhow::Error>;
+
+	/// Returns the amount of this chain's fee token that the given amount of its native token is
+	/// worth
+	async fn quote_fee_token(&self, amount: U256) -> Result<U256, anyhow::Error>;
+
+	/// Query the number of decimals of this chain's fee token
+	async fn fee_token_decimals(&self) -> Result<u8, anyhow::Error>;
 }
 
 pub async fn wait_for_challenge_period<C: Client>(
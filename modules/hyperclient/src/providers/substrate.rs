@@ -18,9 +18,9 @@ use crate::{
 	types::{BoxStream, EventMetadata, Extrinsic, HashAlgorithm, SubstrateStateProof},
 };
 use anyhow::{anyhow, Error};
-use codec::{Decode, Encode};
+use codec::{Compact, Decode, Encode};
 use core::time::Duration;
-use ethers::prelude::{H160, H256};
+use ethers::prelude::{H160, H256, U256};
 use futures::{stream, StreamExt};
 use hashbrown::HashMap;
 use hex_literal::hex;
@@ -28,9 +28,12 @@ use ismp::{
 	consensus::{ConsensusStateId, StateCommitment, StateMachineHeight, StateMachineId},
 	events::{Event, StateMachineUpdated},
 	host::StateMachine,
-	messaging::Message,
+	messaging::{Message, Proof as MessageProof, RequestMessage},
+	router::PostRequest,
+};
+use ismp_solidity_abi::evm_host::{
+	GetRequestHandledFilter, PostRequestHandledFilter, PostResponseHandledFilter,
 };
-use ismp_solidity_abi::evm_host::PostRequestHandledFilter;
 use pallet_ismp::{
 	child_trie::{
 		request_commitment_storage_key, response_commitment_storage_key, CHILD_TRIE_PREFIX,
@@ -50,6 +53,17 @@ use subxt_utils::state_machine_update_time_storage_key;
 
 use super::interface::Query;
 
+/// The dispatch info of a call, as returned by the `TransactionPaymentCallApi`
+#[derive(Decode)]
+struct RuntimeDispatchInfo {
+	/// Weight of the call
+	_weight: (Compact<u64>, Compact<u64>),
+	/// Dispatch class of the call
+	_class: u8,
+	/// Fee for dispatching the call, excluding the tip
+	partial_fee: u128,
+}
+
 /// Contains a scale encoded Mmr Proof or Trie proof
 #[derive(Serialize, Deserialize)]
 pub struct Proof {
@@ -108,6 +122,17 @@ where
 		Ok(Duration::from_millis(timestamp))
 	}
 
+	/// Query the protocol fee charged by the source chain for dispatching the request. This is
+	/// only available on hyperbridge, which tracks the host parameters of all connected chains.
+	pub async fn query_protocol_fee(&self, post: PostRequest) -> Result<U256, Error> {
+		let source = post.source;
+		let params = rpc_params![post, Option::<H256>::None];
+		let response: Option<U256> =
+			self.client.rpc().request("hostExecutive_queryProtocolFee", params).await?;
+
+		response.ok_or_else(|| anyhow!("Host params for {source:?} are not known"))
+	}
+
 	async fn query_ismp_events(
 		&self,
 		previous_height: u64,
@@ -327,6 +352,8 @@ impl<C: subxt::Config + Clone> Client for SubstrateClient<C> {
 							Event::PostRequest(post) =>
 								Some(RequestOrResponse::Request(post.clone())),
 							Event::PostResponse(resp) => Some(RequestOrResponse::Response(resp)),
+							Event::GetResponse(resp) =>
+								Some(RequestOrResponse::GetRequest(resp.get)),
 							_ => None,
 						};
 
@@ -366,6 +393,22 @@ impl<C: subxt::Config + Clone> Client for SubstrateClient<C> {
 		Err(anyhow!("Post request handled stream is currently unavailable"))
 	}
 
+	async fn post_response_handled_stream(
+		&self,
+		_commitment: H256,
+		_initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<PostResponseHandledFilter>>, Error> {
+		Err(anyhow!("Post response handled stream is currently unavailable"))
+	}
+
+	async fn get_request_handled_stream(
+		&self,
+		_commitment: H256,
+		_initial_height: u64,
+	) -> Result<BoxStream<WithMetadata<GetRequestHandledFilter>>, Error> {
+		Err(anyhow!("Get request handled stream is currently unavailable"))
+	}
+
 	async fn query_latest_state_machine_height(
 		&self,
 		state_machine: StateMachineId,
@@ -545,4 +588,54 @@ impl<C: subxt::Config + Clone> Client for SubstrateClient<C> {
 
 		Ok(Duration::from_secs(response))
 	}
+
+	async fn estimate_request_execution_cost(&self, post: PostRequest) -> Result<U256, Error> {
+		// Quote the fee for delivering the request with `Ismp::handle`. The membership proof isn't
+		// known until the request is committed on hyperbridge, so its length fee is not included.
+		let metadata = self.client.metadata();
+		let pallet = metadata
+			.pallet_by_name("Ismp")
+			.ok_or_else(|| anyhow!("Ismp pallet not found on {:?}", self.state_machine.state_id))?;
+		let call_index = pallet
+			.call_variant_by_name("handle")
+			.ok_or_else(|| anyhow!("Ismp::handle not found on {:?}", self.state_machine.state_id))?
+			.index;
+		let height = StateMachineHeight {
+			id: StateMachineId { state_id: post.source, consensus_state_id: Default::default() },
+			height: Default::default(),
+		};
+		let message = Message::Request(RequestMessage {
+			requests: vec![post],
+			proof: MessageProof { height, proof: Default::default() },
+			signer: Default::default(),
+		});
+		let mut call = vec![pallet.index(), call_index];
+		vec![message].encode_to(&mut call);
+
+		let params = (call.clone(), call.len() as u32).encode();
+		let response = self
+			.client
+			.rpc()
+			.state_call("TransactionPaymentCallApi_query_call_info", Some(params.as_slice()), None)
+			.await?;
+		let info = RuntimeDispatchInfo::decode(&mut &*response)?;
+
+		Ok(U256::from(info.partial_fee))
+	}
+
+	async fn quote_fee_token(&self, amount: U256) -> Result<U256, Error> {
+		// fees on substrate chains are paid in the native token
+		Ok(amount)
+	}
+
+	async fn fee_token_decimals(&self) -> Result<u8, Error> {
+		let properties = self.client.rpc().system_properties().await?;
+		properties
+			.get("tokenDecimals")
+			.and_then(|value| value.as_u64().or_else(|| value.as_array()?.first()?.as_u64()))
+			.and_then(|decimals| u8::try_from(decimals).ok())
+			.ok_or_else(|| {
+				anyhow!("Token decimals for {:?} are not known", self.state_machine.state_id)
+			})
+	}
 }
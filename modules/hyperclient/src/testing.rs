@@ -44,7 +44,7 @@ use ismp_solidity_abi::{
 	evm_host::{EvmHost, PostRequestEventFilter},
 	ping_module::{PingMessage, PingModule},
 };
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 const OP_HOST: H160 = H160(hex!("821c5D97213Aaf914174440a53bB4403BF42f27b"));
 const SEPOLIA_HOST: H160 = H160(hex!("Bc0fA79725aCD430D507855e77f30C9d9ED4dC24"));
@@ -80,8 +80,10 @@ pub async fn subscribe_to_request_status() -> Result<(), anyhow::Error> {
 		hash_algo: HashAlgorithm::Keccak,
 	};
 	let config = ClientConfig {
-		source: ChainConfig::Evm(source_chain.clone()),
-		dest: ChainConfig::Evm(dest_chain.clone()),
+		chains: BTreeMap::from([
+			(source_chain.state_machine, ChainConfig::Evm(source_chain.clone())),
+			(dest_chain.state_machine, ChainConfig::Evm(dest_chain.clone())),
+		]),
 		hyperbridge: ChainConfig::Substrate(hyperbrige_config),
 		indexer: None,
 	};
@@ -178,8 +180,10 @@ pub async fn test_timeout_request() -> Result<(), anyhow::Error> {
 		hash_algo: HashAlgorithm::Keccak,
 	};
 	let config = ClientConfig {
-		source: ChainConfig::Evm(source_chain.clone()),
-		dest: ChainConfig::Evm(dest_chain.clone()),
+		chains: BTreeMap::from([
+			(source_chain.state_machine, ChainConfig::Evm(source_chain.clone())),
+			(dest_chain.state_machine, ChainConfig::Evm(dest_chain.clone())),
+		]),
 		hyperbridge: ChainConfig::Substrate(hyperbrige_config),
 		indexer: None,
 	};
@@ -261,8 +265,11 @@ pub async fn test_timeout_request() -> Result<(), anyhow::Error> {
 	let request_status = request_status_stream(&hyperclient, post.clone(), block.low_u64()).await?;
 
 	// Obtaining the request stream and the timeout stream
-	let timed_out =
-		internals::request_timeout_stream(post.timeout_timestamp, hyperclient.source.clone()).await;
+	let timed_out = internals::request_timeout_stream(
+		post.timeout_timestamp,
+		hyperclient.client_for(post.source).await?,
+	)
+	.await;
 
 	let mut stream = futures::stream::select(request_status, timed_out);
 
@@ -14,16 +14,18 @@
 // limitations under the License.
 
 #![cfg(not(target_arch = "wasm32"))]
-use std::str::FromStr;
+use ethers::types::U256;
+use std::{collections::BTreeMap, str::FromStr};
 
 use ismp::{
 	host::StateMachine,
-	router::{PostResponse, Request},
+	router::{GetRequest, PostRequest, PostResponse, Request},
 };
 use substrate_state_machine::HashAlgorithm;
 
 use crate::{
 	indexing::{query_request_status_from_indexer, query_response_status_from_indexer},
+	internals::{convert_decimals, request_timeout_chain},
 	testing::{subscribe_to_request_status, test_timeout_request},
 	types::{ChainConfig, ClientConfig, EvmConfig, MessageStatusWithMetadata, SubstrateConfig},
 	HyperClient,
@@ -36,6 +38,72 @@ pub fn setup_logging() {
 	let _ = tracing_subscriber::fmt().with_env_filter(filter).finish().try_init();
 }
 
+#[test]
+fn should_serialize_client_config_with_state_machine_keys() {
+	let evm_config = EvmConfig {
+		rpc_url: "http://localhost:8545".to_string(),
+		state_machine: StateMachine::Evm(11155111),
+		host_address: Default::default(),
+		consensus_state_id: *b"ETH0",
+	};
+	let substrate_config = SubstrateConfig {
+		rpc_url: "ws://localhost:9944".to_string(),
+		consensus_state_id: *b"PARA",
+		hash_algo: HashAlgorithm::Keccak,
+	};
+	let config = ClientConfig {
+		chains: BTreeMap::from([
+			(StateMachine::Evm(11155111), ChainConfig::Evm(evm_config)),
+			(StateMachine::Kusama(4009), ChainConfig::Substrate(substrate_config.clone())),
+		]),
+		hyperbridge: ChainConfig::Substrate(substrate_config),
+		indexer: None,
+	};
+
+	let encoded = json::to_value(&config).unwrap();
+	assert!(encoded["chains"]["EVM-11155111"].is_object());
+	assert!(encoded["chains"]["KUSAMA-4009"].is_object());
+	assert_eq!(json::from_value::<ClientConfig>(encoded).unwrap(), config);
+}
+
+#[test]
+fn should_time_out_requests_on_the_chain_that_enforces_their_timeout() {
+	let post = PostRequest {
+		source: StateMachine::Evm(11155111),
+		dest: StateMachine::Evm(97),
+		nonce: 0,
+		from: vec![],
+		to: vec![],
+		timeout_timestamp: 0,
+		body: vec![],
+	};
+	assert_eq!(request_timeout_chain(&Request::Post(post)), StateMachine::Evm(97));
+
+	let get = GetRequest {
+		source: StateMachine::Evm(11155111),
+		dest: StateMachine::Kusama(4009),
+		nonce: 0,
+		from: vec![],
+		keys: vec![],
+		height: 0,
+		timeout_timestamp: 0,
+	};
+	assert_eq!(request_timeout_chain(&Request::Get(get)), StateMachine::Evm(11155111));
+}
+
+#[test]
+fn should_convert_fees_between_fee_token_decimals() {
+	// 1.5 USDC with 6 decimals is 1.5 * 10^18 of an 18 decimal stablecoin
+	let amount = U256::from(1_500_000u64);
+	assert_eq!(convert_decimals(amount, 6, 18), U256::from(1_500_000_000_000_000_000u128));
+	assert_eq!(convert_decimals(U256::from(1_500_000_000_000_000_000u128), 18, 6), amount);
+	assert_eq!(convert_decimals(amount, 6, 6), amount);
+	// remainders are rounded up so the relayer fee is never underestimated
+	assert_eq!(convert_decimals(U256::from(1_000_000_000_001u64), 18, 6), U256::from(2u8));
+	assert_eq!(convert_decimals(U256::from(1u8), 100, 0), U256::from(1u8));
+	assert_eq!(convert_decimals(U256::from(1u8), 0, 100), U256::MAX);
+}
+
 #[tokio::test]
 #[ignore]
 async fn hyperclient_integration_tests() -> Result<(), anyhow::Error> {
@@ -95,8 +163,10 @@ async fn test_query_status_from_indexer() -> Result<(), anyhow::Error> {
 	};
 
 	let config = ClientConfig {
-		source: ChainConfig::Evm(source_chain.clone()),
-		dest: ChainConfig::Evm(dest_chain.clone()),
+		chains: BTreeMap::from([
+			(source_chain.state_machine, ChainConfig::Evm(source_chain.clone())),
+			(dest_chain.state_machine, ChainConfig::Evm(dest_chain.clone())),
+		]),
 		hyperbridge: ChainConfig::Substrate(hyperbrige_config),
 		indexer: Some("http://localhost:3000".to_string()),
 	};
@@ -173,8 +243,10 @@ async fn test_query_response_status_from_indexer() -> Result<(), anyhow::Error>
 	};
 
 	let config = ClientConfig {
-		source: ChainConfig::Evm(source_chain.clone()),
-		dest: ChainConfig::Evm(dest_chain.clone()),
+		chains: BTreeMap::from([
+			(source_chain.state_machine, ChainConfig::Evm(source_chain.clone())),
+			(dest_chain.state_machine, ChainConfig::Evm(dest_chain.clone())),
+		]),
 		hyperbridge: ChainConfig::Substrate(hyperbrige_config),
 		indexer: Some("http://localhost:3000".to_string()),
 	};
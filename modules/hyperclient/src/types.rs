@@ -17,9 +17,10 @@ use crate::{
 	any_client::AnyClient,
 	providers::{evm::EvmClient, substrate::SubstrateClient},
 };
+use alloc::collections::BTreeMap;
 use anyhow::anyhow;
 use core::{fmt, pin::Pin};
-use ethers::types::H160;
+use ethers::types::{H160, U256};
 pub use evm_common::types::EvmStateProof;
 use futures::Stream;
 use hex_fmt::HexFmt;
@@ -83,14 +84,67 @@ pub enum ChainConfig {
 	Substrate(SubstrateConfig),
 }
 
+impl ChainConfig {
+	/// Connects to the chain described by this config
+	pub async fn into_client(&self) -> Result<AnyClient, anyhow::Error> {
+		match self {
+			ChainConfig::Evm(config) => {
+				let client = config.into_client().await?;
+				Ok(AnyClient::Evm(client))
+			},
+			ChainConfig::Substrate(config) => match config.hash_algo {
+				HashAlgorithm::Keccak => {
+					let client = config.into_client::<Hyperbridge>().await?;
+					Ok(AnyClient::KeccakSubstrateChain(client))
+				},
+				HashAlgorithm::Blake2 => {
+					let client = config.into_client::<BlakeSubstrateChain>().await?;
+					Ok(AnyClient::BlakeSubstrateChain(client))
+				},
+			},
+		}
+	}
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct ClientConfig {
-	pub source: ChainConfig,
-	pub dest: ChainConfig,
+	/// Configuration for every chain this client routes messages to or from, clients are
+	/// connected to lazily when a message first needs them
+	#[serde(with = "state_machine_keys")]
+	pub chains: BTreeMap<StateMachine, ChainConfig>,
 	pub hyperbridge: ChainConfig,
 	pub indexer: Option<String>,
 }
 
+/// (De)serializes maps keyed by state machines using their string identifiers, since formats
+/// like json only support string keys.
+mod state_machine_keys {
+	use super::*;
+	use serde::{de::Error, Deserializer, Serializer};
+
+	pub fn serialize<S, V>(
+		map: &BTreeMap<StateMachine, V>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		V: Serialize,
+	{
+		serializer.collect_map(map.iter().map(|(key, value)| (key.to_string(), value)))
+	}
+
+	pub fn deserialize<'de, D, V>(deserializer: D) -> Result<BTreeMap<StateMachine, V>, D::Error>
+	where
+		D: Deserializer<'de>,
+		V: Deserialize<'de>,
+	{
+		BTreeMap::<String, V>::deserialize(deserializer)?
+			.into_iter()
+			.map(|(key, value)| Ok((key.parse().map_err(D::Error::custom)?, value)))
+			.collect()
+	}
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq, Default, Copy)]
 pub struct EventMetadata {
 	/// The hash of the block where the event was emitted
@@ -205,6 +259,22 @@ pub enum PostStreamState {
 	End,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GetStreamState {
+	/// Request has been finalized on source chain
+	Pending,
+	/// Source state machine has been updated on hyperbridge, holds the block number at which the
+	/// source was finalized on hyperbridge
+	SourceFinalized(u64),
+	/// Request has been handled by the state coprocessor on hyperbridge, holds the block where
+	/// the get response was dispatched
+	HyperbridgeDelivered(u64),
+	/// Get response has been delivered to the source chain
+	DestinationDelivered,
+	/// Stream has ended, check the message status
+	End,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
 pub enum TimeoutStatus {
@@ -240,45 +310,24 @@ pub enum TimeoutStatus {
 	},
 }
 
-impl ClientConfig {
-	pub async fn dest_chain(&self) -> Result<AnyClient, anyhow::Error> {
-		match &self.dest {
-			ChainConfig::Evm(config) => {
-				let client = config.into_client().await?;
-				Ok(AnyClient::Evm(client))
-			},
-			ChainConfig::Substrate(config) => match config.hash_algo {
-				HashAlgorithm::Keccak => {
-					let client = config.into_client::<Hyperbridge>().await?;
-					Ok(AnyClient::KeccakSubstrateChain(client))
-				},
-				HashAlgorithm::Blake2 => {
-					let client = config.into_client::<BlakeSubstrateChain>().await?;
-					Ok(AnyClient::BlakeSubstrateChain(client))
-				},
-			},
-		}
-	}
-
-	pub async fn source_chain(&self) -> Result<AnyClient, anyhow::Error> {
-		match &self.source {
-			ChainConfig::Evm(config) => {
-				let client = config.into_client().await?;
-				Ok(AnyClient::Evm(client))
-			},
-			ChainConfig::Substrate(config) => match config.hash_algo {
-				HashAlgorithm::Keccak => {
-					let client = config.into_client::<Hyperbridge>().await?;
-					Ok(AnyClient::KeccakSubstrateChain(client))
-				},
-				HashAlgorithm::Blake2 => {
-					let client = config.into_client::<BlakeSubstrateChain>().await?;
-					Ok(AnyClient::BlakeSubstrateChain(client))
-				},
-			},
-		}
-	}
+/// The fees to attach to a post request
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Default)]
+pub struct FeeEstimate {
+	/// Protocol fee charged by the host on the source chain, denominated in its fee token
+	pub protocol_fee: U256,
+	/// Estimated cost of delivering and executing the request on the destination chain,
+	/// denominated in the native token of the destination chain.
+	pub execution_cost: U256,
+	/// The relayer fee to attach to the request, this is the execution cost quoted in the fee
+	/// token of the destination chain. Fee tokens are stablecoins pegged to the same asset across
+	/// chains, so it is converted to the decimals of the source chain's fee token.
+	pub relayer_fee: U256,
+	/// The total amount of the source chain's fee token needed to dispatch the request, the
+	/// protocol fee plus the relayer fee
+	pub recommended_fee: U256,
+}
 
+impl ClientConfig {
 	pub async fn hyperbridge_client(&self) -> Result<SubstrateClient<Hyperbridge>, anyhow::Error> {
 		match self.hyperbridge {
 			ChainConfig::Substrate(ref config) => config.into_client::<Hyperbridge>().await,
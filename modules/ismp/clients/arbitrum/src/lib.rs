@@ -36,6 +36,8 @@ use ismp::{
 
 /// Storage layout slot for the nodes map in the Rollup Contract
 pub const NODES_SLOT: u64 = 118;
+/// Storage layout slot for the assertions map in the BoLD Rollup Contract
+pub const ASSERTIONS_SLOT: u64 = 116;
 
 #[derive(codec::Encode, codec::Decode, Debug)]
 pub struct GlobalState {
@@ -85,6 +87,27 @@ impl TryFrom<u8> for MachineStatus {
 	}
 }
 
+/// https://github.com/OffchainLabs/nitro-contracts/blob/v2.1.0/src/rollup/Assertion.sol#L15
+#[derive(codec::Encode, codec::Decode, Debug, PartialEq, Eq)]
+pub enum AssertionStatus {
+	NoAssertion = 0,
+	Pending = 1,
+	Confirmed = 2,
+}
+
+impl TryFrom<u8> for AssertionStatus {
+	type Error = &'static str;
+
+	fn try_from(status: u8) -> Result<Self, Self::Error> {
+		match status {
+			0 => Ok(AssertionStatus::NoAssertion),
+			1 => Ok(AssertionStatus::Pending),
+			2 => Ok(AssertionStatus::Confirmed),
+			_ => Err("Invalid assertion status received"),
+		}
+	}
+}
+
 #[derive(codec::Encode, codec::Decode, Debug)]
 pub struct ArbitrumPayloadProof {
 	/// Arbitrum header that corresponds to the node being created
@@ -105,6 +128,53 @@ pub struct ArbitrumPayloadProof {
 	pub contract_proof: Vec<Vec<u8>>,
 }
 
+#[derive(codec::Encode, codec::Decode, Debug)]
+pub struct ArbitrumBoldProof {
+	/// Arbitrum header that corresponds to the assertion
+	pub arbitrum_header: CodecHeader,
+	/// Global State of the assertion's after state as recorded in the AssertionCreated event
+	pub global_state: GlobalState,
+	/// Machine status of the assertion's after state as recorded in the AssertionCreated event
+	pub machine_status: MachineStatus,
+	/// End history root of the assertion's after state as recorded in the AssertionCreated event
+	pub end_history_root: H256,
+	/// Hash of the parent assertion as recorded in the AssertionCreated event
+	pub parent_assertion_hash: H256,
+	/// Inbox accumulator as recorded in the AssertionCreated event
+	pub inbox_acc: H256,
+	/// Proof for the first slot of the AssertionNode struct inside the _assertions mapping in the
+	/// RollupCore, which holds the assertion status
+	pub storage_proof: Vec<Vec<u8>>,
+	/// RollupCore contract proof in the ethereum world trie
+	pub contract_proof: Vec<Vec<u8>>,
+}
+
+/// https://github.com/OffchainLabs/nitro-contracts/blob/v2.1.0/src/rollup/RollupLib.sol#L28
+fn get_assertion_hash<H: Keccak256>(
+	global_state: &GlobalState,
+	machine_status: MachineStatus,
+	end_history_root: H256,
+	parent_assertion_hash: H256,
+	inbox_acc: H256,
+) -> H256 {
+	// abi encode the AssertionState struct
+	let mut buf = Vec::new();
+	buf.extend_from_slice(&global_state.block_hash[..]);
+	buf.extend_from_slice(&global_state.send_root[..]);
+	buf.extend_from_slice(&H256::from_low_u64_be(global_state.inbox_position)[..]);
+	buf.extend_from_slice(&H256::from_low_u64_be(global_state.position_in_message)[..]);
+	buf.extend_from_slice(&H256::from_low_u64_be(machine_status as u64)[..]);
+	buf.extend_from_slice(&end_history_root[..]);
+	let after_state_hash = H::keccak256(&buf);
+
+	// abi encode packed
+	let mut buf = Vec::new();
+	buf.extend_from_slice(&parent_assertion_hash[..]);
+	buf.extend_from_slice(&after_state_hash[..]);
+	buf.extend_from_slice(&inbox_acc[..]);
+	H::keccak256(&buf)
+}
+
 /// https://github.com/OffchainLabs/nitro/blob/5e9f4228e6418b114a5aea0aa7f2f0cc161b67c0/contracts/src/rollup/RollupLib.sol#L59
 fn get_state_hash<H: Keccak256>(
 	global_state: GlobalState,
@@ -130,23 +200,7 @@ pub fn verify_arbitrum_payload<H: Keccak256 + Send + Sync>(
 	let storage_root =
 		get_contract_storage_root::<H>(payload.contract_proof, &rollup_core_address.0, root)?;
 
-	let header: Header = payload.arbitrum_header.as_ref().into();
-	if &payload.global_state.send_root[..] != &payload.arbitrum_header.extra_data {
-		Err(Error::Custom(
-			"Arbitrum header extra data does not match send root in global state".to_string(),
-		))?
-	}
-
-	let block_number = payload.arbitrum_header.number.low_u64();
-	let timestamp = payload.arbitrum_header.timestamp;
-	let state_root = payload.arbitrum_header.state_root.0.into();
-
-	let header_hash = header.hash::<H>();
-	if payload.global_state.block_hash != header_hash {
-		Err(Error::Custom(
-			"Arbitrum header hash does not match block hash in global state".to_string(),
-		))?
-	}
+	verify_arbitrum_header::<H>(&payload.arbitrum_header, &payload.global_state)?;
 
 	let state_hash =
 		get_state_hash::<H>(payload.global_state, payload.machine_status, payload.inbox_max_count);
@@ -173,15 +227,98 @@ pub fn verify_arbitrum_payload<H: Keccak256 + Send + Sync>(
 		))?
 	}
 
-	Ok(IntermediateState {
+	Ok(intermediate_state(&payload.arbitrum_header, consensus_state_id))
+}
+
+/// Verifies a confirmed assertion in the `_assertions` mapping of a BoLD RollupCore contract, where
+/// assertions are keyed by their hash rather than by a sequential node number.
+pub fn verify_arbitrum_bold<H: Keccak256 + Send + Sync>(
+	payload: ArbitrumBoldProof,
+	root: H256,
+	rollup_core_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
+	let storage_root =
+		get_contract_storage_root::<H>(payload.contract_proof, &rollup_core_address.0, root)?;
+
+	verify_arbitrum_header::<H>(&payload.arbitrum_header, &payload.global_state)?;
+
+	let assertion_hash = get_assertion_hash::<H>(
+		&payload.global_state,
+		payload.machine_status,
+		payload.end_history_root,
+		payload.parent_assertion_hash,
+		payload.inbox_acc,
+	);
+
+	let assertion_key = derive_map_key::<H>(assertion_hash.0.to_vec(), ASSERTIONS_SLOT);
+	let proof_value = match get_value_from_proof::<H>(
+		assertion_key.0.to_vec(),
+		storage_root,
+		payload.storage_proof,
+	)? {
+		Some(value) => value.clone(),
+		_ => Err(Error::MembershipProofVerificationFailed("Value not found in proof".to_string()))?,
+	};
+
+	let status = decode_assertion_status(&proof_value)?;
+	if status != AssertionStatus::Confirmed {
+		Err(Error::Custom(format!("Assertion {assertion_hash:?} is not confirmed: {status:?}")))?
+	}
+
+	Ok(intermediate_state(&payload.arbitrum_header, consensus_state_id))
+}
+
+/// Decodes the status of an assertion from the rlp encoded first slot of its AssertionNode
+fn decode_assertion_status(proof_value: &[u8]) -> Result<AssertionStatus, Error> {
+	let slot = <alloy_primitives::U256 as Decodable>::decode(&mut &*proof_value)
+		.map_err(|_| Error::Custom(format!("Error decoding assertion node {:?}", proof_value)))?
+		.to_be_bytes::<32>();
+
+	// The first slot of the AssertionNode packs firstChildBlock, secondChildBlock and
+	// createdAtBlock as uint64s, followed by the isFirstChild and status bytes.
+	AssertionStatus::try_from(slot[6]).map_err(|e| Error::Custom(e.into()))
+}
+
+/// Checks that the header is the block committed to by the global state
+fn verify_arbitrum_header<H: Keccak256>(
+	arbitrum_header: &CodecHeader,
+	global_state: &GlobalState,
+) -> Result<(), Error> {
+	let header: Header = arbitrum_header.as_ref().into();
+	if &global_state.send_root[..] != &arbitrum_header.extra_data {
+		Err(Error::Custom(
+			"Arbitrum header extra data does not match send root in global state".to_string(),
+		))?
+	}
+
+	let header_hash = header.hash::<H>();
+	if global_state.block_hash != header_hash {
+		Err(Error::Custom(
+			"Arbitrum header hash does not match block hash in global state".to_string(),
+		))?
+	}
+
+	Ok(())
+}
+
+fn intermediate_state(
+	arbitrum_header: &CodecHeader,
+	consensus_state_id: ConsensusStateId,
+) -> IntermediateState {
+	IntermediateState {
 		height: StateMachineHeight {
 			id: StateMachineId {
 				// note: This will state machine id should not be used to store the state commitment
 				state_id: StateMachine::Evm(Default::default()),
 				consensus_state_id,
 			},
-			height: block_number,
+			height: arbitrum_header.number.low_u64(),
 		},
-		commitment: StateCommitment { timestamp, overlay_root: None, state_root },
-	})
+		commitment: StateCommitment {
+			timestamp: arbitrum_header.timestamp,
+			overlay_root: None,
+			state_root: arbitrum_header.state_root.0.into(),
+		},
+	}
 }
@@ -14,11 +14,14 @@
 // limitations under the License.
 #![cfg(test)]
 
-use ethers::prelude::*;
+use ethers::{abi, prelude::*, utils};
 use hex_literal::hex;
 
-use crate::NODES_SLOT;
-use evm_common::derive_map_key;
+use crate::{
+	decode_assertion_status, get_assertion_hash, AssertionStatus, GlobalState, MachineStatus,
+	ASSERTIONS_SLOT, NODES_SLOT,
+};
+use evm_common::{derive_map_key, derive_unhashed_map_key};
 use ismp_testsuite::mocks::Host;
 use sp_core::{H160, H256};
 
@@ -52,3 +55,111 @@ async fn fetch_arbitrum_node_state_hash() {
 	let state_hash = hex::encode(buf);
 	println!("State Hash {}", state_hash);
 }
+
+#[tokio::test]
+#[ignore]
+/// This test ensures that the assertion key derivation works correctly for BoLD rollups.
+async fn fetch_arbitrum_bold_assertion_status() {
+	let rpc_url = "https://rpc.ankr.com/eth";
+	let provider = Provider::try_from(rpc_url).unwrap();
+	let rollup = H160::from_slice(hex!("5eF0D09d1E6204141B4d37530808eD19f60FBa35").as_slice());
+	// The latest confirmed assertion hash is at slot 115
+	let latest_confirmed_position = H256::from_low_u64_be(115);
+	let latest_confirmed_proof =
+		provider.get_proof(rollup, vec![latest_confirmed_position], None).await.unwrap();
+	let mut latest_confirmed = [0u8; 32];
+	latest_confirmed_proof.storage_proof[0]
+		.value
+		.to_big_endian(&mut latest_confirmed);
+	assert_ne!(latest_confirmed, [0u8; 32], "No assertion has been confirmed");
+
+	let position = derive_unhashed_map_key::<Host>(latest_confirmed.to_vec(), ASSERTIONS_SLOT);
+	let proof = provider.get_proof(rollup, vec![position], None).await.unwrap();
+
+	let mut buf = [0u8; 32];
+	proof.storage_proof[0].value.to_big_endian(&mut buf);
+	let status = AssertionStatus::try_from(buf[6]).unwrap();
+	assert_eq!(status, AssertionStatus::Confirmed);
+}
+
+#[test]
+/// The assertion hash should match the abi encoding of the AssertionState used by RollupLib.
+fn assertion_hash_matches_abi_encoding() {
+	let global_state = GlobalState {
+		block_hash: H256(hex!("e2fa9d3f5b1e4d4c0f7e6b2d0b6f0c1b3a7d8e9f00112233445566778899aabb")),
+		send_root: H256(hex!("7b1c5e2a9d3f4b6c8e0a1c3e5f7092b4d6f8a0c2e4f6183a5c7e9b0d2f4a6c8e")),
+		inbox_position: 1_337_422,
+		position_in_message: 0,
+	};
+	let end_history_root =
+		H256(hex!("0c3b5d7f9a1e2c4d6f8091a3b5c7d9e0f1a2b3c4d5e6f708192a3b4c5d6e7f80"));
+	let parent_assertion_hash =
+		H256(hex!("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90"));
+	let inbox_acc = H256(hex!("5f4e3d2c1b0a99887766554433221100ffeeddccbbaa99887766554433221100"));
+
+	let after_state = abi::encode(&[
+		abi::Token::FixedArray(vec![
+			abi::Token::FixedBytes(global_state.block_hash.0.to_vec()),
+			abi::Token::FixedBytes(global_state.send_root.0.to_vec()),
+		]),
+		abi::Token::FixedArray(vec![
+			abi::Token::Uint(global_state.inbox_position.into()),
+			abi::Token::Uint(global_state.position_in_message.into()),
+		]),
+		abi::Token::Uint((MachineStatus::Finished as u8).into()),
+		abi::Token::FixedBytes(end_history_root.0.to_vec()),
+	]);
+	let after_state_hash = utils::keccak256(after_state);
+	let expected = utils::keccak256(
+		abi::encode_packed(&[
+			abi::Token::FixedBytes(parent_assertion_hash.0.to_vec()),
+			abi::Token::FixedBytes(after_state_hash.to_vec()),
+			abi::Token::FixedBytes(inbox_acc.0.to_vec()),
+		])
+		.unwrap(),
+	);
+
+	let assertion_hash = get_assertion_hash::<Host>(
+		&global_state,
+		MachineStatus::Finished,
+		end_history_root,
+		parent_assertion_hash,
+		inbox_acc,
+	);
+	assert_eq!(assertion_hash.0, expected);
+
+	// the machine status is committed to by the assertion hash
+	let errored = get_assertion_hash::<Host>(
+		&global_state,
+		MachineStatus::Errored,
+		end_history_root,
+		parent_assertion_hash,
+		inbox_acc,
+	);
+	assert_ne!(errored, assertion_hash);
+}
+
+#[test]
+/// The status should be read from the packed first slot of the AssertionNode.
+fn should_decode_the_assertion_status() {
+	// firstChildBlock, secondChildBlock and createdAtBlock are packed from the lowest order bytes,
+	// followed by isFirstChild and the status
+	let slot = |status: u8| {
+		let mut slot = [0u8; 32];
+		slot[24..].copy_from_slice(&20_871_034u64.to_be_bytes());
+		slot[16..24].copy_from_slice(&20_871_112u64.to_be_bytes());
+		slot[8..16].copy_from_slice(&20_845_700u64.to_be_bytes());
+		slot[7] = 1;
+		slot[6] = status;
+		alloy_rlp::encode(alloy_primitives::U256::from_be_bytes(slot))
+	};
+
+	assert_eq!(decode_assertion_status(&slot(0)).unwrap(), AssertionStatus::NoAssertion);
+	assert_eq!(decode_assertion_status(&slot(1)).unwrap(), AssertionStatus::Pending);
+	assert_eq!(decode_assertion_status(&slot(2)).unwrap(), AssertionStatus::Confirmed);
+	assert!(decode_assertion_status(&slot(3)).is_err());
+
+	// storage values are rlp encoded without their leading zeroes
+	let pending = alloy_rlp::encode(alloy_primitives::U256::from(1u64) << 200usize);
+	assert_eq!(decode_assertion_status(&pending).unwrap(), AssertionStatus::Pending);
+}
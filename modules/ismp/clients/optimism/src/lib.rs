@@ -17,6 +17,9 @@
 
 extern crate alloc;
 
+#[cfg(test)]
+mod tests;
+
 use alloc::format;
 use alloy_rlp::Decodable;
 use ethabi::ethereum_types::{H160, H256, U128, U256};
@@ -40,6 +43,10 @@ use ismp::{
 pub const DISPUTE_GAMES_SLOT: u64 = 103;
 /// Slot for the l2Outputs array in the L2Oracle contract
 pub const L2_OUTPUTS_SLOT: u64 = 3;
+/// Slot for the respectedGameType in the OptimismPortal2 contract
+pub const RESPECTED_GAME_TYPE_SLOT: u64 = 59;
+/// Slot holding the createdAt, resolvedAt and status fields in the FaultDisputeGame contract
+pub const GAME_STATUS_SLOT: u64 = 0;
 
 #[derive(codec::Encode, codec::Decode, Debug)]
 pub struct OptimismPayloadProof {
@@ -177,6 +184,43 @@ pub struct OptimismDisputeGameProof {
 	pub timestamp: u64,
 }
 
+/// Proof that the output root was claimed by a dispute game which has been resolved in favour of
+/// the defender
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct ResolvedDisputeGameProof {
+	/// Proof of the output root claimed by the dispute game
+	pub dispute_game: OptimismDisputeGameProof,
+	/// Membership Proof for the OptimismPortal contract account in the ethereum world trie
+	pub portal_proof: Vec<Vec<u8>>,
+	/// Membership proof for the respectedGameType in the OptimismPortal
+	pub respected_game_type_proof: Vec<Vec<u8>>,
+	/// Membership Proof for the dispute game proxy account in the ethereum world trie
+	pub game_proof: Vec<Vec<u8>>,
+	/// Membership proof for the slot holding the resolution status of the dispute game
+	pub game_status_proof: Vec<Vec<u8>>,
+}
+
+// https://github.com/ethereum-optimism/optimism/blob/f707883038d527cbf1e9f8ea513fe33255deadbc/packages/contracts-bedrock/src/dispute/lib/Types.sol#L28
+#[derive(codec::Encode, codec::Decode, Debug, Clone, PartialEq, Eq)]
+pub enum GameStatus {
+	InProgress = 0,
+	ChallengerWins = 1,
+	DefenderWins = 2,
+}
+
+impl TryFrom<u8> for GameStatus {
+	type Error = &'static str;
+
+	fn try_from(status: u8) -> Result<Self, Self::Error> {
+		match status {
+			0 => Ok(GameStatus::InProgress),
+			1 => Ok(GameStatus::ChallengerWins),
+			2 => Ok(GameStatus::DefenderWins),
+			_ => Err("Invalid game status received"),
+		}
+	}
+}
+
 // https://github.com/ethereum-optimism/optimism/blob/f707883038d527cbf1e9f8ea513fe33255deadbc/packages/contracts-bedrock/src/dispute/DisputeGameFactory.sol#L127
 pub fn get_game_uuid<H: Keccak256>(game_type: u32, root_claim: H256, extra_data: Vec<u8>) -> H256 {
 	let tokens = [
@@ -206,20 +250,131 @@ pub fn calculate_output_root<H: Keccak256>(
 // https://github.com/ethereum-optimism/optimism/blob/f707883038d527cbf1e9f8ea513fe33255deadbc/packages/contracts-bedrock/src/libraries/DisputeTypes.sol#L94
 /// Game types
 pub const CANNON: u32 = 0;
-pub const _PERMISSIONED: u32 = 1;
+pub const PERMISSIONED: u32 = 1;
 
+/// Verifies that the output root of the payload was claimed by a cannon dispute game, which the
+/// defender has won. The OptimismPortal's respected game type is not checked.
 pub fn verify_optimism_dispute_game_proof<H: Keccak256 + Send + Sync>(
-	payload: OptimismDisputeGameProof,
+	payload: ResolvedDisputeGameProof,
+	root: H256,
+	dispute_factory_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
+	// Is the game type the respected game type?
+	if payload.dispute_game.game_type != CANNON {
+		Err(Error::MembershipProofVerificationFailed(
+			"Game type must be the respected game type".to_string(),
+		))?;
+	}
+
+	verify_game_resolution::<H>(&payload, root)?;
+
+	verify_dispute_game_output::<H>(
+		payload.dispute_game,
+		root,
+		dispute_factory_address,
+		consensus_state_id,
+	)
+}
+
+/// Verifies that the output root of the payload was claimed by a dispute game of the portal's
+/// respected game type, which the defender has won at least `finality_delay` seconds before
+/// `l1_timestamp`.
+pub fn verify_resolved_optimism_dispute_game_proof<H: Keccak256 + Send + Sync>(
+	payload: ResolvedDisputeGameProof,
 	root: H256,
+	l1_timestamp: u64,
 	dispute_factory_address: H160,
+	optimism_portal_address: H160,
+	finality_delay: u64,
 	consensus_state_id: ConsensusStateId,
 ) -> Result<IntermediateState, Error> {
+	let game_type = payload.dispute_game.game_type;
+	if game_type != CANNON && game_type != PERMISSIONED {
+		Err(Error::MembershipProofVerificationFailed(format!(
+			"Unsupported game type {game_type}"
+		)))?;
+	}
+
 	// Is the game type the respected game type?
-	if payload.game_type != CANNON {
+	let portal_storage_root = get_contract_storage_root::<H>(
+		payload.portal_proof.clone(),
+		&optimism_portal_address.0,
+		root,
+	)?;
+	let respected_game_type = decode_respected_game_type(get_slot_from_proof::<H>(
+		RESPECTED_GAME_TYPE_SLOT,
+		portal_storage_root,
+		payload.respected_game_type_proof.clone(),
+	)?);
+	if game_type != respected_game_type {
 		Err(Error::MembershipProofVerificationFailed(
 			"Game type must be the respected game type".to_string(),
 		))?;
 	}
+
+	let resolved_at = verify_game_resolution::<H>(&payload, root)?;
+	if l1_timestamp.saturating_sub(resolved_at) < finality_delay {
+		Err(Error::Custom(format!(
+			"Dispute game resolved at {resolved_at} is still within the finality delay of {finality_delay}s"
+		)))?;
+	}
+
+	verify_dispute_game_output::<H>(
+		payload.dispute_game,
+		root,
+		dispute_factory_address,
+		consensus_state_id,
+	)
+}
+
+/// Verifies that the dispute game has been resolved in favour of the defender, returning the
+/// timestamp at which it was resolved
+fn verify_game_resolution<H: Keccak256 + Send + Sync>(
+	payload: &ResolvedDisputeGameProof,
+	root: H256,
+) -> Result<u64, Error> {
+	let game_storage_root = get_contract_storage_root::<H>(
+		payload.game_proof.clone(),
+		&payload.dispute_game.proxy.0,
+		root,
+	)?;
+	let (status, resolved_at) = decode_game_status(get_slot_from_proof::<H>(
+		GAME_STATUS_SLOT,
+		game_storage_root,
+		payload.game_status_proof.clone(),
+	)?)?;
+	if status != GameStatus::DefenderWins {
+		Err(Error::MembershipProofVerificationFailed(format!(
+			"Dispute game has not been won by the defender: {status:?}"
+		)))?;
+	}
+
+	Ok(resolved_at)
+}
+
+/// Decodes the respectedGameType of the OptimismPortal, which is packed in the lowest four bytes
+/// of its slot
+pub fn decode_respected_game_type(slot: [u8; 32]) -> u32 {
+	u32::from_be_bytes(slot[28..].try_into().expect("Slice has length 4"))
+}
+
+/// Decodes the status and resolvedAt timestamp of a FaultDisputeGame. The slot packs createdAt
+/// and resolvedAt as uint64s, followed by the status byte
+pub fn decode_game_status(slot: [u8; 32]) -> Result<(GameStatus, u64), Error> {
+	let resolved_at = u64::from_be_bytes(slot[16..24].try_into().expect("Slice has length 8"));
+	let status = GameStatus::try_from(slot[15]).map_err(|e| Error::Custom(e.into()))?;
+
+	Ok((status, resolved_at))
+}
+
+/// Verifies that the output root of the payload was claimed by the dispute game in the payload
+fn verify_dispute_game_output<H: Keccak256 + Send + Sync>(
+	payload: OptimismDisputeGameProof,
+	root: H256,
+	dispute_factory_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
 	let storage_root = get_contract_storage_root::<H>(
 		payload.dispute_factory_proof,
 		&dispute_factory_address.0,
@@ -289,6 +444,27 @@ pub fn verify_optimism_dispute_game_proof<H: Keccak256 + Send + Sync>(
 	})
 }
 
+/// Reads the value of a storage slot of a contract from the proof, as a 32 byte big endian word
+fn get_slot_from_proof<H: Keccak256 + Send + Sync>(
+	slot: u64,
+	storage_root: H256,
+	proof: Vec<Vec<u8>>,
+) -> Result<[u8; 32], Error> {
+	let key = H::keccak256(&H256::from_low_u64_be(slot).0);
+	let proof_value = match get_value_from_proof::<H>(key.0.to_vec(), storage_root, proof)? {
+		Some(value) => value.clone(),
+		_ => Err(Error::MembershipProofVerificationFailed(format!(
+			"Value for slot {slot} not found in proof"
+		)))?,
+	};
+
+	let value = <alloy_primitives::U256 as Decodable>::decode(&mut &*proof_value)
+		.map_err(|_| Error::Custom(format!("Error decoding slot {slot} from {:?}", &proof_value)))?
+		.to_be_bytes::<32>();
+
+	Ok(value)
+}
+
 // https://github.com/ethereum-optimism/optimism/blob/f707883038d527cbf1e9f8ea513fe33255deadbc/packages/contracts-bedrock/src/dispute/lib/LibGameId.sol#L15
 fn get_game_id(game_type: u32, timestamp: u64, game_proxy: H160) -> U256 {
 	let mut bytes = U256::zero();
@@ -0,0 +1,106 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	decode_game_status, decode_respected_game_type, GameStatus, OptimismDisputeGameProof,
+	ResolvedDisputeGameProof, PERMISSIONED,
+};
+use codec::{Decode, Encode};
+use ethabi::ethereum_types::{H160, H256};
+use geth_primitives::CodecHeader;
+
+/// Packs the FaultDisputeGame's createdAt, resolvedAt and status fields the way solidity lays
+/// them out in slot 0
+fn game_status_slot(created_at: u64, resolved_at: u64, status: u8) -> [u8; 32] {
+	let mut slot = [0u8; 32];
+	slot[24..].copy_from_slice(&created_at.to_be_bytes());
+	slot[16..24].copy_from_slice(&resolved_at.to_be_bytes());
+	slot[15] = status;
+	slot
+}
+
+#[test]
+fn should_decode_game_status() {
+	let slot = game_status_slot(1_718_000_000, 1_718_302_400, 2);
+	let (status, resolved_at) = decode_game_status(slot).unwrap();
+	assert_eq!(status, GameStatus::DefenderWins);
+	assert_eq!(resolved_at, 1_718_302_400);
+
+	let slot = game_status_slot(1_718_000_000, 1_718_302_400, 1);
+	assert_eq!(decode_game_status(slot).unwrap().0, GameStatus::ChallengerWins);
+
+	// unresolved games have no resolution timestamp
+	let slot = game_status_slot(1_718_000_000, 0, 0);
+	assert_eq!(decode_game_status(slot).unwrap(), (GameStatus::InProgress, 0));
+}
+
+#[test]
+fn should_reject_unknown_game_status() {
+	let slot = game_status_slot(1_718_000_000, 1_718_302_400, 3);
+	assert!(decode_game_status(slot).is_err());
+}
+
+#[test]
+fn should_decode_respected_game_type() {
+	// respectedGameTypeUpdatedAt is packed above the respectedGameType
+	let mut slot = [0u8; 32];
+	slot[20..28].copy_from_slice(&1_718_000_000u64.to_be_bytes());
+	slot[28..].copy_from_slice(&PERMISSIONED.to_be_bytes());
+	assert_eq!(decode_respected_game_type(slot), PERMISSIONED);
+
+	assert_eq!(decode_respected_game_type([0u8; 32]), 0);
+}
+
+#[test]
+fn should_keep_the_dispute_game_proof_encoding() {
+	// every field of the header decodes from zeroes
+	let header = CodecHeader::decode(&mut &[0u8; 1024][..]).unwrap();
+	let proof = OptimismDisputeGameProof {
+		header: header.clone(),
+		withdrawal_storage_root: H256::repeat_byte(1),
+		version: H256::zero(),
+		dispute_factory_proof: vec![vec![2]],
+		dispute_game_proof: vec![vec![3]],
+		proxy: H160::repeat_byte(4),
+		extra_data: vec![5],
+		game_type: PERMISSIONED,
+		timestamp: 1_718_000_000,
+	};
+	// the layout that relayers without the resolution proofs encode
+	let legacy = (
+		header,
+		H256::repeat_byte(1),
+		H256::zero(),
+		vec![vec![2u8]],
+		vec![vec![3u8]],
+		H160::repeat_byte(4),
+		vec![5u8],
+		PERMISSIONED,
+		1_718_000_000u64,
+	)
+		.encode();
+	assert_eq!(proof.encode(), legacy);
+
+	let resolved = ResolvedDisputeGameProof {
+		dispute_game: proof,
+		portal_proof: vec![vec![6]],
+		respected_game_type_proof: vec![vec![7]],
+		game_proof: vec![vec![8]],
+		game_status_proof: vec![vec![9]],
+	};
+	let decoded = ResolvedDisputeGameProof::decode(&mut &*resolved.encode()).unwrap();
+	assert_eq!(decoded.dispute_game.encode(), legacy);
+	assert_eq!(decoded.game_status_proof, vec![vec![9]]);
+}
@@ -31,19 +31,20 @@ use ismp::{
 	host::{IsmpHost, StateMachine},
 	messaging::StateCommitmentHeight,
 };
-use pallet_ismp::{ConsensusDigest, ISMP_ID};
+use pallet_ismp::ConsensusDigest;
 use primitive_types::H256;
-use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+use sp_consensus_aura::Slot;
 use sp_runtime::{
-	app_crypto::sp_core::storage::StorageKey,
-	generic::Header,
-	traits::{BlakeTwo256, Header as _},
+	app_crypto::sp_core::storage::StorageKey, generic::Header, traits::BlakeTwo256, Digest,
 	DigestItem,
 };
 use sp_trie::StorageProof;
 use substrate_state_machine::{read_proof_check, SubstrateStateMachine};
 
-use crate::{Parachains, RelayChainOracle};
+use crate::{
+	BlockNumberWidth, HeaderFormat, ParachainHeaderFormats, Parachains, RelayChainOracle,
+	TimestampDigest,
+};
 
 /// The parachain consensus client implementation for ISMP.
 pub struct ParachainConsensusClient<T, R, S = SubstrateStateMachine<T>>(PhantomData<(T, R, S)>);
@@ -117,24 +118,35 @@ where
 				.map_err(|e| Error::Custom(format!("Error decoding parachain header: {e}")))?;
 
 			let slot_duration = Parachains::<T>::get(id).expect("Parachain with ID exists; qed");
+			let format = ParachainHeaderFormats::<T>::get(id);
 
-			// ideally all parachain headers are the same
-			let header = Header::<u32, BlakeTwo256>::decode(&mut &*header)
+			let header = format
+				.decode_header(&header)
 				.map_err(|e| Error::Custom(format!("Error decoding parachain header: {e}")))?;
 
 			let (mut timestamp, mut overlay_root, mut mmr_root) =
 				(0, H256::default(), H256::default());
-			for digest in header.digest().logs.iter() {
-				match digest {
-					DigestItem::PreRuntime(consensus_engine_id, value)
-						if *consensus_engine_id == AURA_ENGINE_ID =>
-					{
+			for digest in header.digest.logs.iter() {
+				match (digest, format.digest.timestamp) {
+					(
+						DigestItem::PreRuntime(consensus_engine_id, value),
+						TimestampDigest::Slot(engine_id),
+					) if *consensus_engine_id == engine_id => {
 						let slot = Slot::decode(&mut &value[..])
 							.map_err(|e| Error::Custom(format!("Cannot slot: {e:?}")))?;
 						timestamp = Duration::from_millis(*slot * slot_duration).as_secs();
 					},
-					DigestItem::Consensus(consensus_engine_id, value)
-						if *consensus_engine_id == ISMP_ID =>
+					(
+						DigestItem::PreRuntime(consensus_engine_id, value),
+						TimestampDigest::Millis(engine_id),
+					) if *consensus_engine_id == engine_id => {
+						let millis = u64::decode(&mut &value[..]).map_err(|e| {
+							Error::Custom(format!("Cannot decode timestamp: {e:?}"))
+						})?;
+						timestamp = Duration::from_millis(millis).as_secs();
+					},
+					(DigestItem::Consensus(consensus_engine_id, value), _)
+						if *consensus_engine_id == format.digest.ismp_engine_id =>
 					{
 						let log = ConsensusDigest::decode(&mut &value[..]);
 						if let Ok(log) = log {
@@ -155,7 +167,7 @@ where
 				Err(Error::Custom("Timestamp not found".into()))?
 			}
 
-			let height: u32 = (*header.number()).into();
+			let height = header.number;
 
 			let state_id = match host.host_state_machine() {
 				StateMachine::Kusama(_) => StateMachine::Kusama(id),
@@ -171,7 +183,7 @@ where
 						overlay_root: Some(mmr_root),
 						state_root: overlay_root, // child root
 					},
-					height: height.into(),
+					height,
 				},
 				_ => StateCommitmentHeight {
 					commitment: StateCommitment {
@@ -179,7 +191,7 @@ where
 						overlay_root: Some(overlay_root),
 						state_root: header.state_root,
 					},
-					height: height.into(),
+					height,
 				},
 			};
 
@@ -221,6 +233,36 @@ where
 	}
 }
 
+/// The fields of a parachain header that are used by the consensus client
+#[derive(Debug, Clone)]
+pub struct ParachainHeader {
+	/// The block number
+	pub number: u64,
+	/// The state root
+	pub state_root: H256,
+	/// The header digest
+	pub digest: Digest,
+}
+
+impl HeaderFormat {
+	/// Decodes a parachain header in this format. The header is never hashed, so it is decoded
+	/// with the substrate default hasher regardless of the one the parachain uses.
+	pub fn decode_header(&self, mut bytes: &[u8]) -> Result<ParachainHeader, codec::Error> {
+		let (number, state_root, digest) = match self.block_number {
+			BlockNumberWidth::U32 => {
+				let header = Header::<u32, BlakeTwo256>::decode(&mut bytes)?;
+				(header.number.into(), header.state_root, header.digest)
+			},
+			BlockNumberWidth::U64 => {
+				let header = Header::<u64, BlakeTwo256>::decode(&mut bytes)?;
+				(header.number, header.state_root, header.digest)
+			},
+		};
+
+		Ok(ParachainHeader { number, state_root, digest })
+	}
+}
+
 /// This returns the storage key for a parachain header on the relay chain.
 pub fn parachain_header_storage_key(para_id: u32) -> StorageKey {
 	let mut storage_key = frame_support::storage::storage_prefix(b"Paras", b"Heads").to_vec();
@@ -20,6 +20,9 @@
 extern crate alloc;
 extern crate core;
 
+#[cfg(test)]
+mod tests;
+
 pub mod consensus;
 mod migration;
 
@@ -33,6 +36,8 @@ use cumulus_pallet_parachain_system::{
 use cumulus_primitives_core::relay_chain;
 use ismp::{handlers, messaging::CreateConsensusState};
 pub use pallet::*;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::ConsensusEngineId;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -79,6 +84,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Parachains<T: Config> = StorageMap<_, Identity, u32, u64>;
 
+	/// The format of the headers of the parachains that this state machine is interested in.
+	/// Parachains without an entry use the default substrate header format.
+	#[pallet::storage]
+	pub type ParachainHeaderFormats<T: Config> =
+		StorageMap<_, Identity, u32, HeaderFormat, ValueQuery>;
+
 	/// Events emitted by this pallet
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -123,11 +134,11 @@ pub mod pallet {
 
 		/// Add some new parachains to the parachains whitelist
 		#[pallet::call_index(1)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(para_ids.len() as u64))]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(2 * para_ids.len() as u64))]
 		pub fn add_parachain(origin: OriginFor<T>, para_ids: Vec<ParachainData>) -> DispatchResult {
 			T::AdminOrigin::ensure_origin(origin)?;
 			for para in &para_ids {
-				Parachains::<T>::insert(para.id, para.slot_duration);
+				Pallet::<T>::insert_parachain(para);
 			}
 
 			Self::deposit_event(Event::ParachainsAdded { para_ids });
@@ -137,11 +148,12 @@ pub mod pallet {
 
 		/// Removes some parachains from the parachains whitelist
 		#[pallet::call_index(2)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(para_ids.len() as u64))]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().writes(2 * para_ids.len() as u64))]
 		pub fn remove_parachain(origin: OriginFor<T>, para_ids: Vec<u32>) -> DispatchResult {
 			T::AdminOrigin::ensure_origin(origin)?;
 			for id in &para_ids {
 				Parachains::<T>::remove(id);
+				ParachainHeaderFormats::<T>::remove(id);
 			}
 
 			Self::deposit_event(Event::ParachainsRemoved { para_ids });
@@ -215,7 +227,7 @@ pub mod pallet {
 
 			// insert the parachain ids
 			for para in &self.parachains {
-				Parachains::<T>::insert(para.id, para.slot_duration);
+				Pallet::<T>::insert_parachain(para);
 			}
 		}
 	}
@@ -228,6 +240,27 @@ impl<T: Config> Pallet<T> {
 		Parachains::<T>::iter_keys().collect()
 	}
 
+	/// Returns the parachains who's consensus updates will be inserted by the inherent data
+	/// provider, along with the format of their headers
+	pub fn parachains() -> Vec<ParachainData> {
+		Parachains::<T>::iter()
+			.map(|(id, slot_duration)| ParachainData {
+				id,
+				slot_duration,
+				header_format: ParachainHeaderFormats::<T>::get(id),
+			})
+			.collect()
+	}
+
+	fn insert_parachain(para: &ParachainData) {
+		Parachains::<T>::insert(para.id, para.slot_duration);
+		if para.header_format == HeaderFormat::default() {
+			ParachainHeaderFormats::<T>::remove(para.id);
+		} else {
+			ParachainHeaderFormats::<T>::insert(para.id, para.header_format);
+		}
+	}
+
 	/// Returns the current relay chain state
 	pub fn current_relay_chain_state() -> RelayChainState {
 		RelaychainDataProvider::<T>::current_relay_chain_state()
@@ -284,4 +317,108 @@ pub struct ParachainData {
 	pub id: u32,
 	/// parachain slot duration type
 	pub slot_duration: u64,
+	/// The format of the parachain's headers
+	#[serde(default)]
+	pub header_format: HeaderFormat,
+}
+
+/// Describes how the headers of a parachain are decoded
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Default,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	PartialEq,
+	Hash,
+	Eq,
+	MaxEncodedLen,
+	serde::Deserialize,
+	serde::Serialize,
+)]
+pub struct HeaderFormat {
+	/// The type of the block number in the header
+	pub block_number: BlockNumberWidth,
+	/// Where the timestamp and ismp digest are found in the header digest
+	pub digest: DigestLayout,
+}
+
+/// Types of the block number in parachain headers
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Default,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	PartialEq,
+	Hash,
+	Eq,
+	MaxEncodedLen,
+	serde::Deserialize,
+	serde::Serialize,
+)]
+pub enum BlockNumberWidth {
+	/// `u32` block numbers, the substrate default
+	#[default]
+	U32,
+	/// `u64` block numbers
+	U64,
+}
+
+/// The digests of a parachain header that hold its timestamp and ismp consensus digest
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	PartialEq,
+	Hash,
+	Eq,
+	MaxEncodedLen,
+	serde::Deserialize,
+	serde::Serialize,
+)]
+pub struct DigestLayout {
+	/// The pre-runtime digest that the timestamp of the block is derived from
+	pub timestamp: TimestampDigest,
+	/// The engine id of the consensus digest that holds the ismp [`pallet_ismp::ConsensusDigest`]
+	pub ismp_engine_id: ConsensusEngineId,
+}
+
+impl Default for DigestLayout {
+	fn default() -> Self {
+		Self {
+			timestamp: TimestampDigest::Slot(AURA_ENGINE_ID),
+			ismp_engine_id: pallet_ismp::ISMP_ID,
+		}
+	}
+}
+
+/// Pre-runtime digests that the timestamp of a parachain block can be derived from
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	PartialEq,
+	Hash,
+	Eq,
+	MaxEncodedLen,
+	serde::Deserialize,
+	serde::Serialize,
+)]
+pub enum TimestampDigest {
+	/// The slot the block was authored in, from the pre-runtime digest with this engine id. The
+	/// timestamp is the slot multiplied by the parachain's slot duration.
+	Slot(ConsensusEngineId),
+	/// The timestamp of the block in milliseconds, from the pre-runtime digest with this engine id
+	Millis(ConsensusEngineId),
 }
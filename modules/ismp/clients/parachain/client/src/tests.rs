@@ -0,0 +1,70 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(test)]
+
+use crate::{BlockNumberWidth, HeaderFormat, TimestampDigest};
+use codec::Encode;
+use primitive_types::H256;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::{generic::Header, traits::BlakeTwo256, Digest, DigestItem};
+
+fn digest() -> Digest {
+	Digest {
+		logs: vec![
+			DigestItem::PreRuntime(AURA_ENGINE_ID, 42u64.encode()),
+			DigestItem::Consensus(pallet_ismp::ISMP_ID, vec![1, 2, 3]),
+		],
+	}
+}
+
+#[test]
+fn decodes_substrate_headers_by_default() {
+	let header = Header::<u32, BlakeTwo256>::new(
+		225,
+		H256::repeat_byte(1),
+		H256::repeat_byte(2),
+		H256::repeat_byte(3),
+		digest(),
+	);
+
+	let decoded = HeaderFormat::default().decode_header(&header.encode()).unwrap();
+	assert_eq!(decoded.number, 225);
+	assert_eq!(decoded.state_root, H256::repeat_byte(2));
+	assert_eq!(decoded.digest, digest());
+	assert_eq!(HeaderFormat::default().digest.timestamp, TimestampDigest::Slot(AURA_ENGINE_ID));
+
+	// truncated headers can't be decoded
+	assert!(HeaderFormat::default().decode_header(&header.encode()[..10]).is_err());
+}
+
+#[test]
+fn decodes_headers_with_u64_block_numbers() {
+	let number = u32::MAX as u64 + 1;
+	let header = Header::<u64, BlakeTwo256>::new(
+		number,
+		H256::repeat_byte(1),
+		H256::repeat_byte(2),
+		H256::repeat_byte(3),
+		digest(),
+	);
+	let format = HeaderFormat { block_number: BlockNumberWidth::U64, ..Default::default() };
+
+	let decoded = format.decode_header(&header.encode()).unwrap();
+	assert_eq!(decoded.number, number);
+	assert_eq!(decoded.state_root, H256::repeat_byte(2));
+
+	// the block number doesn't fit the default format
+	assert!(HeaderFormat::default().decode_header(&header.encode()).is_err());
+}
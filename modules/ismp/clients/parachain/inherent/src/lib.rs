@@ -21,15 +21,13 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use codec::{Decode, Encode};
 use cumulus_relay_chain_interface::RelayChainInterface;
-use sp_runtime::{
-	generic::{BlockId, Header},
-	traits::{BlakeTwo256, Block as BlockT},
-};
+use sp_api::ApiExt;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 use ismp::{consensus::StateMachineId, host::StateMachine, messaging::ConsensusMessage};
 use ismp_parachain::{
 	consensus::{parachain_header_storage_key, ParachainConsensusProof},
-	PARACHAIN_CONSENSUS_ID,
+	ParachainData, PARACHAIN_CONSENSUS_ID,
 };
 use ismp_parachain_runtime_api::IsmpParachainApi;
 use pallet_ismp_runtime_api::IsmpRuntimeApi;
@@ -51,11 +49,26 @@ impl ConsensusInherentProvider {
 		C::Api: IsmpParachainApi<B> + IsmpRuntimeApi<B, B::Hash>,
 		B: BlockT,
 	{
-		let para_ids = client.runtime_api().para_ids(parent)?;
+		let version = client
+			.runtime_api()
+			.api_version::<dyn IsmpParachainApi<B>>(parent)?
+			.ok_or_else(|| anyhow!("IsmpParachainApi not found in the runtime"))?;
+		// Runtimes before version 2 only expose the para ids, their headers use the default format
+		let parachains = if version >= 2 {
+			client.runtime_api().parachains(parent)?
+		} else {
+			// the slot duration isn't used by the inherent provider
+			client
+				.runtime_api()
+				.para_ids(parent)?
+				.into_iter()
+				.map(|id| ParachainData { id, slot_duration: 0, header_format: Default::default() })
+				.collect()
+		};
 
-		log::trace!("ParaIds from runtime: {para_ids:?}");
+		log::trace!("Parachains from runtime: {parachains:?}");
 
-		if para_ids.is_empty() {
+		if parachains.is_empty() {
 			return Ok(ConsensusInherentProvider(None));
 		}
 
@@ -73,7 +86,8 @@ impl ConsensusInherentProvider {
 			.ok_or_else(|| anyhow!("Relay chain header for height {} not found", state.number))?;
 
 		let mut para_ids_to_fetch = vec![];
-		for id in para_ids {
+		for para in parachains {
+			let id = para.id;
 			let Some(head) = relay_chain_interface
 				.get_storage_by_key(relay_header.hash(), parachain_header_storage_key(id).as_ref())
 				.await?
@@ -86,8 +100,12 @@ impl ConsensusInherentProvider {
 				continue;
 			};
 
-			let Ok(header) = Header::<u32, BlakeTwo256>::decode(&mut &intermediate[..]) else {
-				continue;
+			let header = match para.header_format.decode_header(&intermediate) {
+				Ok(header) => header,
+				Err(err) => {
+					log::trace!("Failed to decode parachain header for {id}: {err:?}");
+					continue;
+				},
 			};
 
 			let state_id = match client.runtime_api().host_state_machine(parent)? {
@@ -103,7 +121,7 @@ impl ConsensusInherentProvider {
 				)?
 				.unwrap_or_default();
 
-			if height >= header.number as u64 {
+			if height >= header.number {
 				log::trace!("Skipping stale height {height} for parachain {id}");
 				continue;
 			}
@@ -23,13 +23,20 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 use cumulus_pallet_parachain_system::RelayChainState;
+use ismp_parachain::ParachainData;
 
 sp_api::decl_runtime_apis! {
 	/// Ismp Parachain consensus client runtime APIs
+	#[api_version(2)]
 	pub trait IsmpParachainApi {
 		/// Return all the para_ids this runtime is interested in. Used by the inherent provider
 		fn para_ids() -> Vec<u32>;
 
+		/// Return all the parachains this runtime is interested in, along with the format of their
+		/// headers. Used by the inherent provider
+		#[api_version(2)]
+		fn parachains() -> Vec<ParachainData>;
+
 		/// Return the current relay chain state.
 		fn current_relay_chain_state() -> RelayChainState;
 	}
@@ -95,6 +95,30 @@ pub fn req_res_commitment_key<H: Keccak256>(item: RequestResponse) -> Vec<Vec<u8
 	keys
 }
 
+/// Same as [`req_res_commitment_key`], but returns the storage slots before they're hashed, for
+/// chains whose state trees aren't keyed by the hash of the slot.
+pub fn req_res_commitment_unhashed_key<H: Keccak256>(item: RequestResponse) -> Vec<Vec<u8>> {
+	let mut keys = vec![];
+	match item {
+		RequestResponse::Request(requests) =>
+			for req in requests {
+				let commitment = hash_request::<H>(&req);
+				let key =
+					derive_unhashed_map_key::<H>(commitment.0.to_vec(), REQUEST_COMMITMENTS_SLOT);
+				keys.push(add_off_set_to_map_key(&key.0, 1).0.to_vec())
+			},
+		RequestResponse::Response(responses) =>
+			for res in responses {
+				let commitment = hash_response::<H>(&res);
+				let key =
+					derive_unhashed_map_key::<H>(commitment.0.to_vec(), RESPONSE_COMMITMENTS_SLOT);
+				keys.push(add_off_set_to_map_key(&key.0, 1).0.to_vec())
+			},
+	}
+
+	keys
+}
+
 pub fn req_res_receipt_keys<H: Keccak256>(item: RequestResponse) -> Vec<Vec<u8>> {
 	let mut keys = vec![];
 	match item {
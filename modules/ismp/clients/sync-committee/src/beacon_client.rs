@@ -14,26 +14,34 @@
 // limitations under the License.
 
 use alloc::{collections::BTreeMap, format, string::ToString};
-use arbitrum_verifier::verify_arbitrum_payload;
+use arbitrum_verifier::{verify_arbitrum_bold, verify_arbitrum_payload};
 use codec::{Decode, Encode};
 use evm_common::construct_intermediate_state;
 
 use crate::{
-	pallet::{self, LayerTwos},
-	types::{BeaconClientUpdate, ConsensusState, L2Consensus},
+	pallet::{self, L2StateTries, LayerTwos},
+	types::{BeaconClientUpdate, ConsensusState, L2Consensus, L2StateTrie},
 };
 use evm_common::EvmStateMachine;
 use ismp::{
 	consensus::{
-		ConsensusClient, ConsensusClientId, ConsensusStateId, StateMachineClient,
-		VerifiedCommitments,
+		ConsensusClient, ConsensusClientId, ConsensusStateId, IntermediateState,
+		StateMachineClient, VerifiedCommitments,
 	},
 	error::Error,
 	host::{IsmpHost, StateMachine},
 	messaging::StateCommitmentHeight,
 };
-use op_verifier::{verify_optimism_dispute_game_proof, verify_optimism_payload};
+use op_verifier::{
+	verify_optimism_dispute_game_proof, verify_optimism_payload,
+	verify_resolved_optimism_dispute_game_proof,
+};
 use sync_committee_primitives::constants::Config;
+use zk_rollup_verifier::{
+	linea::{verify_linea_payload, LineaStateMachine},
+	scroll::verify_scroll_payload,
+	zksync::{verify_zksync_payload, ZkSyncStateMachine},
+};
 
 use crate::prelude::*;
 
@@ -82,10 +90,15 @@ impl<
 	) -> Result<(Vec<u8>, VerifiedCommitments), Error> {
 		let BeaconClientUpdate {
 			mut l2_oracle_payload,
-			mut dispute_game_payload,
 			consensus_update,
 			mut arbitrum_payload,
-		} = BeaconClientUpdate::decode(&mut &consensus_proof[..])
+			mut arbitrum_bold,
+			mut zksync_payload,
+			mut scroll_payload,
+			mut linea_payload,
+			mut resolved_dispute_game_payload,
+			..
+		} = BeaconClientUpdate::decode_versioned(&consensus_proof)
 			.map_err(|_| Error::Custom("Cannot decode beacon client update".to_string()))?;
 
 		let consensus_state = ConsensusState::decode(&mut &trusted_consensus_state[..])
@@ -98,8 +111,7 @@ impl<
 			)
 			.map_err(|e| Error::Custom(format!("{:?}", e)))?;
 
-		let mut state_machine_map: BTreeMap<StateMachine, Vec<StateCommitmentHeight>> =
-			BTreeMap::new();
+		let mut state_machine_map: VerifiedCommitments = BTreeMap::new();
 
 		let state_root = consensus_update.execution_payload.state_root;
 		let intermediate_state = construct_intermediate_state(
@@ -110,77 +122,111 @@ impl<
 			&state_root[..],
 		)?;
 
-		let ethereum_state_commitment_height = StateCommitmentHeight {
-			commitment: intermediate_state.commitment,
-			height: intermediate_state.height.height,
-		};
-
-		let mut state_commitment_vec: Vec<StateCommitmentHeight> = Vec::new();
-		state_commitment_vec.push(ethereum_state_commitment_height);
-
-		state_machine_map.insert(StateMachine::Evm(consensus_state.chain_id), state_commitment_vec);
+		insert_state_commitment(
+			&mut state_machine_map,
+			StateMachine::Evm(consensus_state.chain_id),
+			intermediate_state,
+		);
 
 		let l2_consensus = consensus_state.l2_consensus.clone();
 
 		for (state_machine, consensus_mechanic) in l2_consensus {
-			match consensus_mechanic {
-				L2Consensus::ArbitrumOrbit(rollup_core_address) => {
-					if let Some(arbitrum_payload) = arbitrum_payload.remove(&state_machine) {
-						let state = verify_arbitrum_payload::<H>(
-							arbitrum_payload,
+			let state = match consensus_mechanic {
+				L2Consensus::ArbitrumOrbit(rollup_core_address) => arbitrum_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_arbitrum_payload::<H>(
+							payload,
 							state_root,
 							rollup_core_address,
 							consensus_state_id.clone(),
-						)?;
-
-						let arbitrum_state_commitment_height = StateCommitmentHeight {
-							commitment: state.commitment,
-							height: state.height.height,
-						};
-
-						let mut state_commitment_vec: Vec<StateCommitmentHeight> = Vec::new();
-						state_commitment_vec.push(arbitrum_state_commitment_height);
-						state_machine_map.insert(state_machine, state_commitment_vec);
-					}
-				},
-				L2Consensus::OpL2Oracle(l2_oracle) => {
-					if let Some(payload) = l2_oracle_payload.remove(&state_machine) {
-						let state = verify_optimism_payload::<H>(
+						)
+					})
+					.transpose()?,
+				L2Consensus::OpL2Oracle(l2_oracle) => l2_oracle_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_optimism_payload::<H>(
 							payload,
 							state_root,
 							l2_oracle,
 							consensus_state_id.clone(),
-						)?;
-
-						let state_commitment_height = StateCommitmentHeight {
-							commitment: state.commitment,
-							height: state.height.height,
-						};
-
-						let mut state_commitment_vec: Vec<StateCommitmentHeight> = Vec::new();
-						state_commitment_vec.push(state_commitment_height);
-						state_machine_map.insert(state_machine, state_commitment_vec);
-					}
-				},
-				L2Consensus::OpFaultProofs(dispute_game_factory) => {
-					if let Some(payload) = dispute_game_payload.remove(&state_machine) {
-						let state = verify_optimism_dispute_game_proof::<H>(
+						)
+					})
+					.transpose()?,
+				L2Consensus::OpFaultProofs(dispute_game_factory) => resolved_dispute_game_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_optimism_dispute_game_proof::<H>(
 							payload,
 							state_root,
 							dispute_game_factory,
 							consensus_state_id.clone(),
-						)?;
-
-						let state_commitment_height = StateCommitmentHeight {
-							commitment: state.commitment,
-							height: state.height.height,
-						};
+						)
+					})
+					.transpose()?,
+				L2Consensus::OpFaultProofGames(params) => resolved_dispute_game_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_resolved_optimism_dispute_game_proof::<H>(
+							payload,
+							state_root,
+							consensus_update.execution_payload.timestamp,
+							params.dispute_game_factory,
+							params.optimism_portal,
+							params.dispute_game_finality_delay,
+							consensus_state_id.clone(),
+						)
+					})
+					.transpose()?,
+				L2Consensus::ArbitrumBold(rollup_core_address) => arbitrum_bold
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_arbitrum_bold::<H>(
+							payload,
+							state_root,
+							rollup_core_address,
+							consensus_state_id.clone(),
+						)
+					})
+					.transpose()?,
+				L2Consensus::ZkSyncEra(diamond_proxy) => zksync_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_zksync_payload::<H>(
+							payload,
+							state_root,
+							diamond_proxy,
+							consensus_state_id.clone(),
+						)
+					})
+					.transpose()?,
+				L2Consensus::Scroll(scroll_chain) => scroll_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_scroll_payload::<H>(
+							payload,
+							state_root,
+							scroll_chain,
+							consensus_state_id.clone(),
+						)
+					})
+					.transpose()?,
+				L2Consensus::Linea(linea_rollup) => linea_payload
+					.remove(&state_machine)
+					.map(|payload| {
+						verify_linea_payload::<H>(
+							payload,
+							state_root,
+							linea_rollup,
+							consensus_state_id.clone(),
+						)
+					})
+					.transpose()?,
+			};
 
-						let mut state_commitment_vec: Vec<StateCommitmentHeight> = Vec::new();
-						state_commitment_vec.push(state_commitment_height);
-						state_machine_map.insert(state_machine, state_commitment_vec);
-					}
-				},
+			if let Some(state) = state {
+				insert_state_commitment(&mut state_machine_map, state_machine, state);
 			}
 		}
 
@@ -214,12 +260,29 @@ impl<
 		match id {
 			StateMachine::Evm(chain_id)
 				if supported_chain_id(chain_id) || LayerTwos::<T>::contains_key(id) =>
-				Ok(Box::new(<EvmStateMachine<H, T>>::default())),
+				match L2StateTries::<T>::get(id) {
+					None => Ok(Box::new(<EvmStateMachine<H, T>>::default())),
+					Some(L2StateTrie::ZkSync) =>
+						Ok(Box::new(<ZkSyncStateMachine<H, T>>::default())),
+					Some(L2StateTrie::Linea) => Ok(Box::new(<LineaStateMachine<H, T>>::default())),
+				},
 			_ => Err(Error::Custom("State machine not supported".to_string())),
 		}
 	}
 }
 
+/// Records the verified state commitment of a state machine in the map of verified commitments
+fn insert_state_commitment(
+	state_machine_map: &mut VerifiedCommitments,
+	state_machine: StateMachine,
+	state: IntermediateState,
+) {
+	state_machine_map.insert(
+		state_machine,
+		vec![StateCommitmentHeight { commitment: state.commitment, height: state.height.height }],
+	);
+}
+
 /// Mainnet and L2 chain Ids
 pub const ARBITRUM_CHAIN_ID: u32 = 42161;
 pub const OPTIMISM_CHAIN_ID: u32 = 10;
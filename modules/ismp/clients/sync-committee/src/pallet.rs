@@ -17,7 +17,7 @@ pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use crate::types::{ConsensusState, L2Consensus};
+	use crate::types::{ConsensusState, L2Consensus, L2StateTrie};
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 	use ismp::{
@@ -58,6 +58,12 @@ pub mod pallet {
 	#[pallet::getter(fn layer_twos)]
 	pub type LayerTwos<T: Config> = StorageMap<_, Twox64Concat, StateMachine, bool, OptionQuery>;
 
+	/// L2s whose state is not committed to in an ethereum merkle patricia trie
+	#[pallet::storage]
+	#[pallet::getter(fn l2_state_tries)]
+	pub type L2StateTries<T: Config> =
+		StorageMap<_, Twox64Concat, StateMachine, L2StateTrie, OptionQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T>
 	where
@@ -65,7 +71,7 @@ pub mod pallet {
 	{
 		/// Add a new l2 consensus to the sync committee consensus state
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 2))]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 3))]
 		pub fn add_l2_consensus(
 			origin: OriginFor<T>,
 			state_machine_id: StateMachineId,
@@ -83,6 +89,10 @@ pub mod pallet {
 				codec::Decode::decode(&mut &encoded_consensus_state[..])
 					.map_err(|_| Error::<T>::ErrorDecodingConsensusState)?;
 
+			match l2_consensus.state_trie() {
+				Some(state_trie) => L2StateTries::<T>::insert(state_machine, state_trie),
+				None => L2StateTries::<T>::remove(state_machine),
+			}
 			consensus_state.l2_consensus.insert(state_machine, l2_consensus);
 			LayerTwos::<T>::insert(state_machine_id.state_id, true);
 
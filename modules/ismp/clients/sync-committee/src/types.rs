@@ -13,12 +13,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use alloc::collections::BTreeMap;
-use arbitrum_verifier::ArbitrumPayloadProof;
-use codec::{Decode, Encode};
+use arbitrum_verifier::{ArbitrumBoldProof, ArbitrumPayloadProof};
+use codec::{Decode, DecodeAll, Encode};
 use ethabi::ethereum_types::H160;
 use ismp::host::StateMachine;
-use op_verifier::{OptimismDisputeGameProof, OptimismPayloadProof};
+use op_verifier::{OptimismDisputeGameProof, OptimismPayloadProof, ResolvedDisputeGameProof};
 use sync_committee_primitives::types::{VerifierState, VerifierStateUpdate};
+use zk_rollup_verifier::{
+	linea::LineaPayloadProof, scroll::ScrollPayloadProof, zksync::ZkSyncPayloadProof,
+};
 
 #[derive(Debug, Encode, Decode, Clone)]
 pub struct ConsensusState {
@@ -32,8 +35,47 @@ pub struct ConsensusState {
 pub struct BeaconClientUpdate {
 	pub consensus_update: VerifierStateUpdate,
 	pub l2_oracle_payload: BTreeMap<StateMachine, OptimismPayloadProof>,
+	/// Dispute game proofs without the game's resolution, these are no longer accepted for any
+	/// L2 and are only kept so that updates from older relayers still decode
 	pub dispute_game_payload: BTreeMap<StateMachine, OptimismDisputeGameProof>,
 	pub arbitrum_payload: BTreeMap<StateMachine, ArbitrumPayloadProof>,
+	pub arbitrum_bold: BTreeMap<StateMachine, ArbitrumBoldProof>,
+	pub zksync_payload: BTreeMap<StateMachine, ZkSyncPayloadProof>,
+	pub scroll_payload: BTreeMap<StateMachine, ScrollPayloadProof>,
+	pub linea_payload: BTreeMap<StateMachine, LineaPayloadProof>,
+	pub resolved_dispute_game_payload: BTreeMap<StateMachine, ResolvedDisputeGameProof>,
+}
+
+/// The update before payloads for Arbitrum BoLD and the ZK rollups were added to it, which is
+/// still accepted so that relayers can be upgraded after the runtime.
+#[derive(Encode, Decode)]
+pub struct LegacyBeaconClientUpdate {
+	pub consensus_update: VerifierStateUpdate,
+	pub l2_oracle_payload: BTreeMap<StateMachine, OptimismPayloadProof>,
+	pub dispute_game_payload: BTreeMap<StateMachine, OptimismDisputeGameProof>,
+	pub arbitrum_payload: BTreeMap<StateMachine, ArbitrumPayloadProof>,
+}
+
+impl BeaconClientUpdate {
+	/// Decodes an update in either the current or the legacy encoding
+	pub fn decode_versioned(bytes: &[u8]) -> Result<Self, codec::Error> {
+		if let Ok(update) = Self::decode_all(&mut &bytes[..]) {
+			return Ok(update);
+		}
+
+		let legacy = LegacyBeaconClientUpdate::decode_all(&mut &bytes[..])?;
+		Ok(Self {
+			consensus_update: legacy.consensus_update,
+			l2_oracle_payload: legacy.l2_oracle_payload,
+			dispute_game_payload: legacy.dispute_game_payload,
+			arbitrum_payload: legacy.arbitrum_payload,
+			arbitrum_bold: Default::default(),
+			zksync_payload: Default::default(),
+			scroll_payload: Default::default(),
+			linea_payload: Default::default(),
+			resolved_dispute_game_payload: Default::default(),
+		})
+	}
 }
 
 /// Description of the various consensus mechanics supported for ethereum L2s
@@ -45,4 +87,46 @@ pub enum L2Consensus {
 	OpL2Oracle(H160),
 	/// Op Stack Dispute game factory address
 	OpFaultProofs(H160),
+	/// Arbitrum chains running the BoLD dispute protocol, Rollup Core Address
+	ArbitrumBold(H160),
+	/// zkSync Era chains Diamond Proxy Address
+	ZkSyncEra(H160),
+	/// Scroll ScrollChain Address
+	Scroll(H160),
+	/// Linea LineaRollup Address
+	Linea(H160),
+	/// Op Stack fault proofs, checking that dispute games are of the portal's respected game type
+	/// and have been resolved in favour of the defender
+	OpFaultProofGames(OpFaultProofParams),
+}
+
+impl L2Consensus {
+	/// The trie that the L2 commits to its state in, if it is not an ethereum merkle patricia trie
+	pub fn state_trie(&self) -> Option<L2StateTrie> {
+		match self {
+			L2Consensus::ZkSyncEra(_) => Some(L2StateTrie::ZkSync),
+			L2Consensus::Linea(_) => Some(L2StateTrie::Linea),
+			_ => None,
+		}
+	}
+}
+
+/// State tries of L2s that differ from the ethereum merkle patricia trie
+#[derive(Encode, Decode, Debug, Clone, Copy, scale_info::TypeInfo, Eq, PartialEq)]
+pub enum L2StateTrie {
+	/// zkSync's blake2s sparse merkle tree
+	ZkSync,
+	/// Linea's MiMC sparse merkle tree
+	Linea,
+}
+
+/// Contracts and parameters used to verify Op Stack fault proofs
+#[derive(Encode, Decode, Debug, Clone, scale_info::TypeInfo, Eq, PartialEq)]
+pub struct OpFaultProofParams {
+	/// Dispute game factory address
+	pub dispute_game_factory: H160,
+	/// OptimismPortal address, only games of its respected game type are accepted
+	pub optimism_portal: H160,
+	/// Number of seconds that must have passed since a game was resolved before it is accepted
+	pub dispute_game_finality_delay: u64,
 }
@@ -0,0 +1,58 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifiers for the state commitments of ZK rollups settling on ethereum. Each rollup's L1
+//! contract records the state roots of the batches whose validity proofs it has verified, these are
+//! proven against the ethereum state root from the sync committee.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(unused_variables)]
+extern crate alloc;
+
+#[cfg(test)]
+mod tests;
+
+pub mod linea;
+pub mod scroll;
+pub mod zksync;
+
+use alloc::format;
+use alloy_rlp::Decodable;
+use ethabi::ethereum_types::H256;
+use evm_common::{get_value_from_proof, prelude::*};
+use ismp::{error::Error, messaging::Keccak256};
+
+/// Reads the 32 byte word stored at the given trie key of a contract's storage from the proof
+pub(crate) fn get_word_from_proof<H: Keccak256 + Send + Sync>(
+	key: H256,
+	storage_root: H256,
+	proof: Vec<Vec<u8>>,
+) -> Result<[u8; 32], Error> {
+	let proof_value = match get_value_from_proof::<H>(key.0.to_vec(), storage_root, proof)? {
+		Some(value) => value.clone(),
+		_ => Err(Error::MembershipProofVerificationFailed("Value not found in proof".to_string()))?,
+	};
+
+	let value = <alloy_primitives::U256 as Decodable>::decode(&mut &*proof_value)
+		.map_err(|_| Error::Custom(format!("Error decoding storage value {:?}", &proof_value)))?
+		.to_be_bytes::<32>();
+
+	Ok(value)
+}
+
+/// Trie key for a contract storage slot that holds a value type
+pub(crate) fn slot_key<H: Keccak256>(slot: u64) -> H256 {
+	H::keccak256(&H256::from_low_u64_be(slot).0)
+}
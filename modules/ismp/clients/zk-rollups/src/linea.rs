@@ -0,0 +1,487 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Linea records the number, timestamp and state root of its last finalized block in the
+//! LineaRollup contract on L1. The state root is the root of Linea's sparse merkle tree, which is
+//! hashed with MiMC rather than keccak, so it cannot be used with the evm state machine client and
+//! state proofs are instead verified by the [`LineaStateMachine`].
+
+use crate::{get_word_from_proof, slot_key};
+use alloc::{collections::BTreeMap, format};
+use codec::{Decode, Encode};
+use ethabi::ethereum_types::{H160, H256, U256, U512};
+use evm_common::{
+	derive_map_key, get_contract_storage_root, prelude::*, req_res_commitment_unhashed_key,
+	req_res_receipt_keys,
+};
+use ismp::{
+	consensus::{
+		ConsensusStateId, IntermediateState, StateCommitment, StateMachineClient,
+		StateMachineHeight, StateMachineId,
+	},
+	error::Error,
+	host::{IsmpHost, StateMachine},
+	messaging::{Keccak256, Proof},
+	router::RequestResponse,
+};
+use pallet_ismp_host_executive::EvmHosts;
+
+/// Slot for currentTimestamp in the LineaRollup contract
+pub const CURRENT_TIMESTAMP_SLOT: u64 = 249;
+/// Slot for currentL2BlockNumber in the LineaRollup contract
+pub const CURRENT_L2_BLOCK_NUMBER_SLOT: u64 = 250;
+/// Slot for the stateRootHashes map in the LineaRollup contract
+pub const STATE_ROOT_HASHES_SLOT: u64 = 251;
+/// Depth of the sparse merkle trees of accounts and of contract storage
+pub const TREE_DEPTH: usize = 40;
+/// Modulus of the scalar field of BLS12-377, which MiMC operates in
+const MODULUS: U256 =
+	U256([0x0a11800000000001, 0x59aa76fed0000001, 0x60b44d1e5c37b001, 0x12ab655e9a2ca556]);
+/// Number of rounds of the MiMC block cipher
+const MIMC_ROUNDS: usize = 62;
+/// Seed that the MiMC round constants are derived from
+const MIMC_SEED: &[u8] = b"seed";
+
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct LineaPayloadProof {
+	/// Number of the last finalized L2 block
+	pub block_number: u64,
+	/// Timestamp of the last finalized L2 block
+	pub timestamp: u64,
+	/// Membership proof for the LineaRollup contract account in the ethereum world trie
+	pub contract_proof: Vec<Vec<u8>>,
+	/// Membership proof for currentL2BlockNumber
+	pub block_number_proof: Vec<Vec<u8>>,
+	/// Membership proof for currentTimestamp
+	pub timestamp_proof: Vec<Vec<u8>>,
+	/// Membership proof for the state root of the block in the stateRootHashes map
+	pub state_root_proof: Vec<Vec<u8>>,
+}
+
+/// Verifies the state root of the last block finalized by the LineaRollup contract
+pub fn verify_linea_payload<H: Keccak256 + Send + Sync>(
+	payload: LineaPayloadProof,
+	root: H256,
+	linea_rollup_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
+	let storage_root =
+		get_contract_storage_root::<H>(payload.contract_proof, &linea_rollup_address.0, root)?;
+
+	let block_number = get_word_from_proof::<H>(
+		slot_key::<H>(CURRENT_L2_BLOCK_NUMBER_SLOT),
+		storage_root,
+		payload.block_number_proof,
+	)?;
+	if U256::from_big_endian(&block_number) != payload.block_number.into() {
+		Err(Error::MembershipProofVerificationFailed(
+			"Block number is not the last finalized block".to_string(),
+		))?
+	}
+
+	let timestamp = get_word_from_proof::<H>(
+		slot_key::<H>(CURRENT_TIMESTAMP_SLOT),
+		storage_root,
+		payload.timestamp_proof,
+	)?;
+	if U256::from_big_endian(&timestamp) != payload.timestamp.into() {
+		Err(Error::MembershipProofVerificationFailed(
+			"Timestamp is not the timestamp of the last finalized block".to_string(),
+		))?
+	}
+
+	let mut key = [0u8; 32];
+	U256::from(payload.block_number).to_big_endian(&mut key);
+	let state_root = get_word_from_proof::<H>(
+		derive_map_key::<H>(key.to_vec(), STATE_ROOT_HASHES_SLOT),
+		storage_root,
+		payload.state_root_proof,
+	)?;
+
+	Ok(IntermediateState {
+		height: StateMachineHeight {
+			id: StateMachineId {
+				// note: This will state machine id should not be used to store the state commitment
+				state_id: StateMachine::Evm(Default::default()),
+				consensus_state_id,
+			},
+			height: payload.block_number,
+		},
+		commitment: StateCommitment {
+			timestamp: payload.timestamp,
+			overlay_root: None,
+			state_root: state_root.into(),
+		},
+	})
+}
+
+/// MiMC hasher over the scalar field of BLS12-377 with exponent 17, in the Miyaguchi-Preneel
+/// construction used by gnark and the Linea contracts.
+/// https://github.com/Consensys/linea-contracts/blob/main/contracts/lib/Mimc.sol
+pub struct Mimc {
+	constants: Vec<U256>,
+}
+
+impl Mimc {
+	/// Derives the round constants, each is the keccak hash of the one before it
+	pub fn new<H: Keccak256>() -> Self {
+		let mut seed = H::keccak256(MIMC_SEED);
+		let constants = (0..MIMC_ROUNDS)
+			.map(|_| {
+				seed = H::keccak256(&seed.0);
+				U256::from_big_endian(&seed.0) % MODULUS
+			})
+			.collect();
+		Self { constants }
+	}
+
+	/// Hashes a sequence of field elements
+	pub fn hash(&self, elements: &[U256]) -> Result<H256, Error> {
+		let mut state = U256::zero();
+		for element in elements {
+			if *element >= MODULUS {
+				Err(Error::Custom(format!("{element:?} is not a field element")))?
+			}
+			let encrypted = self.encrypt(*element, state);
+			state = add_mod(add_mod(state, encrypted), *element);
+		}
+
+		let mut hash = H256::zero();
+		state.to_big_endian(&mut hash.0);
+		Ok(hash)
+	}
+
+	/// Hashes bytes made up of 32 byte big endian field elements
+	pub fn hash_bytes(&self, bytes: &[u8]) -> Result<H256, Error> {
+		if bytes.len() % 32 != 0 {
+			Err(Error::Custom(format!("Cannot hash {} bytes with MiMC", bytes.len())))?
+		}
+		self.hash(&bytes.chunks(32).map(U256::from_big_endian).collect::<Vec<_>>())
+	}
+
+	fn encrypt(&self, message: U256, key: U256) -> U256 {
+		let mut message = message;
+		for constant in &self.constants {
+			let base = add_mod(add_mod(message, key), *constant);
+			let square = mul_mod(base, base);
+			let pow4 = mul_mod(square, square);
+			let pow8 = mul_mod(pow4, pow4);
+			let pow16 = mul_mod(pow8, pow8);
+			message = mul_mod(pow16, base);
+		}
+		add_mod(message, key)
+	}
+}
+
+fn add_mod(a: U256, b: U256) -> U256 {
+	// Both are less than the 253 bit modulus, so the sum cannot overflow
+	(a + b) % MODULUS
+}
+
+fn mul_mod(a: U256, b: U256) -> U256 {
+	let product = a.full_mul(b) % U512::from(MODULUS);
+	U256([product.0[0], product.0[1], product.0[2], product.0[3]])
+}
+
+/// Hashes a 32 byte word by splitting it into two 16 byte field elements, this is how storage
+/// slots and their values are hashed into the storage tree.
+pub fn hash_word(mimc: &Mimc, word: H256) -> Result<H256, Error> {
+	mimc.hash(&[U256::from_big_endian(&word.0[..16]), U256::from_big_endian(&word.0[16..])])
+}
+
+/// Hashes an account as it is encoded in its leaf of the account tree: the nonce, balance, storage
+/// root, MiMC code hash, keccak code hash and code size as 32 byte words.
+pub fn hash_account(mimc: &Mimc, account: &[u8]) -> Result<H256, Error> {
+	if account.len() != 192 {
+		Err(Error::Custom(format!("Invalid account of length {}", account.len())))?
+	}
+	let word = |index: usize| U256::from_big_endian(&account[index * 32..(index + 1) * 32]);
+	mimc.hash(&[
+		word(0),
+		word(1),
+		word(2),
+		word(3),
+		U256::from_big_endian(&account[128..144]),
+		U256::from_big_endian(&account[144..160]),
+		word(5),
+	])
+}
+
+/// The opening of a leaf of a sparse merkle tree. Leaves are kept in a linked list sorted by their
+/// hashed keys, so that the absence of a key is proven by two adjacent leaves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeafOpening {
+	/// Index of the previous leaf in the list
+	pub prev: u64,
+	/// Index of the next leaf in the list
+	pub next: u64,
+	/// Hash of the key
+	pub hkey: H256,
+	/// Hash of the value
+	pub hval: H256,
+}
+
+impl LeafOpening {
+	fn decode(bytes: &[u8]) -> Result<Self, Error> {
+		if bytes.len() != 128 {
+			Err(Error::Custom(format!("Invalid leaf opening of length {}", bytes.len())))?
+		}
+		let index = |word: &[u8]| {
+			U256::from_big_endian(word)
+				.try_into()
+				.map_err(|_| Error::Custom("Invalid leaf index".to_string()))
+		};
+		Ok(Self {
+			prev: index(&bytes[..32])?,
+			next: index(&bytes[32..64])?,
+			hkey: H256::from_slice(&bytes[64..96]),
+			hval: H256::from_slice(&bytes[96..]),
+		})
+	}
+}
+
+/// A merkle proof for a leaf of a sparse merkle tree, as returned by `linea_getProof`
+#[derive(Encode, Decode, Debug, Clone)]
+pub struct LineaMerkleProof {
+	/// Index of the leaf
+	pub leaf_index: u64,
+	/// The next free node followed by the root of the subtree of leaves, the sibling hashes from
+	/// the top of the subtree down to the leaf, and lastly the opening of the leaf
+	pub proof_related_nodes: Vec<Vec<u8>>,
+}
+
+#[derive(Encode, Decode, Debug, Clone)]
+pub enum LineaStorageProof {
+	/// Proof for a slot that is set
+	Inclusion {
+		/// Value stored in the slot
+		value: H256,
+		/// Proof for the leaf of the slot
+		proof: LineaMerkleProof,
+	},
+	/// Proof for an empty slot, made up of the leaves adjacent to where it would be
+	Exclusion {
+		/// Proof for the leaf with the greatest hashed key less than the slot's
+		left: LineaMerkleProof,
+		/// Proof for the leaf with the least hashed key greater than the slot's
+		right: LineaMerkleProof,
+	},
+}
+
+#[derive(Encode, Decode, Debug, Clone)]
+pub struct LineaAccountProof {
+	/// The account as it is encoded in its leaf
+	pub account: Vec<u8>,
+	/// Proof for the leaf of the account in the account tree
+	pub proof: LineaMerkleProof,
+	/// Proofs for the slots requested from the account, in the order of the requested keys
+	pub storage_proofs: Vec<LineaStorageProof>,
+}
+
+#[derive(Encode, Decode, Clone)]
+pub struct LineaStateProof {
+	/// A map of contract address to the proofs for the contract's account and storage
+	pub accounts: BTreeMap<Vec<u8>, LineaAccountProof>,
+}
+
+/// Verifies a merkle proof against the root of a sparse merkle tree and returns the opening of the
+/// proven leaf
+/// https://github.com/Consensys/linea-contracts/blob/main/contracts/lib/SparseMerkleProof.sol
+pub fn verify_merkle_proof(
+	mimc: &Mimc,
+	proof: &LineaMerkleProof,
+	root: H256,
+) -> Result<LeafOpening, Error> {
+	let nodes = &proof.proof_related_nodes;
+	if nodes.len() != TREE_DEPTH + 2 || nodes[0].len() != 64 {
+		Err(Error::Custom("Invalid sparse merkle proof".to_string()))?
+	}
+	let leaf = &nodes[TREE_DEPTH + 1];
+	let opening = LeafOpening::decode(leaf)?;
+
+	let mut hash = mimc.hash_bytes(leaf)?;
+	for height in 0..TREE_DEPTH {
+		let sibling = &nodes[TREE_DEPTH - height];
+		if sibling.len() != 32 {
+			Err(Error::Custom("Invalid sibling in sparse merkle proof".to_string()))?
+		}
+		hash = if (proof.leaf_index >> height) & 1 == 1 {
+			mimc.hash_bytes(&[&sibling[..], &hash.0[..]].concat())?
+		} else {
+			mimc.hash_bytes(&[&hash.0[..], &sibling[..]].concat())?
+		};
+	}
+
+	if hash.0[..] != nodes[0][32..] ||
+		mimc.hash_bytes(&[&nodes[0][..32], &hash.0[..]].concat())? != root
+	{
+		Err(Error::MembershipProofVerificationFailed(
+			"Sparse merkle proof does not lead to the root".to_string(),
+		))?
+	}
+
+	Ok(opening)
+}
+
+/// Verifies the value of a storage slot against the storage root of its contract, empty slots are
+/// returned as `None`
+fn verify_storage_proof(
+	mimc: &Mimc,
+	slot: H256,
+	storage_root: H256,
+	proof: LineaStorageProof,
+) -> Result<Option<H256>, Error> {
+	let hkey = hash_word(mimc, slot)?;
+	match proof {
+		LineaStorageProof::Inclusion { value, proof } => {
+			let leaf = verify_merkle_proof(mimc, &proof, storage_root)?;
+			if leaf.hkey != hkey || leaf.hval != hash_word(mimc, value)? {
+				Err(Error::MembershipProofVerificationFailed(format!(
+					"Leaf does not hold the value of slot {slot:?}"
+				)))?
+			}
+			Ok(Some(value))
+		},
+		LineaStorageProof::Exclusion { left, right } => {
+			let left_leaf = verify_merkle_proof(mimc, &left, storage_root)?;
+			let right_leaf = verify_merkle_proof(mimc, &right, storage_root)?;
+			let hkey = U256::from_big_endian(&hkey.0);
+			if left_leaf.next != right.leaf_index ||
+				right_leaf.prev != left.leaf_index ||
+				U256::from_big_endian(&left_leaf.hkey.0) >= hkey ||
+				U256::from_big_endian(&right_leaf.hkey.0) <= hkey
+			{
+				Err(Error::MembershipProofVerificationFailed(format!(
+					"Leaves are not adjacent to slot {slot:?}"
+				)))?
+			}
+			Ok(None)
+		},
+	}
+}
+
+/// Verifies the values of the given storage slots of the contracts in the proof. Keys that are 52
+/// bytes long are the contract address followed by the slot, shorter keys are slots of the ismp
+/// contract. Empty slots are returned as `None`.
+pub fn verify_linea_state_proof<H: Keccak256>(
+	keys: Vec<Vec<u8>>,
+	root: H256,
+	proof: &Proof,
+	ismp_address: H160,
+) -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+	let mut state_proof = LineaStateProof::decode(&mut &proof.proof[..])
+		.map_err(|_| Error::Custom("Cannot decode Linea state proof".to_string()))?;
+	let mimc = Mimc::new::<H>();
+
+	let mut contract_to_keys = BTreeMap::<H160, Vec<Vec<u8>>>::new();
+	for key in keys {
+		let contract_address =
+			if key.len() == 52 { H160::from_slice(&key[..20]) } else { ismp_address };
+		contract_to_keys.entry(contract_address).or_default().push(key);
+	}
+
+	let mut map = BTreeMap::new();
+	for (contract_address, keys) in contract_to_keys {
+		let account_proof =
+			state_proof.accounts.remove(&contract_address.0.to_vec()).ok_or_else(|| {
+				Error::Custom(format!("Account proof for {contract_address:?} is missing"))
+			})?;
+		let leaf = verify_merkle_proof(&mimc, &account_proof.proof, root)?;
+		if leaf.hkey != mimc.hash(&[U256::from_big_endian(&contract_address.0)])? ||
+			leaf.hval != hash_account(&mimc, &account_proof.account)?
+		{
+			Err(Error::MembershipProofVerificationFailed(format!(
+				"Leaf does not hold the account of {contract_address:?}"
+			)))?
+		}
+		let storage_root = H256::from_slice(&account_proof.account[64..96]);
+
+		if account_proof.storage_proofs.len() != keys.len() {
+			Err(Error::Custom(format!(
+				"Expected {} storage proofs for {contract_address:?}, found {}",
+				keys.len(),
+				account_proof.storage_proofs.len()
+			)))?
+		}
+
+		for (key, storage_proof) in keys.into_iter().zip(account_proof.storage_proofs) {
+			let slot = if key.len() == 52 { &key[20..] } else { &key[..] };
+			if slot.len() != 32 {
+				Err(Error::Custom(format!("Invalid storage slot {slot:?}")))?
+			}
+			let value =
+				verify_storage_proof(&mimc, H256::from_slice(slot), storage_root, storage_proof)?;
+			map.insert(key, value.map(|value| value.0.to_vec()));
+		}
+	}
+
+	Ok(map)
+}
+
+/// State machine client for Linea
+pub struct LineaStateMachine<H: IsmpHost, T: pallet_ismp_host_executive::Config>(
+	core::marker::PhantomData<(H, T)>,
+);
+
+impl<H: IsmpHost, T: pallet_ismp_host_executive::Config> Default for LineaStateMachine<H, T> {
+	fn default() -> Self {
+		Self(core::marker::PhantomData)
+	}
+}
+
+impl<H: IsmpHost, T: pallet_ismp_host_executive::Config> Clone for LineaStateMachine<H, T> {
+	fn clone(&self) -> Self {
+		LineaStateMachine::<H, T>::default()
+	}
+}
+
+impl<H: IsmpHost + Send + Sync, T: pallet_ismp_host_executive::Config> StateMachineClient
+	for LineaStateMachine<H, T>
+{
+	fn verify_membership(
+		&self,
+		host: &dyn IsmpHost,
+		item: RequestResponse,
+		root: StateCommitment,
+		proof: &Proof,
+	) -> Result<(), Error> {
+		let contract_address = EvmHosts::<T>::get(&proof.height.id.state_id)
+			.ok_or_else(|| Error::Custom("Ismp contract address not found".to_string()))?;
+		let keys = req_res_commitment_unhashed_key::<H>(item);
+		let values = verify_linea_state_proof::<H>(keys, root.state_root, proof, contract_address)?;
+
+		if values.into_values().any(|val| val.is_none()) {
+			Err(Error::Custom("Missing values for some keys in the proof".to_string()))?
+		}
+
+		Ok(())
+	}
+
+	fn receipts_state_trie_key(&self, items: RequestResponse) -> Vec<Vec<u8>> {
+		req_res_receipt_keys::<H>(items)
+	}
+
+	fn verify_state_proof(
+		&self,
+		host: &dyn IsmpHost,
+		keys: Vec<Vec<u8>>,
+		root: StateCommitment,
+		proof: &Proof,
+	) -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+		let ismp_address = EvmHosts::<T>::get(&proof.height.id.state_id)
+			.ok_or_else(|| Error::Custom("Ismp contract address not found".to_string()))?;
+		verify_linea_state_proof::<H>(keys, root.state_root, proof, ismp_address)
+	}
+}
@@ -0,0 +1,175 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scroll records the state root of every finalized batch in the ScrollChain contract on L1. Its
+//! L2 state is committed to in an ethereum merkle patricia trie, so the evm state machine client
+//! is used to verify state proofs.
+//!
+//! The number of the last block in a batch is not stored on L1, it is recovered from the block
+//! contexts of the batch's chunks, which are committed to by the data hash in the batch header.
+
+use crate::get_word_from_proof;
+use alloc::format;
+use ethabi::ethereum_types::{H160, H256, U256};
+use evm_common::{derive_map_key, get_contract_storage_root, prelude::*};
+use ismp::{
+	consensus::{
+		ConsensusStateId, IntermediateState, StateCommitment, StateMachineHeight, StateMachineId,
+	},
+	error::Error,
+	host::StateMachine,
+	messaging::Keccak256,
+};
+
+/// Slot for the committedBatches map in the ScrollChain contract
+pub const COMMITTED_BATCHES_SLOT: u64 = 157;
+/// Slot for the finalizedStateRoots map in the ScrollChain contract
+pub const FINALIZED_STATE_ROOTS_SLOT: u64 = 158;
+/// Offset of the batch index in the batch header
+const BATCH_INDEX_OFFSET: usize = 1;
+/// Offset of the data hash in the batch header
+const DATA_HASH_OFFSET: usize = 25;
+/// Offset of the last block timestamp in the batch header of codec versions 3 to 6
+const LAST_BLOCK_TIMESTAMP_OFFSET: usize = 121;
+/// Length of a block context in the chunk data, the block number and timestamp are its first fields
+pub const BLOCK_CONTEXT_LENGTH: usize = 58;
+
+/// The data of a chunk that is hashed into the data hash of its batch
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct ScrollChunk {
+	/// Contexts of the blocks in the chunk, in order
+	pub block_contexts: Vec<Vec<u8>>,
+	/// Hashes of the L1 messages included in the chunk
+	pub l1_message_hashes: Vec<H256>,
+}
+
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct ScrollPayloadProof {
+	/// Index of the finalized batch
+	pub batch_index: u64,
+	/// The batch header as submitted in the commitBatch call, which must have been encoded with a
+	/// codec version that includes the timestamp of the last block in the batch.
+	pub batch_header: Vec<u8>,
+	/// The chunks of the batch, used to recover the number of its last block
+	pub chunks: Vec<ScrollChunk>,
+	/// Membership proof for the ScrollChain contract account in the ethereum world trie
+	pub contract_proof: Vec<Vec<u8>>,
+	/// Membership proof for the batch hash in the committedBatches map
+	pub batch_hash_proof: Vec<Vec<u8>>,
+	/// Membership proof for the state root in the finalizedStateRoots map
+	pub state_root_proof: Vec<Vec<u8>>,
+}
+
+/// Verifies the state root of a finalized batch along with the number and timestamp of its last
+/// block.
+pub fn verify_scroll_payload<H: Keccak256 + Send + Sync>(
+	payload: ScrollPayloadProof,
+	root: H256,
+	scroll_chain_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
+	let header = &payload.batch_header;
+	if header.len() < LAST_BLOCK_TIMESTAMP_OFFSET + 8 || !(3..=6).contains(&header[0]) {
+		Err(Error::Custom(format!("Unsupported batch header of version {:?}", header.first())))?
+	}
+	let batch_index = u64::from_be_bytes(
+		header[BATCH_INDEX_OFFSET..BATCH_INDEX_OFFSET + 8]
+			.try_into()
+			.expect("Slice has length 8"),
+	);
+	if batch_index != payload.batch_index {
+		Err(Error::Custom("Batch header is not for the batch index in the payload".to_string()))?
+	}
+	let timestamp = u64::from_be_bytes(
+		header[LAST_BLOCK_TIMESTAMP_OFFSET..LAST_BLOCK_TIMESTAMP_OFFSET + 8]
+			.try_into()
+			.expect("Slice has length 8"),
+	);
+	let height = last_block_number::<H>(&payload.chunks, header, timestamp)?;
+
+	let storage_root =
+		get_contract_storage_root::<H>(payload.contract_proof, &scroll_chain_address.0, root)?;
+
+	let mut key = [0u8; 32];
+	U256::from(payload.batch_index).to_big_endian(&mut key);
+	let batch_hash = get_word_from_proof::<H>(
+		derive_map_key::<H>(key.to_vec(), COMMITTED_BATCHES_SLOT),
+		storage_root,
+		payload.batch_hash_proof,
+	)?;
+	if batch_hash != H::keccak256(header).0 {
+		Err(Error::MembershipProofVerificationFailed(
+			"Committed batch hash from proof does not match the batch header".to_string(),
+		))?
+	}
+
+	// The state root is only written once the batch has been finalized
+	let state_root = get_word_from_proof::<H>(
+		derive_map_key::<H>(key.to_vec(), FINALIZED_STATE_ROOTS_SLOT),
+		storage_root,
+		payload.state_root_proof,
+	)?;
+
+	Ok(IntermediateState {
+		height: StateMachineHeight {
+			id: StateMachineId {
+				// note: This will state machine id should not be used to store the state commitment
+				state_id: StateMachine::Evm(Default::default()),
+				consensus_state_id,
+			},
+			height,
+		},
+		commitment: StateCommitment {
+			timestamp,
+			overlay_root: None,
+			state_root: state_root.into(),
+		},
+	})
+}
+
+/// Recovers the number of the last block in a batch from its chunks, after checking that they hash
+/// to the data hash in the batch header and that the last block has the given timestamp.
+pub fn last_block_number<H: Keccak256>(
+	chunks: &[ScrollChunk],
+	header: &[u8],
+	timestamp: u64,
+) -> Result<u64, Error> {
+	let mut chunk_hashes = Vec::with_capacity(chunks.len() * 32);
+	for chunk in chunks {
+		if chunk.block_contexts.is_empty() ||
+			chunk.block_contexts.iter().any(|context| context.len() != BLOCK_CONTEXT_LENGTH)
+		{
+			Err(Error::Custom("Invalid block contexts in scroll chunk".to_string()))?
+		}
+		let mut data = chunk.block_contexts.concat();
+		data.extend(chunk.l1_message_hashes.iter().flat_map(|hash| hash.0));
+		chunk_hashes.extend(H::keccak256(&data).0);
+	}
+
+	if H::keccak256(&chunk_hashes).0[..] != header[DATA_HASH_OFFSET..DATA_HASH_OFFSET + 32] {
+		Err(Error::Custom("Chunks do not match the data hash of the batch header".to_string()))?
+	}
+
+	let last_block = chunks
+		.last()
+		.and_then(|chunk| chunk.block_contexts.last())
+		.ok_or_else(|| Error::Custom("Batch has no chunks".to_string()))?;
+	let number = u64::from_be_bytes(last_block[..8].try_into().expect("Slice has length 8"));
+	if u64::from_be_bytes(last_block[8..16].try_into().expect("Slice has length 8")) != timestamp {
+		Err(Error::Custom("Last block timestamp does not match the batch header".to_string()))?
+	}
+
+	Ok(number)
+}
@@ -0,0 +1,510 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+#![cfg(test)]
+
+use crate::{
+	linea::{
+		self, hash_account, hash_word, verify_linea_state_proof, LineaAccountProof,
+		LineaMerkleProof, LineaStateProof, LineaStorageProof, Mimc,
+	},
+	scroll::{last_block_number, ScrollChunk, BLOCK_CONTEXT_LENGTH},
+	zksync::{
+		fold_merkle_path, tree_key, verify_zksync_state_proof, StorageEntryProof, ZkSyncStateProof,
+		TREE_DEPTH,
+	},
+};
+use alloc::collections::BTreeMap;
+use blake2::{Blake2s256, Digest};
+use codec::Encode;
+use ethabi::ethereum_types::{H160, H256, U256};
+use ethers::prelude::{Http, Middleware, Provider};
+use evm_common::derive_map_key;
+use ismp::{
+	consensus::{StateMachineHeight, StateMachineId},
+	host::StateMachine,
+	messaging::{Keccak256, Proof},
+};
+use ismp_testsuite::mocks::Host;
+use serde::Deserialize;
+
+fn blake2s(bytes: &[u8]) -> H256 {
+	H256(Blake2s256::digest(bytes).into())
+}
+
+/// Hashes of the empty subtrees of the merkle tree, from the empty leaf upwards
+fn empty_subtree_hashes() -> Vec<H256> {
+	let mut hashes = vec![blake2s(&[0u8; 40])];
+	for _ in 1..TREE_DEPTH {
+		let hash = *hashes.last().unwrap();
+		hashes.push(blake2s(&[hash.0, hash.0].concat()));
+	}
+	hashes
+}
+
+#[test]
+fn omitted_empty_subtree_hashes_are_restored() {
+	let key = tree_key(H160::repeat_byte(1), H256::repeat_byte(2));
+	let value = H256::repeat_byte(3);
+	let trimmed = StorageEntryProof { value, index: 7, merkle_path: vec![H256::repeat_byte(4)] };
+	let mut full_path = empty_subtree_hashes()[..TREE_DEPTH - 1].to_vec();
+	full_path.push(H256::repeat_byte(4));
+	let full = StorageEntryProof { value, index: 7, merkle_path: full_path };
+
+	assert_eq!(fold_merkle_path(key, &trimmed).unwrap(), fold_merkle_path(key, &full).unwrap());
+
+	let wrong_index = StorageEntryProof { index: 8, ..trimmed.clone() };
+	assert_ne!(
+		fold_merkle_path(key, &trimmed).unwrap(),
+		fold_merkle_path(key, &wrong_index).unwrap()
+	);
+}
+
+#[test]
+fn verifies_zksync_state_proofs() {
+	let ismp_address = H160::repeat_byte(1);
+	let slot = H256::repeat_byte(2).0.to_vec();
+	let other_contract = H160::repeat_byte(5);
+	let other_slot = H256::repeat_byte(6);
+
+	// A tree holding a single value, and an empty slot that shares no path with it below the root
+	let entry = StorageEntryProof { value: H256::repeat_byte(3), index: 1, merkle_path: vec![] };
+	let root = fold_merkle_path(tree_key(ismp_address, H256::from_slice(&slot)), &entry).unwrap();
+	let empty = StorageEntryProof { value: H256::zero(), index: 0, merkle_path: vec![] };
+	let empty_root = fold_merkle_path(tree_key(other_contract, other_slot), &empty).unwrap();
+	let top = empty_subtree_hashes()[TREE_DEPTH - 1];
+	assert_eq!(empty_root, blake2s(&[top.0, top.0].concat()));
+
+	let proof = |storage_proof: BTreeMap<Vec<u8>, Vec<StorageEntryProof>>| Proof {
+		height: StateMachineHeight {
+			id: StateMachineId { state_id: StateMachine::Evm(324), consensus_state_id: *b"ETH0" },
+			height: 1,
+		},
+		proof: ZkSyncStateProof { storage_proof }.encode(),
+	};
+
+	let values = verify_zksync_state_proof(
+		vec![slot.clone()],
+		root,
+		&proof(BTreeMap::from([(ismp_address.0.to_vec(), vec![entry.clone()])])),
+		ismp_address,
+	)
+	.unwrap();
+	assert_eq!(values.get(&slot), Some(&Some(H256::repeat_byte(3).0.to_vec())));
+
+	// The value must be in the tree
+	let tampered = StorageEntryProof { value: H256::repeat_byte(4), ..entry };
+	assert!(verify_zksync_state_proof(
+		vec![slot],
+		root,
+		&proof(BTreeMap::from([(ismp_address.0.to_vec(), vec![tampered])])),
+		ismp_address,
+	)
+	.is_err());
+
+	// Empty slots of other contracts are returned as None
+	let key = [&other_contract.0[..], &other_slot.0[..]].concat();
+	let values = verify_zksync_state_proof(
+		vec![key.clone()],
+		empty_root,
+		&proof(BTreeMap::from([(other_contract.0.to_vec(), vec![empty])])),
+		ismp_address,
+	)
+	.unwrap();
+	assert_eq!(values.get(&key), Some(&None));
+}
+
+fn proof_at(state_id: StateMachine, proof: Vec<u8>) -> Proof {
+	Proof {
+		height: StateMachineHeight {
+			id: StateMachineId { state_id, consensus_state_id: *b"ETH0" },
+			height: 1,
+		},
+		proof,
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZkSyncStorageProof {
+	value: ethers::types::H256,
+	index: u64,
+	proof: Vec<ethers::types::H256>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ZkSyncProof {
+	storage_proof: Vec<ZkSyncStorageProof>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct L1BatchDetails {
+	root_hash: ethers::types::H256,
+}
+
+#[tokio::test]
+#[ignore]
+/// Verifies proofs served by a zkSync Era mainnet node against the root hash of their batch
+async fn verifies_zksync_mainnet_state_proofs() {
+	let provider = Provider::<Http>::try_from("https://mainnet.era.zksync.io").unwrap();
+	let latest: ethers::types::U64 = provider.request("zks_L1BatchNumber", ()).await.unwrap();
+	// The root hash of the latest batches may not have been computed yet
+	let batch = latest.as_u64() - 10;
+	let details: L1BatchDetails = provider.request("zks_getL1BatchDetails", [batch]).await.unwrap();
+
+	// totalSupply of the L2BaseToken system contract is always set, the other slot is empty
+	let base_token = ethers::types::H160::from_low_u64_be(0x800a);
+	let total_supply = ethers::types::H256::from_low_u64_be(1);
+	let empty = ethers::types::H256::repeat_byte(0xab);
+	let response: ZkSyncProof = provider
+		.request("zks_getProof", (base_token, vec![total_supply, empty], batch))
+		.await
+		.unwrap();
+	let entries = response
+		.storage_proof
+		.into_iter()
+		.map(|entry| StorageEntryProof {
+			value: H256(entry.value.0),
+			index: entry.index,
+			merkle_path: entry.proof.into_iter().map(|hash| H256(hash.0)).collect(),
+		})
+		.collect();
+	let proof = proof_at(
+		StateMachine::Evm(324),
+		ZkSyncStateProof { storage_proof: BTreeMap::from([(base_token.0.to_vec(), entries)]) }
+			.encode(),
+	);
+
+	let keys = vec![total_supply.0.to_vec(), empty.0.to_vec()];
+	let values =
+		verify_zksync_state_proof(keys, H256(details.root_hash.0), &proof, H160(base_token.0))
+			.unwrap();
+	assert!(values[&total_supply.0.to_vec()].is_some());
+	assert_eq!(values[&empty.0.to_vec()], None);
+}
+
+fn block_context(number: u64, timestamp: u64) -> Vec<u8> {
+	let mut context = vec![0u8; BLOCK_CONTEXT_LENGTH];
+	context[..8].copy_from_slice(&number.to_be_bytes());
+	context[8..16].copy_from_slice(&timestamp.to_be_bytes());
+	context
+}
+
+#[test]
+fn recovers_scroll_last_block_from_chunks() {
+	let chunks = vec![
+		ScrollChunk {
+			block_contexts: vec![block_context(100, 1000), block_context(101, 1003)],
+			l1_message_hashes: vec![H256::repeat_byte(1)],
+		},
+		ScrollChunk { block_contexts: vec![block_context(102, 1006)], l1_message_hashes: vec![] },
+	];
+	let chunk_hash = |chunk: &ScrollChunk| {
+		let mut data = chunk.block_contexts.concat();
+		data.extend(chunk.l1_message_hashes.iter().flat_map(|hash| hash.0));
+		Host::keccak256(&data).0
+	};
+	let data_hash = Host::keccak256(&chunks.iter().flat_map(chunk_hash).collect::<Vec<_>>());
+	let mut header = vec![0u8; 193];
+	header[0] = 3;
+	header[25..57].copy_from_slice(&data_hash.0);
+
+	assert_eq!(last_block_number::<Host>(&chunks, &header, 1006).unwrap(), 102);
+	// The last block must have the timestamp in the batch header
+	assert!(last_block_number::<Host>(&chunks, &header, 1003).is_err());
+	// The chunks must be the ones committed to by the batch header
+	assert!(last_block_number::<Host>(&chunks[..1], &header, 1003).is_err());
+	let mut tampered = chunks.clone();
+	tampered[1].block_contexts[0] = block_context(200, 1006);
+	assert!(last_block_number::<Host>(&tampered, &header, 1006).is_err());
+}
+
+fn leaf_opening(prev: u64, next: u64, hkey: H256, hval: H256) -> Vec<u8> {
+	[H256::from_low_u64_be(prev), H256::from_low_u64_be(next), hkey, hval]
+		.iter()
+		.flat_map(|word| word.0)
+		.collect()
+}
+
+/// Builds a sparse merkle tree whose first two leaves are set and returns its root along with
+/// proofs for both leaves
+fn two_leaf_tree(mimc: &Mimc, leaves: [Vec<u8>; 2]) -> (H256, [LineaMerkleProof; 2]) {
+	let hash = |left: &H256, right: &H256| mimc.hash_bytes(&[left.0, right.0].concat()).unwrap();
+	let mut empty_subtrees = vec![H256::zero()];
+	for height in 1..linea::TREE_DEPTH {
+		empty_subtrees.push(hash(&empty_subtrees[height - 1], &empty_subtrees[height - 1]));
+	}
+	let leaf_hashes = leaves.clone().map(|leaf| mimc.hash_bytes(&leaf).unwrap());
+
+	let mut subtree_root = hash(&leaf_hashes[0], &leaf_hashes[1]);
+	for height in 1..linea::TREE_DEPTH {
+		subtree_root = hash(&subtree_root, &empty_subtrees[height]);
+	}
+	let next_free_node = H256::from_low_u64_be(2);
+	let root = hash(&next_free_node, &subtree_root);
+
+	let proof = |index: usize| {
+		let mut nodes = vec![[next_free_node.0, subtree_root.0].concat()];
+		nodes.extend(empty_subtrees[1..].iter().rev().map(|hash| hash.0.to_vec()));
+		nodes.push(leaf_hashes[1 - index].0.to_vec());
+		nodes.push(leaves[index].clone());
+		LineaMerkleProof { leaf_index: index as u64, proof_related_nodes: nodes }
+	};
+
+	(root, [proof(0), proof(1)])
+}
+
+#[test]
+fn verifies_linea_state_proofs() {
+	let mimc = Mimc::new::<Host>();
+	let ismp_address = H160::repeat_byte(1);
+	let slot = H256::repeat_byte(2);
+	let value = H256::repeat_byte(3);
+	let empty_slot = H256::repeat_byte(4);
+	// The head and tail leaves hold the least and greatest field elements as their keys
+	let head = H256::zero();
+	let mut tail = H256::zero();
+	U256::from_str_radix("12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000000", 16)
+		.unwrap()
+		.to_big_endian(&mut tail.0);
+
+	// A storage tree holding the slot, and one that only holds the head and tail leaves
+	let (storage_root, [_, slot_proof]) = two_leaf_tree(
+		&mimc,
+		[
+			leaf_opening(0, 1, head, H256::zero()),
+			leaf_opening(0, 1, hash_word(&mimc, slot).unwrap(), hash_word(&mimc, value).unwrap()),
+		],
+	);
+	let (empty_storage_root, [left, right]) = two_leaf_tree(
+		&mimc,
+		[leaf_opening(0, 1, head, H256::zero()), leaf_opening(0, 1, tail, H256::zero())],
+	);
+
+	let account = |storage_root: H256| {
+		let mut account = vec![0u8; 192];
+		account[64..96].copy_from_slice(&storage_root.0);
+		account
+	};
+	let account_tree = |account: &[u8]| {
+		let hkey = mimc.hash(&[U256::from_big_endian(&ismp_address.0)]).unwrap();
+		two_leaf_tree(
+			&mimc,
+			[
+				leaf_opening(0, 1, head, H256::zero()),
+				leaf_opening(0, 1, hkey, hash_account(&mimc, account).unwrap()),
+			],
+		)
+	};
+	let state_proof = |account: Vec<u8>, proof, storage_proof| {
+		proof_at(
+			StateMachine::Evm(59144),
+			LineaStateProof {
+				accounts: BTreeMap::from([(
+					ismp_address.0.to_vec(),
+					LineaAccountProof { account, proof, storage_proofs: vec![storage_proof] },
+				)]),
+			}
+			.encode(),
+		)
+	};
+
+	let (root, [_, account_proof]) = account_tree(&account(storage_root));
+	let inclusion = LineaStorageProof::Inclusion { value, proof: slot_proof.clone() };
+	let values = verify_linea_state_proof::<Host>(
+		vec![slot.0.to_vec()],
+		root,
+		&state_proof(account(storage_root), account_proof.clone(), inclusion),
+		ismp_address,
+	)
+	.unwrap();
+	assert_eq!(values[&slot.0.to_vec()], Some(value.0.to_vec()));
+
+	// The value must be the one in the leaf
+	let tampered = LineaStorageProof::Inclusion { value: H256::repeat_byte(5), proof: slot_proof };
+	assert!(verify_linea_state_proof::<Host>(
+		vec![slot.0.to_vec()],
+		root,
+		&state_proof(account(storage_root), account_proof, tampered),
+		ismp_address,
+	)
+	.is_err());
+
+	// Empty slots are proven by the leaves on either side of them
+	let (root, [_, account_proof]) = account_tree(&account(empty_storage_root));
+	let exclusion = LineaStorageProof::Exclusion { left: left.clone(), right: right.clone() };
+	let values = verify_linea_state_proof::<Host>(
+		vec![empty_slot.0.to_vec()],
+		root,
+		&state_proof(account(empty_storage_root), account_proof.clone(), exclusion),
+		ismp_address,
+	)
+	.unwrap();
+	assert_eq!(values[&empty_slot.0.to_vec()], None);
+
+	// The leaves must be adjacent to the slot
+	let swapped = LineaStorageProof::Exclusion { left: right, right: left };
+	assert!(verify_linea_state_proof::<Host>(
+		vec![empty_slot.0.to_vec()],
+		root,
+		&state_proof(account(empty_storage_root), account_proof, swapped),
+		ismp_address,
+	)
+	.is_err());
+}
+
+#[test]
+fn mimc_only_hashes_field_elements() {
+	let mimc = Mimc::new::<Host>();
+	assert!(mimc.hash(&[U256::MAX]).is_err());
+	assert!(mimc.hash_bytes(&[0u8; 31]).is_err());
+	assert_ne!(mimc.hash(&[U256::one()]).unwrap(), mimc.hash(&[U256::from(2)]).unwrap());
+	assert_ne!(
+		mimc.hash(&[U256::one(), U256::from(2)]).unwrap(),
+		mimc.hash(&[U256::from(2), U256::one()]).unwrap()
+	);
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LineaProofNodes {
+	value: Option<ethers::types::Bytes>,
+	proof_related_nodes: Vec<ethers::types::Bytes>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LineaLeafProof {
+	leaf_index: u64,
+	proof: LineaProofNodes,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged, rename_all = "camelCase")]
+enum LineaSlotProof {
+	#[serde(rename_all = "camelCase")]
+	Exclusion {
+		left_leaf_index: u64,
+		left_proof: LineaProofNodes,
+		right_leaf_index: u64,
+		right_proof: LineaProofNodes,
+	},
+	Inclusion(LineaLeafProof),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LineaProof {
+	account_proof: LineaLeafProof,
+	storage_proofs: Vec<LineaSlotProof>,
+}
+
+fn merkle_proof(leaf_index: u64, nodes: LineaProofNodes) -> LineaMerkleProof {
+	LineaMerkleProof {
+		leaf_index,
+		proof_related_nodes: nodes
+			.proof_related_nodes
+			.into_iter()
+			.map(|node| node.to_vec())
+			.collect(),
+	}
+}
+
+#[tokio::test]
+#[ignore]
+/// Verifies proofs served by a Linea mainnet node against the last state root finalized on L1
+async fn verifies_linea_mainnet_state_proofs() {
+	let l1 = Provider::<Http>::try_from("https://rpc.ankr.com/eth").unwrap();
+	let linea = Provider::<Http>::try_from("https://rpc.linea.build").unwrap();
+	let linea_rollup: ethers::types::H160 =
+		"0xd19d4B5d358258f05D7B411E21A1460D11B0876F".parse().unwrap();
+
+	let block_number = l1
+		.get_storage_at(
+			linea_rollup,
+			ethers::types::H256::from_low_u64_be(linea::CURRENT_L2_BLOCK_NUMBER_SLOT),
+			None,
+		)
+		.await
+		.unwrap();
+	let block_number = U256::from_big_endian(&block_number.0).low_u64();
+	let mut key = [0u8; 32];
+	U256::from(block_number).to_big_endian(&mut key);
+	let root = l1
+		.get_storage_at(
+			linea_rollup,
+			ethers::types::H256(
+				derive_map_key::<Host>(key.to_vec(), linea::STATE_ROOT_HASHES_SLOT).0,
+			),
+			None,
+		)
+		.await
+		.unwrap();
+
+	// The name of WETH is stored in its first slot, the other slot is empty
+	let weth: ethers::types::H160 = "0xe5D7C2a44FfDDf6b295A15c148167daaAf5Cf34f".parse().unwrap();
+	let name = ethers::types::H256::zero();
+	let empty = ethers::types::H256::repeat_byte(0xab);
+	let response: LineaProof = linea
+		.request("linea_getProof", (weth, vec![name, empty], format!("{block_number:#x}")))
+		.await
+		.unwrap();
+
+	let storage_proofs = response
+		.storage_proofs
+		.into_iter()
+		.map(|proof| match proof {
+			LineaSlotProof::Inclusion(leaf) => LineaStorageProof::Inclusion {
+				value: H256::from_slice(&leaf.proof.value.clone().unwrap_or_default()),
+				proof: merkle_proof(leaf.leaf_index, leaf.proof),
+			},
+			LineaSlotProof::Exclusion {
+				left_leaf_index,
+				left_proof,
+				right_leaf_index,
+				right_proof,
+			} => LineaStorageProof::Exclusion {
+				left: merkle_proof(left_leaf_index, left_proof),
+				right: merkle_proof(right_leaf_index, right_proof),
+			},
+		})
+		.collect();
+	let account_proof = response.account_proof;
+	let proof = proof_at(
+		StateMachine::Evm(59144),
+		LineaStateProof {
+			accounts: BTreeMap::from([(
+				weth.0.to_vec(),
+				LineaAccountProof {
+					account: account_proof.proof.value.clone().unwrap_or_default().to_vec(),
+					proof: merkle_proof(account_proof.leaf_index, account_proof.proof),
+					storage_proofs,
+				},
+			)]),
+		}
+		.encode(),
+	);
+
+	let values = verify_linea_state_proof::<Host>(
+		vec![name.0.to_vec(), empty.0.to_vec()],
+		H256(root.0),
+		&proof,
+		H160(weth.0),
+	)
+	.unwrap();
+	assert!(values[&name.0.to_vec()].is_some());
+	assert_eq!(values[&empty.0.to_vec()], None);
+}
@@ -0,0 +1,327 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! zkSync Era commits to the storage of its batches in a sparse merkle tree of depth 256 hashed
+//! with blake2s, whose root is recorded as the batch hash in the diamond proxy on L1.
+
+use crate::{get_word_from_proof, slot_key};
+use alloc::{collections::BTreeMap, format};
+use blake2::{Blake2s256, Digest};
+use codec::{Decode, Encode};
+use ethabi::ethereum_types::{H160, H256, U256};
+use evm_common::{
+	derive_map_key, get_contract_storage_root, prelude::*, req_res_commitment_unhashed_key,
+	req_res_receipt_keys,
+};
+use ismp::{
+	consensus::{
+		ConsensusStateId, IntermediateState, StateCommitment, StateMachineClient,
+		StateMachineHeight, StateMachineId,
+	},
+	error::Error,
+	host::{IsmpHost, StateMachine},
+	messaging::{Keccak256, Proof},
+	router::RequestResponse,
+};
+use pallet_ismp_host_executive::EvmHosts;
+
+/// Slot for totalBatchesExecuted in the ZkSyncHyperchainStorage of the diamond proxy
+pub const TOTAL_BATCHES_EXECUTED_SLOT: u64 = 11;
+/// Slot for the storedBatchHashes map in the ZkSyncHyperchainStorage of the diamond proxy
+pub const STORED_BATCH_HASHES_SLOT: u64 = 14;
+/// Depth of the zkSync storage merkle tree
+pub const TREE_DEPTH: usize = 256;
+
+/// https://github.com/matter-labs/era-contracts/blob/v24/l1-contracts/contracts/state-transition/chain-interfaces/IExecutor.sol#L50
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct StoredBatchInfo {
+	pub batch_number: u64,
+	/// Root hash of the storage merkle tree after the batch
+	pub batch_hash: H256,
+	pub index_repeated_storage_changes: u64,
+	pub number_of_layer1_txs: U256,
+	pub priority_operations_hash: H256,
+	pub l2_logs_tree_root: H256,
+	pub timestamp: U256,
+	pub commitment: H256,
+}
+
+impl StoredBatchInfo {
+	/// keccak256(abi.encode(storedBatchInfo))
+	pub fn hash<H: Keccak256>(&self) -> H256 {
+		let tokens = [
+			ethabi::Token::Uint(self.batch_number.into()),
+			ethabi::Token::FixedBytes(self.batch_hash.0.to_vec()),
+			ethabi::Token::Uint(self.index_repeated_storage_changes.into()),
+			ethabi::Token::Uint(self.number_of_layer1_txs),
+			ethabi::Token::FixedBytes(self.priority_operations_hash.0.to_vec()),
+			ethabi::Token::FixedBytes(self.l2_logs_tree_root.0.to_vec()),
+			ethabi::Token::Uint(self.timestamp),
+			ethabi::Token::FixedBytes(self.commitment.0.to_vec()),
+		];
+		H::keccak256(&ethabi::encode(&tokens))
+	}
+}
+
+#[derive(codec::Encode, codec::Decode, Debug, Clone)]
+pub struct ZkSyncPayloadProof {
+	/// The batch as recorded in the BlockCommit event that was emitted for it
+	pub batch: StoredBatchInfo,
+	/// Membership proof for the diamond proxy account in the ethereum world trie
+	pub contract_proof: Vec<Vec<u8>>,
+	/// Membership proof for the batch hash in the storedBatchHashes map
+	pub batch_hash_proof: Vec<Vec<u8>>,
+	/// Membership proof for totalBatchesExecuted
+	pub total_batches_executed_proof: Vec<Vec<u8>>,
+}
+
+/// Verifies that the batch has been executed by the diamond proxy, which only happens after its
+/// validity proof has been verified. The state commitment is at the height of the batch number.
+pub fn verify_zksync_payload<H: Keccak256 + Send + Sync>(
+	payload: ZkSyncPayloadProof,
+	root: H256,
+	diamond_proxy_address: H160,
+	consensus_state_id: ConsensusStateId,
+) -> Result<IntermediateState, Error> {
+	let storage_root =
+		get_contract_storage_root::<H>(payload.contract_proof, &diamond_proxy_address.0, root)?;
+
+	let mut key = [0u8; 32];
+	U256::from(payload.batch.batch_number).to_big_endian(&mut key);
+	let batch_hash_key = derive_map_key::<H>(key.to_vec(), STORED_BATCH_HASHES_SLOT);
+	let stored_batch_hash =
+		get_word_from_proof::<H>(batch_hash_key, storage_root, payload.batch_hash_proof)?;
+	if stored_batch_hash != payload.batch.hash::<H>().0 {
+		Err(Error::MembershipProofVerificationFailed(
+			"Stored batch hash from proof does not match calculated batch hash".to_string(),
+		))?
+	}
+
+	let total_batches_executed = get_word_from_proof::<H>(
+		slot_key::<H>(TOTAL_BATCHES_EXECUTED_SLOT),
+		storage_root,
+		payload.total_batches_executed_proof,
+	)?;
+	if U256::from_big_endian(&total_batches_executed) < payload.batch.batch_number.into() {
+		Err(Error::Custom(format!(
+			"Batch {} has not been executed on L1",
+			payload.batch.batch_number
+		)))?
+	}
+
+	Ok(IntermediateState {
+		height: StateMachineHeight {
+			id: StateMachineId {
+				// note: This will state machine id should not be used to store the state commitment
+				state_id: StateMachine::Evm(Default::default()),
+				consensus_state_id,
+			},
+			height: payload.batch.batch_number,
+		},
+		commitment: StateCommitment {
+			timestamp: payload.batch.timestamp.low_u64(),
+			overlay_root: None,
+			state_root: payload.batch.batch_hash,
+		},
+	})
+}
+
+/// A leaf of the storage merkle tree along with its merkle path, as returned by `zks_getProof`
+#[derive(Encode, Decode, Clone, Debug)]
+pub struct StorageEntryProof {
+	/// Value stored in the slot, or zero if the slot is empty
+	pub value: H256,
+	/// Enumeration index of the leaf, or zero if the slot is empty
+	pub index: u64,
+	/// Sibling hashes from the leaf up to the root. Hashes of empty subtrees at the bottom of the
+	/// path may be omitted.
+	pub merkle_path: Vec<H256>,
+}
+
+#[derive(Encode, Decode, Clone)]
+pub struct ZkSyncStateProof {
+	/// A map of contract address to the proofs for the slots requested from the contract, in the
+	/// order of the requested keys
+	pub storage_proof: BTreeMap<Vec<u8>, Vec<StorageEntryProof>>,
+}
+
+fn blake2s(bytes: &[u8]) -> H256 {
+	H256(Blake2s256::digest(bytes).into())
+}
+
+/// The key of a contract storage slot in the merkle tree
+pub fn tree_key(address: H160, slot: H256) -> H256 {
+	let mut bytes = [0u8; 64];
+	bytes[12..32].copy_from_slice(&address.0);
+	bytes[32..].copy_from_slice(&slot.0);
+	blake2s(&bytes)
+}
+
+fn hash_leaf(value: H256, index: u64) -> H256 {
+	let mut bytes = [0u8; 40];
+	bytes[..8].copy_from_slice(&index.to_be_bytes());
+	bytes[8..].copy_from_slice(&value.0);
+	blake2s(&bytes)
+}
+
+fn hash_branch(left: &H256, right: &H256) -> H256 {
+	let mut bytes = [0u8; 64];
+	bytes[..32].copy_from_slice(&left.0);
+	bytes[32..].copy_from_slice(&right.0);
+	blake2s(&bytes)
+}
+
+/// Computes the root of the merkle tree from the leaf at `key` and its merkle path
+/// https://github.com/matter-labs/zksync-era/blob/core-v24.0.0/core/lib/merkle_tree/src/hasher/mod.rs#L154
+pub fn fold_merkle_path(key: H256, entry: &StorageEntryProof) -> Result<H256, Error> {
+	if entry.merkle_path.len() > TREE_DEPTH {
+		Err(Error::Custom(format!(
+			"Merkle path of length {} is longer than the tree depth",
+			entry.merkle_path.len()
+		)))?
+	}
+
+	let key = U256::from_big_endian(&key.0);
+	let empty_hashes = TREE_DEPTH - entry.merkle_path.len();
+	let mut empty_subtree = hash_leaf(H256::zero(), 0);
+	let mut hash = hash_leaf(entry.value, entry.index);
+	for depth in 0..TREE_DEPTH {
+		let sibling = if depth < empty_hashes {
+			empty_subtree
+		} else {
+			entry.merkle_path[depth - empty_hashes]
+		};
+		hash = if key.bit(depth) {
+			hash_branch(&sibling, &hash)
+		} else {
+			hash_branch(&hash, &sibling)
+		};
+		if depth < empty_hashes {
+			empty_subtree = hash_branch(&empty_subtree, &empty_subtree);
+		}
+	}
+
+	Ok(hash)
+}
+
+/// Verifies the values of the given storage slots of the contracts in the proof. Keys that are 52
+/// bytes long are the contract address followed by the slot, shorter keys are slots of the ismp
+/// contract. Empty slots are returned as `None`.
+pub fn verify_zksync_state_proof(
+	keys: Vec<Vec<u8>>,
+	root: H256,
+	proof: &Proof,
+	ismp_address: H160,
+) -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+	let mut state_proof = ZkSyncStateProof::decode(&mut &proof.proof[..])
+		.map_err(|_| Error::Custom("Cannot decode zkSync state proof".to_string()))?;
+
+	let mut contract_to_keys = BTreeMap::<H160, Vec<Vec<u8>>>::new();
+	for key in keys {
+		let contract_address =
+			if key.len() == 52 { H160::from_slice(&key[..20]) } else { ismp_address };
+		contract_to_keys.entry(contract_address).or_default().push(key);
+	}
+
+	let mut map = BTreeMap::new();
+	for (contract_address, keys) in contract_to_keys {
+		let entries =
+			state_proof.storage_proof.remove(&contract_address.0.to_vec()).ok_or_else(|| {
+				Error::Custom(format!("Storage proof for {contract_address:?} is missing"))
+			})?;
+		if entries.len() != keys.len() {
+			Err(Error::Custom(format!(
+				"Expected {} storage proofs for {contract_address:?}, found {}",
+				keys.len(),
+				entries.len()
+			)))?
+		}
+
+		for (key, entry) in keys.into_iter().zip(entries) {
+			let slot = if key.len() == 52 { &key[20..] } else { &key[..] };
+			if slot.len() != 32 {
+				Err(Error::Custom(format!("Invalid storage slot {slot:?}")))?
+			}
+			let computed =
+				fold_merkle_path(tree_key(contract_address, H256::from_slice(slot)), &entry)?;
+			if computed != root {
+				Err(Error::MembershipProofVerificationFailed(format!(
+					"Merkle path for {key:?} does not lead to the state root"
+				)))?
+			}
+
+			let value = (!entry.value.is_zero()).then(|| entry.value.0.to_vec());
+			map.insert(key, value);
+		}
+	}
+
+	Ok(map)
+}
+
+/// State machine client for zkSync Era chains
+pub struct ZkSyncStateMachine<H: IsmpHost, T: pallet_ismp_host_executive::Config>(
+	core::marker::PhantomData<(H, T)>,
+);
+
+impl<H: IsmpHost, T: pallet_ismp_host_executive::Config> Default for ZkSyncStateMachine<H, T> {
+	fn default() -> Self {
+		Self(core::marker::PhantomData)
+	}
+}
+
+impl<H: IsmpHost, T: pallet_ismp_host_executive::Config> Clone for ZkSyncStateMachine<H, T> {
+	fn clone(&self) -> Self {
+		ZkSyncStateMachine::<H, T>::default()
+	}
+}
+
+impl<H: IsmpHost + Send + Sync, T: pallet_ismp_host_executive::Config> StateMachineClient
+	for ZkSyncStateMachine<H, T>
+{
+	fn verify_membership(
+		&self,
+		host: &dyn IsmpHost,
+		item: RequestResponse,
+		root: StateCommitment,
+		proof: &Proof,
+	) -> Result<(), Error> {
+		let contract_address = EvmHosts::<T>::get(&proof.height.id.state_id)
+			.ok_or_else(|| Error::Custom("Ismp contract address not found".to_string()))?;
+		let keys = req_res_commitment_unhashed_key::<H>(item);
+		let values = verify_zksync_state_proof(keys, root.state_root, proof, contract_address)?;
+
+		if values.into_values().any(|val| val.is_none()) {
+			Err(Error::Custom("Missing values for some keys in the proof".to_string()))?
+		}
+
+		Ok(())
+	}
+
+	fn receipts_state_trie_key(&self, items: RequestResponse) -> Vec<Vec<u8>> {
+		req_res_receipt_keys::<H>(items)
+	}
+
+	fn verify_state_proof(
+		&self,
+		host: &dyn IsmpHost,
+		keys: Vec<Vec<u8>>,
+		root: StateCommitment,
+		proof: &Proof,
+	) -> Result<BTreeMap<Vec<u8>, Option<Vec<u8>>>, Error> {
+		let ismp_address = EvmHosts::<T>::get(&proof.height.id.state_id)
+			.ok_or_else(|| Error::Custom("Ismp contract address not found".to_string()))?;
+		verify_zksync_state_proof(keys, root.state_root, proof, ismp_address)
+	}
+}
@@ -20,14 +20,14 @@ extern crate alloc;
 use alloc::{boxed::Box, string::ToString, vec};
 use alloy_sol_types::SolType;
 use core::marker::PhantomData;
-use pallet_token_governor::TokenGatewayParams;
+use pallet_token_governor::{
+	convert_to_balance, convert_to_erc20, TokenGatewayParams, MAX_DECIMALS,
+};
 
 use frame_support::{
 	ensure,
-	traits::{
-		fungibles::{self},
-		Get,
-	},
+	pallet_prelude::DispatchResult,
+	traits::{fungibles, Get},
 };
 
 use ismp::{
@@ -40,9 +40,15 @@ use ismp::{
 };
 pub use pallet::*;
 use sp_core::{H160, H256, U256};
-use sp_runtime::{traits::AccountIdConversion, Permill};
+use sp_runtime::{
+	traits::{AccountIdConversion, SaturatedConversion},
+	Permill,
+};
 use staging_xcm::{
-	v3::{AssetId, Fungibility, Junction, MultiAsset, MultiAssets, MultiLocation, WeightLimit},
+	v3::{
+		AssetId, Fungibility, Junction, Junctions, MultiAsset, MultiAssets, MultiLocation,
+		WeightLimit,
+	},
 	VersionedMultiAssets, VersionedMultiLocation,
 };
 use xcm_utilities::MultiAccount;
@@ -96,12 +102,33 @@ pub mod pallet {
 	#[pallet::getter(fn params)]
 	pub type Params<T> = StorageValue<_, AssetGatewayParams, OptionQuery>;
 
+	/// Maps the XCM location of a supported asset to its ERC6160 asset identifier on the token
+	/// governor
+	#[pallet::storage]
+	pub type AssetIds<T> = StorageMap<_, Blake2_128Concat, MultiLocation, H256, OptionQuery>;
+
+	/// Maps ERC6160 asset identifiers to the XCM location of the asset
+	#[pallet::storage]
+	pub type AssetLocations<T> = StorageMap<_, Identity, H256, MultiLocation, OptionQuery>;
+
+	/// Maps ERC6160 asset identifiers to the decimals of the asset on its reserve chain
+	#[pallet::storage]
+	pub type AssetDecimals<T> = StorageMap<_, Identity, H256, u8, OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Error encountered while dispatching post request
 		DispatchPostError,
 		/// Pallet has not been initialized
 		NotInitialized,
+		/// The asset has not been registered on the token governor
+		UnknownAsset,
+		/// The asset location or identifier is already registered
+		AssetAlreadyRegistered,
+		/// The asset decimals exceed the maximum supported decimals
+		InvalidDecimals,
+		/// The amount can't be represented exactly in the ERC6160 denomination
+		InexactAmount,
 	}
 
 	/// Events emiited by the relayer pallet
@@ -116,6 +143,8 @@ pub mod pallet {
 			to: H160,
 			/// Amount transferred
 			amount: <T::Assets as fungibles::Inspect<T::AccountId>>::Balance,
+			/// The ERC6160 asset identifier
+			asset_id: H256,
 			/// Destination chain
 			dest: StateMachine,
 			/// Request commitment
@@ -129,6 +158,8 @@ pub mod pallet {
 			beneficiary: T::AccountId,
 			/// Amount transferred
 			amount: <T::Assets as fungibles::Inspect<T::AccountId>>::Balance,
+			/// The ERC6160 asset identifier
+			asset_id: H256,
 			/// Destination chain
 			source: StateMachine,
 		},
@@ -140,9 +171,27 @@ pub mod pallet {
 			beneficiary: T::AccountId,
 			/// Amount transferred
 			amount: <T::Assets as fungibles::Inspect<T::AccountId>>::Balance,
+			/// The ERC6160 asset identifier
+			asset_id: H256,
 			/// Destination chain
 			source: StateMachine,
 		},
+
+		/// An XCM asset location has been mapped to an ERC6160 asset
+		AssetRegistered {
+			/// The ERC6160 asset identifier
+			asset_id: H256,
+			/// The XCM location of the asset
+			location: MultiLocation,
+		},
+
+		/// An XCM asset location is no longer supported
+		AssetDeregistered {
+			/// The ERC6160 asset identifier
+			asset_id: H256,
+			/// The XCM location of the asset
+			location: MultiLocation,
+		},
 	}
 
 	#[derive(Clone, Encode, Decode, scale_info::TypeInfo, Eq, PartialEq, RuntimeDebug)]
@@ -183,6 +232,52 @@ pub mod pallet {
 			Params::<T>::put(current_params);
 			Ok(())
 		}
+
+		/// Maps the XCM location of an asset to an ERC6160 asset which has already been registered
+		/// on the token governor, allowing it to be teleported through the gateway. `decimals` are
+		/// the decimals of the asset on its reserve chain, amounts are converted from and to the 18
+		/// decimals of its ERC6160 representation with them.
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		#[pallet::call_index(1)]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			asset_id: H256,
+			location: MultiLocation,
+			decimals: u8,
+		) -> DispatchResult {
+			<T as pallet_ismp::Config>::AdminOrigin::ensure_origin(origin)?;
+			ensure!(decimals <= MAX_DECIMALS, Error::<T>::InvalidDecimals);
+			ensure!(
+				pallet_token_governor::AssetMetadatas::<T>::contains_key(&asset_id),
+				Error::<T>::UnknownAsset
+			);
+			ensure!(
+				!AssetIds::<T>::contains_key(&location) &&
+					!AssetLocations::<T>::contains_key(&asset_id),
+				Error::<T>::AssetAlreadyRegistered
+			);
+
+			AssetIds::<T>::insert(location.clone(), asset_id);
+			AssetLocations::<T>::insert(asset_id, location.clone());
+			AssetDecimals::<T>::insert(asset_id, decimals);
+
+			Self::deposit_event(Event::<T>::AssetRegistered { asset_id, location });
+			Ok(())
+		}
+
+		/// Removes the XCM location mapping for an ERC6160 asset. In-flight requests for this
+		/// asset will fail on arrival or timeout, so this should be used with caution.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 3))]
+		#[pallet::call_index(2)]
+		pub fn deregister_asset(origin: OriginFor<T>, asset_id: H256) -> DispatchResult {
+			<T as pallet_ismp::Config>::AdminOrigin::ensure_origin(origin)?;
+			let location = AssetLocations::<T>::take(&asset_id).ok_or(Error::<T>::UnknownAsset)?;
+			AssetIds::<T>::remove(&location);
+			AssetDecimals::<T>::remove(&asset_id);
+
+			Self::deposit_event(Event::<T>::AssetDeregistered { asset_id, location });
+			Ok(())
+		}
 	}
 }
 
@@ -215,9 +310,77 @@ where
 		sp_io::hashing::keccak_256(b"DOT").into()
 	}
 
+	/// Returns the ERC6160 asset identifier for the asset at the given location. The relay chain
+	/// native asset is always supported.
+	pub fn asset_id(location: &MultiLocation) -> Option<H256> {
+		AssetIds::<T>::get(location)
+			.or_else(|| (*location == MultiLocation::parent()).then(Self::dot_asset_id))
+	}
+
+	/// Returns the XCM location of the given ERC6160 asset.
+	pub fn asset_location(asset_id: H256) -> Option<MultiLocation> {
+		AssetLocations::<T>::get(&asset_id)
+			.or_else(|| (asset_id == Self::dot_asset_id()).then(MultiLocation::parent))
+	}
+
+	/// Returns the decimals of the given ERC6160 asset on its reserve chain.
+	pub fn asset_decimals(asset_id: H256) -> Option<u8> {
+		AssetDecimals::<T>::get(&asset_id)
+			.or_else(|| (asset_id == Self::dot_asset_id()).then_some(DOT_DECIMALS))
+	}
+
+	/// Returns the location of the chain which acts as the reserve for the asset, this is the
+	/// relay chain or the parachain the asset location is rooted in.
+	pub fn reserve_location(asset: &MultiLocation) -> MultiLocation {
+		match asset.interior.first() {
+			Some(Junction::Parachain(id)) =>
+				MultiLocation::new(asset.parents, Junctions::X1(Junction::Parachain(*id))),
+			_ => MultiLocation::new(asset.parents, Junctions::Here),
+		}
+	}
+
+	/// Sends the custodied asset back to the beneficiary on its reserve chain using xcm
+	pub fn send_to_reserve(
+		location: MultiLocation,
+		beneficiary: [u8; 32],
+		amount: u128,
+	) -> DispatchResult {
+		let xcm_beneficiary: MultiLocation =
+			Junction::AccountId32 { network: None, id: beneficiary }.into();
+		let xcm_dest = VersionedMultiLocation::V3(Self::reserve_location(&location));
+		let fee_asset_item = 0;
+		let weight_limit = WeightLimit::Unlimited;
+		let asset =
+			MultiAsset { id: AssetId::Concrete(location), fun: Fungibility::Fungible(amount) };
+
+		let mut assets = MultiAssets::new();
+		assets.push(asset);
+
+		pallet_xcm::Pallet::<T>::limited_reserve_transfer_assets(
+			frame_system::RawOrigin::Signed(Self::account_id()).into(),
+			Box::new(xcm_dest),
+			Box::new(xcm_beneficiary.into()),
+			Box::new(VersionedMultiAssets::V3(assets)),
+			fee_asset_item,
+			weight_limit,
+		)
+	}
+
+	/// Converts an amount with the given decimals to its ERC6160 denomination, rejecting amounts
+	/// which can't be represented exactly.
+	pub fn convert_amount(amount: u128, decimals: u8) -> Result<alloy_primitives::U256, Error<T>> {
+		let mut bytes = [0u8; 32];
+		convert_to_erc20(amount, decimals)
+			.map_err(|_| Error::<T>::InexactAmount)?
+			.to_big_endian(&mut bytes);
+		Ok(alloy_primitives::U256::from_be_bytes(bytes))
+	}
+
 	/// Dispatch ismp request to token gateway on destination chain
 	pub fn dispatch_request(
 		multi_account: MultiAccount<T::AccountId>,
+		asset_id: H256,
+		decimals: u8,
 		amount: <T::Assets as fungibles::Inspect<T::AccountId>>::Balance,
 	) -> Result<(), Error<T>> {
 		let dispatcher = <T as Config>::IsmpHost::default();
@@ -225,16 +388,10 @@ where
 		let mut to = [0u8; 32];
 		to[12..].copy_from_slice(&multi_account.evm_account.0);
 		let from: [u8; 32] = multi_account.substrate_account.clone().into();
-		let asset_id = Self::dot_asset_id().0.into();
 		let body = Body {
-			amount: {
-				let amount: u128 = amount.into();
-				let mut bytes = [0u8; 32];
-				convert_to_erc20(amount).to_big_endian(&mut bytes);
-				alloy_primitives::U256::from_be_bytes(bytes)
-			},
-			max_fee: Default::default(),
-			asset_id,
+			amount: Self::convert_amount(amount.into(), decimals)?,
+			max_fee: Self::convert_amount(multi_account.max_fee, decimals)?,
+			asset_id: asset_id.0.into(),
 			redeem: false,
 			from: from.into(),
 			to: to.into(),
@@ -255,8 +412,11 @@ where
 			},
 		};
 
-		let metadata =
-			FeeMetadata { payer: multi_account.substrate_account.clone(), fee: Default::default() };
+		// The relayer fee is paid in the native token by the sender
+		let metadata = FeeMetadata {
+			payer: multi_account.substrate_account.clone(),
+			fee: multi_account.relayer_fee.saturated_into(),
+		};
 		let commitment = dispatcher
 			.dispatch_request(DispatchRequest::Post(dispatch_post), metadata)
 			.map_err(|_| Error::<T>::DispatchPostError)?;
@@ -266,6 +426,7 @@ where
 			to: multi_account.evm_account,
 			dest: multi_account.dest_state_machine,
 			amount,
+			asset_id,
 			commitment,
 		});
 
@@ -352,21 +513,22 @@ where
 			}
 		})?;
 
-		// Check that the asset id is equal to the known asset id
-		ensure!(
-			body.asset_id.0 == Pallet::<T>::dot_asset_id().0,
-			ismp::error::Error::ModuleDispatchError {
+		// Check that the asset id has been registered
+		let asset_id: H256 = body.asset_id.0.into();
+		let (location, decimals) = Pallet::<T>::asset_location(asset_id)
+			.zip(Pallet::<T>::asset_decimals(asset_id))
+			.ok_or_else(|| ismp::error::Error::ModuleDispatchError {
 				msg: "Token Gateway: AssetId is unknown".to_string(),
 				meta: Meta {
 					source: request.source_chain(),
 					dest: request.dest_chain(),
 					nonce: request.nonce(),
 				},
-			}
-		);
+			})?;
 
-		let amount = convert_to_balance(U256::from_big_endian(&body.amount.to_be_bytes::<32>()))
-			.map_err(|_| ismp::error::Error::ModuleDispatchError {
+		let amount =
+			convert_to_balance(U256::from_big_endian(&body.amount.to_be_bytes::<32>()), decimals)
+				.map_err(|_| ismp::error::Error::ModuleDispatchError {
 				msg: "Token Gateway: Trying to withdraw Invalid amount".to_string(),
 				meta: Meta {
 					source: request.source_chain(),
@@ -375,41 +537,22 @@ where
 				},
 			})?;
 
-		let asset_id = MultiLocation::parent();
-
-		// We don't custody user funds, we send the dot back to the relaychain using xcm
-		let xcm_beneficiary: MultiLocation =
-			Junction::AccountId32 { network: None, id: body.to.0 }.into();
-		let xcm_dest = VersionedMultiLocation::V3(MultiLocation::parent());
-		let fee_asset_item = 0;
-		let weight_limit = WeightLimit::Unlimited;
-		let asset =
-			MultiAsset { id: AssetId::Concrete(asset_id), fun: Fungibility::Fungible(amount) };
-
-		let mut assets = MultiAssets::new();
-		assets.push(asset);
-
-		// Send xcm back to relaychain
-		pallet_xcm::Pallet::<T>::limited_reserve_transfer_assets(
-			frame_system::RawOrigin::Signed(Pallet::<T>::account_id()).into(),
-			Box::new(xcm_dest),
-			Box::new(xcm_beneficiary.into()),
-			Box::new(VersionedMultiAssets::V3(assets)),
-			fee_asset_item,
-			weight_limit,
-		)
-		.map_err(|_| ismp::error::Error::ModuleDispatchError {
-			msg: "Token Gateway: Failed execute xcm to relay chain".to_string(),
-			meta: Meta {
-				source: request.source_chain(),
-				dest: request.dest_chain(),
-				nonce: request.nonce(),
-			},
+		// We don't custody user funds, we send the asset back to its reserve chain using xcm
+		Pallet::<T>::send_to_reserve(location, body.to.0, amount).map_err(|_| {
+			ismp::error::Error::ModuleDispatchError {
+				msg: "Token Gateway: Failed execute xcm to reserve chain".to_string(),
+				meta: Meta {
+					source: request.source_chain(),
+					dest: request.dest_chain(),
+					nonce: request.nonce(),
+				},
+			}
 		})?;
 
 		Pallet::<T>::deposit_event(Event::<T>::AssetReceived {
 			beneficiary: body.to.0.into(),
 			amount: amount.into(),
+			asset_id,
 			source: request.source_chain(),
 		});
 
@@ -428,7 +571,7 @@ where
 	}
 
 	fn on_timeout(&self, request: Timeout) -> Result<(), ismp::error::Error> {
-		// We don't custody user funds, we send the dot back to the relaychain using xcm
+		// We don't custody user funds, we send the asset back to its reserve chain using xcm
 		match request {
 			Timeout::Request(Request::Post(post)) => {
 				let request = Request::Post(post.clone());
@@ -455,52 +598,46 @@ where
 						},
 					}
 				})?;
-				// Send xcm back to relaychain
-
-				let amount =
-					convert_to_balance(U256::from_big_endian(&body.amount.to_be_bytes::<32>()))
-						.map_err(|_| ismp::error::Error::ModuleDispatchError {
-							msg: "Token Gateway: Trying to withdraw Invalid amount".to_string(),
-							meta: Meta {
-								source: request.source_chain(),
-								dest: request.dest_chain(),
-								nonce: request.nonce(),
-							},
-						})?;
-				// We do an xcm limited reserve transfer from the pallet custody account to the user
-				// on the relaychain;
-				let xcm_beneficiary: MultiLocation =
-					Junction::AccountId32 { network: None, id: beneficiary.clone().into() }.into();
-				let xcm_dest = VersionedMultiLocation::V3(MultiLocation::parent());
-				let fee_asset_item = 0;
-				let weight_limit = WeightLimit::Unlimited;
-				let asset = MultiAsset {
-					id: AssetId::Concrete(MultiLocation::parent()),
-					fun: Fungibility::Fungible(amount),
-				};
-
-				let mut assets = MultiAssets::new();
-				assets.push(asset);
-				pallet_xcm::Pallet::<T>::limited_reserve_transfer_assets(
-					frame_system::RawOrigin::Signed(Pallet::<T>::account_id()).into(),
-					Box::new(xcm_dest),
-					Box::new(xcm_beneficiary.into()),
-					Box::new(VersionedMultiAssets::V3(assets)),
-					fee_asset_item,
-					weight_limit,
+				let asset_id: H256 = body.asset_id.0.into();
+				let (location, decimals) = Pallet::<T>::asset_location(asset_id)
+					.zip(Pallet::<T>::asset_decimals(asset_id))
+					.ok_or_else(|| ismp::error::Error::ModuleDispatchError {
+						msg: "Token Gateway: AssetId is unknown".to_string(),
+						meta: Meta {
+							source: request.source_chain(),
+							dest: request.dest_chain(),
+							nonce: request.nonce(),
+						},
+					})?;
+
+				let amount = convert_to_balance(
+					U256::from_big_endian(&body.amount.to_be_bytes::<32>()),
+					decimals,
 				)
 				.map_err(|_| ismp::error::Error::ModuleDispatchError {
-					msg: "Token Gateway: Failed to execute xcm to relay chain".to_string(),
+					msg: "Token Gateway: Trying to withdraw Invalid amount".to_string(),
 					meta: Meta {
 						source: request.source_chain(),
 						dest: request.dest_chain(),
 						nonce: request.nonce(),
 					},
 				})?;
+				// We do an xcm limited reserve transfer from the pallet custody account to the user
+				// on the reserve chain
+				Pallet::<T>::send_to_reserve(location, beneficiary.clone().into(), amount)
+					.map_err(|_| ismp::error::Error::ModuleDispatchError {
+						msg: "Token Gateway: Failed to execute xcm to reserve chain".to_string(),
+						meta: Meta {
+							source: request.source_chain(),
+							dest: request.dest_chain(),
+							nonce: request.nonce(),
+						},
+					})?;
 
 				Pallet::<T>::deposit_event(Event::<T>::AssetRefunded {
 					beneficiary,
 					amount: amount.into(),
+					asset_id,
 					source: request.dest_chain(),
 				});
 
@@ -523,62 +660,18 @@ where
 	}
 }
 
-/// Converts an ERC20 U256 to a DOT u128
-pub fn convert_to_balance(value: U256) -> Result<u128, anyhow::Error> {
-	let dec_str = (value / U256::from(100_000_000u128)).to_string();
-	dec_str.parse().map_err(|e| anyhow::anyhow!("{e:?}"))
-}
-
-/// Converts a DOT u128 to an Erc20 denomination
-pub fn convert_to_erc20(value: u128) -> U256 {
-	U256::from(value) * U256::from(100_000_000u128)
-}
+/// Decimals of the relay chain native asset
+const DOT_DECIMALS: u8 = 10;
 
 #[cfg(test)]
 mod tests {
-	use sp_core::U256;
 	use sp_runtime::Permill;
 	use std::ops::Mul;
 
-	use crate::{convert_to_balance, convert_to_erc20};
 	#[test]
 	fn test_per_mill() {
 		let per_mill = Permill::from_parts(1_000);
 
 		println!("{}", per_mill.mul(20_000_000u128));
 	}
-
-	#[test]
-	fn balance_conversions() {
-		let supposedly_small_u256 = U256::from_dec_str("1000000000000000000").unwrap();
-		// convert erc20 value to dot value
-		let converted_balance = convert_to_balance(supposedly_small_u256).unwrap();
-		println!("{}", converted_balance);
-
-		let dot = 10_000_000_000u128;
-
-		assert_eq!(converted_balance, dot);
-
-		// Convert 1 dot to erc20
-
-		let dot = 10_000_000_000u128;
-		let erc_20_val = convert_to_erc20(dot);
-		assert_eq!(erc_20_val, U256::from_dec_str("1000000000000000000").unwrap());
-	}
-
-	#[test]
-	fn max_value_check() {
-		let max = U256::MAX;
-
-		let converted_balance = convert_to_balance(max);
-		assert!(converted_balance.is_err())
-	}
-
-	#[test]
-	fn min_value_check() {
-		let min = U256::from(1u128);
-
-		let converted_balance = convert_to_balance(min).unwrap();
-		assert_eq!(converted_balance, 0);
-	}
 }
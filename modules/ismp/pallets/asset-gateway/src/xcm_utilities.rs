@@ -6,8 +6,8 @@ use sp_core::{Get, H160};
 use staging_xcm::{
 	prelude::MultiLocation,
 	v3::{
-		Error as XcmError, Junction, Junctions, MultiAsset, NetworkId, Result as XcmResult,
-		XcmContext,
+		AssetId, Error as XcmError, Junction, Junctions, MultiAsset, NetworkId,
+		Result as XcmResult, XcmContext,
 	},
 };
 use staging_xcm_builder::{AssetChecking, FungiblesMutateAdapter};
@@ -43,19 +43,29 @@ pub struct MultiAccount<A> {
 	pub dest_state_machine: StateMachine,
 	/// Request time out in seconds
 	pub timeout: u64,
+	/// Relayer fee in the native token, paid by the origin substrate account
+	pub relayer_fee: u128,
+	/// Maximum liquidity fee, in the teleported asset, that may be paid to a filler on the
+	/// destination
+	pub max_fee: u128,
 }
 
-// Supports a Multilocation interior of Junctions::X3
+// Supports a Multilocation interior of Junctions::X3, Junctions::X4 or Junctions::X5
 // Junctions::X3(AccountId32 { .. }, AccountKey20 { .. }, GeneralIndex(..))
-// The value specified in the GeneralIndex will be used as the timeout in seconds for the ismp
-// request that will be dispatched
+// Junctions::X4(AccountId32 { .. }, AccountKey20 { .. }, GeneralIndex(..), GeneralIndex(..))
+// Junctions::X5(AccountId32 { .. }, AccountKey20 { .. }, GeneralIndex(..), GeneralIndex(..),
+// GeneralIndex(..))
+// The value specified in the first GeneralIndex will be used as the timeout in seconds for the ismp
+// request that will be dispatched. The optional second GeneralIndex is the relayer fee and the
+// optional third is the maximum liquidity fee.
 impl<A> ConvertLocation<MultiAccount<A>> for MultilocationToMultiAccount<A>
 where
 	A: From<[u8; 32]> + Into<[u8; 32]> + Clone,
 {
 	fn convert_location(location: &MultiLocation) -> Option<MultiAccount<A>> {
-		// We only support locations X3 Junctions addressed to our parachain and an ethereum account
-		match location {
+		// We only support locations X3, X4 or X5 Junctions addressed to our parachain and an
+		// ethereum account
+		let (id, network, key, timeout, relayer_fee, max_fee) = match location {
 			MultiLocation {
 				parents: 0,
 				interior:
@@ -64,21 +74,43 @@ where
 						Junction::AccountKey20 { network: Some(network), key },
 						Junction::GeneralIndex(timeout),
 					),
-			} => {
-				// Ensure that the network Id is one of the supported ethereum networks
-				// If it transforms correctly we return the ethereum account
-				let dest_state_machine =
-					StateMachine::try_from(WrappedNetworkId(network.clone())).ok()?;
-				Some(MultiAccount {
-					substrate_account: A::from(*id),
-					evm_account: H160::from(*key),
-					dest_state_machine,
-					timeout: *timeout as u64,
-				})
-			},
+			} => (id, network, key, timeout, 0, 0),
+			MultiLocation {
+				parents: 0,
+				interior:
+					Junctions::X4(
+						Junction::AccountId32 { id, .. },
+						Junction::AccountKey20 { network: Some(network), key },
+						Junction::GeneralIndex(timeout),
+						Junction::GeneralIndex(relayer_fee),
+					),
+			} => (id, network, key, timeout, *relayer_fee, 0),
+			MultiLocation {
+				parents: 0,
+				interior:
+					Junctions::X5(
+						Junction::AccountId32 { id, .. },
+						Junction::AccountKey20 { network: Some(network), key },
+						Junction::GeneralIndex(timeout),
+						Junction::GeneralIndex(relayer_fee),
+						Junction::GeneralIndex(max_fee),
+					),
+			} => (id, network, key, timeout, *relayer_fee, *max_fee),
 			// Any other multilocation format is unsupported
-			_ => None,
-		}
+			_ => return None,
+		};
+
+		// Ensure that the network Id is one of the supported ethereum networks
+		// If it transforms correctly we return the ethereum account
+		let dest_state_machine = StateMachine::try_from(WrappedNetworkId(network.clone())).ok()?;
+		Some(MultiAccount {
+			substrate_account: A::from(*id),
+			evm_account: H160::from(*key),
+			dest_state_machine,
+			timeout: *timeout as u64,
+			relayer_fee,
+			max_fee,
+		})
 	}
 }
 
@@ -156,6 +188,13 @@ where
 
 		// Ismp xcm transaction
 		if let Some(who) = MultilocationToMultiAccount::<T::AccountId>::convert_location(who) {
+			// Only assets which have been registered on the token governor can be teleported
+			let AssetId::Concrete(location) = &what.id else { Err(MatchError::AssetNotHandled)? };
+			let erc6160_asset_id = Pallet::<T>::asset_id(location)
+				.ok_or_else(|| XcmError::FailedToTransactAsset("Unknown asset"))?;
+			let decimals = Pallet::<T>::asset_decimals(erc6160_asset_id)
+				.ok_or_else(|| XcmError::FailedToTransactAsset("Unknown asset decimals"))?;
+
 			// We would remove the protocol fee at this point
 
 			let protocol_account = Pallet::<T>::protocol_account_id();
@@ -164,6 +203,11 @@ where
 
 			let protocol_fees = protocol_percentage * u128::from(amount);
 			let remainder = amount - protocol_fees.into();
+			// Reject amounts which can't be teleported exactly before anything is minted, so the
+			// truncated remainder isn't left in custody
+			Pallet::<T>::convert_amount(remainder.into(), decimals)
+				.and_then(|_| Pallet::<T>::convert_amount(who.max_fee, decimals))
+				.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
 			// Mint protocol fees
 			T::Assets::mint_into(asset_id.clone(), &protocol_account, protocol_fees.into())
 				.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
@@ -171,7 +215,7 @@ where
 			T::Assets::mint_into(asset_id, &pallet_account, remainder)
 				.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
 			// We dispatch an ismp request to the destination chain
-			Pallet::<T>::dispatch_request(who, remainder)
+			Pallet::<T>::dispatch_request(who, erc6160_asset_id, decimals, remainder)
 				.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
 		} else {
 			Err(MatchError::AccountIdConversionFailed)?
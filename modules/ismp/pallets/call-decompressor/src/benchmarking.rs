@@ -0,0 +1,218 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for decompressing calls with each of the supported codecs.
+//!
+//! The compressed calls are built by hand, since the codecs only ship decompressors. The first
+//! `c` bytes of a call are incompressible and stored verbatim, the remaining bytes are zeroes.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use alloc::{vec, vec::Vec};
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+
+/// Size of the largest block in a zstd frame
+const ZSTD_MAX_BLOCK_SIZE: usize = 128 * 1024;
+
+/// Size of the uncompressed meta-blocks in brotli streams
+const BROTLI_META_BLOCK_SIZE: usize = 32 * 1024;
+
+/// A zstd dictionary trained on samples of ismp messages
+const DICTIONARY: &[u8] = include_bytes!("../fixtures/dictionary.zstd");
+
+/// Pseudo random bytes which can't be compressed
+fn incompressible(len: usize) -> Vec<u8> {
+	let mut state = 0x9e37_79b9_7f4a_7c15u64;
+	(0..len)
+		.map(|_| {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			state as u8
+		})
+		.collect()
+}
+
+/// A call of `s` bytes whose first `c` bytes are incompressible
+fn call(c: u32, s: u32) -> Vec<u8> {
+	let mut call = incompressible(c.min(s) as usize);
+	call.resize(s as usize, 0);
+	call
+}
+
+/// A zstd frame of the call, with the incompressible bytes in raw blocks and the zeroes in RLE
+/// blocks
+fn zstd_frame(c: u32, s: u32, dictionary_id: Option<u32>) -> Vec<u8> {
+	let raw = incompressible(c.min(s) as usize);
+	let mut frame = codecs::ZSTD_MAGIC.to_vec();
+	// the frame header descriptor only sets the dictionary id flag, followed by a 128KiB window
+	frame.push(if dictionary_id.is_some() { 3 } else { 0 });
+	frame.push(7 << 3);
+	if let Some(id) = dictionary_id {
+		frame.extend(id.to_le_bytes());
+	}
+
+	// (block type, block content, decompressed block size)
+	let mut blocks = raw
+		.chunks(ZSTD_MAX_BLOCK_SIZE)
+		.map(|chunk| (0u32, chunk, chunk.len()))
+		.collect::<Vec<_>>();
+	let mut zeroes = s as usize - raw.len();
+	while zeroes > 0 {
+		let size = zeroes.min(ZSTD_MAX_BLOCK_SIZE);
+		blocks.push((1, &[0u8][..], size));
+		zeroes -= size;
+	}
+	if blocks.is_empty() {
+		blocks.push((0, &[][..], 0));
+	}
+
+	let last = blocks.len() - 1;
+	for (index, (block_type, content, size)) in blocks.into_iter().enumerate() {
+		let header = (index == last) as u32 | block_type << 1 | (size as u32) << 3;
+		frame.extend(&header.to_le_bytes()[..3]);
+		frame.extend(content);
+	}
+
+	frame
+}
+
+/// A brotli stream of the call in uncompressed meta-blocks
+fn brotli_stream(call: &[u8]) -> Vec<u8> {
+	let mut stream = BitWriter::default();
+	// WBITS = 16
+	stream.write(0, 1);
+	for chunk in call.chunks(BROTLI_META_BLOCK_SIZE) {
+		// ISLAST = 0, MNIBBLES = 4, MLEN - 1
+		stream.write(0, 1);
+		stream.write(0, 2);
+		stream.write(chunk.len() as u32 - 1, 16);
+		// ISUNCOMPRESSED, the chunk starts at the next byte
+		stream.write(1, 1);
+		stream.align();
+		stream.bytes.extend(chunk);
+	}
+	// ISLAST, ISLASTEMPTY
+	stream.write(1, 1);
+	stream.write(1, 1);
+
+	stream.bytes
+}
+
+/// Writes values least significant bit first, as brotli streams are read
+#[derive(Default)]
+struct BitWriter {
+	bytes: Vec<u8>,
+	/// Bits used in the last byte
+	used: u32,
+}
+
+impl BitWriter {
+	fn write(&mut self, value: u32, bits: u32) {
+		for bit in 0..bits {
+			if self.used == 0 {
+				self.bytes.push(0);
+			}
+			let last = self.bytes.len() - 1;
+			self.bytes[last] |= (((value >> bit) & 1) as u8) << self.used;
+			self.used = (self.used + 1) % 8;
+		}
+	}
+
+	fn align(&mut self) {
+		self.used = 0;
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	/// Upper bound of the benchmarked call sizes
+	const MAX_SIZE: u32 = 4 * ONE_MB;
+
+	#[benchmark]
+	fn decompress_zstd(
+		c: Linear<0, MAX_SIZE>,
+		s: Linear<0, MAX_SIZE>,
+	) -> Result<(), BenchmarkError> {
+		let mut compressed = vec![Codec::Zstd as u8];
+		compressed.extend(zstd_frame(c, s, None));
+
+		#[block]
+		{
+			Pallet::<T>::decompress(&compressed, s)?;
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn decompress_zstd_with_dictionary(
+		c: Linear<0, MAX_SIZE>,
+		s: Linear<0, MAX_SIZE>,
+	) -> Result<(), BenchmarkError> {
+		let id = codecs::zstd_dictionary_id(DICTIONARY)
+			.ok_or(BenchmarkError::Stop("Invalid benchmark dictionary"))?;
+		let dictionary = BoundedVec::try_from(DICTIONARY.to_vec())
+			.map_err(|_| BenchmarkError::Stop("MaxDictionarySize is below the dictionary size"))?;
+		Dictionaries::<T>::insert(id, dictionary);
+
+		let mut compressed = vec![Codec::ZstdWithDictionary as u8];
+		compressed.extend(id.to_le_bytes());
+		compressed.extend(zstd_frame(c, s, Some(id)));
+
+		#[block]
+		{
+			Pallet::<T>::decompress(&compressed, s)?;
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn decompress_lz4(
+		c: Linear<0, MAX_SIZE>,
+		s: Linear<0, MAX_SIZE>,
+	) -> Result<(), BenchmarkError> {
+		let mut compressed = vec![Codec::Lz4 as u8];
+		compressed.extend(lz4_flex::block::compress(&call(c, s)));
+
+		#[block]
+		{
+			Pallet::<T>::decompress(&compressed, s)?;
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn decompress_brotli(
+		c: Linear<0, MAX_SIZE>,
+		s: Linear<0, MAX_SIZE>,
+	) -> Result<(), BenchmarkError> {
+		let mut compressed = vec![Codec::Brotli as u8];
+		compressed.extend(brotli_stream(&call(c, s)));
+
+		#[block]
+		{
+			Pallet::<T>::decompress(&compressed, s)?;
+		}
+
+		Ok(())
+	}
+}
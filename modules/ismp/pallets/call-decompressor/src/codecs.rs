@@ -0,0 +1,155 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decompression codecs supported for compressed calls
+
+use alloc::{vec, vec::Vec};
+use brotli_decompressor::{
+	Allocator, BrotliDecompressStream, BrotliResult, BrotliState, SliceWrapper, SliceWrapperMut,
+};
+#[cfg(feature = "std")]
+use ruzstd::io::Read;
+#[cfg(not(feature = "std"))]
+use ruzstd::io_nostd::Read;
+use ruzstd::{FrameDecoder, StreamingDecoder};
+
+/// Magic number which prefixes every zstd frame
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Magic number which prefixes every zstd dictionary
+pub const ZSTD_DICTIONARY_MAGIC: [u8; 4] = [0x37, 0xa4, 0x30, 0xec];
+
+/// The compression codecs which can be selected by the header byte of a compressed call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Codec {
+	/// Zstd compression
+	Zstd = 0,
+	/// Zstd compression using one of the dictionaries stored in the pallet. The header byte is
+	/// followed by the little endian encoded dictionary id.
+	ZstdWithDictionary = 1,
+	/// Lz4 block compression
+	Lz4 = 2,
+	/// Brotli compression
+	Brotli = 3,
+}
+
+impl TryFrom<u8> for Codec {
+	type Error = DecompressionError;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Codec::Zstd),
+			1 => Ok(Codec::ZstdWithDictionary),
+			2 => Ok(Codec::Lz4),
+			3 => Ok(Codec::Brotli),
+			_ => Err(DecompressionError),
+		}
+	}
+}
+
+/// Error encountered while decompressing a call
+#[derive(Debug)]
+pub struct DecompressionError;
+
+/// Decompress a zstd frame, optionally using the provided dictionary
+pub fn zstd(
+	compressed: &[u8],
+	size: usize,
+	dictionary: Option<&[u8]>,
+) -> Result<Vec<u8>, DecompressionError> {
+	let mut frame_decoder = FrameDecoder::new();
+	if let Some(dictionary) = dictionary {
+		frame_decoder.add_dict(dictionary).map_err(|_| DecompressionError)?;
+	}
+	let mut decoder = StreamingDecoder::new_with_decoder(compressed, frame_decoder)
+		.map_err(|_| DecompressionError)?;
+
+	let mut result = vec![0u8; size];
+	let _ = decoder.read(&mut result);
+	Ok(result)
+}
+
+/// Returns the id of a zstd dictionary, if it is valid
+pub fn zstd_dictionary_id(dictionary: &[u8]) -> Option<u32> {
+	if !dictionary.starts_with(&ZSTD_DICTIONARY_MAGIC) {
+		return None;
+	}
+	FrameDecoder::new().add_dict(dictionary).ok()?;
+	let id = dictionary.get(4..8)?.try_into().ok()?;
+
+	Some(u32::from_le_bytes(id))
+}
+
+/// Decompress an lz4 block
+pub fn lz4(compressed: &[u8], size: usize) -> Result<Vec<u8>, DecompressionError> {
+	lz4_flex::block::decompress(compressed, size).map_err(|_| DecompressionError)
+}
+
+/// Decompress a brotli stream
+pub fn brotli(compressed: &[u8], size: usize) -> Result<Vec<u8>, DecompressionError> {
+	let mut state = BrotliState::new(HeapAllocator, HeapAllocator, HeapAllocator);
+	let mut result = vec![0u8; size];
+	let (mut available_in, mut input_offset) = (compressed.len(), 0);
+	let (mut available_out, mut output_offset, mut total_out) = (size, 0, 0);
+
+	match BrotliDecompressStream(
+		&mut available_in,
+		&mut input_offset,
+		compressed,
+		&mut available_out,
+		&mut output_offset,
+		&mut result,
+		&mut total_out,
+		&mut state,
+	) {
+		BrotliResult::ResultSuccess => Ok(result),
+		_ => Err(DecompressionError),
+	}
+}
+
+/// Heap backed allocator for the brotli decoder
+struct HeapAllocator;
+
+/// Memory handed out by the [`HeapAllocator`]
+struct HeapMemory<T>(Vec<T>);
+
+impl<T> Default for HeapMemory<T> {
+	fn default() -> Self {
+		Self(Vec::new())
+	}
+}
+
+impl<T> SliceWrapper<T> for HeapMemory<T> {
+	fn slice(&self) -> &[T] {
+		&self.0
+	}
+}
+
+impl<T> SliceWrapperMut<T> for HeapMemory<T> {
+	fn slice_mut(&mut self) -> &mut [T] {
+		&mut self.0
+	}
+}
+
+impl<T: Clone + Default> Allocator<T> for HeapAllocator {
+	type AllocatedMemory = HeapMemory<T>;
+
+	fn alloc_cell(&mut self, len: usize) -> Self::AllocatedMemory {
+		HeapMemory(vec![T::default(); len])
+	}
+
+	fn free_cell(&mut self, _data: Self::AllocatedMemory) {}
+}
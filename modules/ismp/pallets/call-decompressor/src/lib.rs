@@ -16,26 +16,30 @@
 
 extern crate alloc;
 
-use alloc::{vec, vec::Vec};
+mod benchmarking;
+pub mod codecs;
+pub mod weights;
+
+use alloc::vec::Vec;
 use codec::DecodeLimit;
+use codecs::Codec;
 use frame_support::{
-	dispatch::DispatchResult,
-	traits::{Get, IsSubType},
+	dispatch::{
+		extract_actual_weight, DispatchErrorWithPostInfo, DispatchResultWithPostInfo,
+		GetDispatchInfo, PostDispatchInfo,
+	},
+	traits::{Contains, Get},
+	weights::Weight,
 };
 pub use pallet::*;
-#[cfg(feature = "std")]
-use ruzstd::io::Read;
-#[cfg(not(feature = "std"))]
-use ruzstd::io_nostd::Read;
-use ruzstd::StreamingDecoder;
-use sp_core::H256;
 use sp_runtime::{
-	traits::ValidateUnsigned,
+	traits::{Dispatchable, ValidateUnsigned},
 	transaction_validity::{
 		InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
 	},
 	DispatchError,
 };
+pub use weights::WeightInfo;
 
 const ONE_MB: u32 = 1_000_000;
 /// This is the maximum nesting level required to decode
@@ -48,9 +52,8 @@ const MAX_EXTRINSIC_DECODE_DEPTH_LIMIT: u32 = 4;
 pub mod pallet {
 	use super::*;
 	use alloc::vec;
-	use frame_support::{pallet_prelude::*, traits::IsSubType};
+	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use sp_core::H256;
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -58,13 +61,43 @@ pub mod pallet {
 
 	/// The config trait
 	#[pallet::config]
-	pub trait Config:
-		frame_system::Config + pallet_ismp::Config + pallet_ismp_relayer::Config
-	{
+	pub trait Config: frame_system::Config {
+		/// The overarching call type, the decompressed calls are weighed with its dispatch info.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+
 		/// Represents the maximum call size in megabytes(MB)
 		type MaxCallSize: Get<u32>;
+
+		/// The runtime calls which may be executed from compressed calls, these are dispatched
+		/// with the unsigned origin.
+		type AllowedCalls: Contains<<Self as Config>::RuntimeCall>;
+
+		/// Validates the decompressed calls as unsigned transactions, this should be the runtime.
+		type UnsignedValidator: ValidateUnsigned<Call = <Self as Config>::RuntimeCall>;
+
+		/// Origin allowed to manage the compression dictionaries
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum size of a compression dictionary in bytes
+		type MaxDictionarySize: Get<u32>;
+
+		/// Upper bound of the weight of a decompressed call. Compressed calls are charged this
+		/// before dispatch, and refunded what the decompressed call didn't use.
+		type MaxCallWeight: Get<Weight>;
+
+		/// Weights of decompressing calls with each codec
+		type WeightInfo: WeightInfo;
 	}
 
+	/// Zstd dictionaries which compressed calls can be decompressed with, keyed by their
+	/// dictionary id.
+	#[pallet::storage]
+	#[pallet::getter(fn dictionaries)]
+	pub type Dictionaries<T: Config> =
+		StorageMap<_, Twox64Concat, u32, BoundedVec<u8, T::MaxDictionarySize>, OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Unsupported Call
@@ -77,51 +110,85 @@ pub mod pallet {
 		ErrorDecodingCall,
 		/// Call Size Out Of Bound
 		CallSizeOutOfBound,
+		/// Unknown compression codec
+		UnknownCodec,
+		/// Unknown compression dictionary
+		UnknownDictionary,
+		/// Invalid compression dictionary
+		InvalidDictionary,
+		/// Compression dictionary is larger than `MaxDictionarySize`
+		DictionaryTooLarge,
+		/// The decompressed call may use more than `MaxCallWeight`
+		CallWeightOutOfBound,
 	}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T>
-	where
-		<T as frame_system::Config>::Hash: From<H256>,
-		<T as frame_system::Config>::AccountId: From<[u8; 32]>,
-		T::Balance: Into<u128>,
-		<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp::Call<T>>,
-		<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp_relayer::Call<T>>,
-	{
+	impl<T: Config> Pallet<T> {
 		/// This is for decompressing and executing compressed encoded runtime calls
 		///
 		///  The dispatch origin for this call must be an unsigned one.
 		///
-		/// - `compressed`: the compressed encoded runtime call represented in bytes.
+		/// - `compressed`: the compressed encoded runtime call represented in bytes, prefixed by
+		/// the [`Codec`] it was compressed with.
 		/// - `encoded_call_size`: this is the size of the not compressed(decompressed) encoded call
 		/// in bytes.
+		///
+		/// Charges the decompression and the `MaxCallWeight` upper bound of the decompressed
+		/// call, refunding the weight the decompressed call didn't use.
 		#[pallet::call_index(0)]
-		#[pallet::weight({1_000_000})]
+		#[pallet::weight(Pallet::<T>::decompress_call_weight(compressed, *encoded_call_size))]
 		pub fn decompress_call(
 			origin: OriginFor<T>,
 			compressed: Vec<u8>,
 			encoded_call_size: u32,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 			ensure!(
 				encoded_call_size < T::MaxCallSize::get() * ONE_MB,
 				Error::<T>::CallSizeOutOfBound
 			);
-			let call_bytes = Self::decompress(compressed, encoded_call_size)?;
-			Self::decode_and_execute(call_bytes)?;
+			let decompression_weight = Self::decompression_weight(&compressed, encoded_call_size);
+			let call_bytes = Self::decompress(&compressed, encoded_call_size).map_err(|error| {
+				DispatchErrorWithPostInfo { post_info: Some(decompression_weight).into(), error }
+			})?;
+			Self::decode_and_execute(call_bytes, decompression_weight)
+		}
+
+		/// Adds a zstd dictionary which compressed calls can reference by its dictionary id.
+		///
+		/// The dispatch origin for this call must be `T::AdminOrigin`.
+		///
+		/// - `dictionary`: the zstd dictionary, trained on samples of the calls it will be used to
+		/// compress. It may be at most `T::MaxDictionarySize` bytes.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn add_dictionary(origin: OriginFor<T>, dictionary: Vec<u8>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let dictionary = BoundedVec::<u8, T::MaxDictionarySize>::try_from(dictionary)
+				.map_err(|_| Error::<T>::DictionaryTooLarge)?;
+			let id =
+				codecs::zstd_dictionary_id(&dictionary).ok_or(Error::<T>::InvalidDictionary)?;
+			Dictionaries::<T>::insert(id, dictionary);
+			Ok(())
+		}
+
+		/// Removes a zstd dictionary.
+		///
+		/// The dispatch origin for this call must be `T::AdminOrigin`.
+		///
+		/// - `id`: the dictionary id.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_dictionary(origin: OriginFor<T>, id: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Dictionaries::<T>::contains_key(id), Error::<T>::UnknownDictionary);
+			Dictionaries::<T>::remove(id);
 			Ok(())
 		}
 	}
 
 	#[pallet::validate_unsigned]
-	impl<T: Config> ValidateUnsigned for Pallet<T>
-	where
-		<T as frame_system::Config>::Hash: From<H256>,
-		<T as frame_system::Config>::AccountId: From<[u8; 32]>,
-		T::Balance: Into<u128>,
-		<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp::Call<T>>,
-		<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp_relayer::Call<T>>,
-	{
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
 
 		// empty pre-dispatch so we don't modify storage
@@ -134,46 +201,34 @@ pub mod pallet {
 				return Err(TransactionValidityError::Invalid(InvalidTransaction::Call));
 			};
 
-			let decompressed = Self::decompress(compressed.clone(), encoded_call_size.clone())
+			if *encoded_call_size >= T::MaxCallSize::get() * ONE_MB {
+				return Err(TransactionValidityError::Invalid(
+					InvalidTransaction::ExhaustsResources,
+				));
+			}
+
+			let decompressed = Self::decompress(compressed, *encoded_call_size)
 				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Call))?;
 
-			let runtime_call = T::RuntimeCall::decode_with_depth_limit(
+			let runtime_call = <T as Config>::RuntimeCall::decode_with_depth_limit(
 				MAX_EXTRINSIC_DECODE_DEPTH_LIMIT,
 				&mut &decompressed[..],
 			)
 			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Call))?;
 
-			let provides = if let Some(call) =
-				IsSubType::<pallet_ismp::Call<T>>::is_sub_type(&runtime_call).cloned()
-			{
-				let _: Result<(), TransactionValidityError> = match call {
-					pallet_ismp::Call::handle_unsigned { messages: _ } => Ok(()),
-					_ => Err(TransactionValidityError::Invalid(InvalidTransaction::Call))?,
-				};
-
-				let ValidTransaction { provides, .. } =
-					<pallet_ismp::Pallet<T> as ValidateUnsigned>::validate_unsigned(source, &call)
-						.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Call))?;
-
-				provides
-			} else if let Some(call) =
-				IsSubType::<pallet_ismp_relayer::Call<T>>::is_sub_type(&runtime_call).cloned()
-			{
-				let _: Result<(), TransactionValidityError> = match call.clone() {
-					pallet_ismp_relayer::Call::accumulate_fees { withdrawal_proof: _ } => Ok(()),
-					_ => Err(TransactionValidityError::Invalid(InvalidTransaction::Call))?,
-				};
-
-				let ValidTransaction { provides, .. } =
-					<pallet_ismp_relayer::Pallet<T> as ValidateUnsigned>::validate_unsigned(
-						source, &call,
-					)
-					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Call))?;
-
-				provides
-			} else {
+			if !T::AllowedCalls::contains(&runtime_call) {
 				return Err(TransactionValidityError::Invalid(InvalidTransaction::Call));
-			};
+			}
+
+			if !runtime_call.get_dispatch_info().weight.all_lte(T::MaxCallWeight::get()) {
+				return Err(TransactionValidityError::Invalid(
+					InvalidTransaction::ExhaustsResources,
+				));
+			}
+
+			let ValidTransaction { provides, .. } =
+				T::UnsignedValidator::validate_unsigned(source, &runtime_call)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Call))?;
 
 			Ok(ValidTransaction {
 				priority: 100,
@@ -186,62 +241,117 @@ pub mod pallet {
 	}
 }
 
-impl<T: Config> Pallet<T>
-where
-	<T as frame_system::Config>::Hash: From<H256>,
-	<T as frame_system::Config>::AccountId: From<[u8; 32]>,
-	T::Balance: Into<u128>,
-	<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp::Call<T>>,
-	<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ismp_relayer::Call<T>>,
-{
+impl<T: Config> Pallet<T> {
 	/// This decompresses the encoded runtime call
 	///
-	/// - `compressed_bytes`: the compressed encoded runtime call represented in bytes.
+	/// - `compressed_bytes`: the compressed encoded runtime call represented in bytes, prefixed
+	/// by the [`Codec`] it was compressed with. Zstd frames without a codec prefix are also
+	/// accepted.
 	/// - `encoded_call_size`: this is the size of the not compressed(decompressed) encoded call
 	/// in bytes.
 	pub fn decompress(
-		compressed_bytes: Vec<u8>,
+		compressed_bytes: &[u8],
 		encoded_call_size: u32,
 	) -> Result<Vec<u8>, DispatchError> {
-		let mut decoder = StreamingDecoder::new(compressed_bytes.as_slice())
-			.map_err(|_| Error::<T>::DecompressionFailed)?;
+		let size = encoded_call_size as usize;
+		if compressed_bytes.starts_with(&codecs::ZSTD_MAGIC) {
+			return Ok(codecs::zstd(compressed_bytes, size, None)
+				.map_err(|_| Error::<T>::DecompressionFailed)?);
+		}
+
+		let (codec, compressed) =
+			compressed_bytes.split_first().ok_or(Error::<T>::DecompressionFailed)?;
+		let codec = Codec::try_from(*codec).map_err(|_| Error::<T>::UnknownCodec)?;
+		let decompressed = match codec {
+			Codec::Zstd => codecs::zstd(compressed, size, None),
+			Codec::ZstdWithDictionary => {
+				let id = compressed
+					.get(..4)
+					.and_then(|id| id.try_into().ok())
+					.map(u32::from_le_bytes)
+					.ok_or(Error::<T>::DecompressionFailed)?;
+				let dictionary = Dictionaries::<T>::get(id).ok_or(Error::<T>::UnknownDictionary)?;
+				codecs::zstd(&compressed[4..], size, Some(&dictionary[..]))
+			},
+			Codec::Lz4 => codecs::lz4(compressed, size),
+			Codec::Brotli => codecs::brotli(compressed, size),
+		};
 
-		let mut result = vec![0u8; encoded_call_size as usize];
-		let _ = decoder.read(&mut result);
-		Ok(result)
+		Ok(decompressed.map_err(|_| Error::<T>::DecompressionFailed)?)
+	}
+
+	/// Weight of decompressing `compressed` into a call of `encoded_call_size` bytes with the
+	/// codec selected by its header byte. Unknown codecs are rejected before decompressing.
+	pub fn decompression_weight(compressed: &[u8], encoded_call_size: u32) -> Weight {
+		let len = compressed.len() as u32;
+		if compressed.starts_with(&codecs::ZSTD_MAGIC) {
+			return T::WeightInfo::decompress_zstd(len, encoded_call_size);
+		}
+
+		match compressed.first().map(|codec| Codec::try_from(*codec)) {
+			Some(Ok(Codec::Zstd)) => T::WeightInfo::decompress_zstd(len, encoded_call_size),
+			Some(Ok(Codec::ZstdWithDictionary)) =>
+				T::WeightInfo::decompress_zstd_with_dictionary(len, encoded_call_size)
+					.saturating_add(Weight::from_parts(0, T::MaxDictionarySize::get().into())),
+			Some(Ok(Codec::Lz4)) => T::WeightInfo::decompress_lz4(len, encoded_call_size),
+			Some(Ok(Codec::Brotli)) => T::WeightInfo::decompress_brotli(len, encoded_call_size),
+			_ => Weight::zero(),
+		}
+	}
+
+	/// The weight of [`Pallet::decompress_call`], which is the decompression along with the
+	/// `MaxCallWeight` upper bound of the decompressed call. Calls which are too large are only
+	/// charged for the size check, as they are never decompressed.
+	pub fn decompress_call_weight(compressed: &[u8], encoded_call_size: u32) -> Weight {
+		if encoded_call_size >= T::MaxCallSize::get() * ONE_MB {
+			return Self::decompression_weight(compressed, 0);
+		}
+
+		Self::decompression_weight(compressed, encoded_call_size)
+			.saturating_add(T::MaxCallWeight::get())
 	}
 
 	/// This decoded and executes the encoded runtime call which is represented in  bytes
 	/// - `call_bytes`: the uncompressed encoded runtime call.
-	pub fn decode_and_execute(call_bytes: Vec<u8>) -> DispatchResult {
-		let runtime_call = <T as frame_system::Config>::RuntimeCall::decode_with_depth_limit(
+	/// - `decompression_weight`: the weight of decompressing the call.
+	///
+	/// Returns the actual weight of the dispatched call, including the decompression. Calls which
+	/// aren't dispatched are only charged for the decompression.
+	pub fn decode_and_execute(
+		call_bytes: Vec<u8>,
+		decompression_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let charge_decompression = |error: Error<T>| DispatchErrorWithPostInfo {
+			post_info: Some(decompression_weight).into(),
+			error: error.into(),
+		};
+		let runtime_call = <T as Config>::RuntimeCall::decode_with_depth_limit(
 			MAX_EXTRINSIC_DECODE_DEPTH_LIMIT,
 			&mut &call_bytes[..],
 		)
-		.map_err(|_| Error::<T>::ErrorDecodingCall)?;
-
-		if let Some(call) = IsSubType::<pallet_ismp::Call<T>>::is_sub_type(&runtime_call).cloned() {
-			match call {
-				pallet_ismp::Call::handle_unsigned { messages } =>
-					<pallet_ismp::Pallet<T>>::handle_messages(messages)
-						.map_err(|_| Error::<T>::ErrorExecutingCall)?,
-				_ => Err(Error::<T>::CallNotSupported)?,
-			};
-		} else if let Some(call) =
-			IsSubType::<pallet_ismp_relayer::Call<T>>::is_sub_type(&runtime_call).cloned()
-		{
-			match call {
-				pallet_ismp_relayer::Call::accumulate_fees { withdrawal_proof } =>
-					<pallet_ismp_relayer::Pallet<T>>::accumulate_fees(
-						frame_system::RawOrigin::None.into(),
-						withdrawal_proof,
-					)?,
-				_ => Err(Error::<T>::CallNotSupported)?,
-			};
-		} else {
-			return Err(Error::<T>::CallNotSupported)?;
+		.map_err(|_| charge_decompression(Error::<T>::ErrorDecodingCall))?;
+
+		if !T::AllowedCalls::contains(&runtime_call) {
+			return Err(charge_decompression(Error::<T>::CallNotSupported));
+		}
+
+		let info = runtime_call.get_dispatch_info();
+		if !info.weight.all_lte(T::MaxCallWeight::get()) {
+			return Err(charge_decompression(Error::<T>::CallWeightOutOfBound));
 		}
 
-		Ok(())
+		let result = runtime_call.dispatch(frame_system::RawOrigin::None.into());
+		let weight = decompression_weight.saturating_add(extract_actual_weight(&result, &info));
+
+		match result {
+			Ok(_) => Ok(Some(weight).into()),
+			Err(err) => {
+				log::trace!(target: "ismp", "Error executing decompressed call: {:?}", err.error);
+				Err(DispatchErrorWithPostInfo {
+					post_info: Some(weight).into(),
+					error: Error::<T>::ErrorExecutingCall.into(),
+				})
+			},
+		}
 	}
 }
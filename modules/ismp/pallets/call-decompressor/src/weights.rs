@@ -0,0 +1,59 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for decompressing calls with each of the supported codecs
+
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+/// Weight functions for the call decompressor. `c` is the length of the compressed call and `s`
+/// is the size of the decompressed call, both in bytes.
+pub trait WeightInfo {
+	/// Decompressing a zstd frame
+	fn decompress_zstd(c: u32, s: u32) -> Weight;
+	/// Decompressing a zstd frame with a stored dictionary, including the dictionary lookup
+	fn decompress_zstd_with_dictionary(c: u32, s: u32) -> Weight;
+	/// Decompressing an lz4 block
+	fn decompress_lz4(c: u32, s: u32) -> Weight;
+	/// Decompressing a brotli stream
+	fn decompress_brotli(c: u32, s: u32) -> Weight;
+}
+
+/// Conservative weights for runtimes which haven't benchmarked the pallet
+impl WeightInfo for () {
+	fn decompress_zstd(c: u32, s: u32) -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(s.into()))
+	}
+
+	fn decompress_zstd_with_dictionary(c: u32, s: u32) -> Weight {
+		Weight::from_parts(400_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+	}
+
+	fn decompress_lz4(c: u32, s: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(s.into()))
+	}
+
+	fn decompress_brotli(c: u32, s: u32) -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(s.into()))
+	}
+}
@@ -14,6 +14,11 @@
 // limitations under the License.
 
 //! Enables fishermen keep hyperbridge safe by vetoing fraudulent state commitments.
+//!
+//! Any account can join the fisherman set by putting up a bond. A veto takes effect immediately,
+//! but it opens a dispute which the fisherman must back with evidence before the dispute period
+//! elapses. Vetoes that are backed by evidence are rewarded from the protocol account, while
+//! vetoes that aren't are slashed and the vetoed state commitment is restored.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -23,14 +28,28 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use alloc::vec;
-	use frame_support::pallet_prelude::*;
+	use alloc::vec::Vec;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, Mutate},
+			tokens::{Fortitude, Preservation},
+		},
+		PalletId,
+	};
 	use frame_system::pallet_prelude::*;
 	use ismp::{
 		consensus::{StateCommitment, StateMachineHeight},
 		events::StateCommitmentVetoed,
 		host::IsmpHost,
 	};
+	use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+
+	/// The account that holds the bonds of all fishermen
+	pub const PALLET_ID: PalletId = PalletId(*b"fishermn");
+
+	/// The balance type of [`pallet_ismp::Config::Currency`]
+	pub type BalanceOf<T> = <T as pallet_ismp::Config>::Balance;
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -44,34 +63,120 @@ pub mod pallet {
 
 		/// The underlying [`IsmpHost`] implementation
 		type IsmpHost: IsmpHost + Default;
+
+		/// The bond that must back each veto in dispute, so a fisherman with `n` open vetoes needs
+		/// a bond of at least `(n + 1) * MinimumBond` to veto again. This is also the amount that
+		/// is slashed for each veto that isn't backed by evidence.
+		type MinimumBond: Get<BalanceOf<Self>>;
+
+		/// The number of blocks a fisherman has to back a veto with evidence
+		type DisputePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The reward paid to fishermen for vetoes that are backed by evidence
+		type VetoReward: Get<BalanceOf<Self>>;
+
+		/// The protocol account that rewards are paid from and that slashed bonds are paid to
+		type ProtocolAccount: Get<Self::AccountId>;
 	}
 
-	/// Set of whitelisted fishermen accounts
+	/// A bonded fisherman
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug, Default)]
+	pub struct Fisherman<Balance> {
+		/// The bond put up by the fisherman
+		pub bond: Balance,
+		/// The number of vetoes by this fisherman that are still in dispute
+		pub open_vetoes: u32,
+	}
+
+	/// A veto that has not yet been backed by evidence
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+	pub struct Veto<BlockNumber> {
+		/// The vetoed state commitment
+		pub commitment: StateCommitment,
+		/// The block after which the veto can be slashed
+		pub deadline: BlockNumber,
+		/// The consensus states that were replaced by consensus updates when the vetoed state
+		/// commitment was stored, one of which verified the vetoed state commitment
+		pub consensus_states: Vec<Vec<u8>>,
+	}
+
+	/// Evidence that a vetoed state commitment was fraudulent
+	#[derive(Clone, Encode, Decode, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+	pub enum VetoEvidence {
+		/// Two valid consensus proofs that represent conflicting views of the network. This
+		/// also freezes the consensus client.
+		FraudProof {
+			/// The first consensus proof
+			proof_1: Vec<u8>,
+			/// The second consensus proof
+			proof_2: Vec<u8>,
+		},
+		/// A consensus proof, valid against the consensus state that verified the vetoed state
+		/// commitment, which finalizes a different state commitment at the vetoed height
+		ConflictingProof(Vec<u8>),
+	}
+
+	/// Set of bonded fishermen
+	#[pallet::storage]
+	#[pallet::getter(fn fishermen)]
+	pub type Fishermen<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Fisherman<BalanceOf<T>>, OptionQuery>;
+
+	/// Vetoes that are still in dispute, by the vetoed height and the fisherman who vetoed it
 	#[pallet::storage]
-	#[pallet::getter(fn whitelist)]
-	pub type Fishermen<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+	#[pallet::getter(fn vetoes)]
+	pub type Vetoes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		StateMachineHeight,
+		Blake2_128Concat,
+		T::AccountId,
+		Veto<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Account Already Whitelisted
-		AlreadyAdded,
+		/// The bond is less than the minimum bond, or doesn't cover another veto
+		InsufficientBond,
 		/// Account wasn't found in the set.
 		NotInSet,
 		/// An account not in the fishermen set attempted to execute a veto
 		UnauthorizedAction,
 		/// State commitment was not found
 		VetoFailed,
+		/// Fishermen can't unbond while their vetoes are in dispute
+		OpenVetoes,
+		/// There is no veto by the fisherman in dispute at the provided height
+		UnknownVeto,
+		/// The evidence could not be verified
+		InvalidEvidence,
+		/// The dispute period for the veto has elapsed
+		DisputePeriodElapsed,
+		/// The dispute period for the veto has not yet elapsed
+		DisputePeriodNotElapsed,
+		/// The bond could not be transferred
+		TransferFailed,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// An account `account` has been added to the fishermen set.
-		Added { account: T::AccountId },
-		/// An account `account` has been removed from the fishermen set.
-		Removed { account: T::AccountId },
-		/// The provided state commitment was vetoed `state_machine` is by account
-		StateCommitmentVetoed { height: StateMachineHeight, commitment: StateCommitment },
+		/// An account has bonded `amount` and is in the fishermen set.
+		Bonded { account: T::AccountId, amount: BalanceOf<T> },
+		/// An account has withdrawn its bond and left the fishermen set.
+		Unbonded { account: T::AccountId, amount: BalanceOf<T> },
+		/// The provided state commitment was vetoed by `fisherman`
+		StateCommitmentVetoed {
+			height: StateMachineHeight,
+			commitment: StateCommitment,
+			fisherman: T::AccountId,
+		},
+		/// The veto at `height` was backed by evidence and `fisherman` was rewarded
+		VetoUpheld { height: StateMachineHeight, fisherman: T::AccountId, reward: BalanceOf<T> },
+		/// The veto at `height` wasn't backed by evidence, `fisherman` was slashed and the state
+		/// commitment was restored
+		VetoSlashed { height: StateMachineHeight, fisherman: T::AccountId, slashed: BalanceOf<T> },
 	}
 
 	#[pallet::call]
@@ -79,51 +184,107 @@ pub mod pallet {
 	where
 		T::AccountId: AsRef<[u8]>,
 	{
-		/// Adds a new fisherman to the set
+		/// Bonds `amount` to join the fishermen set, or adds to the bond of an existing
+		/// fisherman.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 2))]
-		pub fn add(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
-			T::AdminOrigin::ensure_origin(origin)?;
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 3))]
+		pub fn bond(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+
+			let mut fisherman = Fishermen::<T>::get(&account).unwrap_or_default();
+			fisherman.bond = fisherman.bond.saturating_add(amount.clone());
+			ensure!(fisherman.bond >= T::MinimumBond::get(), Error::<T>::InsufficientBond);
+
+			<T as pallet_ismp::Config>::Currency::transfer(
+				&account,
+				&PALLET_ID.into_account_truncating(),
+				amount,
+				Preservation::Preserve,
+			)
+			.map_err(|_| Error::<T>::TransferFailed)?;
 
-			ensure!(!Fishermen::<T>::contains_key(&account), Error::<T>::AlreadyAdded);
-			Fishermen::<T>::insert(&account, ());
+			let amount = fisherman.bond.clone();
+			Fishermen::<T>::insert(&account, fisherman);
 
-			Self::deposit_event(Event::Added { account });
+			Self::deposit_event(Event::Bonded { account, amount });
 			Ok(())
 		}
 
-		/// Removes a fisherman from the set
+		/// Withdraws the bond and leaves the fishermen set. This is only possible once all of
+		/// the fisherman's vetoes have been resolved.
 		#[pallet::call_index(1)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 2))]
-		pub fn remove(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
-			T::AdminOrigin::ensure_origin(origin)?;
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 3))]
+		pub fn unbond(origin: OriginFor<T>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
 
-			ensure!(Fishermen::<T>::contains_key(&account), Error::<T>::NotInSet);
+			let fisherman = Fishermen::<T>::get(&account).ok_or_else(|| Error::<T>::NotInSet)?;
+			ensure!(fisherman.open_vetoes == 0, Error::<T>::OpenVetoes);
+
+			if !fisherman.bond.is_zero() {
+				<T as pallet_ismp::Config>::Currency::transfer(
+					&PALLET_ID.into_account_truncating(),
+					&account,
+					fisherman.bond.clone(),
+					Preservation::Expendable,
+				)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+			}
 			Fishermen::<T>::remove(&account);
 
-			Self::deposit_event(Event::Removed { account });
+			Self::deposit_event(Event::Unbonded { account, amount: fisherman.bond });
 			Ok(())
 		}
 
 		/// A fisherman has determined that some [`StateCommitment`] (which is ideally still in it's
 		/// challenge period) is infact fraudulent and misrepresentative of the state
 		/// changes at the provided height. This allows them to veto the state commitment.
-		/// They aren't required to provide any proofs for this.
+		/// The veto must be backed by evidence within the dispute period, or the fisherman is
+		/// slashed.
 		#[pallet::call_index(2)]
-		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(2, 3))]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(5, 4))]
 		pub fn veto_state_commitment(
 			origin: OriginFor<T>,
 			height: StateMachineHeight,
 		) -> DispatchResult {
 			let account = ensure_signed(origin.clone())?;
-			ensure!(Fishermen::<T>::contains_key(&account), Error::<T>::UnauthorizedAction);
+			let mut fisherman =
+				Fishermen::<T>::get(&account).ok_or_else(|| Error::<T>::UnauthorizedAction)?;
+			let required = T::MinimumBond::get()
+				.saturating_mul(fisherman.open_vetoes.saturating_add(1).into());
+			ensure!(fisherman.bond >= required, Error::<T>::InsufficientBond);
+			ensure!(!Vetoes::<T>::contains_key(height, &account), Error::<T>::VetoFailed);
 
 			let ismp_host = <T as Config>::IsmpHost::default();
 			let commitment =
 				ismp_host.state_machine_commitment(height).map_err(|_| Error::<T>::VetoFailed)?;
+			// The consensus states replaced in the block the commitment was stored may be pruned
+			// before the veto is resolved, so they're kept with the veto
+			let consensus_states = ismp_host
+				.state_machine_update_time(height)
+				.map(|updated_at| {
+					pallet_ismp::PreviousConsensusStates::<T>::get(
+						height.id.consensus_state_id,
+						updated_at.as_secs(),
+					)
+				})
+				.unwrap_or_default();
 			ismp_host.delete_state_commitment(height).map_err(|_| Error::<T>::VetoFailed)?;
 
-			Self::deposit_event(Event::StateCommitmentVetoed { height, commitment });
+			fisherman.open_vetoes += 1;
+			Fishermen::<T>::insert(&account, fisherman);
+			let deadline =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::DisputePeriod::get());
+			Vetoes::<T>::insert(
+				height,
+				&account,
+				Veto { commitment: commitment.clone(), deadline, consensus_states },
+			);
+
+			Self::deposit_event(Event::StateCommitmentVetoed {
+				height,
+				commitment,
+				fisherman: account.clone(),
+			});
 			pallet_ismp::Pallet::<T>::deposit_pallet_event(
 				ismp::events::Event::StateCommitmentVetoed(StateCommitmentVetoed {
 					height,
@@ -132,5 +293,171 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		/// Backs the fisherman's veto at the provided height with evidence, which rewards the
+		/// fisherman. Anyone can submit evidence for a veto.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(4, 4))]
+		pub fn submit_evidence(
+			origin: OriginFor<T>,
+			height: StateMachineHeight,
+			fisherman: T::AccountId,
+			evidence: VetoEvidence,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let veto =
+				Vetoes::<T>::get(height, &fisherman).ok_or_else(|| Error::<T>::UnknownVeto)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= veto.deadline,
+				Error::<T>::DisputePeriodElapsed
+			);
+			Self::verify_evidence(height, veto, evidence)?;
+
+			Self::resolve(height, &fisherman);
+			let protocol_account = T::ProtocolAccount::get();
+			let available = <T as pallet_ismp::Config>::Currency::reducible_balance(
+				&protocol_account,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			);
+			let reward = T::VetoReward::get().min(available);
+			if !reward.is_zero() {
+				<T as pallet_ismp::Config>::Currency::transfer(
+					&protocol_account,
+					&fisherman,
+					reward.clone(),
+					Preservation::Preserve,
+				)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+			}
+
+			Self::deposit_event(Event::VetoUpheld { height, fisherman, reward });
+			Ok(())
+		}
+
+		/// Slashes a fisherman whose veto wasn't backed by evidence within the dispute period and
+		/// restores the vetoed state commitment. Anyone can resolve an expired veto.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(4, 5))]
+		pub fn resolve_veto(
+			origin: OriginFor<T>,
+			height: StateMachineHeight,
+			fisherman: T::AccountId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let veto =
+				Vetoes::<T>::get(height, &fisherman).ok_or_else(|| Error::<T>::UnknownVeto)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > veto.deadline,
+				Error::<T>::DisputePeriodNotElapsed
+			);
+
+			let slashed = Self::resolve(height, &fisherman)
+				.map(|fisherman| fisherman.bond)
+				.unwrap_or_default()
+				.min(T::MinimumBond::get());
+			if !slashed.is_zero() {
+				<T as pallet_ismp::Config>::Currency::transfer(
+					&PALLET_ID.into_account_truncating(),
+					&T::ProtocolAccount::get(),
+					slashed.clone(),
+					Preservation::Expendable,
+				)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+				Fishermen::<T>::mutate(&fisherman, |fisherman| {
+					if let Some(fisherman) = fisherman {
+						fisherman.bond = fisherman.bond.saturating_sub(slashed.clone());
+					}
+				});
+			}
+
+			// Restore the commitment, unless an honest update has replaced it in the meantime
+			let ismp_host = <T as Config>::IsmpHost::default();
+			if ismp_host.state_machine_commitment(height).is_err() {
+				ismp_host
+					.store_state_machine_commitment(height, veto.commitment)
+					.map_err(|_| Error::<T>::VetoFailed)?;
+				let latest = ismp_host.latest_commitment_height(height.id).unwrap_or_default();
+				if latest < height.height {
+					ismp_host
+						.store_latest_commitment_height(height)
+						.map_err(|_| Error::<T>::VetoFailed)?;
+				}
+			}
+
+			Self::deposit_event(Event::VetoSlashed { height, fisherman, slashed });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Closes the dispute for the fisherman's veto at the provided height, returns the updated
+		/// fisherman.
+		fn resolve(
+			height: StateMachineHeight,
+			account: &T::AccountId,
+		) -> Option<Fisherman<BalanceOf<T>>> {
+			Vetoes::<T>::remove(height, account);
+			Fishermen::<T>::mutate(account, |fisherman| {
+				let fisherman = fisherman.as_mut()?;
+				fisherman.open_vetoes = fisherman.open_vetoes.saturating_sub(1);
+				Some(fisherman.clone())
+			})
+		}
+
+		/// Verifies that the evidence shows the vetoed commitment to be fraudulent
+		fn verify_evidence(
+			height: StateMachineHeight,
+			veto: Veto<BlockNumberFor<T>>,
+			evidence: VetoEvidence,
+		) -> DispatchResult {
+			let host = <T as Config>::IsmpHost::default();
+			let consensus_state_id = height.id.consensus_state_id;
+			let consensus_client = host
+				.consensus_client_id(consensus_state_id)
+				.and_then(|id| host.consensus_client(id).ok())
+				.ok_or_else(|| Error::<T>::InvalidEvidence)?;
+
+			match evidence {
+				VetoEvidence::FraudProof { proof_1, proof_2 } => {
+					let trusted_state = host
+						.consensus_state(consensus_state_id)
+						.map_err(|_| Error::<T>::InvalidEvidence)?;
+					consensus_client
+						.verify_fraud_proof(&host, trusted_state, proof_1, proof_2)
+						.map_err(|_| Error::<T>::InvalidEvidence)?;
+					host.freeze_consensus_client(consensus_state_id)
+						.map_err(|_| Error::<T>::InvalidEvidence)?;
+				},
+				// The current consensus state has generally moved past the vetoed height, so the
+				// proof is verified against the consensus states that could have verified it
+				VetoEvidence::ConflictingProof(proof) => {
+					let conflicting = veto.consensus_states.into_iter().any(|consensus_state| {
+						consensus_client
+							.verify_consensus(
+								&host,
+								consensus_state_id,
+								consensus_state,
+								proof.clone(),
+							)
+							.ok()
+							.and_then(|(_, commitments)| {
+								commitments.get(&height.id.state_id).map(|commitments| {
+									commitments.iter().any(|verified| {
+										verified.height == height.height &&
+											verified.commitment != veto.commitment
+									})
+								})
+							})
+							.unwrap_or(false)
+					});
+					ensure!(conflicting, Error::<T>::InvalidEvidence);
+				},
+			}
+
+			Ok(())
+		}
 	}
 }
@@ -0,0 +1,104 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![deny(missing_docs)]
+
+//! RPC API Implementation for pallet-ismp-host-executive
+//!
+//! Exposes the protocol fees charged by the hosts of all connected chains, so that applications
+//! can work out the fees to attach to their requests.
+
+use ismp::{host::StateMachine, router::PostRequest};
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use pallet_ismp_host_executive_runtime_api::HostExecutiveRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::U256;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(e: impl std::fmt::Display) -> RpcError {
+	RpcError::Call(CallError::Custom(ErrorObject::owned(
+		9877, // no real reason for this value
+		"Something wrong",
+		Some(format!("{}", e)),
+	)))
+}
+
+/// Host executive RPC methods.
+#[rpc(client, server)]
+pub trait HostExecutiveApi<Hash> {
+	/// Query the fee charged per byte of a request or response body by the host on the given
+	/// state machine
+	#[method(name = "hostExecutive_queryPerByteFee")]
+	fn query_per_byte_fee(
+		&self,
+		state_machine: StateMachine,
+		at: Option<Hash>,
+	) -> RpcResult<Option<U256>>;
+
+	/// Query the protocol fee charged by the source chain for dispatching the given request. This
+	/// is denominated in the fee token of the source chain and does not include the relayer fee.
+	#[method(name = "hostExecutive_queryProtocolFee")]
+	fn query_protocol_fee(&self, request: PostRequest, at: Option<Hash>)
+		-> RpcResult<Option<U256>>;
+}
+
+/// An implementation of the host executive RPC methods.
+pub struct HostExecutiveRpcHandler<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> HostExecutiveRpcHandler<C, B> {
+	/// Create new `HostExecutiveRpcHandler` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> HostExecutiveApiServer<Block::Hash> for HostExecutiveRpcHandler<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: HostExecutiveRuntimeApi<Block>,
+{
+	fn query_per_byte_fee(
+		&self,
+		state_machine: StateMachine,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<U256>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		api.per_byte_fee(at, state_machine)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching per byte fee"))
+	}
+
+	fn query_protocol_fee(
+		&self,
+		request: PostRequest,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<U256>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		api.request_protocol_fee(at, request)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching protocol fee"))
+	}
+}
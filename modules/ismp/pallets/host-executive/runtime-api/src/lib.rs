@@ -0,0 +1,34 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pallet-ismp-host-executive runtime Apis
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(missing_docs)]
+
+use ismp::{host::StateMachine, router::PostRequest};
+use primitive_types::U256;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime APIs for querying the protocol fees charged by the connected hosts
+	pub trait HostExecutiveRuntimeApi {
+		/// Return the fee charged per byte of a request or response body by the host on the given
+		/// state machine
+		fn per_byte_fee(state_machine: StateMachine) -> Option<U256>;
+
+		/// Return the protocol fee charged by the source chain for dispatching the given request
+		fn request_protocol_fee(request: PostRequest) -> Option<U256>;
+	}
+}
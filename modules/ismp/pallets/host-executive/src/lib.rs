@@ -21,6 +21,17 @@ mod params;
 
 extern crate alloc;
 
+use frame_support::{ensure, traits::Get, weights::Weight};
+use ismp::{
+	dispatcher::{DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
+	host::{IsmpHost, StateMachine},
+	messaging::{hash_request, Proof},
+	module::IsmpModule,
+	router::{PostRequest, Request, Response, Timeout},
+};
+use pallet_hyperbridge::VersionedHostParams;
+use primitive_types::{H256, U256};
+
 pub use pallet::*;
 pub use params::*;
 
@@ -35,11 +46,13 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use ismp::{
 		dispatcher::{DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
-		host::StateMachine,
+		handlers::validate_state_machine,
+		host::{IsmpHost, StateMachine},
+		router::RequestResponse,
 	};
 	use pallet_hyperbridge::{Message, PALLET_HYPERBRIDGE};
 	use pallet_ismp::ModuleId;
-	use primitive_types::H160;
+	use primitive_types::{H160, H256};
 
 	/// ISMP module identifier
 	pub const PALLET_ID: ModuleId = ModuleId::Pallet(PalletId(*b"hostexec"));
@@ -54,8 +67,13 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		/// The [`IsmpDispatcher`] implementation to use for dispatching requests
-		type IsmpHost: IsmpDispatcher<Account = Self::AccountId, Balance = Self::Balance>;
+		/// The underlying [`IsmpHost`] implementation, used for dispatching requests and verifying
+		/// that they were delivered
+		type IsmpHost: IsmpHost + IsmpDispatcher<Account = Self::AccountId, Balance = Self::Balance>;
+
+		/// The relative timeout in seconds of host parameter updates and governance actions.
+		/// Updates that time out before they are acknowledged by the remote host are rolled back.
+		type HostParamsUpdateTimeout: Get<u64>;
 	}
 
 	/// Host Params for all connected chains
@@ -69,6 +87,19 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Host param updates that have been dispatched, but have not yet been acknowledged by the
+	/// remote host. There can only be one pending update per state machine, until its request
+	/// times out.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_host_params)]
+	pub type PendingHostParams<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		StateMachine,
+		PendingHostParam<<T as pallet_ismp::Config>::Balance>,
+		OptionQuery,
+	>;
+
 	/// EvmHost addresses of all connected Evm chains
 	#[pallet::storage]
 	#[pallet::getter(fn evm_hosts)]
@@ -78,7 +109,16 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// `AdminOrigin` has initiated a host parameter update to the mentioned state
-		/// machine
+		/// machine. The update is applied once it has been acknowledged by the remote host.
+		HostParamsUpdatePending {
+			/// State machine's whose host params should be updated
+			state_machine: StateMachine,
+			/// The commitment of the request that carries the update
+			commitment: H256,
+			/// The new host params
+			params: HostParam<<T as pallet_ismp::Config>::Balance>,
+		},
+		/// A host parameter update has been acknowledged by the remote host and applied
 		HostParamsUpdated {
 			/// State machine's whose host params should be updated
 			state_machine: StateMachine,
@@ -87,6 +127,14 @@ pub mod pallet {
 			/// The new host params
 			new: HostParam<<T as pallet_ismp::Config>::Balance>,
 		},
+		/// A pending host parameter update timed out before it was acknowledged by the remote
+		/// host, and has been discarded
+		HostParamsUpdateRolledBack {
+			/// State machine's whose host params update was discarded
+			state_machine: StateMachine,
+			/// The commitment of the request that carried the update
+			commitment: H256,
+		},
 		/// `AdminOrigin` has set the initial host parameters for the mentioned state
 		/// machine
 		HostParamsSet {
@@ -102,6 +150,15 @@ pub mod pallet {
 			/// The address of the IsmpHost
 			address: H160,
 		},
+		/// `AdminOrigin` has dispatched a governance action to the mentioned evm host
+		EvmHostActionDispatched {
+			/// State machine whose host the action was dispatched to
+			state_machine: StateMachine,
+			/// The commitment of the request that carries the action
+			commitment: H256,
+			/// The dispatched action
+			action: EvmHostAction,
+		},
 		/// The host address for some EvmHost has been udpated
 		HostAddressUpdated {
 			/// State machine's whose host EvmHost address was just added
@@ -121,6 +178,19 @@ pub mod pallet {
 		UnknownStateMachine,
 		/// Mismatched state machine and HostParams
 		MismatchedHostParams,
+		/// There is already a pending host params update for the state machine
+		UpdateAlreadyPending,
+		/// There is no pending host params update for the state machine
+		NoPendingUpdate,
+		/// The provided request does not match the pending host params update
+		UnknownRequest,
+		/// The proof could not be verified
+		ProofValidationError,
+		/// The proof does not show that the request was received by the remote host
+		RequestNotReceived,
+		/// Hosts that reject incoming messages can't be unfrozen through governance, so they can
+		/// only be frozen by their admin
+		InvalidFrozenState,
 	}
 
 	#[pallet::call]
@@ -128,8 +198,9 @@ pub mod pallet {
 	where
 		T::AccountId: From<[u8; 32]>,
 	{
-		/// Initialize the host params for all the different state machines
-		#[pallet::weight(T::DbWeight::get().writes(1))]
+		/// Initialize the host params for all the different state machines. This discards any
+		/// pending host params updates for them.
+		#[pallet::weight(T::DbWeight::get().writes(2))]
 		#[pallet::call_index(0)]
 		pub fn set_host_params(
 			origin: OriginFor<T>,
@@ -139,14 +210,20 @@ pub mod pallet {
 
 			for (state_machine, params) in params {
 				HostParams::<T>::insert(state_machine.clone(), params.clone());
+				PendingHostParams::<T>::remove(&state_machine);
 				Self::deposit_event(Event::<T>::HostParamsSet { state_machine, params });
 			}
 
 			Ok(())
 		}
 
-		/// Update the host params for the provided state machine
-		#[pallet::weight(T::DbWeight::get().writes(1))]
+		/// Dispatch a host params update to the provided state machine. The update is only applied
+		/// once the remote host acknowledges it, and is discarded if the request times out.
+		/// Pending updates whose requests have timed out are superseded by new updates, while
+		/// `set_host_params` discards them at any time.
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 2).saturating_add(dispatch_weight::<T>())
+		)]
 		#[pallet::call_index(1)]
 		pub fn update_host_params(
 			origin: OriginFor<T>,
@@ -157,6 +234,7 @@ pub mod pallet {
 
 			let params = HostParams::<T>::get(&state_machine)
 				.ok_or_else(|| Error::<T>::UnknownStateMachine)?;
+			Self::ensure_no_pending_update(state_machine)?;
 
 			let (post, updated) = match (params.clone(), update) {
 				(HostParam::EvmHostParam(mut inner), HostParamUpdate::EvmHostParam(update)) => {
@@ -170,7 +248,7 @@ pub mod pallet {
 						dest: state_machine,
 						from: PALLET_ID.to_bytes(),
 						to: inner.host_manager.0.to_vec(),
-						timeout: 0,
+						timeout: T::HostParamsUpdateTimeout::get(),
 						body,
 					};
 
@@ -185,7 +263,7 @@ pub mod pallet {
 						dest: state_machine,
 						from: PALLET_ID.to_bytes(),
 						to: PALLET_HYPERBRIDGE.0.to_vec(),
-						timeout: 0,
+						timeout: T::HostParamsUpdateTimeout::get(),
 						body,
 					};
 
@@ -194,20 +272,16 @@ pub mod pallet {
 				_ => return Err(Error::<T>::MismatchedHostParams.into()),
 			};
 
-			let dispatcher = <T as Config>::IsmpHost::default();
-			dispatcher
-				.dispatch_request(
-					DispatchRequest::Post(post),
-					FeeMetadata { payer: [0u8; 32].into(), fee: Default::default() },
-				)
-				.map_err(|_| Error::<T>::DispatchFailed)?;
-
-			HostParams::<T>::insert(state_machine, updated.clone());
+			let (commitment, timeout_timestamp) = Self::dispatch(post)?;
+			PendingHostParams::<T>::insert(
+				state_machine,
+				PendingHostParam { params: updated.clone(), commitment, timeout_timestamp },
+			);
 
-			Self::deposit_event(Event::<T>::HostParamsUpdated {
+			Self::deposit_event(Event::<T>::HostParamsUpdatePending {
 				state_machine,
-				old: params,
-				new: updated,
+				commitment,
+				params: updated,
 			});
 
 			Ok(())
@@ -238,5 +312,269 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Apply a pending host params update, given a state proof that the request carrying it
+		/// was received by the remote host.
+		#[pallet::weight(confirm_host_params_weight::<T>(&proof))]
+		#[pallet::call_index(3)]
+		pub fn confirm_host_params(
+			origin: OriginFor<T>,
+			request: PostRequest,
+			proof: Proof,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let state_machine = request.dest;
+			let pending = PendingHostParams::<T>::get(&state_machine)
+				.ok_or_else(|| Error::<T>::NoPendingUpdate)?;
+			let request = Request::Post(request);
+			ensure!(
+				hash_request::<<T as Config>::IsmpHost>(&request) == pending.commitment,
+				Error::<T>::UnknownRequest
+			);
+			ensure!(proof.height.id.state_id == state_machine, Error::<T>::ProofValidationError);
+
+			let host = <T as Config>::IsmpHost::default();
+			let state_machine_client = validate_state_machine(&host, proof.height)
+				.map_err(|_| Error::<T>::ProofValidationError)?;
+			let root = host
+				.state_machine_commitment(proof.height)
+				.map_err(|_| Error::<T>::ProofValidationError)?;
+			let keys = state_machine_client
+				.receipts_state_trie_key(RequestResponse::Request(vec![request]));
+			let values = state_machine_client
+				.verify_state_proof(&host, keys.clone(), root, &proof)
+				.map_err(|_| Error::<T>::ProofValidationError)?;
+			let received = keys
+				.iter()
+				.all(|key| values.get(key).map(|value| value.is_some()).unwrap_or(false));
+			ensure!(!keys.is_empty() && received, Error::<T>::RequestNotReceived);
+
+			Self::apply_pending_update(state_machine, pending);
+
+			Ok(())
+		}
+
+		/// Dispatch a governance action to the `HostManager` of the provided evm host. Consensus
+		/// client and handler upgrades change the host params, so they are tracked like host
+		/// params updates and only applied once the remote host acknowledges them.
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 2).saturating_add(dispatch_weight::<T>())
+		)]
+		#[pallet::call_index(4)]
+		pub fn dispatch_evm_host_action(
+			origin: OriginFor<T>,
+			state_machine: StateMachine,
+			action: EvmHostAction,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let params = match HostParams::<T>::get(&state_machine)
+				.ok_or_else(|| Error::<T>::UnknownStateMachine)?
+			{
+				HostParam::EvmHostParam(params) => params,
+				HostParam::SubstrateHostParam(_) => Err(Error::<T>::MismatchedHostParams)?,
+			};
+			ensure!(
+				!matches!(
+					action,
+					EvmHostAction::SetFrozenState(FrozenStatus::Incoming | FrozenStatus::All)
+				),
+				Error::<T>::InvalidFrozenState
+			);
+
+			let updated = match action {
+				EvmHostAction::SetConsensusClient { address, .. } =>
+					Some(EvmHostParam { consensus_client: address, ..params.clone() }),
+				EvmHostAction::SetHandler { address, .. } =>
+					Some(EvmHostParam { handler: address, ..params.clone() }),
+				EvmHostAction::SetFrozenState(_) | EvmHostAction::WithdrawRevenue { .. } => None,
+			};
+			if updated.is_some() {
+				Self::ensure_no_pending_update(state_machine)?;
+			}
+
+			let post = DispatchPost {
+				dest: state_machine,
+				from: PALLET_ID.to_bytes(),
+				to: params.host_manager.0.to_vec(),
+				timeout: T::HostParamsUpdateTimeout::get(),
+				body: action.abi_encode(),
+			};
+
+			let (commitment, timeout_timestamp) = Self::dispatch(post)?;
+			if let Some(updated) = updated {
+				let params = HostParam::EvmHostParam(updated);
+				PendingHostParams::<T>::insert(
+					state_machine,
+					PendingHostParam { params: params.clone(), commitment, timeout_timestamp },
+				);
+				Self::deposit_event(Event::<T>::HostParamsUpdatePending {
+					state_machine,
+					commitment,
+					params,
+				});
+			}
+
+			Self::deposit_event(Event::<T>::EvmHostActionDispatched {
+				state_machine,
+				commitment,
+				action,
+			});
+
+			Ok(())
+		}
+	}
+
+	// Hack for implementing the [`Default`] bound needed for
+	// [`IsmpModule`](ismp::module::IsmpModule)
+	impl<T> Default for Pallet<T> {
+		fn default() -> Self {
+			Self(PhantomData)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	T::AccountId: From<[u8; 32]>,
+{
+	/// Dispatches a governance request, returning its commitment and timeout timestamp
+	fn dispatch(post: DispatchPost) -> Result<(H256, u64), Error<T>> {
+		let host = <T as Config>::IsmpHost::default();
+		// this mirrors how the dispatcher computes the timeout of the request
+		let timeout_timestamp = match post.timeout {
+			0 => 0,
+			timeout => host.timestamp().as_secs().saturating_add(timeout),
+		};
+		let commitment = host
+			.dispatch_request(
+				DispatchRequest::Post(post),
+				FeeMetadata { payer: [0u8; 32].into(), fee: Default::default() },
+			)
+			.map_err(|_| Error::<T>::DispatchFailed)?;
+
+		Ok((commitment, timeout_timestamp))
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensures there's no pending update for the state machine. Pending updates whose requests
+	/// have timed out are rolled back, since the remote host can no longer receive them.
+	fn ensure_no_pending_update(state_machine: StateMachine) -> Result<(), Error<T>> {
+		let Some(pending) = PendingHostParams::<T>::get(&state_machine) else { return Ok(()) };
+		let now = <T as Config>::IsmpHost::default().timestamp().as_secs();
+		ensure!(
+			pending.timeout_timestamp != 0 && now > pending.timeout_timestamp,
+			Error::<T>::UpdateAlreadyPending
+		);
+
+		PendingHostParams::<T>::remove(&state_machine);
+		Self::deposit_event(Event::<T>::HostParamsUpdateRolledBack {
+			state_machine,
+			commitment: pending.commitment,
+		});
+
+		Ok(())
+	}
+
+	/// Moves the pending update into the applied host params
+	fn apply_pending_update(
+		state_machine: StateMachine,
+		pending: PendingHostParam<<T as pallet_ismp::Config>::Balance>,
+	) {
+		PendingHostParams::<T>::remove(&state_machine);
+		let old = HostParams::<T>::get(&state_machine);
+		HostParams::<T>::insert(state_machine, pending.params.clone());
+
+		if let Some(old) = old {
+			Self::deposit_event(Event::<T>::HostParamsUpdated {
+				state_machine,
+				old,
+				new: pending.params,
+			});
+		}
+	}
+
+	/// Returns the pending update for the request's destination, if the request carries it
+	fn pending_update_for(
+		post: &PostRequest,
+	) -> Option<PendingHostParam<<T as pallet_ismp::Config>::Balance>> {
+		let pending = PendingHostParams::<T>::get(&post.dest)?;
+		let commitment = hash_request::<<T as Config>::IsmpHost>(&Request::Post(post.clone()));
+		(pending.commitment == commitment).then_some(pending)
+	}
+}
+
+/// Execution time in picoseconds per byte of a state proof. Verifying a proof decodes and hashes
+/// every trie node in it, this is a conservative estimate of that cost.
+const PROOF_VERIFICATION_WEIGHT_PER_BYTE: u64 = 20_000;
+
+/// Weight of dispatching a governance request through the ismp host, which reads the nonce and
+/// timestamp, then stores the request commitment
+fn dispatch_weight<T: Config>() -> Weight {
+	T::DbWeight::get().reads_writes(2, 3)
+}
+
+/// Weight of [`Pallet::confirm_host_params`], which grows with the size of the state proof
+fn confirm_host_params_weight<T: Config>(proof: &Proof) -> Weight {
+	let proof_len = proof.proof.len() as u64;
+	T::DbWeight::get().reads_writes(8, 2).saturating_add(Weight::from_parts(
+		proof_len.saturating_mul(PROOF_VERIFICATION_WEIGHT_PER_BYTE),
+		proof_len,
+	))
+}
+
+impl<T: Config> IsmpModule for Pallet<T> {
+	fn on_accept(&self, _request: PostRequest) -> Result<(), ismp::error::Error> {
+		Err(ismp::error::Error::Custom("Module does not accept requests".into()))
+	}
+
+	fn on_response(&self, _response: Response) -> Result<(), ismp::error::Error> {
+		// remote hosts don't respond to governance requests, updates are confirmed with a state
+		// proof of their receipt instead
+		Err(ismp::error::Error::Custom("Module does not expect responses".into()))
+	}
+
+	fn on_timeout(&self, timeout: Timeout) -> Result<(), ismp::error::Error> {
+		let Timeout::Request(Request::Post(post)) = timeout else {
+			return Err(ismp::error::Error::Custom("Module does not expect this timeout".into()))
+		};
+
+		// updates that were superseded by `set_host_params` are no longer tracked
+		if let Some(pending) = Self::pending_update_for(&post) {
+			PendingHostParams::<T>::remove(&post.dest);
+			Self::deposit_event(Event::<T>::HostParamsUpdateRolledBack {
+				state_machine: post.dest,
+				commitment: pending.commitment,
+			});
+		}
+
+		Ok(())
+	}
+}
+
+impl<T> Pallet<T>
+where
+	T: Config,
+	<T as pallet_ismp::Config>::Balance: Into<u128>,
+{
+	/// Returns the fee charged per byte of a request or response body by the host on the given
+	/// state machine. For substrate-based hosts, this is the fee configured in their
+	/// `pallet_hyperbridge::HostParams`.
+	pub fn per_byte_fee(state_machine: StateMachine) -> Option<U256> {
+		match HostParams::<T>::get(state_machine)? {
+			HostParam::SubstrateHostParam(VersionedHostParams::V1(per_byte_fee)) =>
+				Some(U256::from(per_byte_fee.into())),
+			HostParam::EvmHostParam(params) => Some(params.per_byte_fee),
+		}
+	}
+
+	/// Returns the protocol fee charged by the source chain of the request for dispatching it.
+	/// Like the hosts, this charges for a minimum of 32 bytes even for smaller bodies.
+	pub fn request_protocol_fee(request: &PostRequest) -> Option<U256> {
+		let per_byte_fee = Self::per_byte_fee(request.source)?;
+
+		Some(per_byte_fee * U256::from(request.body.len().max(32)))
 	}
 }
@@ -1,7 +1,7 @@
 use alloc::{vec, vec::Vec};
 use frame_support::{pallet_prelude::ConstU32, BoundedVec};
 use pallet_hyperbridge::VersionedHostParams;
-use primitive_types::{H160, U256};
+use primitive_types::{H160, H256, U256};
 use sp_runtime::RuntimeDebug;
 
 /// The host parameters of all connected chains
@@ -26,6 +26,97 @@ pub enum HostParamUpdate<T> {
 	EvmHostParam(EvmHostParamUpdate),
 }
 
+/// A host parameter update that has been dispatched to a connected chain, but has not yet been
+/// acknowledged by its host.
+#[derive(
+	Clone, codec::Encode, codec::Decode, scale_info::TypeInfo, PartialEq, Eq, RuntimeDebug,
+)]
+pub struct PendingHostParam<T> {
+	/// The host params that will be applied once the update is acknowledged
+	pub params: HostParam<T>,
+	/// The commitment of the request that carries the update
+	pub commitment: H256,
+	/// The timestamp in seconds after which the request times out, and the update can be
+	/// superseded. Zero if the request never times out.
+	pub timeout_timestamp: u64,
+}
+
+/// The frozen state of an evm host, this mirrors `FrozenStatus` in the `IIsmpHost` interface
+#[derive(
+	Clone, Copy, codec::Encode, codec::Decode, scale_info::TypeInfo, PartialEq, Eq, RuntimeDebug,
+)]
+pub enum FrozenStatus {
+	/// The host is not frozen
+	None,
+	/// The host rejects incoming messages
+	Incoming,
+	/// The host rejects outgoing messages
+	Outgoing,
+	/// The host rejects all messages
+	All,
+}
+
+/// Cross-chain governance actions for evm hosts, other than host params updates. These are
+/// dispatched to the `HostManager` contract on the host.
+#[derive(
+	Clone, codec::Encode, codec::Decode, scale_info::TypeInfo, PartialEq, Eq, RuntimeDebug,
+)]
+pub enum EvmHostAction {
+	/// Freeze or unfreeze the host
+	SetFrozenState(FrozenStatus),
+	/// Replace the consensus client contract
+	SetConsensusClient {
+		/// The address of the new consensus client
+		address: H160,
+		/// The code hash the contract deployed at `address` must have
+		code_hash: H256,
+	},
+	/// Upgrade the `HandlerV1` contract
+	SetHandler {
+		/// The address of the new handler
+		address: H160,
+		/// The code hash the contract deployed at `address` must have
+		code_hash: H256,
+	},
+	/// Withdraw protocol revenue held by the host
+	WithdrawRevenue {
+		/// The treasury account the revenue is sent to
+		beneficiary: H160,
+		/// The amount to withdraw
+		amount: U256,
+		/// Withdraw the native token instead of the fee token
+		native: bool,
+	},
+}
+
+impl EvmHostAction {
+	/// Encodes the action as the body of a request to the `HostManager`
+	pub fn abi_encode(&self) -> Vec<u8> {
+		match self.clone() {
+			EvmHostAction::SetFrozenState(status) =>
+				FrozenStatusAbi { status: status as u8 }.encode(),
+			EvmHostAction::SetConsensusClient { address, code_hash } => ConsensusClientUpgradeAbi {
+				implementation: address.0.into(),
+				codeHash: code_hash.0.into(),
+			}
+			.encode(),
+			EvmHostAction::SetHandler { address, code_hash } =>
+				HandlerUpgradeAbi { implementation: address.0.into(), codeHash: code_hash.0.into() }
+					.encode(),
+			EvmHostAction::WithdrawRevenue { beneficiary, amount, native } => WithdrawParamsAbi {
+				beneficiary: beneficiary.0.into(),
+				amount: {
+					let mut buf = [0u8; 32];
+					amount.to_little_endian(&mut buf);
+					alloy_primitives::U256::from_le_bytes(buf)
+				},
+				native,
+			}
+			.encode(),
+		}
+	}
+}
+
 /// The host parameters for evm-based hosts
 #[derive(
 	Clone, codec::Encode, codec::Decode, scale_info::TypeInfo, PartialEq, Eq, RuntimeDebug, Default,
@@ -203,6 +294,38 @@ alloy_sol_macro::sol! {
 		// state machine identifier for hyperbridge
 		bytes hyperbridge;
 	}
+
+	// Parameters for withdrawing revenue from the host
+	struct WithdrawParamsAbi {
+		// the beneficiary address
+		address beneficiary;
+		// the amount to be disbursed
+		uint256 amount;
+		// withdraw the native token?
+		bool native;
+	}
+
+	// The new frozen state of the host
+	struct FrozenStatusAbi {
+		// the FrozenStatus enum variant
+		uint8 status;
+	}
+
+	// Replaces the consensus client contract
+	struct ConsensusClientUpgradeAbi {
+		// the new consensus client
+		address implementation;
+		// the expected code hash of the new consensus client
+		bytes32 codeHash;
+	}
+
+	// Replaces the handler contract
+	struct HandlerUpgradeAbi {
+		// the new handler
+		address implementation;
+		// the expected code hash of the new handler
+		bytes32 codeHash;
+	}
 }
 
 impl EvmHostParamsAbi {
@@ -217,6 +340,54 @@ impl EvmHostParamsAbi {
 	}
 }
 
+impl WithdrawParamsAbi {
+	/// Encodes the WithdrawParams alongside the enum variant for the HostManager request
+	pub fn encode(&self) -> Vec<u8> {
+		use alloy_sol_types::SolType;
+
+		let variant = vec![0u8]; // enum variant for the host manager
+		let encoded = WithdrawParamsAbi::abi_encode(self);
+
+		[variant, encoded].concat()
+	}
+}
+
+impl FrozenStatusAbi {
+	/// Encodes the FrozenStatus alongside the enum variant for the HostManager request
+	pub fn encode(&self) -> Vec<u8> {
+		use alloy_sol_types::SolType;
+
+		let variant = vec![2u8]; // enum variant for the host manager
+		let encoded = FrozenStatusAbi::abi_encode(self);
+
+		[variant, encoded].concat()
+	}
+}
+
+impl ConsensusClientUpgradeAbi {
+	/// Encodes the consensus client upgrade alongside the enum variant for the HostManager request
+	pub fn encode(&self) -> Vec<u8> {
+		use alloy_sol_types::SolType;
+
+		let variant = vec![3u8]; // enum variant for the host manager
+		let encoded = ConsensusClientUpgradeAbi::abi_encode(self);
+
+		[variant, encoded].concat()
+	}
+}
+
+impl HandlerUpgradeAbi {
+	/// Encodes the handler upgrade alongside the enum variant for the HostManager request
+	pub fn encode(&self) -> Vec<u8> {
+		use alloy_sol_types::SolType;
+
+		let variant = vec![4u8]; // enum variant for the host manager
+		let encoded = HandlerUpgradeAbi::abi_encode(self);
+
+		[variant, encoded].concat()
+	}
+}
+
 impl TryFrom<EvmHostParam> for EvmHostParamsAbi {
 	type Error = anyhow::Error;
 
@@ -20,8 +20,8 @@ use crate::{
 	dispatcher::{RefundingRouter, RequestMetadata},
 	utils::{ConsensusClientProvider, ResponseReceipt},
 	ChallengePeriod, Config, ConsensusClientUpdateTime, ConsensusStateClient, ConsensusStates,
-	FrozenConsensusClients, LatestStateMachineHeight, Nonce, Pallet, Responded,
-	StateMachineUpdateTime, UnbondingPeriod,
+	FrozenConsensusClients, LatestStateMachineHeight, Nonce, Pallet, PreviousConsensusStates,
+	Responded, StateMachineUpdateTime, UnbondingPeriod,
 };
 use alloc::{format, string::ToString};
 use codec::{Decode, Encode};
@@ -148,6 +148,17 @@ impl<T: Config> IsmpHost for Pallet<T> {
 	}
 
 	fn store_consensus_state(&self, id: ConsensusClientId, state: Vec<u8>) -> Result<(), Error> {
+		if let Some(previous) = ConsensusStates::<T>::get(id) {
+			let now = self.timestamp().as_secs();
+			let challenge_period = ChallengePeriod::<T>::get(&id).unwrap_or_default();
+			let expired = PreviousConsensusStates::<T>::iter_key_prefix(id)
+				.filter(|updated_at| updated_at.saturating_add(challenge_period) < now)
+				.collect::<Vec<_>>();
+			for updated_at in expired {
+				PreviousConsensusStates::<T>::remove(id, updated_at);
+			}
+			PreviousConsensusStates::<T>::append(id, now, previous);
+		}
 		ConsensusStates::<T>::insert(id, state);
 		Ok(())
 	}
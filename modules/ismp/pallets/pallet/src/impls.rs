@@ -16,14 +16,15 @@
 //! Pallet methods
 
 use crate::{
-	child_trie::{RequestCommitments, ResponseCommitments},
+	child_trie::{RequestCommitments, RequestReceipts, ResponseCommitments, ResponseReceipts},
 	dispatcher::{FeeMetadata, RequestMetadata},
 	mmr::{Leaf, LeafIndexAndPos, Proof, ProofKeys},
+	utils::{CommitmentFee, MessageStatus},
 	weights::get_weight,
 	Config, Error, Event, NoOpMmrTree, Pallet, Responded,
 };
 use alloc::{string::ToString, vec, vec::Vec};
-use codec::Decode;
+use codec::{Decode, Encode};
 use frame_support::dispatch::{DispatchResultWithPostInfo, Pays, PostDispatchInfo};
 use frame_system::Phase;
 use ismp::{
@@ -34,6 +35,7 @@ use ismp::{
 use log::debug;
 use mmr_primitives::{ForkIdentifier, MerkleMountainRangeTree};
 use sp_core::{offchain::StorageKind, H256};
+use sp_runtime::SaturatedConversion;
 
 impl<T: Config> Pallet<T> {
 	/// Deposit a pallet [`Event<T>`]
@@ -68,6 +70,15 @@ impl<T: Config> Pallet<T> {
 					Ok(val)
 				})
 				.collect::<Result<Vec<_>, _>>()?,
+			// Commitments may have been removed from the child trie once they timed out, so their
+			// leaf positions are read from the offchain db
+			ProofKeys::Commitments(commitments) => commitments
+				.into_iter()
+				.map(|commitment| {
+					LeafIndexAndPos::offchain(commitment)
+						.ok_or_else(|| sp_mmr_primitives::Error::LeafNotFound)
+				})
+				.collect::<Result<Vec<_>, _>>()?,
 		};
 		let indices =
 			leaf_indices_and_positions.iter().map(|val| val.leaf_index).collect::<Vec<_>>();
@@ -143,16 +154,14 @@ impl<T: Config> Pallet<T> {
 			commitment,
 		});
 
+		let mmr = LeafIndexAndPos {
+			leaf_index: leaf_index_and_pos.index,
+			pos: leaf_index_and_pos.position,
+		};
+		sp_io::offchain_index::set(&LeafIndexAndPos::offchain_key(commitment), &mmr.encode());
 		RequestCommitments::<T>::insert(
 			commitment,
-			RequestMetadata {
-				mmr: LeafIndexAndPos {
-					leaf_index: leaf_index_and_pos.index,
-					pos: leaf_index_and_pos.position,
-				},
-				fee: meta,
-				claimed: false,
-			},
+			RequestMetadata { mmr, fee: meta, claimed: false },
 		);
 
 		Ok(commitment)
@@ -183,16 +192,14 @@ impl<T: Config> Pallet<T> {
 			commitment,
 			req_commitment,
 		});
+		let mmr = LeafIndexAndPos {
+			leaf_index: leaf_index_and_pos.index,
+			pos: leaf_index_and_pos.position,
+		};
+		sp_io::offchain_index::set(&LeafIndexAndPos::offchain_key(commitment), &mmr.encode());
 		ResponseCommitments::<T>::insert(
 			commitment,
-			RequestMetadata {
-				mmr: LeafIndexAndPos {
-					leaf_index: leaf_index_and_pos.index,
-					pos: leaf_index_and_pos.position,
-				},
-				fee: meta,
-				claimed: false,
-			},
+			RequestMetadata { mmr, fee: meta, claimed: false },
 		);
 		Responded::<T>::insert(req_commitment, true);
 		Ok(commitment)
@@ -285,6 +292,36 @@ impl<T: Config> Pallet<T> {
 	pub fn responses(commitments: Vec<H256>) -> Vec<Response> {
 		commitments.into_iter().filter_map(|cm| Self::response(cm)).collect()
 	}
+
+	/// Returns everything this host knows about the request or response with the given
+	/// commitment. Receipts and `Responded` are keyed by the request commitment.
+	///
+	/// Commitments are only removed from the child trie when their timeouts are processed, so a
+	/// message dispatched from this host whose commitment is gone has timed out. Dispatched
+	/// messages are found through their leaf positions in the offchain db, so this should only be
+	/// called from the runtime api.
+	pub fn message_status(commitment: H256) -> MessageStatus {
+		let fee = |meta: RequestMetadata<T>| CommitmentFee {
+			payer: meta.fee.payer.encode(),
+			fee: meta.fee.fee.saturated_into::<u128>(),
+			claimed: meta.claimed,
+		};
+
+		let request = RequestCommitments::<T>::get(commitment).map(fee);
+		let response = ResponseCommitments::<T>::get(commitment).map(fee);
+		let timed_out = request.is_none() &&
+			response.is_none() &&
+			LeafIndexAndPos::offchain(commitment).is_some();
+
+		MessageStatus {
+			request,
+			response,
+			request_receipt: RequestReceipts::<T>::get(commitment),
+			response_receipt: ResponseReceipts::<T>::get(commitment),
+			responded: Responded::<T>::get(commitment),
+			timed_out,
+		}
+	}
 }
 
 impl<T: Config> ForkIdentifier<T> for Pallet<T> {
@@ -334,6 +334,20 @@ pub mod pallet {
 	pub type ConsensusStates<T: Config> =
 		StorageMap<_, Twox64Concat, ConsensusClientId, Vec<u8>, OptionQuery>;
 
+	/// Consensus states that were replaced by consensus updates, by the timestamp in seconds of
+	/// the update that replaced them. These are kept for the challenge period, so that the state
+	/// commitments of an update can be disputed against the consensus state that verified them.
+	#[pallet::storage]
+	pub type PreviousConsensusStates<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ConsensusStateId,
+		Twox64Concat,
+		u64,
+		Vec<Vec<u8>>,
+		ValueQuery,
+	>;
+
 	/// A mapping of consensus state identifier to it's associated consensus client identifier
 	#[pallet::storage]
 	pub type ConsensusStateClient<T: Config> =
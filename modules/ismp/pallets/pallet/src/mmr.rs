@@ -20,7 +20,7 @@ use frame_support::__private::RuntimeDebug;
 use ismp::router::{Request, Response};
 use mmr_primitives::FullLeaf;
 use scale_info::TypeInfo;
-use sp_core::H256;
+use sp_core::{offchain::StorageKind, H256};
 use sp_mmr_primitives::NodeIndex;
 use sp_std::prelude::*;
 
@@ -53,6 +53,25 @@ pub struct LeafIndexAndPos {
 	pub pos: u64,
 }
 
+impl LeafIndexAndPos {
+	/// Offchain key under which the leaf index and position of a request or response commitment
+	/// is indexed, so that it can be found after the commitment is removed from the child trie
+	pub fn offchain_key(commitment: H256) -> Vec<u8> {
+		let prefix = b"leaf_index";
+		(prefix, commitment).encode()
+	}
+
+	/// Reads the leaf index and position of a request or response commitment from the offchain
+	/// db. Can only be used from an off-chain context.
+	pub fn offchain(commitment: H256) -> Option<Self> {
+		let encoded = sp_io::offchain::local_storage_get(
+			StorageKind::PERSISTENT,
+			&Self::offchain_key(commitment),
+		)?;
+		Decode::decode(&mut &*encoded).ok()
+	}
+}
+
 /// A concrete Leaf for the MMR
 #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
 pub enum Leaf {
@@ -78,6 +97,10 @@ pub enum ProofKeys {
 	Requests(Vec<H256>),
 	/// Response commitments
 	Responses(Vec<H256>),
+	/// Request or response commitments, for callers that don't know which kind of message each
+	/// commitment belongs to. Their leaf positions are read from the offchain db, so these may
+	/// include commitments which have since timed out.
+	Commitments(Vec<H256>),
 }
 
 /// An MMR proof data for a group of leaves.
@@ -62,6 +62,36 @@ pub struct ResponseReceipt {
 	pub relayer: Vec<u8>,
 }
 
+/// Fee metadata for a request or response commitment stored on this host
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct CommitmentFee {
+	/// Scale encoded account which paid the fee
+	pub payer: Vec<u8>,
+	/// The fee that was paid for relayers
+	pub fee: u128,
+	/// Has the fee been claimed by the relayer?
+	pub claimed: bool,
+}
+
+/// Everything this host knows about a request or response commitment. Commitments and receipts
+/// are deleted once the timeout of their message is processed, which is reported in `timed_out`.
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq, Default)]
+pub struct MessageStatus {
+	/// Present if this is the commitment of a request dispatched from, or routed through, this
+	/// host
+	pub request: Option<CommitmentFee>,
+	/// Present if this is the commitment of a response dispatched from this host
+	pub response: Option<CommitmentFee>,
+	/// The relayer that delivered this request to this host
+	pub request_receipt: Option<Vec<u8>>,
+	/// The response to this request that was delivered to this host, and its relayer
+	pub response_receipt: Option<ResponseReceipt>,
+	/// Has this host dispatched a response to this request?
+	pub responded: bool,
+	/// Has the timeout of this request or response been processed on this host?
+	pub timed_out: bool,
+}
+
 /// A  convenience trait that returns a list of all configured consensus clients
 /// This trait should be implemented in the runtime
 pub trait ConsensusClientProvider {
@@ -34,7 +34,12 @@ use evm_common::{
 	},
 	utils::{add_off_set_to_map_key, derive_unhashed_map_key},
 };
-use frame_support::{dispatch::DispatchResult, ensure};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	storage::child,
+	traits::{fungible::Mutate, tokens::Preservation, Get},
+};
 use frame_system::pallet_prelude::OriginFor;
 use ismp::{
 	dispatcher::{DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
@@ -44,10 +49,16 @@ use ismp::{
 };
 pub use pallet::*;
 use pallet_hyperbridge::{Message, WithdrawalRequest, PALLET_HYPERBRIDGE};
-use pallet_ismp::child_trie::{RequestCommitments, ResponseCommitments};
+use pallet_ismp::{
+	child_trie::{RequestCommitments, ResponseCommitments, CHILD_TRIE_PREFIX},
+	RELAYER_FEE_ACCOUNT,
+};
 use pallet_ismp_host_executive::{HostParam, HostParams};
-use sp_core::U256;
-use sp_runtime::{AccountId32, DispatchError};
+use sp_core::{storage::ChildInfo, U256};
+use sp_runtime::{
+	traits::{AccountIdConversion, SaturatedConversion},
+	AccountId32, DispatchError,
+};
 use sp_std::prelude::*;
 
 pub const MODULE_ID: &'static [u8] = b"ISMP-RLYR";
@@ -311,8 +322,26 @@ where
 		};
 		let available_amount = Fees::<T>::get(withdrawal_data.dest_chain, address.clone());
 
-		if available_amount < Self::min_withdrawal_amount() {
-			Err(Error::<T>::NotEnoughBalance)?
+		match withdrawal_data.dest_chain {
+			// Fees on evm chains are denominated in dollars
+			s if s.is_evm() => {
+				if available_amount < Self::min_withdrawal_amount() {
+					Err(Error::<T>::NotEnoughBalance)?
+				}
+
+				if address.len() != 20 {
+					Err(Error::<T>::InvalidPublicKey)?
+				}
+			},
+			// Fees on substrate chains are denominated in their native token
+			_ =>
+				if available_amount.is_zero() {
+					Err(Error::<T>::EmptyBalance)?
+				},
+		}
+
+		if withdrawal_data.dest_chain == <T as pallet_ismp::Config>::HostStateMachine::get() {
+			return Self::withdraw_local(address, available_amount);
 		}
 
 		let dispatcher = <T as Config>::IsmpHost::default();
@@ -377,6 +406,35 @@ where
 		Ok(())
 	}
 
+	/// Pays out fees accrued on this chain directly to the relayer's account, rather than
+	/// dispatching a withdrawal request.
+	fn withdraw_local(address: Vec<u8>, available_amount: U256) -> DispatchResult {
+		let host_state_machine = <T as pallet_ismp::Config>::HostStateMachine::get();
+		let account: [u8; 32] =
+			address.as_slice().try_into().map_err(|_| Error::<T>::InvalidPublicKey)?;
+		let amount: u128 =
+			available_amount.try_into().map_err(|_| Error::<T>::ErrorCompletingCall)?;
+
+		Nonce::<T>::mutate(address.clone(), host_state_machine, |value| *value += 1);
+
+		<T as pallet_ismp::Config>::Currency::transfer(
+			&RELAYER_FEE_ACCOUNT.into_account_truncating(),
+			&account.into(),
+			amount.saturated_into(),
+			Preservation::Expendable,
+		)?;
+
+		Fees::<T>::insert(host_state_machine, address.clone(), U256::zero());
+
+		Self::deposit_event(Event::<T>::Withdraw {
+			address: sp_runtime::BoundedVec::truncate_from(address),
+			state_machine: host_state_machine,
+			amount: available_amount,
+		});
+
+		Ok(())
+	}
+
 	pub fn accumulate(mut withdrawal_proof: WithdrawalProof) -> DispatchResult {
 		// Filter out duplicate commitments
 		withdrawal_proof.commitments = withdrawal_proof
@@ -421,8 +479,18 @@ where
 			_ => {},
 		}
 
-		let source_result =
-			Self::verify_withdrawal_proof(&withdrawal_proof.source_proof, source_keys.clone())?;
+		let source_result = if state_machine == <T as pallet_ismp::Config>::HostStateMachine::get()
+		{
+			// Fees for requests dispatched from this chain are read directly from the child trie,
+			// there is no consensus client to verify our own state proofs against.
+			let child_info = ChildInfo::new_default(CHILD_TRIE_PREFIX);
+			source_keys
+				.iter()
+				.map(|key| (key.clone(), child::get_raw(&child_info, key)))
+				.collect()
+		} else {
+			Self::verify_withdrawal_proof(&withdrawal_proof.source_proof, source_keys.clone())?
+		};
 		let dest_result = Self::verify_withdrawal_proof(
 			&withdrawal_proof.dest_proof,
 			dest_keys.clone().into_iter().chain(slot_2_keys).collect(),
@@ -593,6 +661,34 @@ where
 		keys
 	}
 
+	/// Decode the fee from the commitment metadata stored on the source chain. EVM hosts store
+	/// the fee as an RLP encoded uint256, while substrate hosts store the scale encoded
+	/// [`RequestMetadata`](pallet_ismp::dispatcher::RequestMetadata).
+	pub fn decode_fee(state_machine: StateMachine, encoded: &[u8]) -> Result<U256, Error<T>> {
+		let fee = match state_machine {
+			s if s.is_evm() => {
+				use alloy_rlp::Decodable;
+				let fee = alloy_primitives::U256::decode(&mut &*encoded)
+					.map_err(|_| Error::<T>::ProofValidationError)?;
+				U256::from_big_endian(&fee.to_be_bytes::<32>())
+			},
+			s if s.is_substrate() => {
+				use codec::Decode;
+				let fee: u128 =
+					pallet_ismp::dispatcher::RequestMetadata::<T>::decode(&mut &*encoded)
+						.map_err(|_| Error::<T>::ProofValidationError)?
+						.fee
+						.fee
+						.into();
+				U256::from(fee)
+			},
+			// unsupported
+			_ => Err(Error::<T>::MismatchedStateMachine)?,
+		};
+
+		Ok(fee)
+	}
+
 	pub fn validate_results(
 		proof: &WithdrawalProof,
 		source_keys: Vec<Vec<u8>>,
@@ -617,27 +713,8 @@ where
 							continue;
 						};
 
-					let fee = match proof.source_proof.height.id.state_id {
-						s if s.is_evm() => {
-							use alloy_rlp::Decodable;
-							let fee = alloy_primitives::U256::decode(&mut &*encoded_metadata)
-								.map_err(|_| Error::<T>::ProofValidationError)?;
-							U256::from_big_endian(&fee.to_be_bytes::<32>())
-						},
-						s if s.is_substrate() => {
-							use codec::Decode;
-							let fee: u128 = pallet_ismp::dispatcher::RequestMetadata::<T>::decode(
-								&mut &*encoded_metadata,
-							)
-							.map_err(|_| Error::<T>::ProofValidationError)?
-							.fee
-							.fee
-							.into();
-							U256::from(fee)
-						},
-						// unsupported
-						_ => Err(Error::<T>::MismatchedStateMachine)?,
-					};
+					let fee =
+						Self::decode_fee(proof.source_proof.height.id.state_id, &encoded_metadata)?;
 					let encoded_receipt = dest_result
 						.get(&dest_key)
 						.cloned()
@@ -670,27 +747,8 @@ where
 						} else {
 							continue;
 						};
-					let fee = match proof.source_proof.height.id.state_id {
-						s if s.is_evm() => {
-							use alloy_rlp::Decodable;
-							let fee = alloy_primitives::U256::decode(&mut &*encoded_metadata)
-								.map_err(|_| Error::<T>::ProofValidationError)?;
-							U256::from_big_endian(&fee.to_be_bytes::<32>())
-						},
-						s if s.is_substrate() => {
-							use codec::Decode;
-							let fee: u128 = pallet_ismp::dispatcher::RequestMetadata::<T>::decode(
-								&mut &*encoded_metadata,
-							)
-							.map_err(|_| Error::<T>::ProofValidationError)?
-							.fee
-							.fee
-							.into();
-							U256::from(fee)
-						},
-						// unsupported
-						_ => Err(Error::<T>::MismatchedStateMachine)?,
-					};
+					let fee =
+						Self::decode_fee(proof.source_proof.height.id.state_id, &encoded_metadata)?;
 					let encoded_receipt = dest_result
 						.get(&dest_key)
 						.cloned()
@@ -0,0 +1,455 @@
+// Copyright (c) 2024 Polytope Labs.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An optional index of the ISMP events deposited in finalized blocks, kept in the node's offchain
+//! db. Each event is stored once, in the order it was finalized, and referenced by its position
+//! from a list per [`IndexKey`], so that history queries can be paginated without re-executing the
+//! runtime for every block.
+//!
+//! The index starts at the first block finalized after it was enabled. Blocks finalized while the
+//! node was offline are indexed when it restarts, and indexed blocks that are no longer canonical,
+//! e.g. after the node's database was replaced, are reverted and indexed again.
+
+use crate::{
+	block_events_with_metadata, event_routing, runtime_error_into_rpc_error, EventWithMetadata,
+};
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use ismp::{
+	events::Event,
+	host::StateMachine,
+	messaging::{hash_request, hash_response, Keccak256},
+	router::{Request, Response},
+};
+use jsonrpsee::core::Error as RpcError;
+use log::{error, info, warn};
+use pallet_ismp_runtime_api::IsmpRuntimeApi;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	offchain::{storage::OffchainDb, DbExternalities, OffchainStorage, StorageKind},
+	Bytes, H256,
+};
+use sp_runtime::{
+	traits::{Block as BlockT, Header},
+	SaturatedConversion,
+};
+use std::{collections::BTreeMap, sync::Arc};
+
+const LOG_TARGET: &str = "ismp-event-index";
+
+/// Prefix for all the offchain db keys written by the index
+const INDEX_PREFIX: &[u8] = b"ismp-event-index";
+
+/// Maximum number of events that can be returned by a single history query
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Number of blocks indexed before the indexer yields to other tasks
+const MAX_BLOCKS_PER_BATCH: u64 = 256;
+
+/// The keys under which events are indexed
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+	/// The dispatch, delivery and timeout events of the request or response with this commitment.
+	/// Responses are also indexed under the commitment of their request.
+	Commitment(H256),
+	/// Messages sent from this state machine, and the state machine updates and vetoes for it
+	Source(StateMachine),
+	/// Messages sent to this state machine
+	Dest(StateMachine),
+	/// Requests and responses sent from or to this module
+	Module(Bytes),
+}
+
+impl IndexKey {
+	/// Returns the keys that the event should be indexed under
+	pub fn for_event(event: &Event) -> Vec<IndexKey> {
+		let commitments = match event {
+			Event::PostRequest(post) => vec![hash_request::<Hasher>(&Request::Post(post.clone()))],
+			Event::GetRequest(get) => vec![hash_request::<Hasher>(&Request::Get(get.clone()))],
+			Event::PostResponse(response) => vec![
+				hash_response::<Hasher>(&Response::Post(response.clone())),
+				hash_request::<Hasher>(&Request::Post(response.post.clone())),
+			],
+			Event::GetResponse(response) => vec![
+				hash_response::<Hasher>(&Response::Get(response.clone())),
+				hash_request::<Hasher>(&Request::Get(response.get.clone())),
+			],
+			Event::PostRequestHandled(handled) |
+			Event::PostResponseHandled(handled) |
+			Event::GetRequestHandled(handled) => vec![handled.commitment],
+			Event::PostRequestTimeoutHandled(timeout) |
+			Event::PostResponseTimeoutHandled(timeout) |
+			Event::GetRequestTimeoutHandled(timeout) => vec![timeout.commitment],
+			Event::StateMachineUpdated(_) | Event::StateCommitmentVetoed(_) => vec![],
+		};
+
+		let (source, dest, modules) = event_routing(event);
+		let mut keys = commitments.into_iter().map(IndexKey::Commitment).collect::<Vec<_>>();
+		keys.extend(source.map(IndexKey::Source));
+		keys.extend(dest.map(IndexKey::Dest));
+		for module in modules {
+			let key = IndexKey::Module(module.clone().into());
+			// requests between two accounts of the same module are only indexed once
+			if !keys.contains(&key) {
+				keys.push(key)
+			}
+		}
+
+		keys
+	}
+
+	fn encode_key(&self) -> Vec<u8> {
+		match self {
+			IndexKey::Commitment(commitment) => (0u8, commitment).encode(),
+			IndexKey::Source(state_machine) => (1u8, state_machine).encode(),
+			IndexKey::Dest(state_machine) => (2u8, state_machine).encode(),
+			IndexKey::Module(module) => (3u8, &module.0).encode(),
+		}
+	}
+}
+
+/// A page of indexed events
+#[derive(Serialize, Deserialize)]
+pub struct IndexedEvents {
+	/// The events, oldest first
+	pub events: Vec<EventWithMetadata>,
+	/// The total number of events indexed under the queried key
+	pub total: u64,
+}
+
+/// Progress of the index
+#[derive(Encode, Decode, Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct IndexState {
+	/// The last block that was indexed
+	pub(crate) block_number: u64,
+	/// The hash of the last block that was indexed
+	pub(crate) block_hash: H256,
+	/// The number of events indexed so far
+	pub(crate) events: u64,
+}
+
+struct Hasher;
+
+impl Keccak256 for Hasher {
+	fn keccak256(bytes: &[u8]) -> H256 {
+		sp_core::keccak_256(bytes).into()
+	}
+}
+
+fn state_key() -> Vec<u8> {
+	[INDEX_PREFIX, b"::state"].concat()
+}
+
+fn journal_key() -> Vec<u8> {
+	[INDEX_PREFIX, b"::journal"].concat()
+}
+
+fn block_key(block_number: u64) -> Vec<u8> {
+	[INDEX_PREFIX, b"::block", &block_number.to_be_bytes()].concat()
+}
+
+fn event_key(position: u64) -> Vec<u8> {
+	[INDEX_PREFIX, b"::event", &position.to_be_bytes()].concat()
+}
+
+fn count_key(key: &IndexKey) -> Vec<u8> {
+	[INDEX_PREFIX, b"::count", &key.encode_key()].concat()
+}
+
+fn entry_key(key: &IndexKey, index: u64) -> Vec<u8> {
+	[INDEX_PREFIX, b"::entry", &key.encode_key(), &index.to_be_bytes()].concat()
+}
+
+fn get<S: OffchainStorage, T: Decode>(db: &mut OffchainDb<S>, key: &[u8]) -> Option<T> {
+	db.local_storage_get(StorageKind::PERSISTENT, key)
+		.and_then(|value| T::decode(&mut &value[..]).ok())
+}
+
+fn set<S: OffchainStorage, T: Encode>(db: &mut OffchainDb<S>, key: &[u8], value: &T) {
+	db.local_storage_set(StorageKind::PERSISTENT, key, &value.encode())
+}
+
+/// Writes the events of a block to the index and advances the cursor past it, returning the new
+/// cursor.
+///
+/// All the writes for the block, cursor included, are first stored under a single journal key and
+/// only then applied, so a block is never left partially indexed: an interrupted write is
+/// completed by [`apply_journal`] when the indexer restarts.
+pub(crate) fn index_events<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	state: &IndexState,
+	block_number: u64,
+	block_hash: H256,
+	events: Vec<EventWithMetadata>,
+) -> IndexState {
+	let (writes, state) = block_writes(db, state, block_number, block_hash, events);
+	write_journal(db, &writes);
+	apply_journal(db);
+	state
+}
+
+/// Computes the writes that index the events of a block, along with the cursor after it.
+pub(crate) fn block_writes<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	state: &IndexState,
+	block_number: u64,
+	block_hash: H256,
+	events: Vec<EventWithMetadata>,
+) -> (Vec<(Vec<u8>, Vec<u8>)>, IndexState) {
+	let mut counts = BTreeMap::new();
+	let mut writes = vec![];
+	let mut position = state.events;
+	for event in events {
+		for key in IndexKey::for_event(&event.event) {
+			let count_key = count_key(&key);
+			let count = match counts.get(&count_key) {
+				Some(count) => *count,
+				None => get::<_, u64>(db, &count_key).unwrap_or_default(),
+			};
+			writes.push((entry_key(&key, count), position.encode()));
+			counts.insert(count_key, count + 1);
+		}
+		writes.push((event_key(position), event.encode()));
+		position += 1;
+	}
+	writes.extend(counts.into_iter().map(|(key, count)| (key, count.encode())));
+
+	let state = IndexState { block_number, block_hash, events: position };
+	writes.push((block_key(block_number), state.encode()));
+	writes.push((state_key(), state.encode()));
+
+	(writes, state)
+}
+
+/// Starts the index after the given block, which is recorded as indexed without any events.
+pub(crate) fn start_index<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	block_number: u64,
+	block_hash: H256,
+) -> IndexState {
+	let state = IndexState { block_number, block_hash, events: 0 };
+	set(db, &block_key(block_number), &state);
+	set(db, &state_key(), &state);
+	state
+}
+
+/// Returns the state of the index right after the given block was indexed.
+pub(crate) fn indexed_block<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	block_number: u64,
+) -> Option<IndexState> {
+	get(db, &block_key(block_number))
+}
+
+/// Removes the events indexed after the given block, returning it as the new cursor. The events
+/// themselves are left in place to be overwritten, only the counts of their keys are rolled back.
+pub(crate) fn revert_to<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	state: &IndexState,
+	ancestor: IndexState,
+) -> IndexState {
+	let mut counts = BTreeMap::new();
+	for position in ancestor.events..state.events {
+		let Some(event) = get::<_, EventWithMetadata>(db, &event_key(position)) else { continue };
+		for key in IndexKey::for_event(&event.event) {
+			let count_key = count_key(&key);
+			let count = match counts.get(&count_key) {
+				Some(count) => *count,
+				None => get::<_, u64>(db, &count_key).unwrap_or_default(),
+			};
+			counts.insert(count_key, count.saturating_sub(1));
+		}
+	}
+
+	let mut writes =
+		counts.into_iter().map(|(key, count)| (key, count.encode())).collect::<Vec<_>>();
+	writes.push((state_key(), ancestor.encode()));
+	write_journal(db, &writes);
+	apply_journal(db);
+	ancestor
+}
+
+/// Stores the writes for a block under the journal key, in a single write.
+pub(crate) fn write_journal<S: OffchainStorage>(
+	db: &mut OffchainDb<S>,
+	writes: &[(Vec<u8>, Vec<u8>)],
+) {
+	db.local_storage_set(StorageKind::PERSISTENT, &journal_key(), &writes.encode())
+}
+
+/// Applies the writes left in the journal, if any, and clears it.
+pub(crate) fn apply_journal<S: OffchainStorage>(db: &mut OffchainDb<S>) {
+	let Some(writes) = get::<_, Vec<(Vec<u8>, Vec<u8>)>>(db, &journal_key()) else { return };
+	for (key, value) in writes {
+		db.local_storage_set(StorageKind::PERSISTENT, &key, &value);
+	}
+	db.local_storage_clear(StorageKind::PERSISTENT, &journal_key());
+}
+
+/// Reads a page of the events indexed under the given key, starting at `offset`. Returns `None`
+/// if the index has not been enabled on this node.
+pub fn query<S: OffchainStorage>(
+	mut db: OffchainDb<S>,
+	key: &IndexKey,
+	offset: u64,
+	limit: u32,
+) -> Option<IndexedEvents> {
+	get::<_, IndexState>(&mut db, &state_key())?;
+
+	let total = get::<_, u64>(&mut db, &count_key(key)).unwrap_or_default();
+	let end = total.min(offset.saturating_add(limit.min(MAX_PAGE_SIZE) as u64));
+	let events = (offset..end)
+		.filter_map(|index| {
+			let position = get::<_, u64>(&mut db, &entry_key(key, index))?;
+			get::<_, EventWithMetadata>(&mut db, &event_key(position))
+		})
+		.collect();
+
+	Some(IndexedEvents { events, total })
+}
+
+/// Maintains the event index from finality notifications.
+pub struct EventIndexer<B, C, S> {
+	client: Arc<C>,
+	offchain_db: OffchainDb<S>,
+	state: IndexState,
+	_phantom: std::marker::PhantomData<B>,
+}
+
+impl<B, C, S> EventIndexer<B, C, S>
+where
+	B: BlockT,
+	B::Hash: Into<H256>,
+	u64: From<<B::Header as Header>::Number>,
+	S: OffchainStorage + 'static,
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + BlockchainEvents<B>,
+	C::Api: IsmpRuntimeApi<B, B::Hash>,
+{
+	/// Create and run the event indexer.
+	pub async fn start<BE>(client: Arc<C>, backend: Arc<BE>)
+	where
+		BE: Backend<B, OffchainStorage = S>,
+	{
+		let mut offchain_db = match backend.offchain_storage() {
+			Some(offchain_storage) => OffchainDb::new(offchain_storage),
+			None => {
+				warn!(
+					target: LOG_TARGET,
+					"Can't spawn the ISMP event index for a node without offchain storage."
+				);
+				return;
+			},
+		};
+
+		let mut finality_notifications = client.finality_notification_stream();
+		let info = client.info();
+		apply_journal(&mut offchain_db);
+		let state = match get::<_, IndexState>(&mut offchain_db, &state_key()) {
+			Some(state) => state,
+			None => {
+				let number = u64::from(info.finalized_number);
+				info!(target: LOG_TARGET, "Starting ISMP event index after block #{number}");
+				start_index(&mut offchain_db, number, info.finalized_hash.into())
+			},
+		};
+
+		let mut indexer = EventIndexer { client, offchain_db, state, _phantom: Default::default() };
+		indexer.index_up_to(u64::from(info.finalized_number)).await;
+
+		while let Some(notification) = finality_notifications.next().await {
+			indexer.index_up_to(u64::from(*notification.header.number())).await;
+		}
+	}
+
+	/// Indexes every block after the last indexed block up to and including the given finalized
+	/// block, in batches of [`MAX_BLOCKS_PER_BATCH`] blocks. Indexed blocks that are no longer
+	/// canonical are reverted first. Stops at the first block whose events can't be read, which
+	/// is retried on the next finality notification.
+	async fn index_up_to(&mut self, finalized: u64) {
+		if let Err(err) = self.revert_non_canonical() {
+			error!(target: LOG_TARGET, "Failed to check the indexed blocks, will retry: {err:?}");
+			return;
+		}
+
+		while self.state.block_number < finalized {
+			let end = finalized.min(self.state.block_number + MAX_BLOCKS_PER_BATCH);
+			for block_number in self.state.block_number + 1..=end {
+				if let Err(err) = self.index_block(block_number) {
+					error!(
+						target: LOG_TARGET,
+						"Failed to index events for block #{block_number}, will retry: {err:?}"
+					);
+					return;
+				}
+			}
+			tokio::task::yield_now().await;
+		}
+	}
+
+	/// Reverts the index to the last indexed block that is still canonical.
+	fn revert_non_canonical(&mut self) -> Result<(), RpcError> {
+		let mut ancestor = self.state;
+		loop {
+			let canonical: H256 = self.canonical_hash(ancestor.block_number)?.into();
+			if canonical == ancestor.block_hash {
+				break;
+			}
+			ancestor = match ancestor
+				.block_number
+				.checked_sub(1)
+				.and_then(|parent| indexed_block(&mut self.offchain_db, parent))
+			{
+				Some(parent) => parent,
+				// the first block of the index has no events, so it's simply replaced
+				None => IndexState { block_hash: canonical, ..ancestor },
+			};
+		}
+
+		if ancestor != self.state {
+			warn!(
+				target: LOG_TARGET,
+				"Indexed block #{} is no longer canonical, reverting the index to block #{}",
+				self.state.block_number,
+				ancestor.block_number,
+			);
+			self.state = revert_to(&mut self.offchain_db, &self.state, ancestor);
+		}
+
+		Ok(())
+	}
+
+	fn canonical_hash(&self, block_number: u64) -> Result<B::Hash, RpcError> {
+		self.client
+			.hash(block_number.saturated_into())
+			.ok()
+			.flatten()
+			.ok_or_else(|| runtime_error_into_rpc_error(format!("Block #{block_number} not found")))
+	}
+
+	fn index_block(&mut self, block_number: u64) -> Result<(), RpcError> {
+		let hash = self.canonical_hash(block_number)?;
+		let header = self.client.header(hash).ok().flatten().ok_or_else(|| {
+			runtime_error_into_rpc_error(format!("Header for block {hash:?} not found"))
+		})?;
+		let events =
+			block_events_with_metadata::<B, _, _>(&*self.client, &self.offchain_db, &header)?;
+		self.state =
+			index_events(&mut self.offchain_db, &self.state, block_number, hash.into(), events);
+		Ok(())
+	}
+}
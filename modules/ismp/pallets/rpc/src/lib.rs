@@ -30,6 +30,8 @@
 //!     pub deny_unsafe: DenyUnsafe,
 //!     /// Backend used by the node.
 //!     pub backend: Arc<B>,
+//!     /// Executor for RPC subscriptions
+//!     pub subscription_executor: SubscriptionTaskExecutor,
 //! }
 //!
 //! /// Instantiate all full RPC extensions.
@@ -39,6 +41,7 @@
 //!     where
 //!         C: ProvideRuntimeApi<Block>,
 //!         C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+//!         C: BlockchainEvents<Block>,
 //!         C: Send + Sync + 'static,
 //!         C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 //!         C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
@@ -51,29 +54,35 @@
 //!     use substrate_frame_rpc_system::{System, SystemApiServer};
 //!
 //!     let mut module = RpcModule::new(());
-//!     let FullDeps { client, pool, deny_unsafe, backend } = deps;
+//!     let FullDeps { client, pool, deny_unsafe, backend, subscription_executor } = deps;
 //!
 //!     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 //!     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 //!     // IsmpRpcHander goes here
-//!     module.merge(IsmpRpcHandler::new(client, backend)?.into_rpc())?;
+//!     module.merge(IsmpRpcHandler::new(client, backend, subscription_executor)?.into_rpc())?;
 //!
 //!
 //!     Ok(module)
 //! }
 //! ```
+//!
+//! The event history served by `ismp_queryIndexedEvents` is maintained by the [`index`] module's
+//! `EventIndexer`, which the node has to spawn separately.
 
 use jsonrpsee::{
 	core::{Error as RpcError, RpcResult},
 	proc_macros::rpc,
-	types::{error::CallError, ErrorObject},
+	types::{error::CallError, ErrorObject, SubscriptionResult},
+	SubscriptionSink,
 };
 
 use anyhow::anyhow;
-use codec::Encode;
+use codec::{Decode, Encode};
+use futures::{channel::mpsc, FutureExt, StreamExt};
 use ismp::{
 	consensus::{ConsensusClientId, StateMachineId},
 	events::Event,
+	host::StateMachine,
 	router::{Request, Response},
 };
 use pallet_ismp::{
@@ -81,19 +90,34 @@ use pallet_ismp::{
 	mmr::{Leaf, LeafIndexQuery, ProofKeys},
 };
 use pallet_ismp_runtime_api::IsmpRuntimeApi;
-use sc_client_api::{Backend, BlockBackend, ChildInfo, ProofProvider, StateBackend};
+use sc_client_api::{
+	Backend, BlockBackend, BlockchainEvents, ChildInfo, FinalityNotification, ProofProvider,
+	StateBackend,
+};
+use sc_rpc::SubscriptionTaskExecutor;
 use serde::{Deserialize, Serialize};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{
 	offchain::{storage::OffchainDb, OffchainDbExt, OffchainStorage},
-	H256,
+	traits::SpawnNamed,
+	Bytes, H256, U256,
 };
 use sp_runtime::traits::{Block as BlockT, Hash, Header};
 use sp_trie::LayoutV0;
-use std::{collections::HashMap, fmt::Display, sync::Arc};
+use std::{
+	collections::HashMap,
+	fmt::Display,
+	sync::{Arc, Mutex, PoisonError},
+};
 use trie_db::{Recorder, Trie, TrieDBBuilder};
 
+pub mod index;
+#[cfg(test)]
+mod tests;
+
+use index::{IndexKey, IndexedEvents};
+
 /// A type that could be a block number or a block hash
 #[derive(Clone, Hash, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -132,7 +156,7 @@ fn runtime_error_into_rpc_error(e: impl std::fmt::Display) -> RpcError {
 }
 
 /// Relevant transaction metadata for an event
-#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq, Default)]
+#[derive(Clone, Serialize, Deserialize, Encode, Decode, Debug, Eq, PartialEq, Default)]
 pub struct EventMetadata {
 	/// The hash of the block where the event was emitted
 	pub block_hash: H256,
@@ -143,7 +167,7 @@ pub struct EventMetadata {
 }
 
 /// Holds an event along with relevant metadata about the event
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct EventWithMetadata {
 	/// The event metdata
 	pub meta: EventMetadata,
@@ -151,6 +175,331 @@ pub struct EventWithMetadata {
 	pub event: Event,
 }
 
+/// Fee metadata for a request or response commitment
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct CommitmentFee {
+	/// Scale encoded account which paid the fee
+	pub payer: Bytes,
+	/// The fee that was paid for relayers
+	pub fee: U256,
+	/// Has the fee been claimed by the relayer?
+	pub claimed: bool,
+}
+
+/// The relayer that delivered a response and the hash of that response
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ResponseReceipt {
+	/// Hash of the response
+	pub response: H256,
+	/// Address of the relayer
+	pub relayer: Bytes,
+}
+
+/// The delivery status of a request or response, as known to this host. Commitments and receipts
+/// are deleted once a timeout is processed, which is recorded in `timed_out`.
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq, Default)]
+pub struct MessageStatus {
+	/// Present if this is the commitment of a request dispatched from, or routed through, this
+	/// host
+	pub request: Option<CommitmentFee>,
+	/// Present if this is the commitment of a response dispatched from this host
+	pub response: Option<CommitmentFee>,
+	/// The relayer that delivered this request to this host
+	pub request_receipt: Option<Bytes>,
+	/// The response to this request that was delivered to this host
+	pub response_receipt: Option<ResponseReceipt>,
+	/// Has this host dispatched a response to this request?
+	pub responded: bool,
+	/// Has the timeout of this request or response been processed on this host?
+	pub timed_out: bool,
+}
+
+impl From<pallet_ismp::MessageStatus> for MessageStatus {
+	fn from(status: pallet_ismp::MessageStatus) -> Self {
+		let fee = |fee: pallet_ismp::CommitmentFee| CommitmentFee {
+			payer: fee.payer.into(),
+			fee: fee.fee.into(),
+			claimed: fee.claimed,
+		};
+
+		MessageStatus {
+			request: status.request.map(fee),
+			response: status.response.map(fee),
+			request_receipt: status.request_receipt.map(Into::into),
+			response_receipt: status.response_receipt.map(|receipt| ResponseReceipt {
+				response: receipt.response,
+				relayer: receipt.relayer.into(),
+			}),
+			responded: status.responded,
+			timed_out: status.timed_out,
+		}
+	}
+}
+
+/// The kinds of ISMP events, used to filter event subscriptions
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub enum EventKind {
+	/// [`Event::StateMachineUpdated`]
+	StateMachineUpdated,
+	/// [`Event::StateCommitmentVetoed`]
+	StateCommitmentVetoed,
+	/// [`Event::PostRequest`]
+	PostRequest,
+	/// [`Event::PostResponse`]
+	PostResponse,
+	/// [`Event::GetResponse`]
+	GetResponse,
+	/// [`Event::GetRequest`]
+	GetRequest,
+	/// [`Event::PostRequestHandled`]
+	PostRequestHandled,
+	/// [`Event::PostResponseHandled`]
+	PostResponseHandled,
+	/// [`Event::PostRequestTimeoutHandled`]
+	PostRequestTimeoutHandled,
+	/// [`Event::PostResponseTimeoutHandled`]
+	PostResponseTimeoutHandled,
+	/// [`Event::GetRequestHandled`]
+	GetRequestHandled,
+	/// [`Event::GetRequestTimeoutHandled`]
+	GetRequestTimeoutHandled,
+}
+
+impl From<&Event> for EventKind {
+	fn from(event: &Event) -> Self {
+		match event {
+			Event::StateMachineUpdated(_) => EventKind::StateMachineUpdated,
+			Event::StateCommitmentVetoed(_) => EventKind::StateCommitmentVetoed,
+			Event::PostRequest(_) => EventKind::PostRequest,
+			Event::PostResponse(_) => EventKind::PostResponse,
+			Event::GetResponse(_) => EventKind::GetResponse,
+			Event::GetRequest(_) => EventKind::GetRequest,
+			Event::PostRequestHandled(_) => EventKind::PostRequestHandled,
+			Event::PostResponseHandled(_) => EventKind::PostResponseHandled,
+			Event::PostRequestTimeoutHandled(_) => EventKind::PostRequestTimeoutHandled,
+			Event::PostResponseTimeoutHandled(_) => EventKind::PostResponseTimeoutHandled,
+			Event::GetRequestHandled(_) => EventKind::GetRequestHandled,
+			Event::GetRequestTimeoutHandled(_) => EventKind::GetRequestTimeoutHandled,
+		}
+	}
+}
+
+/// Filters for ISMP event subscriptions. An event must match every filter that is provided, and
+/// events that do not carry the filtered field never match it.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, Eq, PartialEq)]
+pub struct EventFilter {
+	/// Only yield events of these kinds
+	pub kinds: Option<Vec<EventKind>>,
+	/// Only yield messages from this state machine. State machine updates and vetoes are matched
+	/// against the state machine whose commitment they refer to.
+	pub source: Option<StateMachine>,
+	/// Only yield messages to this state machine
+	pub dest: Option<StateMachine>,
+	/// Only yield requests and responses which were sent from or to this module
+	pub module: Option<Bytes>,
+}
+
+impl EventFilter {
+	/// Returns true if the event passes this filter
+	pub fn matches(&self, event: &Event) -> bool {
+		if let Some(ref kinds) = self.kinds {
+			if !kinds.contains(&EventKind::from(event)) {
+				return false;
+			}
+		}
+
+		let (source, dest, modules) = event_routing(event);
+
+		if self.source.is_some() && self.source != source {
+			return false;
+		}
+
+		if self.dest.is_some() && self.dest != dest {
+			return false;
+		}
+
+		if let Some(ref module) = self.module {
+			if !modules.into_iter().any(|address| address.as_slice() == &module[..]) {
+				return false;
+			}
+		}
+
+		true
+	}
+}
+
+/// Returns the source and destination state machines of an event, along with the modules it was
+/// sent from or to. State machine updates and vetoes only have a source.
+fn event_routing(event: &Event) -> (Option<StateMachine>, Option<StateMachine>, Vec<&Vec<u8>>) {
+	match event {
+		Event::PostRequest(post) =>
+			(Some(post.source), Some(post.dest), vec![&post.from, &post.to]),
+		Event::GetRequest(get) => (Some(get.source), Some(get.dest), vec![&get.from]),
+		Event::PostResponse(response) => (
+			Some(response.source_chain()),
+			Some(response.dest_chain()),
+			vec![&response.post.from, &response.post.to],
+		),
+		Event::GetResponse(response) =>
+			(Some(response.get.dest), Some(response.get.source), vec![&response.get.from]),
+		Event::PostRequestTimeoutHandled(timeout) |
+		Event::PostResponseTimeoutHandled(timeout) |
+		Event::GetRequestTimeoutHandled(timeout) => (Some(timeout.source), Some(timeout.dest), vec![]),
+		Event::StateMachineUpdated(update) =>
+			(Some(update.state_machine_id.state_id), None, vec![]),
+		Event::StateCommitmentVetoed(veto) => (Some(veto.height.id.state_id), None, vec![]),
+		Event::PostRequestHandled(_) |
+		Event::PostResponseHandled(_) |
+		Event::GetRequestHandled(_) => (None, None, vec![]),
+	}
+}
+
+/// Reads the ISMP events deposited in the given block along with their metadata
+fn block_events_with_metadata<Block, C, S>(
+	client: &C,
+	offchain_db: &OffchainDb<S>,
+	header: &Block::Header,
+) -> Result<Vec<EventWithMetadata>, RpcError>
+where
+	Block: BlockT,
+	S: OffchainStorage + Clone + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	C::Api: IsmpRuntimeApi<Block, Block::Hash>,
+	Block::Hash: Into<H256>,
+	u64: From<<Block::Header as Header>::Number>,
+{
+	let mut api = client.runtime_api();
+	api.register_extension(OffchainDbExt::new(offchain_db.clone()));
+	let at = header.hash();
+
+	let block_events = api.block_events_with_metadata(at).map_err(|e| {
+		runtime_error_into_rpc_error(format!("failed to read block events {:?}", e))
+	})?;
+
+	let mut events = vec![];
+
+	for (event, index) in block_events {
+		let extrinsic_hash = if let Some(index) = index {
+			let extrinsic = client
+				.block_body(at)
+				.map_err(|err| {
+					runtime_error_into_rpc_error(format!(
+						"Error fetching extrinsic for block {at:?}: {err:?}"
+					))
+				})?
+				.ok_or_else(|| {
+					runtime_error_into_rpc_error(format!("No extrinsics found for block {at:?}"))
+				})?
+				// using swap remove should be fine unless the node is in an inconsistent
+				// state
+				.swap_remove(index as usize);
+			let ext_bytes = serde_json::to_string(&extrinsic).map_err(|err| {
+				runtime_error_into_rpc_error(format!("Failed to serialize extrinsic: {err:?}"))
+			})?;
+			let len = ext_bytes.as_bytes().len() - 1;
+			let extrinsic = hex::decode(ext_bytes.as_bytes()[3..len].to_vec()).map_err(|err| {
+				runtime_error_into_rpc_error(format!("Failed to decode extrinsic: {err:?}"))
+			})?;
+			<Block::Header as Header>::Hashing::hash(extrinsic.as_slice())
+		} else {
+			Default::default()
+		};
+
+		events.push(EventWithMetadata {
+			meta: EventMetadata {
+				block_hash: at.into(),
+				transaction_hash: extrinsic_hash.into(),
+				block_number: u64::from(*header.number()),
+			},
+			event,
+		});
+	}
+
+	Ok(events)
+}
+
+/// Reads the ISMP events of every block finalized by the given notification, blocks which were
+/// implicitly finalized by it come first
+fn finalized_events<Block, C, S>(
+	client: &C,
+	offchain_db: &OffchainDb<S>,
+	notification: &FinalityNotification<Block>,
+) -> Result<Vec<EventWithMetadata>, RpcError>
+where
+	Block: BlockT,
+	S: OffchainStorage + Clone + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + BlockBackend<Block> + HeaderBackend<Block>,
+	C::Api: IsmpRuntimeApi<Block, Block::Hash>,
+	Block::Hash: Into<H256>,
+	u64: From<<Block::Header as Header>::Number>,
+{
+	let mut events = vec![];
+	for hash in notification.tree_route.iter().chain(std::iter::once(&notification.hash)) {
+		let header = client
+			.header(*hash)
+			.map_err(|e| runtime_error_into_rpc_error(e.to_string()))?
+			.ok_or_else(|| {
+				runtime_error_into_rpc_error(format!(
+					"Header for finalized block {hash:?} not found"
+				))
+			})?;
+		events.extend(block_events_with_metadata::<Block, _, _>(client, offchain_db, &header)?);
+	}
+
+	Ok(events)
+}
+
+/// The ISMP events of a finalized block, shared by all event subscriptions
+type FinalizedEvents = Arc<Result<Vec<EventWithMetadata>, String>>;
+
+/// Fans out the events of each finalized block to all event subscriptions, so that they are read
+/// once per block regardless of the number of subscribers
+#[derive(Default)]
+struct EventSubscribers {
+	/// Whether the task reading the events of finalized blocks has been spawned
+	started: bool,
+	/// Senders for each active subscription
+	senders: Vec<mpsc::UnboundedSender<FinalizedEvents>>,
+}
+
+/// Reads the ISMP events of each finalized block once and sends them to every event subscription
+async fn fan_out_finalized_events<Block, C, S>(
+	client: Arc<C>,
+	offchain_db: OffchainDb<S>,
+	subscribers: Arc<Mutex<EventSubscribers>>,
+) where
+	Block: BlockT,
+	S: OffchainStorage + Clone + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ BlockchainEvents<Block>,
+	C::Api: IsmpRuntimeApi<Block, Block::Hash>,
+	Block::Hash: Into<H256>,
+	u64: From<<Block::Header as Header>::Number>,
+{
+	let mut notifications = client.finality_notification_stream();
+	while let Some(notification) = notifications.next().await {
+		let has_subscribers =
+			!subscribers.lock().unwrap_or_else(PoisonError::into_inner).senders.is_empty();
+		// no need to read events that nobody is listening for
+		if !has_subscribers {
+			continue;
+		}
+
+		let events = Arc::new(
+			finalized_events::<Block, _, _>(&*client, &offchain_db, &notification)
+				.map_err(|err| err.to_string()),
+		);
+		// closed subscriptions are dropped here
+		subscribers
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.senders
+			.retain(|sender| sender.unbounded_send(events.clone()).is_ok());
+	}
+}
+
 /// ISMP RPC methods.
 #[rpc(client, server)]
 pub trait IsmpApi<Hash>
@@ -173,6 +522,12 @@ where
 	#[method(name = "ismp_queryStateProof")]
 	fn query_state_proof(&self, height: u32, keys: Vec<Vec<u8>>) -> RpcResult<Proof>;
 
+	/// Query an mmr proof for a batch of request or response commitments at the latest finalized
+	/// height. Unlike `ismp_queryMmrProof`, callers don't need to know the kind of each message or
+	/// the height it was dispatched at.
+	#[method(name = "ismp_queryMmrProofByCommitments")]
+	fn query_mmr_proof_by_commitments(&self, commitments: Vec<H256>) -> RpcResult<Proof>;
+
 	/// Query pallet ismp child trie proof
 	#[method(name = "ismp_queryChildTrieProof")]
 	fn query_child_trie_proof(&self, height: u32, keys: Vec<Vec<u8>>) -> RpcResult<Proof>;
@@ -197,6 +552,15 @@ where
 	#[method(name = "ismp_queryStateMachineLatestHeight")]
 	fn query_state_machine_latest_height(&self, id: StateMachineId) -> RpcResult<u64>;
 
+	/// Query the commitments, receipts and fee metadata stored for a request or response
+	/// commitment, optionally at the given block height
+	#[method(name = "ismp_queryMessageStatus")]
+	fn query_message_status(
+		&self,
+		commitment: H256,
+		height: Option<u32>,
+	) -> RpcResult<MessageStatus>;
+
 	/// Query ISMP Events that were deposited in a series of blocks
 	/// Using String keys because HashMap fails to deserialize when key is not a String
 	#[method(name = "ismp_queryEvents")]
@@ -214,6 +578,26 @@ where
 		from: BlockNumberOrHash<Hash>,
 		to: BlockNumberOrHash<Hash>,
 	) -> RpcResult<HashMap<String, Vec<EventWithMetadata>>>;
+
+	/// Subscribe to the ISMP events deposited in finalized blocks, optionally filtered by event
+	/// kind, source or destination state machine and module
+	#[subscription(
+		name = "ismp_subscribeEvents" => "ismp_events",
+		unsubscribe = "ismp_unsubscribeEvents",
+		item = EventWithMetadata
+	)]
+	fn subscribe_events(&self, filter: Option<EventFilter>);
+
+	/// Query a page of the events recorded under the given key by the node's event index, oldest
+	/// first. At most [`index::MAX_PAGE_SIZE`] events are returned per query. Fails if the node was
+	/// started without the event index.
+	#[method(name = "ismp_queryIndexedEvents")]
+	fn query_indexed_events(
+		&self,
+		key: IndexKey,
+		offset: u64,
+		limit: u32,
+	) -> RpcResult<IndexedEvents>;
 }
 
 /// An implementation of ISMP specific RPC methods.
@@ -221,6 +605,8 @@ pub struct IsmpRpcHandler<C, B, S, T> {
 	client: Arc<C>,
 	backend: Arc<T>,
 	offchain_db: OffchainDb<S>,
+	executor: SubscriptionTaskExecutor,
+	subscribers: Arc<Mutex<EventSubscribers>>,
 	_marker: std::marker::PhantomData<B>,
 }
 
@@ -230,15 +616,27 @@ where
 	S: OffchainStorage + Clone + Send + Sync + 'static,
 	T: Backend<B, OffchainStorage = S> + Send + Sync + 'static,
 {
-	/// Create new `IsmpRpcHandler` with the given reference to the client.
-	pub fn new(client: Arc<C>, backend: Arc<T>) -> Result<Self, anyhow::Error> {
+	/// Create new `IsmpRpcHandler` with the given reference to the client. Event subscriptions
+	/// are driven on the provided executor.
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<T>,
+		executor: SubscriptionTaskExecutor,
+	) -> Result<Self, anyhow::Error> {
 		let offchain_db = OffchainDb::new(
 			backend
 				.offchain_storage()
 				.ok_or_else(|| anyhow!("Offchain Storage not present in backend!"))?,
 		);
 
-		Ok(Self { client, offchain_db, backend, _marker: Default::default() })
+		Ok(Self {
+			client,
+			offchain_db,
+			backend,
+			executor,
+			subscribers: Default::default(),
+			_marker: Default::default(),
+		})
 	}
 }
 
@@ -253,7 +651,8 @@ where
 		+ ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
 		+ ProofProvider<Block>
-		+ BlockBackend<Block>,
+		+ BlockBackend<Block>
+		+ BlockchainEvents<Block>,
 	C::Api: IsmpRuntimeApi<Block, Block::Hash>,
 	Block::Hash: Into<H256>,
 	u64: From<<Block::Header as Header>::Number>,
@@ -290,6 +689,17 @@ where
 		Ok(Proof { proof: proof.encode(), height })
 	}
 
+	fn query_mmr_proof_by_commitments(&self, commitments: Vec<H256>) -> RpcResult<Proof> {
+		let mut api = self.client.runtime_api();
+		api.register_extension(OffchainDbExt::new(self.offchain_db.clone()));
+		let info = self.client.info();
+		let (_, proof): (Vec<Leaf>, pallet_ismp::mmr::Proof<Block::Hash>) = api
+			.generate_proof(info.finalized_hash, ProofKeys::Commitments(commitments))
+			.map_err(|_| runtime_error_into_rpc_error("Error calling runtime api"))?
+			.map_err(|_| runtime_error_into_rpc_error("Error generating mmr proof"))?;
+		Ok(Proof { proof: proof.encode(), height: u64::from(info.finalized_number) as u32 })
+	}
+
 	fn query_state_proof(&self, height: u32, keys: Vec<Vec<u8>>) -> RpcResult<Proof> {
 		let at = self.client.block_hash(height.into()).ok().flatten().ok_or_else(|| {
 			runtime_error_into_rpc_error("Could not find valid blockhash for provided height")
@@ -386,6 +796,33 @@ where
 		})
 	}
 
+	fn query_message_status(
+		&self,
+		commitment: H256,
+		height: Option<u32>,
+	) -> RpcResult<MessageStatus> {
+		let api = self.client.runtime_api();
+		let at = height
+			.and_then(|height| self.client.block_hash(height.into()).ok().flatten())
+			.unwrap_or(self.client.info().best_hash);
+		let version = api
+			.api_version::<dyn IsmpRuntimeApi<Block, Block::Hash>>(at)
+			.ok()
+			.flatten()
+			.ok_or_else(|| {
+				runtime_error_into_rpc_error("IsmpRuntimeApi not found in the runtime")
+			})?;
+		// Message statuses were added in version 2 of the runtime api
+		if version < 2 {
+			Err(runtime_error_into_rpc_error(
+				"Message status is not supported by the runtime at this block",
+			))?
+		}
+		api.message_status(at, commitment)
+			.map(Into::into)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching message status"))
+	}
+
 	fn query_events(
 		&self,
 		from: BlockNumberOrHash<Block::Hash>,
@@ -480,60 +917,11 @@ where
 			.ok_or_else(|| runtime_error_into_rpc_error("Invalid block number or hash provided"))?;
 
 		while header.number() >= from_block.number() {
-			let mut api = self.client.runtime_api();
-			api.register_extension(OffchainDbExt::new(self.offchain_db.clone()));
-			let at = header.hash();
-
-			let block_events = api.block_events_with_metadata(at).map_err(|e| {
-				runtime_error_into_rpc_error(format!("failed to read block events {:?}", e))
-			})?;
-
-			let mut temp = vec![];
-
-			for (event, index) in block_events {
-				let extrinsic_hash = if let Some(index) = index {
-					let extrinsic = self
-						.client
-						.block_body(at)
-						.map_err(|err| {
-							runtime_error_into_rpc_error(format!(
-								"Error fetching extrinsic for block {at:?}: {err:?}"
-							))
-						})?
-						.ok_or_else(|| {
-							runtime_error_into_rpc_error(format!(
-								"No extrinsics found for block {at:?}"
-							))
-						})?
-						// using swap remove should be fine unless the node is in an inconsistent
-						// state
-						.swap_remove(index as usize);
-					let ext_bytes = serde_json::to_string(&extrinsic).map_err(|err| {
-						runtime_error_into_rpc_error(format!(
-							"Failed to serialize extrinsic: {err:?}"
-						))
-					})?;
-					let len = ext_bytes.as_bytes().len() - 1;
-					let extrinsic =
-						hex::decode(ext_bytes.as_bytes()[3..len].to_vec()).map_err(|err| {
-							runtime_error_into_rpc_error(format!(
-								"Failed to decode extrinsic: {err:?}"
-							))
-						})?;
-					<Block::Header as Header>::Hashing::hash(extrinsic.as_slice())
-				} else {
-					Default::default()
-				};
-
-				temp.push(EventWithMetadata {
-					meta: EventMetadata {
-						block_hash: at.into(),
-						transaction_hash: extrinsic_hash.into(),
-						block_number: u64::from(*header.number()),
-					},
-					event,
-				});
-			}
+			let temp = block_events_with_metadata::<Block, _, _>(
+				&*self.client,
+				&self.offchain_db,
+				&header,
+			)?;
 
 			// Display is truncated for H256
 			events.insert(format!("{:?}", header.hash()), temp);
@@ -547,4 +935,60 @@ where
 		}
 		Ok(events)
 	}
+
+	fn subscribe_events(
+		&self,
+		mut sink: SubscriptionSink,
+		filter: Option<EventFilter>,
+	) -> SubscriptionResult {
+		let filter = filter.unwrap_or_default();
+		let (sender, receiver) = mpsc::unbounded();
+		{
+			let mut subscribers = self.subscribers.lock().unwrap_or_else(PoisonError::into_inner);
+			subscribers.senders.push(sender);
+			if !subscribers.started {
+				subscribers.started = true;
+				let fut = fan_out_finalized_events::<Block, _, _>(
+					self.client.clone(),
+					self.offchain_db.clone(),
+					self.subscribers.clone(),
+				);
+				self.executor.spawn("ismp-rpc-events", Some("rpc"), fut.boxed());
+			}
+		}
+
+		let stream = receiver
+			.flat_map(move |events: FinalizedEvents| {
+				let events = match &*events {
+					Ok(events) => events
+						.iter()
+						.filter(|event| filter.matches(&event.event))
+						.cloned()
+						.map(Ok)
+						.collect::<Vec<_>>(),
+					Err(err) => vec![Err(runtime_error_into_rpc_error(err))],
+				};
+
+				futures::stream::iter(events)
+			})
+			.boxed();
+
+		let fut = async move {
+			sink.pipe_from_try_stream(stream).await;
+		};
+
+		self.executor.spawn("ismp-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+
+	fn query_indexed_events(
+		&self,
+		key: IndexKey,
+		offset: u64,
+		limit: u32,
+	) -> RpcResult<IndexedEvents> {
+		index::query(self.offchain_db.clone(), &key, offset, limit).ok_or_else(|| {
+			runtime_error_into_rpc_error("The ISMP event index is not enabled on this node")
+		})
+	}
 }
@@ -0,0 +1,303 @@
+// Copyright (c) 2024 Polytope Labs.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use crate::{
+	index::{self, IndexKey, IndexState, MAX_PAGE_SIZE},
+	EventFilter, EventKind, EventMetadata, EventWithMetadata,
+};
+use ismp::{
+	consensus::StateMachineId,
+	events::{Event, RequestResponseHandled, StateMachineUpdated, TimeoutHandled},
+	host::StateMachine,
+	messaging::{hash_request, Keccak256},
+	router::{PostRequest, PostResponse, Request},
+};
+use sp_core::{
+	offchain::storage::{InMemOffchainStorage, OffchainDb},
+	Bytes, H256,
+};
+
+struct Hasher;
+
+impl Keccak256 for Hasher {
+	fn keccak256(bytes: &[u8]) -> H256 {
+		sp_core::keccak_256(bytes).into()
+	}
+}
+
+fn post(nonce: u64, from: &[u8], to: &[u8]) -> PostRequest {
+	PostRequest {
+		source: StateMachine::Kusama(2000),
+		dest: StateMachine::Evm(1),
+		nonce,
+		from: from.to_vec(),
+		to: to.to_vec(),
+		timeout_timestamp: 0,
+		body: vec![],
+	}
+}
+
+fn with_metadata(event: Event, block_number: u64) -> EventWithMetadata {
+	EventWithMetadata { meta: EventMetadata { block_number, ..Default::default() }, event }
+}
+
+fn nonces(events: &[EventWithMetadata]) -> Vec<u64> {
+	events
+		.iter()
+		.map(|event| match &event.event {
+			Event::PostRequest(post) => post.nonce,
+			_ => panic!("Unexpected event"),
+		})
+		.collect()
+}
+
+#[test]
+fn should_index_post_request_under_all_keys() {
+	let request = post(0, b"sender", b"receiver");
+	let commitment = hash_request::<Hasher>(&Request::Post(request.clone()));
+
+	assert_eq!(
+		IndexKey::for_event(&Event::PostRequest(request)),
+		vec![
+			IndexKey::Commitment(commitment),
+			IndexKey::Source(StateMachine::Kusama(2000)),
+			IndexKey::Dest(StateMachine::Evm(1)),
+			IndexKey::Module(b"sender".to_vec().into()),
+			IndexKey::Module(b"receiver".to_vec().into()),
+		]
+	);
+}
+
+#[test]
+fn should_index_module_once_for_requests_within_a_module() {
+	let keys = IndexKey::for_event(&Event::PostRequest(post(0, b"module", b"module")));
+
+	assert_eq!(
+		keys.iter().filter(|key| matches!(key, IndexKey::Module(_))).collect::<Vec<_>>(),
+		vec![&IndexKey::Module(b"module".to_vec().into())]
+	);
+}
+
+#[test]
+fn should_index_handled_events_by_commitment_only() {
+	let commitment = H256::random();
+	let event =
+		Event::PostRequestHandled(RequestResponseHandled { commitment, relayer: vec![1; 32] });
+
+	assert_eq!(IndexKey::for_event(&event), vec![IndexKey::Commitment(commitment)]);
+}
+
+#[test]
+fn should_paginate_indexed_events() {
+	let mut db = OffchainDb::new(InMemOffchainStorage::default());
+	let key = IndexKey::Source(StateMachine::Kusama(2000));
+	assert!(index::query(db.clone(), &key, 0, 10).is_none());
+
+	let mut state = IndexState::default();
+	for block_number in 1..=3 {
+		let events = (0..2)
+			.map(|i| {
+				let nonce = (block_number - 1) * 2 + i;
+				with_metadata(Event::PostRequest(post(nonce, b"sender", b"receiver")), block_number)
+			})
+			.collect();
+		state = index::index_events(&mut db, &state, block_number, H256::random(), events);
+	}
+	assert_eq!(state.block_number, 3);
+	assert_eq!(state.events, 6);
+
+	let page = index::query(db.clone(), &key, 0, 4).unwrap();
+	assert_eq!(page.total, 6);
+	assert_eq!(nonces(&page.events), vec![0, 1, 2, 3]);
+
+	let page = index::query(db.clone(), &key, 4, 4).unwrap();
+	assert_eq!(page.total, 6);
+	assert_eq!(nonces(&page.events), vec![4, 5]);
+
+	let page = index::query(db.clone(), &key, 6, 4).unwrap();
+	assert_eq!(page.total, 6);
+	assert!(page.events.is_empty());
+
+	// each commitment only has its own request
+	let commitment = hash_request::<Hasher>(&Request::Post(post(3, b"sender", b"receiver")));
+	let page = index::query(db.clone(), &IndexKey::Commitment(commitment), 0, 10).unwrap();
+	assert_eq!(page.total, 1);
+	assert_eq!(nonces(&page.events), vec![3]);
+
+	let page = index::query(db, &IndexKey::Dest(StateMachine::Kusama(2000)), 0, 10).unwrap();
+	assert_eq!(page.total, 0);
+}
+
+#[test]
+fn should_cap_page_size() {
+	let mut db = OffchainDb::new(InMemOffchainStorage::default());
+	let events = (0..MAX_PAGE_SIZE as u64 + 1)
+		.map(|nonce| with_metadata(Event::PostRequest(post(nonce, b"sender", b"receiver")), 1))
+		.collect();
+	index::index_events(&mut db, &IndexState::default(), 1, H256::random(), events);
+
+	let page =
+		index::query(db, &IndexKey::Source(StateMachine::Kusama(2000)), 0, u32::MAX).unwrap();
+	assert_eq!(page.total, MAX_PAGE_SIZE as u64 + 1);
+	assert_eq!(page.events.len(), MAX_PAGE_SIZE as usize);
+}
+
+#[test]
+fn should_complete_interrupted_writes_from_journal() {
+	let mut db = OffchainDb::new(InMemOffchainStorage::default());
+	let key = IndexKey::Source(StateMachine::Kusama(2000));
+	let events = vec![with_metadata(Event::PostRequest(post(0, b"sender", b"receiver")), 1)];
+
+	// the node stopped after journaling the block but before applying any of its writes
+	let (writes, state) =
+		index::block_writes(&mut db, &IndexState::default(), 1, H256::random(), events);
+	index::write_journal(&mut db, &writes);
+	assert!(index::query(db.clone(), &key, 0, 10).is_none());
+
+	index::apply_journal(&mut db);
+	let page = index::query(db.clone(), &key, 0, 10).unwrap();
+	assert_eq!(page.total, 1);
+	assert_eq!(nonces(&page.events), vec![0]);
+	assert_eq!(state.events, 1);
+
+	// the journal is cleared once applied
+	index::apply_journal(&mut db);
+	assert_eq!(index::query(db, &key, 0, 10).unwrap().total, 1);
+}
+
+#[test]
+fn should_revert_blocks_that_are_no_longer_canonical() {
+	let mut db = OffchainDb::new(InMemOffchainStorage::default());
+	let key = IndexKey::Module(b"sender".to_vec().into());
+	let mut state = index::start_index(&mut db, 0, H256::random());
+	for block_number in 1..=3 {
+		let events = vec![with_metadata(
+			Event::PostRequest(post(block_number, b"sender", b"receiver")),
+			block_number,
+		)];
+		state = index::index_events(&mut db, &state, block_number, H256::random(), events);
+	}
+	assert_eq!(index::query(db.clone(), &key, 0, 10).unwrap().total, 3);
+
+	// blocks 2 and 3 were replaced
+	let ancestor = index::indexed_block(&mut db, 1).unwrap();
+	assert_eq!(ancestor.events, 1);
+	state = index::revert_to(&mut db, &state, ancestor);
+	assert_eq!(state, ancestor);
+	let page = index::query(db.clone(), &key, 0, 10).unwrap();
+	assert_eq!(page.total, 1);
+	assert_eq!(nonces(&page.events), vec![1]);
+	let commitment = hash_request::<Hasher>(&Request::Post(post(3, b"sender", b"receiver")));
+	assert_eq!(
+		index::query(db.clone(), &IndexKey::Commitment(commitment), 0, 10)
+			.unwrap()
+			.total,
+		0
+	);
+
+	// the canonical blocks are indexed in their place
+	let events = vec![with_metadata(Event::PostRequest(post(4, b"sender", b"other")), 2)];
+	state = index::index_events(&mut db, &state, 2, H256::random(), events);
+	assert_eq!(state.events, 2);
+	let page = index::query(db.clone(), &key, 0, 10).unwrap();
+	assert_eq!(nonces(&page.events), vec![1, 4]);
+	assert_eq!(
+		index::query(db, &IndexKey::Module(b"receiver".to_vec().into()), 0, 10)
+			.unwrap()
+			.total,
+		1
+	);
+}
+
+#[test]
+fn should_filter_events_by_kind() {
+	let request = Event::PostRequest(post(0, b"from", b"to"));
+	let handled = Event::PostRequestHandled(RequestResponseHandled {
+		commitment: H256::random(),
+		relayer: vec![],
+	});
+
+	let filter = EventFilter::default();
+	assert!(filter.matches(&request));
+	assert!(filter.matches(&handled));
+
+	let filter = EventFilter { kinds: Some(vec![EventKind::PostRequest]), ..Default::default() };
+	assert!(filter.matches(&request));
+	assert!(!filter.matches(&handled));
+}
+
+#[test]
+fn should_filter_messages_by_source_dest_and_module() {
+	let request = Event::PostRequest(post(0, b"from", b"to"));
+	// responses travel back to the source of the request
+	let response = Event::PostResponse(PostResponse {
+		post: post(0, b"from", b"to"),
+		response: vec![],
+		timeout_timestamp: 0,
+	});
+	let timeout = Event::PostRequestTimeoutHandled(TimeoutHandled {
+		commitment: H256::random(),
+		source: StateMachine::Kusama(2000),
+		dest: StateMachine::Evm(1),
+	});
+
+	let filter = EventFilter {
+		source: Some(StateMachine::Kusama(2000)),
+		dest: Some(StateMachine::Evm(1)),
+		..Default::default()
+	};
+	assert!(filter.matches(&request));
+	assert!(!filter.matches(&response));
+	assert!(filter.matches(&timeout));
+
+	let filter = EventFilter { source: Some(StateMachine::Evm(1)), ..Default::default() };
+	assert!(!filter.matches(&request));
+	assert!(filter.matches(&response));
+
+	// modules match either end of the message
+	for module in [&b"from"[..], &b"to"[..]] {
+		let filter = EventFilter { module: Some(Bytes(module.to_vec())), ..Default::default() };
+		assert!(filter.matches(&request));
+		assert!(filter.matches(&response));
+		assert!(!filter.matches(&timeout));
+	}
+	let filter = EventFilter { module: Some(Bytes(b"other".to_vec())), ..Default::default() };
+	assert!(!filter.matches(&request));
+}
+
+#[test]
+fn should_filter_state_machine_updates_by_source_only() {
+	let update = Event::StateMachineUpdated(StateMachineUpdated {
+		state_machine_id: StateMachineId {
+			state_id: StateMachine::Kusama(2000),
+			consensus_state_id: *b"PARA",
+		},
+		latest_height: 1,
+	});
+	let handled = Event::GetRequestHandled(RequestResponseHandled {
+		commitment: H256::random(),
+		relayer: vec![],
+	});
+
+	let filter = EventFilter { source: Some(StateMachine::Kusama(2000)), ..Default::default() };
+	assert!(filter.matches(&update));
+	// events without the filtered field never match it
+	assert!(!filter.matches(&handled));
+
+	let filter = EventFilter { dest: Some(StateMachine::Kusama(2000)), ..Default::default() };
+	assert!(!filter.matches(&update));
+}
@@ -26,12 +26,16 @@ use ismp::{
 	host::StateMachine,
 	router::{Request, Response},
 };
-use pallet_ismp::mmr::{Leaf, Proof, ProofKeys};
+use pallet_ismp::{
+	mmr::{Leaf, Proof, ProofKeys},
+	MessageStatus,
+};
 use primitive_types::H256;
 use sp_mmr_primitives::Error;
 
 sp_api::decl_runtime_apis! {
 	/// Required runtime APIs needed for client subsystems like the RPC
+	#[api_version(2)]
 	pub trait IsmpRuntimeApi<Hash: codec::Codec> {
 		/// Should return the host's state machine identifier
 		fn host_state_machine() -> StateMachine;
@@ -64,5 +68,10 @@ sp_api::decl_runtime_apis! {
 
 		/// Fetch the responses for the given commitments.
 		fn responses(response_commitments: Vec<H256>) -> Vec<Response>;
+
+		/// Fetch the commitments, receipts and fee metadata stored for the given request or
+		/// response commitment.
+		#[api_version(2)]
+		fn message_status(commitment: H256) -> MessageStatus;
 	}
 }
@@ -20,7 +20,8 @@ use alloc::collections::BTreeMap;
 use cumulus_pallet_parachain_system::ParachainSetCode;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, Get},
+	traits::{ConstU32, ConstU64, Contains, Get},
+	weights::Weight,
 	PalletId,
 };
 use frame_system::{EnsureRoot, EventRecord};
@@ -48,6 +49,7 @@ use sp_runtime::{
 	traits::{IdentityLookup, Keccak256},
 	BuildStorage,
 };
+use staging_xcm::v3::{Junction, MultiLocation};
 use substrate_state_machine::SubstrateStateMachine;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -73,6 +75,7 @@ frame_support::construct_runtime!(
 		Assets: pallet_assets,
 		Gateway: pallet_asset_gateway,
 		TokenGovernor: pallet_token_governor,
+		TokenGateway: pallet_token_gateway,
 		Sudo: pallet_sudo,
 		IsmpSyncCommittee: ismp_sync_committee::pallet,
 	}
@@ -131,9 +134,19 @@ impl pallet_balances::Config for Test {
 	type MaxFreezes = ();
 }
 
+parameter_types! {
+	pub const FishermanBond: Balance = 10 * UNIT;
+	pub const VetoReward: Balance = UNIT;
+	pub FishermenRewardAccount: AccountId32 = AccountId32::new([7u8; 32]);
+}
+
 impl pallet_fishermen::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type IsmpHost = Ismp;
+	type MinimumBond = FishermanBond;
+	type DisputePeriod = ConstU64<10>;
+	type VetoReward = VetoReward;
+	type ProtocolAccount = FishermenRewardAccount;
 }
 
 impl pallet_sudo::Config for Test {
@@ -219,6 +232,21 @@ impl pallet_token_governor::Config for Test {
 	type TreasuryAccount = TreasuryAccount;
 }
 
+pub struct AssetIdFactory;
+
+impl pallet_token_gateway::AssetIdFactory<MultiLocation> for AssetIdFactory {
+	fn asset_id(symbol: &[u8]) -> MultiLocation {
+		Junction::GeneralKey { length: 32, data: sp_io::hashing::keccak_256(symbol) }.into()
+	}
+}
+
+impl pallet_token_gateway::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Dispatcher = Ismp;
+	type Assets = Assets;
+	type AssetIdFactory = AssetIdFactory;
+}
+
 impl pallet_mmr::Config for Test {
 	const INDEXING_PREFIX: &'static [u8] = b"ISMP";
 	type Hashing = Keccak256;
@@ -234,10 +262,37 @@ impl pallet_ismp_relayer::Config for Test {
 impl pallet_ismp_host_executive::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type IsmpHost = Ismp;
+	type HostParamsUpdateTimeout = ConstU64<1_000>;
+}
+
+pub struct CompressedCalls;
+
+impl Contains<RuntimeCall> for CompressedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Ismp(pallet_ismp::Call::handle_unsigned { .. }) |
+				RuntimeCall::Relayer(pallet_ismp_relayer::Call::accumulate_fees { .. }) |
+				RuntimeCall::TokenGovernor(
+					pallet_token_governor::Call::create_erc6160_asset_unsigned { .. }
+				)
+		)
+	}
+}
+
+parameter_types! {
+	pub MaxCompressedCallWeight: Weight = Weight::from_parts(1_000_000_000_000, 5 * 1024 * 1024);
 }
 
 impl pallet_call_decompressor::Config for Test {
+	type RuntimeCall = RuntimeCall;
 	type MaxCallSize = ConstU32<2>;
+	type AllowedCalls = CompressedCalls;
+	type UnsignedValidator = Test;
+	type AdminOrigin = EnsureRoot<AccountId32>;
+	type MaxDictionarySize = ConstU32<{ 128 * 1024 }>;
+	type MaxCallWeight = MaxCompressedCallWeight;
+	type WeightInfo = ();
 }
 
 #[derive(Default)]
@@ -313,10 +368,23 @@ impl ConsensusClient for MockConsensusClient {
 		&self,
 		_host: &dyn IsmpHost,
 		_cs_id: ismp::consensus::ConsensusStateId,
-		_trusted_consensus_state: Vec<u8>,
-		_proof: Vec<u8>,
+		trusted_consensus_state: Vec<u8>,
+		proof: Vec<u8>,
 	) -> Result<(Vec<u8>, VerifiedCommitments), IsmpError> {
-		Ok(Default::default())
+		if proof.is_empty() {
+			return Ok(Default::default());
+		}
+
+		// Non-empty proofs are the trusted state they're valid against, followed by the new state
+		// and the commitments they finalize
+		let (trusted, new_state, commitments): (Vec<u8>, Vec<u8>, VerifiedCommitments) =
+			codec::Decode::decode(&mut &*proof)
+				.map_err(|_| IsmpError::Custom("Invalid mock proof".into()))?;
+		if trusted != trusted_consensus_state {
+			Err(IsmpError::Custom("Untrusted mock proof".into()))?
+		}
+
+		Ok((new_state, commitments))
 	}
 
 	fn verify_fraud_proof(
@@ -6,5 +6,7 @@ mod pallet_hyperbridge;
 mod pallet_ismp;
 mod pallet_ismp_host_executive;
 mod pallet_ismp_relayer;
+mod pallet_token_gateway;
+mod pallet_token_governor;
 
 mod xcm_integration_test;
@@ -2,19 +2,23 @@
 
 use crate::{
 	relay_chain::{self, RuntimeOrigin},
-	runtime::Test,
+	runtime::{new_test_ext, Test},
 	xcm::{MockNet, ParaA, Relay},
 };
 use alloy_sol_types::SolValue;
-use frame_support::{assert_ok, traits::fungibles::Inspect};
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
 use ismp::{
 	host::StateMachine,
 	module::IsmpModule,
 	router::{PostRequest, Request, Timeout},
 };
-use pallet_asset_gateway::{convert_to_erc20, Body, Module};
-use sp_core::{ByteArray, H160};
+use pallet_asset_gateway::{
+	convert_to_erc20, xcm_utilities::MultilocationToMultiAccount, Body, Module,
+};
+use pallet_token_governor::AssetMetadata;
+use sp_core::{crypto::AccountId32, ByteArray, H160, H256};
 use staging_xcm::v3::{Junction, Junctions, MultiLocation, NetworkId, WeightLimit};
+use staging_xcm_executor::traits::ConvertLocation;
 use xcm_simulator::TestExt;
 use xcm_simulator_example::ALICE;
 
@@ -142,7 +146,7 @@ fn should_process_on_accept_module_callback_correctly() {
 			amount: {
 				let mut bytes = [0u8; 32];
 				// Module callback will convert to ten decimals
-				convert_to_erc20(amount).to_big_endian(&mut bytes);
+				convert_to_erc20(amount, 10).unwrap().to_big_endian(&mut bytes);
 				alloy_primitives::U256::from_be_bytes(bytes)
 			},
 			asset_id: pallet_asset_gateway::Pallet::<Test>::dot_asset_id().0.into(),
@@ -252,7 +256,7 @@ fn should_process_on_timeout_module_callback_correctly() {
 			amount: {
 				let mut bytes = [0u8; 32];
 				// Module callback will convert to 10 decimals
-				convert_to_erc20(amount).to_big_endian(&mut bytes);
+				convert_to_erc20(amount, 10).unwrap().to_big_endian(&mut bytes);
 				alloy_primitives::U256::from_be_bytes(bytes)
 			},
 			asset_id: pallet_asset_gateway::Pallet::<Test>::dot_asset_id().0.into(),
@@ -297,3 +301,122 @@ fn should_process_on_timeout_module_callback_correctly() {
 		assert_eq!(current_balance, alice_balance + transferred);
 	})
 }
+
+#[test]
+fn should_only_register_assets_known_to_the_token_governor() {
+	new_test_ext().execute_with(|| {
+		let asset_id: H256 = sp_io::hashing::keccak_256(b"USDT").into();
+		let location = MultiLocation::new(
+			1,
+			Junctions::X3(
+				Junction::Parachain(1000),
+				Junction::PalletInstance(50),
+				Junction::GeneralIndex(1984),
+			),
+		);
+
+		assert_noop!(
+			pallet_asset_gateway::Pallet::<Test>::register_asset(
+				frame_system::RawOrigin::Root.into(),
+				asset_id,
+				location.clone(),
+				6,
+			),
+			pallet_asset_gateway::Error::<Test>::UnknownAsset
+		);
+
+		pallet_token_governor::AssetMetadatas::<Test>::insert(asset_id, AssetMetadata::default());
+		assert_noop!(
+			pallet_asset_gateway::Pallet::<Test>::register_asset(
+				frame_system::RawOrigin::Root.into(),
+				asset_id,
+				location.clone(),
+				u8::MAX,
+			),
+			pallet_asset_gateway::Error::<Test>::InvalidDecimals
+		);
+		assert_ok!(pallet_asset_gateway::Pallet::<Test>::register_asset(
+			frame_system::RawOrigin::Root.into(),
+			asset_id,
+			location.clone(),
+			6,
+		));
+
+		assert_eq!(pallet_asset_gateway::Pallet::<Test>::asset_id(&location), Some(asset_id));
+		assert_eq!(
+			pallet_asset_gateway::Pallet::<Test>::asset_location(asset_id),
+			Some(location.clone())
+		);
+		assert_eq!(pallet_asset_gateway::Pallet::<Test>::asset_decimals(asset_id), Some(6));
+		assert_eq!(
+			pallet_asset_gateway::Pallet::<Test>::reserve_location(&location),
+			MultiLocation::new(1, Junctions::X1(Junction::Parachain(1000)))
+		);
+		// the relay chain asset is always supported
+		let dot_asset_id = pallet_asset_gateway::Pallet::<Test>::dot_asset_id();
+		assert_eq!(
+			pallet_asset_gateway::Pallet::<Test>::asset_id(&MultiLocation::parent()),
+			Some(dot_asset_id)
+		);
+		assert_eq!(pallet_asset_gateway::Pallet::<Test>::asset_decimals(dot_asset_id), Some(10));
+
+		assert_ok!(pallet_asset_gateway::Pallet::<Test>::deregister_asset(
+			frame_system::RawOrigin::Root.into(),
+			asset_id,
+		));
+		assert_eq!(pallet_asset_gateway::Pallet::<Test>::asset_id(&location), None);
+		assert_eq!(pallet_asset_gateway::Pallet::<Test>::asset_decimals(asset_id), None);
+	})
+}
+
+#[test]
+fn should_reject_amounts_that_are_not_exact_erc6160_amounts() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			pallet_asset_gateway::Pallet::<Test>::convert_amount(100, 20).unwrap(),
+			alloy_primitives::U256::from(1u8)
+		);
+		assert_eq!(
+			pallet_asset_gateway::Pallet::<Test>::convert_amount(101, 20),
+			Err(pallet_asset_gateway::Error::<Test>::InexactAmount)
+		);
+	})
+}
+
+#[test]
+fn should_convert_locations_with_relayer_and_liquidity_fees() {
+	let account = |interior: Junctions| {
+		MultilocationToMultiAccount::<AccountId32>::convert_location(&MultiLocation::new(
+			0, interior,
+		))
+		.unwrap()
+	};
+	let id = Junction::AccountId32 { network: None, id: ALICE.into() };
+	let key = Junction::AccountKey20 {
+		network: Some(NetworkId::Ethereum { chain_id: 1 }),
+		key: [1u8; 20],
+	};
+
+	let multi_account = account(Junctions::X3(id, key, Junction::GeneralIndex(60 * 60)));
+	assert_eq!(multi_account.dest_state_machine, StateMachine::Evm(1));
+	assert_eq!(multi_account.evm_account, H160::from([1u8; 20]));
+	assert_eq!(multi_account.timeout, 60 * 60);
+	assert_eq!((multi_account.relayer_fee, multi_account.max_fee), (0, 0));
+
+	let multi_account = account(Junctions::X4(
+		id,
+		key,
+		Junction::GeneralIndex(60 * 60),
+		Junction::GeneralIndex(100),
+	));
+	assert_eq!((multi_account.relayer_fee, multi_account.max_fee), (100, 0));
+
+	let multi_account = account(Junctions::X5(
+		id,
+		key,
+		Junction::GeneralIndex(60 * 60),
+		Junction::GeneralIndex(100),
+		Junction::GeneralIndex(SEND_AMOUNT / 100),
+	));
+	assert_eq!((multi_account.relayer_fee, multi_account.max_fee), (100, SEND_AMOUNT / 100));
+}
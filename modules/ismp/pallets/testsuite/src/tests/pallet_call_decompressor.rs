@@ -17,22 +17,25 @@
 
 use crate::{
 	runtime::{
-		new_test_ext, Ismp, RuntimeCall, RuntimeOrigin, Test, Timestamp, MOCK_CONSENSUS_STATE_ID,
+		new_test_ext, Ismp, MaxCompressedCallWeight, RuntimeCall, RuntimeOrigin, Test, Timestamp,
+		MOCK_CONSENSUS_STATE_ID,
 	},
 	tests::pallet_ismp_relayer::{encode_accumulate_fees_call, read_file_string},
 };
 use codec::Encode;
+use frame_support::{dispatch::GetDispatchInfo, traits::Get};
 use ismp::{
 	consensus::{StateMachineHeight, StateMachineId},
 	host::{IsmpHost, StateMachine},
 	messaging::{Message, Proof, RequestMessage, ResponseMessage, TimeoutMessage},
 	router::{PostResponse, Request, RequestResponse},
 };
+use pallet_call_decompressor::codecs::Codec;
 use ruzstd::StreamingDecoder;
 use sp_core::{H256, H512};
 use sp_runtime::{DispatchError, ModuleError};
 use std::{
-	io::Read,
+	io::{Read, Write},
 	time::{Duration, Instant},
 };
 use zstd_safe::WriteBuf;
@@ -146,8 +149,8 @@ fn should_decompress_and_execute_pallet_ismp_get_response_calls_correctly() {
 			final_compressed_call.to_vec(),
 			call.len() as u32,
 		)
-		.err()
-		.unwrap();
+		.unwrap_err()
+		.error;
 
 		// Decoding the call was completed without errors
 		assert_eq!(
@@ -198,8 +201,8 @@ fn should_decompress_and_execute_pallet_ismp_get_time_out_calls_correctly() {
 			final_compressed_call.to_vec(),
 			call.len() as u32,
 		)
-		.err()
-		.unwrap();
+		.unwrap_err()
+		.error;
 
 		// Decoding the call was completed without errors
 		assert_eq!(
@@ -263,8 +266,8 @@ fn should_decompress_and_execute_pallet_ismp_post_request_calls_correctly() {
 			final_compressed_call.to_vec(),
 			call.len() as u32,
 		)
-		.err()
-		.unwrap();
+		.unwrap_err()
+		.error;
 
 		// Decoding the call was completed without errors
 		assert_eq!(
@@ -332,8 +335,8 @@ fn should_decompress_and_execute_pallet_ismp_post_response_calls_correctly() {
 			final_compressed_call.to_vec(),
 			call.len() as u32,
 		)
-		.err()
-		.unwrap();
+		.unwrap_err()
+		.error;
 
 		// Decoding the call was completed without errors
 		assert_eq!(
@@ -374,8 +377,8 @@ fn decompress_stack_exhaustion_poc() {
 			final_compressed_call.to_vec(),
 			1000000,
 		)
-		.err()
-		.unwrap();
+		.unwrap_err()
+		.error;
 
 		assert_eq!(
 			res,
@@ -387,3 +390,328 @@ fn decompress_stack_exhaustion_poc() {
 		);
 	});
 }
+
+/// Encoded `handle_unsigned` call for a batch of post requests
+fn encoded_post_request_call() -> Vec<u8> {
+	let host = Ismp::default();
+	let requests = (0..100)
+		.into_iter()
+		.map(|i| ismp::router::PostRequest {
+			source: host.host_state_machine(),
+			dest: StateMachine::Evm(1),
+			nonce: i,
+			from: H256::random().0.to_vec(),
+			to: H256::random().0.to_vec(),
+			timeout_timestamp: Duration::from_millis(Timestamp::now()).as_secs() + 2_000_000_000,
+			body: H512::random().0.to_vec(),
+		})
+		.collect::<Vec<_>>();
+
+	let msg = RequestMessage {
+		requests,
+		proof: Proof {
+			height: StateMachineHeight {
+				id: StateMachineId {
+					state_id: StateMachine::Evm(1),
+					consensus_state_id: MOCK_CONSENSUS_STATE_ID,
+				},
+				height: 3,
+			},
+			proof: H512::random().0.to_vec(),
+		},
+		signer: H512::random().0.to_vec(),
+	};
+
+	RuntimeCall::Ismp(pallet_ismp::Call::handle_unsigned { messages: vec![Message::Request(msg)] })
+		.encode()
+}
+
+fn call_decompressor_error(error: u8, message: &'static str) -> DispatchError {
+	DispatchError::Module(ModuleError {
+		index: 11,
+		error: [error, 0, 0, 0],
+		message: Some(message),
+	})
+}
+
+#[test]
+fn should_decompress_and_execute_lz4_calls_correctly() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let call = encoded_post_request_call();
+		let mut compressed = vec![Codec::Lz4 as u8];
+		compressed.extend(lz4_flex::block::compress(&call));
+
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed,
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+
+		// Decoding the call was completed without errors
+		assert_eq!(res, call_decompressor_error(1, "ErrorExecutingCall"));
+	})
+}
+
+#[test]
+fn should_decompress_and_execute_brotli_calls_correctly() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let call = encoded_post_request_call();
+		let mut compressed = vec![Codec::Brotli as u8];
+		{
+			let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+			writer.write_all(&call).unwrap();
+		}
+
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed,
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+
+		// Decoding the call was completed without errors
+		assert_eq!(res, call_decompressor_error(1, "ErrorExecutingCall"));
+	})
+}
+
+#[test]
+fn should_charge_the_call_weight_bound_and_refund_unused_weight() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let call = encoded_post_request_call();
+		let mut compressed = vec![Codec::Lz4 as u8];
+		compressed.extend(lz4_flex::block::compress(&call));
+
+		// the call is weighed without decompressing it
+		let decompression_weight = pallet_call_decompressor::Pallet::<Test>::decompression_weight(
+			&compressed,
+			call.len() as u32,
+		);
+		let weight = pallet_call_decompressor::Pallet::<Test>::decompress_call_weight(
+			&compressed,
+			call.len() as u32,
+		);
+		assert_eq!(weight, decompression_weight + MaxCompressedCallWeight::get());
+
+		// the decompression scales with the size of the compressed and decompressed call
+		assert!(
+			pallet_call_decompressor::Pallet::<Test>::decompression_weight(
+				&compressed,
+				2 * call.len() as u32
+			)
+			.ref_time() > decompression_weight.ref_time()
+		);
+		assert!(
+			pallet_call_decompressor::Pallet::<Test>::decompression_weight(
+				&[compressed.clone(), vec![0u8; 1_000]].concat(),
+				call.len() as u32
+			)
+			.ref_time() > decompression_weight.ref_time()
+		);
+
+		// the unused weight of the dispatched call is refunded
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed.clone(),
+			call.len() as u32,
+		)
+		.unwrap_err();
+		assert_eq!(res.error, call_decompressor_error(1, "ErrorExecutingCall"));
+		let actual_weight = res.post_info.actual_weight.unwrap();
+		assert!(actual_weight.all_gte(decompression_weight));
+		assert!(actual_weight.all_lt(weight));
+
+		// calls which fail to decompress are only charged for the decompression
+		let truncated = &compressed[..compressed.len() / 2];
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			truncated.to_vec(),
+			call.len() as u32,
+		)
+		.unwrap_err();
+		assert_eq!(res.error, call_decompressor_error(2, "DecompressionFailed"));
+		assert_eq!(
+			res.post_info.actual_weight,
+			Some(pallet_call_decompressor::Pallet::<Test>::decompression_weight(
+				truncated,
+				call.len() as u32
+			))
+		);
+	})
+}
+
+#[test]
+fn should_reject_calls_that_are_not_allowed() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let call =
+			RuntimeCall::System(frame_system::Call::remark { remark: vec![1u8; 32] }).encode();
+		let mut buffer = vec![0u8; 1_000];
+		let written = zstd_safe::compress(&mut buffer[..], &call, 3).unwrap();
+		let mut compressed = vec![Codec::Zstd as u8];
+		compressed.extend_from_slice(&buffer[..written]);
+
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed,
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+
+		assert_eq!(res, call_decompressor_error(0, "CallNotSupported"));
+	})
+}
+
+#[test]
+fn should_reject_unknown_codecs_and_dictionaries() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let call = encoded_post_request_call();
+
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			vec![u8::MAX, 0, 0, 0, 0],
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+		assert_eq!(res, call_decompressor_error(5, "UnknownCodec"));
+
+		let mut compressed = vec![Codec::ZstdWithDictionary as u8];
+		compressed.extend(42u32.to_le_bytes());
+		compressed.extend(lz4_flex::block::compress(&call));
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed,
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+		assert_eq!(res, call_decompressor_error(6, "UnknownDictionary"));
+
+		let res = pallet_call_decompressor::Pallet::<Test>::add_dictionary(
+			RuntimeOrigin::root(),
+			vec![0u8; 64],
+		)
+		.err()
+		.unwrap();
+		assert_eq!(res, call_decompressor_error(7, "InvalidDictionary"));
+
+		let res =
+			pallet_call_decompressor::Pallet::<Test>::remove_dictionary(RuntimeOrigin::root(), 42)
+				.err()
+				.unwrap();
+		assert_eq!(res, call_decompressor_error(6, "UnknownDictionary"));
+	})
+}
+
+/// Encoded `handle_unsigned` call for a single post request
+fn encoded_single_post_request_call(nonce: u64) -> Vec<u8> {
+	let host = Ismp::default();
+	let request = ismp::router::PostRequest {
+		source: host.host_state_machine(),
+		dest: StateMachine::Evm(1),
+		nonce,
+		from: vec![1u8; 32],
+		to: vec![2u8; 32],
+		timeout_timestamp: nonce,
+		body: nonce.encode(),
+	};
+	let msg = RequestMessage {
+		requests: vec![request],
+		proof: Proof {
+			height: StateMachineHeight {
+				id: StateMachineId {
+					state_id: StateMachine::Evm(1),
+					consensus_state_id: MOCK_CONSENSUS_STATE_ID,
+				},
+				height: 3,
+			},
+			proof: vec![3u8; 64],
+		},
+		signer: vec![4u8; 32],
+	};
+
+	RuntimeCall::Ismp(pallet_ismp::Call::handle_unsigned { messages: vec![Message::Request(msg)] })
+		.encode()
+}
+
+#[test]
+fn should_decompress_and_execute_calls_with_dictionaries() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		// train a dictionary on samples of the calls it will compress
+		let samples = (0..2_000).map(encoded_single_post_request_call).collect::<Vec<_>>();
+		let mut dictionary = vec![0u8; 4096];
+		let written = zstd_safe::train_from_buffer(
+			&mut dictionary[..],
+			&samples.concat(),
+			&samples.iter().map(Vec::len).collect::<Vec<_>>(),
+		)
+		.unwrap();
+		dictionary.truncate(written);
+		let id = pallet_call_decompressor::codecs::zstd_dictionary_id(&dictionary).unwrap();
+
+		pallet_call_decompressor::Pallet::<Test>::add_dictionary(
+			RuntimeOrigin::root(),
+			dictionary.clone(),
+		)
+		.unwrap();
+		assert_eq!(
+			pallet_call_decompressor::Dictionaries::<Test>::get(id).unwrap().to_vec(),
+			dictionary
+		);
+
+		let call = encoded_single_post_request_call(2_001);
+		let mut buffer = vec![0u8; 1_000];
+		let written = zstd_safe::CCtx::create()
+			.compress_using_dict(&mut buffer[..], &call, &dictionary, 3)
+			.unwrap();
+		let mut compressed = vec![Codec::ZstdWithDictionary as u8];
+		compressed.extend(id.to_le_bytes());
+		compressed.extend_from_slice(&buffer[..written]);
+
+		// the call is weighed with the dispatch info of the decompressed call
+		let decoded = <RuntimeCall as codec::Decode>::decode(&mut &call[..]).unwrap();
+		let weight = pallet_call_decompressor::Pallet::<Test>::decompress_call_weight(
+			&compressed,
+			call.len() as u32,
+		);
+		assert!(weight.all_gte(decoded.get_dispatch_info().weight));
+
+		let res = pallet_call_decompressor::Pallet::<Test>::decompress_call(
+			RuntimeOrigin::none(),
+			compressed,
+			call.len() as u32,
+		)
+		.unwrap_err()
+		.error;
+
+		// Decoding the call was completed without errors
+		assert_eq!(res, call_decompressor_error(1, "ErrorExecutingCall"));
+
+		pallet_call_decompressor::Pallet::<Test>::remove_dictionary(RuntimeOrigin::root(), id)
+			.unwrap();
+		assert!(pallet_call_decompressor::Dictionaries::<Test>::get(id).is_none());
+	})
+}
+
+#[test]
+fn should_reject_dictionaries_that_are_too_large() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let res = pallet_call_decompressor::Pallet::<Test>::add_dictionary(
+			RuntimeOrigin::root(),
+			vec![0u8; 128 * 1024 + 1],
+		)
+		.err()
+		.unwrap();
+		assert_eq!(res, call_decompressor_error(8, "DictionaryTooLarge"));
+	})
+}
@@ -15,35 +15,54 @@
 
 #![cfg(test)]
 
-use crate::runtime::{new_test_ext, Ismp, RuntimeOrigin, Test};
+use crate::runtime::{
+	new_test_ext, setup_mock_client, Balances, FishermenRewardAccount, Ismp, RuntimeOrigin, System,
+	Test, MOCK_CONSENSUS_STATE_ID, UNIT,
+};
+use codec::Encode;
+use frame_support::traits::fungible::{Inspect, Mutate};
 use ismp::{
-	consensus::{StateCommitment, StateMachineHeight, StateMachineId},
+	consensus::{StateCommitment, StateMachineHeight, StateMachineId, VerifiedCommitments},
 	error::Error,
 	host::{IsmpHost, StateMachine},
+	messaging::StateCommitmentHeight,
 };
+use pallet_fishermen::{Fisherman, VetoEvidence};
 use sp_core::{crypto::AccountId32, H256};
-use sp_runtime::{DispatchError, ModuleError};
+use sp_runtime::DispatchError;
+
+fn bonded_fisherman() -> AccountId32 {
+	let account: AccountId32 = H256::random().0.into();
+	Balances::mint_into(&account, 100 * UNIT).unwrap();
+	pallet_fishermen::Pallet::<Test>::bond(RuntimeOrigin::signed(account.clone()), 10 * UNIT)
+		.unwrap();
+
+	account
+}
 
 #[test]
 fn test_can_veto_state_commitments() {
 	new_test_ext().execute_with(|| {
 		let account: AccountId32 = H256::random().0.into();
+		Balances::mint_into(&account, 100 * UNIT).unwrap();
 
-		// add a new fisherman
-		pallet_fishermen::Pallet::<Test>::add(RuntimeOrigin::root(), account.clone()).unwrap();
-		assert_eq!(pallet_fishermen::Fishermen::<Test>::get(account.clone()), Some(()));
-
-		// sanity check, can't add it again
-		let result = pallet_fishermen::Pallet::<Test>::add(RuntimeOrigin::root(), account.clone());
+		// sanity check, the bond must cover the minimum
+		let result =
+			pallet_fishermen::Pallet::<Test>::bond(RuntimeOrigin::signed(account.clone()), UNIT);
 		assert_eq!(
 			result,
-			Err(DispatchError::Module(ModuleError {
-				index: 9,
-				error: [0, 0, 0, 0],
-				message: Some("AlreadyAdded"),
-			}))
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::InsufficientBond))
 		);
 
+		// join the fishermen set
+		pallet_fishermen::Pallet::<Test>::bond(RuntimeOrigin::signed(account.clone()), 10 * UNIT)
+			.unwrap();
+		assert_eq!(
+			pallet_fishermen::Fishermen::<Test>::get(account.clone()),
+			Some(Fisherman { bond: 10 * UNIT, open_vetoes: 0 })
+		);
+		assert_eq!(Balances::balance(&account), 90 * UNIT);
+
 		let host = Ismp::default();
 		let height = StateMachineHeight {
 			id: StateMachineId { state_id: StateMachine::Evm(97), consensus_state_id: *b"ETH0" },
@@ -66,11 +85,7 @@ fn test_can_veto_state_commitments() {
 		);
 		assert_eq!(
 			result,
-			Err(DispatchError::Module(ModuleError {
-				index: 9,
-				error: [2, 0, 0, 0],
-				message: Some("UnauthorizedAction"),
-			}))
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::UnauthorizedAction))
 		);
 
 		// actual veto
@@ -83,8 +98,244 @@ fn test_can_veto_state_commitments() {
 		// should have been deleted
 		let result = host.state_machine_commitment(height);
 		assert!(matches!(result, Err(Error::StateCommitmentNotFound { .. })));
+		assert!(pallet_fishermen::Vetoes::<Test>::contains_key(height, account.clone()));
+
+		// can't leave while the veto is in dispute
+		let result = pallet_fishermen::Pallet::<Test>::unbond(RuntimeOrigin::signed(account));
+		assert_eq!(result, Err(DispatchError::from(pallet_fishermen::Error::<Test>::OpenVetoes)));
+	})
+}
+
+#[test]
+fn should_reward_vetoes_backed_by_evidence() {
+	new_test_ext().execute_with(|| {
+		let host = Ismp::default();
+		let height = setup_mock_client::<_, Test>(&host);
+		Balances::mint_into(&FishermenRewardAccount::get(), 10 * UNIT).unwrap();
+		let account = bonded_fisherman();
+
+		pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			height,
+		)
+		.unwrap();
+
+		// the mock consensus client doesn't finalize any commitments
+		let result = pallet_fishermen::Pallet::<Test>::submit_evidence(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			height,
+			account.clone(),
+			VetoEvidence::ConflictingProof(vec![]),
+		);
+		assert_eq!(
+			result,
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::InvalidEvidence))
+		);
+
+		pallet_fishermen::Pallet::<Test>::submit_evidence(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			height,
+			account.clone(),
+			VetoEvidence::FraudProof { proof_1: vec![], proof_2: vec![] },
+		)
+		.unwrap();
 
-		pallet_fishermen::Pallet::<Test>::remove(RuntimeOrigin::root(), account.clone()).unwrap();
+		assert_eq!(Balances::balance(&account), 91 * UNIT);
+		assert_eq!(Balances::balance(&FishermenRewardAccount::get()), 9 * UNIT);
+		assert!(host.is_consensus_client_frozen(height.id.consensus_state_id).is_err());
+		assert!(!pallet_fishermen::Vetoes::<Test>::contains_key(height, account.clone()));
+
+		pallet_fishermen::Pallet::<Test>::unbond(RuntimeOrigin::signed(account.clone())).unwrap();
+		assert_eq!(Balances::balance(&account), 101 * UNIT);
 		assert_eq!(pallet_fishermen::Fishermen::<Test>::get(account), None);
 	})
 }
+
+#[test]
+fn should_slash_vetoes_without_evidence() {
+	new_test_ext().execute_with(|| {
+		let host = Ismp::default();
+		let height = setup_mock_client::<_, Test>(&host);
+		let commitment = host.state_machine_commitment(height).unwrap();
+		let account = bonded_fisherman();
+
+		pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			height,
+		)
+		.unwrap();
+
+		let result = pallet_fishermen::Pallet::<Test>::resolve_veto(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			height,
+			account.clone(),
+		);
+		assert_eq!(
+			result,
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::DisputePeriodNotElapsed))
+		);
+
+		System::set_block_number(System::block_number() + 11);
+		let result = pallet_fishermen::Pallet::<Test>::submit_evidence(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			height,
+			account.clone(),
+			VetoEvidence::FraudProof { proof_1: vec![], proof_2: vec![] },
+		);
+		assert_eq!(
+			result,
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::DisputePeriodElapsed))
+		);
+
+		pallet_fishermen::Pallet::<Test>::resolve_veto(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			height,
+			account.clone(),
+		)
+		.unwrap();
+
+		// the bond was slashed and the commitment restored
+		assert_eq!(
+			pallet_fishermen::Fishermen::<Test>::get(account.clone()),
+			Some(Fisherman { bond: 0, open_vetoes: 0 })
+		);
+		assert_eq!(Balances::balance(&FishermenRewardAccount::get()), 10 * UNIT);
+		assert_eq!(host.state_machine_commitment(height).unwrap(), commitment);
+		assert_eq!(host.latest_commitment_height(height.id).unwrap(), height.height);
+
+		// slashed fishermen can't veto until they top up their bond
+		let result = pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account),
+			height,
+		);
+		assert_eq!(
+			result,
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::InsufficientBond))
+		);
+	})
+}
+
+#[test]
+fn should_back_each_open_veto_with_the_minimum_bond() {
+	new_test_ext().execute_with(|| {
+		let host = Ismp::default();
+		let height = setup_mock_client::<_, Test>(&host);
+		let other_height = StateMachineHeight { height: height.height + 1, ..height };
+		host.store_state_machine_commitment(
+			other_height,
+			host.state_machine_commitment(height).unwrap(),
+		)
+		.unwrap();
+		let account = bonded_fisherman();
+
+		pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			height,
+		)
+		.unwrap();
+
+		// the bond only covers one veto
+		let result = pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			other_height,
+		);
+		assert_eq!(
+			result,
+			Err(DispatchError::from(pallet_fishermen::Error::<Test>::InsufficientBond))
+		);
+
+		pallet_fishermen::Pallet::<Test>::bond(RuntimeOrigin::signed(account.clone()), 10 * UNIT)
+			.unwrap();
+		pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			other_height,
+		)
+		.unwrap();
+
+		// each veto without evidence is slashed by the minimum bond
+		System::set_block_number(System::block_number() + 11);
+		for height in [height, other_height] {
+			pallet_fishermen::Pallet::<Test>::resolve_veto(
+				RuntimeOrigin::signed(H256::random().0.into()),
+				height,
+				account.clone(),
+			)
+			.unwrap();
+		}
+
+		assert_eq!(
+			pallet_fishermen::Fishermen::<Test>::get(account),
+			Some(Fisherman { bond: 0, open_vetoes: 0 })
+		);
+		assert_eq!(Balances::balance(&FishermenRewardAccount::get()), 20 * UNIT);
+	})
+}
+
+#[test]
+fn should_verify_conflicting_proofs_against_the_consensus_state_of_the_vetoed_update() {
+	new_test_ext().execute_with(|| {
+		let host = Ismp::default();
+		let height = setup_mock_client::<_, Test>(&host);
+		host.store_challenge_period(MOCK_CONSENSUS_STATE_ID, 1000).unwrap();
+		Balances::mint_into(&FishermenRewardAccount::get(), 10 * UNIT).unwrap();
+		let account = bonded_fisherman();
+
+		// a consensus update replaces the trusted state and stores a new commitment
+		let vetoed_height = StateMachineHeight { height: height.height + 1, ..height };
+		let vetoed = StateCommitment {
+			timestamp: 2000,
+			overlay_root: None,
+			state_root: H256::repeat_byte(1),
+		};
+		host.store_consensus_state(MOCK_CONSENSUS_STATE_ID, vec![1]).unwrap();
+		host.store_state_machine_commitment(vetoed_height, vetoed).unwrap();
+		host.store_state_machine_update_time(vetoed_height, host.timestamp()).unwrap();
+
+		pallet_fishermen::Pallet::<Test>::veto_state_commitment(
+			RuntimeOrigin::signed(account.clone()),
+			vetoed_height,
+		)
+		.unwrap();
+		assert_eq!(
+			pallet_fishermen::Vetoes::<Test>::get(vetoed_height, account.clone())
+				.unwrap()
+				.consensus_states,
+			vec![vec![]]
+		);
+
+		let proof = |trusted: Vec<u8>, commitment: StateCommitment| {
+			let commitments: VerifiedCommitments = vec![(
+				vetoed_height.id.state_id,
+				vec![StateCommitmentHeight { commitment, height: vetoed_height.height }],
+			)]
+			.into_iter()
+			.collect();
+			VetoEvidence::ConflictingProof((trusted, vec![2u8], commitments).encode())
+		};
+		let conflicting = StateCommitment { state_root: H256::repeat_byte(2), ..vetoed };
+
+		// proofs against the current consensus state can't be verified at the vetoed height
+		for evidence in [proof(vec![1], conflicting), proof(vec![], vetoed)] {
+			let result = pallet_fishermen::Pallet::<Test>::submit_evidence(
+				RuntimeOrigin::signed(H256::random().0.into()),
+				vetoed_height,
+				account.clone(),
+				evidence,
+			);
+			assert_eq!(
+				result,
+				Err(DispatchError::from(pallet_fishermen::Error::<Test>::InvalidEvidence))
+			);
+		}
+
+		pallet_fishermen::Pallet::<Test>::submit_evidence(
+			RuntimeOrigin::signed(H256::random().0.into()),
+			vetoed_height,
+			account.clone(),
+			proof(vec![], conflicting),
+		)
+		.unwrap();
+		assert_eq!(Balances::balance(&account), 91 * UNIT);
+		assert!(!pallet_fishermen::Vetoes::<Test>::contains_key(vetoed_height, account));
+	})
+}
@@ -17,17 +17,22 @@
 
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use frame_support::traits::fungible::{Inspect, Mutate};
-use frame_system::Origin;
-use sp_core::{crypto::AccountId32, H256};
+use codec::Encode;
+use frame_support::traits::{
+	fungible::{Inspect, Mutate},
+	Hooks,
+};
+use frame_system::{pallet_prelude::HeaderFor, Origin};
+use pallet_mmr::NodesUtils;
+use sp_core::{crypto::AccountId32, offchain::StorageKind, H256};
 use sp_runtime::traits::AccountIdConversion;
 
 use ismp::{
 	consensus::{StateMachineHeight, StateMachineId},
-	dispatcher::{DispatchGet, DispatchRequest, FeeMetadata, IsmpDispatcher},
+	dispatcher::{DispatchGet, DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
 	host::{IsmpHost, StateMachine},
 	messaging::{hash_request, Message, Proof, ResponseMessage, TimeoutMessage},
-	router::{GetResponse, PostRequest, Request, RequestResponse, Response, Timeout},
+	router::{GetResponse, PostRequest, PostResponse, Request, RequestResponse, Response, Timeout},
 };
 use ismp_testsuite::{
 	check_challenge_period, check_client_expiry, missing_state_commitment_check,
@@ -35,8 +40,9 @@ use ismp_testsuite::{
 };
 use pallet_ismp::{
 	child_trie::{RequestCommitments, RequestReceipts},
-	mmr::Leaf,
-	FundMessageParams, MessageCommitment, RELAYER_FEE_ACCOUNT,
+	mmr::{Leaf, ProofKeys},
+	CommitmentFee, FundMessageParams, MessageCommitment, MessageStatus, PreviousConsensusStates,
+	RELAYER_FEE_ACCOUNT,
 };
 
 use crate::runtime::*;
@@ -134,7 +140,7 @@ fn should_handle_post_response_timeouts_correctly() {
 #[test]
 fn should_handle_get_request_timeouts_correctly() {
 	let mut ext = new_test_ext();
-	ext.execute_with(|| {
+	let requests = ext.execute_with(|| {
 		let host = Ismp::default();
 		setup_mock_client::<_, Test>(&host);
 		host.store_challenge_period(MOCK_CONSENSUS_STATE_ID, 0).unwrap();
@@ -171,10 +177,22 @@ fn should_handle_get_request_timeouts_correctly() {
 
 		set_timestamp(Some(Duration::from_secs(100_000_000).as_millis() as u64));
 		pallet_ismp::Pallet::<Test>::handle_messages(vec![Message::Timeout(timeout_msg)]).unwrap();
-		for request in requests {
+		for request in &requests {
 			// commitments should not be found in storage after timeout has been processed
-			let commitment = hash_request::<Ismp>(&request);
-			assert!(host.request_commitment(commitment).is_err())
+			let commitment = hash_request::<Ismp>(request);
+			assert!(host.request_commitment(commitment).is_err());
+		}
+
+		requests
+	});
+	ext.persist_offchain_overlay();
+
+	ext.execute_with(|| {
+		// but the requests are still indexed offchain, so the timeouts are reported
+		for request in requests {
+			let status = Ismp::message_status(hash_request::<Ismp>(&request));
+			assert_eq!(status.request, None);
+			assert!(status.timed_out);
 		}
 	})
 }
@@ -363,3 +381,160 @@ fn test_fund_message() {
 		assert_eq!(metadata.fee.fee, 20 * UNIT);
 	});
 }
+
+#[test]
+fn test_message_status() {
+	let mut ext = new_test_ext();
+	let account: AccountId32 = H256::random().0.into();
+	let host = Ismp::default();
+
+	ext.execute_with(|| {
+		let msg = DispatchGet {
+			dest: StateMachine::Evm(1),
+			from: vec![0u8; 32],
+			keys: vec![vec![1u8; 32], vec![1u8; 32]],
+			height: 3,
+			timeout: 2_000_000_000,
+		};
+
+		Balances::mint_into(&account, 10 * UNIT).unwrap();
+
+		let commitment = host
+			.dispatch_request(
+				DispatchRequest::Get(msg),
+				FeeMetadata { payer: account.clone().into(), fee: 10 * UNIT },
+			)
+			.unwrap();
+
+		// unknown commitments have no status
+		assert_eq!(Ismp::message_status(H256::random()), MessageStatus::default());
+
+		let status = Ismp::message_status(commitment);
+		assert_eq!(
+			status.request,
+			Some(CommitmentFee { payer: account.encode(), fee: 10 * UNIT, claimed: false })
+		);
+		assert_eq!(status.response, None);
+		assert_eq!(status.request_receipt, None);
+		assert!(!status.responded);
+		assert!(!status.timed_out);
+
+		// receipts are keyed by the request commitment
+		RequestReceipts::<Test>::insert(commitment, &[1u8; 32]);
+		assert_eq!(Ismp::message_status(commitment).request_receipt, Some(vec![1u8; 32]));
+	});
+}
+
+#[test]
+fn should_generate_mmr_proofs_from_request_and_response_commitments() {
+	let mut ext = new_test_ext();
+	let host = Ismp::default();
+
+	let (request_commitment, response_commitment) = ext.execute_with(|| {
+		let request_commitment = host
+			.dispatch_request(
+				DispatchRequest::Post(DispatchPost {
+					dest: StateMachine::Evm(1),
+					from: vec![0u8; 32],
+					to: vec![0u8; 32],
+					timeout: 0,
+					body: vec![0u8; 64],
+				}),
+				FeeMetadata { payer: [0u8; 32].into(), fee: 0 },
+			)
+			.unwrap();
+
+		let post = PostRequest {
+			source: StateMachine::Evm(1),
+			dest: host.host_state_machine(),
+			nonce: 0,
+			from: vec![0u8; 32],
+			to: vec![0u8; 32],
+			timeout_timestamp: 0,
+			body: vec![0u8; 64],
+		};
+		RequestReceipts::<Test>::insert(
+			hash_request::<Ismp>(&Request::Post(post.clone())),
+			&vec![0u8; 32],
+		);
+		let response_commitment = host
+			.dispatch_response(
+				PostResponse { post, response: vec![1u8; 64], timeout_timestamp: 0 },
+				FeeMetadata { payer: [0u8; 32].into(), fee: 0 },
+			)
+			.unwrap();
+
+		Ismp::on_finalize(1);
+		(request_commitment, response_commitment)
+	});
+	ext.persist_offchain_overlay();
+
+	ext.execute_with(|| {
+		// move the nodes to their canonical keys, as the mmr gadget does on finality
+		let fork_identifier = Ismp::child_trie_root();
+		for pos in 0..NodesUtils::new(Mmr::leaf_count()).size() {
+			let temp_key = NodesUtils::node_temp_offchain_key::<HeaderFor<Test>>(
+				b"ISMP",
+				pos,
+				fork_identifier,
+			);
+			let node =
+				sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &temp_key).unwrap();
+			let canon_key = NodesUtils::node_canon_offchain_key(b"ISMP", pos);
+			sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &canon_key, &node);
+		}
+
+		let (_, requests) =
+			Ismp::generate_proof(ProofKeys::Requests(vec![request_commitment])).unwrap();
+		let (_, responses) =
+			Ismp::generate_proof(ProofKeys::Responses(vec![response_commitment])).unwrap();
+
+		// timed out commitments are removed from the child trie, but can still be proven
+		RequestCommitments::<Test>::remove(request_commitment);
+		assert!(Ismp::generate_proof(ProofKeys::Requests(vec![request_commitment])).is_err());
+
+		let (leaves, proof) = Ismp::generate_proof(ProofKeys::Commitments(vec![
+			request_commitment,
+			response_commitment,
+		]))
+		.unwrap();
+		assert!(matches!(leaves[..], [Leaf::Request(_), Leaf::Response(_)]));
+		assert_eq!(
+			proof.leaf_indices_and_pos,
+			vec![requests.leaf_indices_and_pos[0], responses.leaf_indices_and_pos[0]]
+		);
+		assert_eq!(proof.leaf_count, 2);
+
+		// unknown commitments have no leaves
+		assert!(Ismp::generate_proof(ProofKeys::Commitments(vec![H256::random()])).is_err());
+	});
+}
+
+#[test]
+fn should_keep_replaced_consensus_states_for_the_challenge_period() {
+	new_test_ext().execute_with(|| {
+		let host = Ismp::default();
+		setup_mock_client::<_, Test>(&host);
+		host.store_challenge_period(MOCK_CONSENSUS_STATE_ID, 1000).unwrap();
+
+		let updated_at = host.timestamp().as_secs();
+		host.store_consensus_state(MOCK_CONSENSUS_STATE_ID, vec![1]).unwrap();
+		host.store_consensus_state(MOCK_CONSENSUS_STATE_ID, vec![2]).unwrap();
+		assert_eq!(
+			PreviousConsensusStates::<Test>::get(MOCK_CONSENSUS_STATE_ID, updated_at),
+			vec![vec![], vec![1]]
+		);
+
+		// replaced states are pruned once the challenge period has elapsed
+		set_timestamp(Some((updated_at + 1001) * 1000));
+		host.store_consensus_state(MOCK_CONSENSUS_STATE_ID, vec![3]).unwrap();
+		assert!(!PreviousConsensusStates::<Test>::contains_key(
+			MOCK_CONSENSUS_STATE_ID,
+			updated_at
+		));
+		assert_eq!(
+			PreviousConsensusStates::<Test>::get(MOCK_CONSENSUS_STATE_ID, updated_at + 1001),
+			vec![vec![2]]
+		);
+	})
+}
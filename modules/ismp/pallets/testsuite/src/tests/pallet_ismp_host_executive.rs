@@ -15,10 +15,24 @@
 
 #![cfg(test)]
 
-use crate::runtime::{last_event, new_test_ext, RuntimeEvent, RuntimeOrigin, Test};
-use ismp::host::StateMachine;
-use pallet_ismp_host_executive::{EvmHostParam, EvmHostParamUpdate, HostParam, HostParamUpdate};
-use sp_core::{crypto::AccountId32, H160, H256};
+use crate::runtime::{
+	last_event, new_test_ext, set_timestamp, Ismp, RuntimeEvent, RuntimeOrigin, Test,
+	MOCK_CONSENSUS_STATE_ID,
+};
+use codec::Encode;
+use ismp::{
+	consensus::{StateMachineHeight, StateMachineId},
+	host::StateMachine,
+	messaging::{hash_request, Proof},
+	module::IsmpModule,
+	router::{PostRequest, PostResponse, Request, Response, Timeout},
+};
+use pallet_hyperbridge::{Message, VersionedHostParams, PALLET_HYPERBRIDGE};
+use pallet_ismp_host_executive::{
+	EvmHostAction, EvmHostParam, EvmHostParamUpdate, FrozenStatus, HostParam, HostParamUpdate,
+	HostParams, PendingHostParams, PALLET_ID,
+};
+use sp_core::{crypto::AccountId32, H160, H256, U256};
 use sp_runtime::DispatchError;
 use std::collections::BTreeMap;
 
@@ -44,23 +58,336 @@ fn test_host_executive() {
 		pallet_ismp_host_executive::Pallet::<Test>::set_host_params(RuntimeOrigin::root(), map)
 			.unwrap();
 
-		let mut params = EvmHostParamUpdate::default();
+		let mut update = EvmHostParamUpdate::default();
 		let new_handler = H160::random();
-		params.handler = Some(new_handler);
+		update.handler = Some(new_handler);
 		pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
 			RuntimeOrigin::root(),
 			StateMachine::Polkadot(2000),
-			HostParamUpdate::EvmHostParam(params),
+			HostParamUpdate::EvmHostParam(update.clone()),
 		)
 		.unwrap();
 
 		let RuntimeEvent::HostExecutive(
-			pallet_ismp_host_executive::Event::<Test>::HostParamsUpdated { state_machine, .. },
+			pallet_ismp_host_executive::Event::<Test>::HostParamsUpdatePending {
+				state_machine,
+				..
+			},
 		) = last_event::<Test>()
 		else {
-			panic!("Ismp request not found")
+			panic!("Pending update not found")
 		};
+		assert_eq!(state_machine, StateMachine::Polkadot(2000));
 
-		assert_eq!(state_machine, StateMachine::Polkadot(2000))
+		// the update is only applied once it is acknowledged
+		assert_eq!(HostParams::<Test>::get(StateMachine::Polkadot(2000)), Some(params));
+		assert!(PendingHostParams::<Test>::contains_key(StateMachine::Polkadot(2000)));
+
+		// only one update can be pending at a time
+		let result = pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
+			RuntimeOrigin::root(),
+			StateMachine::Polkadot(2000),
+			HostParamUpdate::EvmHostParam(update),
+		);
+		assert_eq!(
+			result,
+			Err(pallet_ismp_host_executive::Error::<Test>::UpdateAlreadyPending.into())
+		);
+	})
+}
+
+/// Dispatches a substrate host params update to `Polkadot(2000)` and returns the request that
+/// carries it
+fn dispatch_substrate_update(update: VersionedHostParams<u128>) -> PostRequest {
+	set_timestamp::<Test>(10_000_000_000);
+	let map = BTreeMap::from([(
+		StateMachine::Polkadot(2000),
+		HostParam::SubstrateHostParam(VersionedHostParams::V1(1)),
+	)]);
+	pallet_ismp_host_executive::Pallet::<Test>::set_host_params(RuntimeOrigin::root(), map)
+		.unwrap();
+
+	let nonce = pallet_ismp::Nonce::<Test>::get();
+	pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
+		RuntimeOrigin::root(),
+		StateMachine::Polkadot(2000),
+		HostParamUpdate::SubstrateHostParam(update.clone()),
+	)
+	.unwrap();
+
+	let post = PostRequest {
+		source: StateMachine::Kusama(100),
+		dest: StateMachine::Polkadot(2000),
+		nonce,
+		from: PALLET_ID.to_bytes(),
+		to: PALLET_HYPERBRIDGE.0.to_vec(),
+		timeout_timestamp: 10_000_000 + 1_000,
+		body: Message::<AccountId32, u128>::UpdateHostParams(update).encode(),
+	};
+	let pending = PendingHostParams::<Test>::get(StateMachine::Polkadot(2000)).unwrap();
+	assert_eq!(pending.commitment, hash_request::<Ismp>(&Request::Post(post.clone())));
+
+	post
+}
+
+#[test]
+fn should_reject_responses() {
+	new_test_ext().execute_with(|| {
+		let post = dispatch_substrate_update(VersionedHostParams::V1(5));
+		let module = pallet_ismp_host_executive::Pallet::<Test>::default();
+
+		// updates are only applied with a proof of their receipt
+		let response = PostResponse { post, response: vec![], timeout_timestamp: 0 };
+		assert!(module.on_response(Response::Post(response)).is_err());
+
+		assert_eq!(
+			HostParams::<Test>::get(StateMachine::Polkadot(2000)),
+			Some(HostParam::SubstrateHostParam(VersionedHostParams::V1(1)))
+		);
+		assert!(PendingHostParams::<Test>::contains_key(StateMachine::Polkadot(2000)));
+	})
+}
+
+#[test]
+fn should_supersede_pending_updates_that_timed_out() {
+	new_test_ext().execute_with(|| {
+		let post = dispatch_substrate_update(VersionedHostParams::V1(5));
+		let pending = PendingHostParams::<Test>::get(StateMachine::Polkadot(2000)).unwrap();
+		assert_eq!(pending.timeout_timestamp, post.timeout_timestamp);
+
+		// the update blocks new ones until its request times out
+		let update = HostParamUpdate::SubstrateHostParam(VersionedHostParams::V1(7));
+		set_timestamp::<Test>(post.timeout_timestamp * 1000);
+		let result = pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
+			RuntimeOrigin::root(),
+			StateMachine::Polkadot(2000),
+			update.clone(),
+		);
+		assert_eq!(
+			result,
+			Err(pallet_ismp_host_executive::Error::<Test>::UpdateAlreadyPending.into())
+		);
+
+		set_timestamp::<Test>((post.timeout_timestamp + 1) * 1000);
+		pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
+			RuntimeOrigin::root(),
+			StateMachine::Polkadot(2000),
+			update,
+		)
+		.unwrap();
+
+		let superseded = PendingHostParams::<Test>::get(StateMachine::Polkadot(2000)).unwrap();
+		assert_ne!(superseded.commitment, pending.commitment);
+		assert_eq!(superseded.params, HostParam::SubstrateHostParam(VersionedHostParams::V1(7)));
+		assert!(frame_system::Pallet::<Test>::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::HostExecutive(
+				pallet_ismp_host_executive::Event::<Test>::HostParamsUpdateRolledBack {
+					commitment,
+					..
+				}
+			) if *commitment == pending.commitment
+		)));
+
+		// the timeout of the superseded request doesn't discard the new update
+		let module = pallet_ismp_host_executive::Pallet::<Test>::default();
+		module.on_timeout(Timeout::Request(Request::Post(post))).unwrap();
+		assert_eq!(PendingHostParams::<Test>::get(StateMachine::Polkadot(2000)), Some(superseded));
+	})
+}
+
+#[test]
+fn should_roll_back_host_params_update_on_timeout() {
+	new_test_ext().execute_with(|| {
+		let post = dispatch_substrate_update(VersionedHostParams::V1(5));
+		let module = pallet_ismp_host_executive::Pallet::<Test>::default();
+
+		module.on_timeout(Timeout::Request(Request::Post(post.clone()))).unwrap();
+
+		assert_eq!(
+			HostParams::<Test>::get(StateMachine::Polkadot(2000)),
+			Some(HostParam::SubstrateHostParam(VersionedHostParams::V1(1)))
+		);
+		assert!(!PendingHostParams::<Test>::contains_key(StateMachine::Polkadot(2000)));
+		let RuntimeEvent::HostExecutive(
+			pallet_ismp_host_executive::Event::<Test>::HostParamsUpdateRolledBack {
+				state_machine,
+				..
+			},
+		) = last_event::<Test>()
+		else {
+			panic!("Rollback not found")
+		};
+		assert_eq!(state_machine, StateMachine::Polkadot(2000));
+
+		// a new update can be dispatched once the previous one is rolled back
+		pallet_ismp_host_executive::Pallet::<Test>::update_host_params(
+			RuntimeOrigin::root(),
+			StateMachine::Polkadot(2000),
+			HostParamUpdate::SubstrateHostParam(VersionedHostParams::V1(7)),
+		)
+		.unwrap();
+	})
+}
+
+#[test]
+fn should_reject_confirmation_without_pending_update() {
+	new_test_ext().execute_with(|| {
+		let account: AccountId32 = H256::random().0.into();
+		let mut post = dispatch_substrate_update(VersionedHostParams::V1(5));
+		let proof = Proof {
+			height: StateMachineHeight {
+				id: StateMachineId {
+					state_id: StateMachine::Polkadot(2000),
+					consensus_state_id: MOCK_CONSENSUS_STATE_ID,
+				},
+				height: 1,
+			},
+			proof: vec![],
+		};
+
+		post.nonce += 1;
+		let result = pallet_ismp_host_executive::Pallet::<Test>::confirm_host_params(
+			RuntimeOrigin::signed(account.clone()),
+			post.clone(),
+			proof.clone(),
+		);
+		assert_eq!(result, Err(pallet_ismp_host_executive::Error::<Test>::UnknownRequest.into()));
+
+		post.dest = StateMachine::Polkadot(3000);
+		let result = pallet_ismp_host_executive::Pallet::<Test>::confirm_host_params(
+			RuntimeOrigin::signed(account),
+			post,
+			proof,
+		);
+		assert_eq!(result, Err(pallet_ismp_host_executive::Error::<Test>::NoPendingUpdate.into()));
+	})
+}
+
+#[test]
+fn should_dispatch_evm_host_actions() {
+	new_test_ext().execute_with(|| {
+		let params = EvmHostParam { handler: H160::random(), ..Default::default() };
+		let map = BTreeMap::from([
+			(StateMachine::Evm(1), HostParam::EvmHostParam(params.clone())),
+			(
+				StateMachine::Polkadot(2000),
+				HostParam::SubstrateHostParam(VersionedHostParams::V1(1)),
+			),
+		]);
+		pallet_ismp_host_executive::Pallet::<Test>::set_host_params(RuntimeOrigin::root(), map)
+			.unwrap();
+
+		// actions can only be dispatched to evm hosts
+		let result = pallet_ismp_host_executive::Pallet::<Test>::dispatch_evm_host_action(
+			RuntimeOrigin::root(),
+			StateMachine::Polkadot(2000),
+			EvmHostAction::SetFrozenState(FrozenStatus::All),
+		);
+		assert_eq!(
+			result,
+			Err(pallet_ismp_host_executive::Error::<Test>::MismatchedHostParams.into())
+		);
+
+		// hosts that reject incoming messages couldn't be unfrozen through governance
+		for status in [FrozenStatus::Incoming, FrozenStatus::All] {
+			let result = pallet_ismp_host_executive::Pallet::<Test>::dispatch_evm_host_action(
+				RuntimeOrigin::root(),
+				StateMachine::Evm(1),
+				EvmHostAction::SetFrozenState(status),
+			);
+			assert_eq!(
+				result,
+				Err(pallet_ismp_host_executive::Error::<Test>::InvalidFrozenState.into())
+			);
+		}
+
+		pallet_ismp_host_executive::Pallet::<Test>::dispatch_evm_host_action(
+			RuntimeOrigin::root(),
+			StateMachine::Evm(1),
+			EvmHostAction::SetFrozenState(FrozenStatus::Outgoing),
+		)
+		.unwrap();
+		let RuntimeEvent::HostExecutive(
+			pallet_ismp_host_executive::Event::<Test>::EvmHostActionDispatched { action, .. },
+		) = last_event::<Test>()
+		else {
+			panic!("Dispatched action not found")
+		};
+		assert_eq!(action, EvmHostAction::SetFrozenState(FrozenStatus::Outgoing));
+		assert!(!PendingHostParams::<Test>::contains_key(StateMachine::Evm(1)));
+
+		// upgrades are tracked until they are acknowledged
+		let handler = H160::random();
+		pallet_ismp_host_executive::Pallet::<Test>::dispatch_evm_host_action(
+			RuntimeOrigin::root(),
+			StateMachine::Evm(1),
+			EvmHostAction::SetHandler { address: handler, code_hash: H256::random() },
+		)
+		.unwrap();
+		let pending = PendingHostParams::<Test>::get(StateMachine::Evm(1)).unwrap();
+		assert_eq!(
+			pending.params,
+			HostParam::EvmHostParam(EvmHostParam { handler, ..params.clone() })
+		);
+		assert_eq!(
+			HostParams::<Test>::get(StateMachine::Evm(1)),
+			Some(HostParam::EvmHostParam(params))
+		);
+
+		let result = pallet_ismp_host_executive::Pallet::<Test>::dispatch_evm_host_action(
+			RuntimeOrigin::root(),
+			StateMachine::Evm(1),
+			EvmHostAction::SetConsensusClient {
+				address: H160::random(),
+				code_hash: H256::random(),
+			},
+		);
+		assert_eq!(
+			result,
+			Err(pallet_ismp_host_executive::Error::<Test>::UpdateAlreadyPending.into())
+		);
+	})
+}
+
+#[test]
+fn test_request_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		let mut evm_host_params = EvmHostParam::default();
+		evm_host_params.per_byte_fee = U256::from(10);
+		let map = BTreeMap::from([
+			(StateMachine::Evm(1), HostParam::EvmHostParam(evm_host_params)),
+			(
+				StateMachine::Polkadot(2000),
+				HostParam::SubstrateHostParam(VersionedHostParams::V1(5)),
+			),
+		]);
+		pallet_ismp_host_executive::Pallet::<Test>::set_host_params(RuntimeOrigin::root(), map)
+			.unwrap();
+
+		let mut post = PostRequest {
+			source: StateMachine::Evm(1),
+			dest: StateMachine::Kusama(100),
+			nonce: 0,
+			from: vec![],
+			to: vec![],
+			timeout_timestamp: 0,
+			body: vec![0u8; 64],
+		};
+		assert_eq!(
+			pallet_ismp_host_executive::Pallet::<Test>::request_protocol_fee(&post),
+			Some(U256::from(640))
+		);
+
+		// bodies smaller than a word are charged for 32 bytes
+		post.source = StateMachine::Polkadot(2000);
+		post.body = vec![0u8; 4];
+		assert_eq!(
+			pallet_ismp_host_executive::Pallet::<Test>::request_protocol_fee(&post),
+			Some(U256::from(160))
+		);
+
+		post.source = StateMachine::Polkadot(3000);
+		assert_eq!(pallet_ismp_host_executive::Pallet::<Test>::request_protocol_fee(&post), None);
 	})
 }
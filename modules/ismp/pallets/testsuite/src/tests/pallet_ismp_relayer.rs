@@ -19,7 +19,10 @@ use alloy_primitives::hex;
 use codec::{Decode, Encode};
 use ethereum_triedb::{keccak::KeccakHasher, MemoryDB, StorageProof};
 use evm_common::types::EvmStateProof;
-use frame_support::crypto::ecdsa::ECDSAExt;
+use frame_support::{
+	crypto::ecdsa::ECDSAExt,
+	traits::fungible::{Inspect, Mutate},
+};
 use ismp::{
 	consensus::{StateCommitment, StateMachineHeight, StateMachineId},
 	host::{IsmpHost, StateMachine},
@@ -29,7 +32,7 @@ use ismp::{
 use pallet_ismp::{
 	child_trie::{RequestCommitments, RequestReceipts, ResponseCommitments, ResponseReceipts},
 	dispatcher::FeeMetadata,
-	ResponseReceipt,
+	ResponseReceipt, RELAYER_FEE_ACCOUNT,
 };
 use pallet_ismp_host_executive::{EvmHostParam, EvmHosts, HostParam};
 use pallet_ismp_relayer::{
@@ -37,14 +40,15 @@ use pallet_ismp_relayer::{
 	withdrawal::{Key, Signature, WithdrawalInputData, WithdrawalProof},
 };
 use sp_core::{Pair, H160, H256, U256};
+use sp_runtime::{traits::AccountIdConversion, AccountId32};
 use sp_trie::LayoutV0;
 use std::{fs::File, io::Read, time::Duration};
 use substrate_state_machine::{HashAlgorithm, StateMachineProof, SubstrateStateProof};
 use trie_db::{Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieMut};
 
 use crate::runtime::{
-	new_test_ext, set_timestamp, Ismp, RuntimeCall, RuntimeOrigin, Test, MOCK_CONSENSUS_CLIENT_ID,
-	MOCK_CONSENSUS_STATE_ID,
+	new_test_ext, set_timestamp, Balances, Ismp, RuntimeCall, RuntimeOrigin, Test,
+	MOCK_CONSENSUS_CLIENT_ID, MOCK_CONSENSUS_STATE_ID, UNIT,
 };
 use ismp_bsc::BSC_CONSENSUS_ID;
 use ismp_sync_committee::BEACON_CONSENSUS_ID;
@@ -291,6 +295,151 @@ fn test_withdrawal_proof() {
 	})
 }
 
+#[test]
+fn test_accumulate_fees_for_requests_dispatched_from_the_host() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		set_timestamp::<Test>(10_000_000_000);
+		let host = Ismp::default();
+		let host_state_machine = host.host_state_machine();
+		let requests = (0u64..2)
+			.into_iter()
+			.map(|nonce| {
+				let post = PostRequest {
+					source: host_state_machine,
+					dest: StateMachine::Kusama(2001),
+					nonce,
+					from: vec![],
+					to: vec![],
+					timeout_timestamp: 0,
+					body: vec![],
+				};
+				hash_request::<Ismp>(&Request::Post(post))
+			})
+			.collect::<Vec<_>>();
+		let post = PostRequest {
+			source: StateMachine::Kusama(2001),
+			dest: host_state_machine,
+			nonce: 0,
+			from: vec![],
+			to: vec![],
+			timeout_timestamp: 0,
+			body: vec![],
+		};
+		let response = ismp::router::PostResponse {
+			post: post.clone(),
+			response: vec![0; 32],
+			timeout_timestamp: 0,
+		};
+		let (request, response) =
+			(hash_request::<Ismp>(&Request::Post(post)), hash_post_response::<Ismp>(&response));
+
+		let leaf_meta = RequestMetadata {
+			mmr: LeafIndexAndPos { leaf_index: 0, pos: 0 },
+			fee: FeeMetadata::<Test> { payer: [0; 32].into(), fee: 1000u128.into() },
+			claimed: false,
+		};
+		let mut dest_root = H256::default();
+		let mut dest_db = MemoryDB::<KeccakHasher>::default();
+		let mut dest_trie =
+			TrieDBMutBuilder::<LayoutV0<KeccakHasher>>::new(&mut dest_db, &mut dest_root).build();
+		// the commitments are only in the host's child trie, there's no proof for them
+		for commitment in &requests {
+			RequestCommitments::<Test>::insert(*commitment, leaf_meta.clone());
+			let receipt_key = RequestReceipts::<Test>::storage_key(*commitment);
+			dest_trie.insert(&receipt_key, &vec![1u8; 32].encode()).unwrap();
+		}
+		ResponseCommitments::<Test>::insert(response, leaf_meta.clone());
+		let receipt = ResponseReceipt { response, relayer: vec![2; 32] };
+		dest_trie
+			.insert(&ResponseReceipts::<Test>::storage_key(request), &receipt.encode())
+			.unwrap();
+		drop(dest_trie);
+
+		let mut dest_recorder = Recorder::<LayoutV0<KeccakHasher>>::default();
+		let dest_trie = TrieDBBuilder::<LayoutV0<KeccakHasher>>::new(&dest_db, &dest_root)
+			.with_recorder(&mut dest_recorder)
+			.build();
+		let mut keys = vec![];
+		for commitment in &requests {
+			dest_trie.get(&RequestReceipts::<Test>::storage_key(*commitment)).unwrap();
+			keys.push(Key::Request(*commitment));
+		}
+		dest_trie.get(&ResponseReceipts::<Test>::storage_key(request)).unwrap();
+		keys.push(Key::Response { response_commitment: response, request_commitment: request });
+		let dest_state_proof = SubstrateStateProof::OverlayProof(StateMachineProof {
+			hasher: HashAlgorithm::Keccak,
+			storage_proof: dest_recorder.drain().into_iter().map(|f| f.data).collect(),
+		});
+
+		let dest_height = StateMachineHeight {
+			id: StateMachineId {
+				state_id: StateMachine::Kusama(2001),
+				consensus_state_id: MOCK_CONSENSUS_STATE_ID,
+			},
+			height: 1,
+		};
+		host.store_state_machine_commitment(
+			dest_height,
+			StateCommitment {
+				timestamp: 100,
+				overlay_root: Some(dest_root),
+				state_root: Default::default(),
+			},
+		)
+		.unwrap();
+		host.store_state_machine_update_time(dest_height, Duration::from_secs(100))
+			.unwrap();
+		host.store_consensus_state(MOCK_CONSENSUS_STATE_ID, Default::default()).unwrap();
+		host.store_consensus_state_id(MOCK_CONSENSUS_STATE_ID, MOCK_CONSENSUS_CLIENT_ID)
+			.unwrap();
+		host.store_unbonding_period(MOCK_CONSENSUS_STATE_ID, 10_000_000_000).unwrap();
+		host.store_challenge_period(MOCK_CONSENSUS_STATE_ID, 0).unwrap();
+
+		let withdrawal_proof = WithdrawalProof {
+			commitments: keys,
+			source_proof: Proof {
+				height: StateMachineHeight {
+					id: StateMachineId {
+						state_id: host_state_machine,
+						consensus_state_id: MOCK_CONSENSUS_STATE_ID,
+					},
+					height: 1,
+				},
+				// never read, the fees are read from the host's own state
+				proof: vec![],
+			},
+			dest_proof: Proof { height: dest_height, proof: dest_state_proof.encode() },
+		};
+
+		pallet_ismp_relayer::Pallet::<Test>::accumulate_fees(
+			RuntimeOrigin::none(),
+			withdrawal_proof.clone(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			pallet_ismp_relayer::Fees::<Test>::get(host_state_machine, vec![1; 32]),
+			U256::from(2000u128)
+		);
+		assert_eq!(
+			pallet_ismp_relayer::Fees::<Test>::get(host_state_machine, vec![2; 32]),
+			U256::from(1000u128)
+		);
+		for commitment in &requests {
+			assert!(RequestCommitments::<Test>::get(*commitment).unwrap().claimed);
+		}
+		assert!(ResponseCommitments::<Test>::get(response).unwrap().claimed);
+
+		// fees can only be claimed once
+		assert!(pallet_ismp_relayer::Pallet::<Test>::accumulate_fees(
+			RuntimeOrigin::none(),
+			withdrawal_proof
+		)
+		.is_err());
+	})
+}
+
 #[test]
 fn test_withdrawal_fees() {
 	let mut ext = new_test_ext();
@@ -380,6 +529,52 @@ fn test_withdrawal_fees_evm() {
 	})
 }
 
+#[test]
+fn test_withdrawal_fees_native() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let host_state_machine = Ismp::default().host_state_machine();
+		let pair = sp_core::sr25519::Pair::from_seed_slice(H256::random().as_bytes()).unwrap();
+		let public_key = pair.public().0.to_vec();
+		let account: AccountId32 = pair.public().0.into();
+		Balances::mint_into(&RELAYER_FEE_ACCOUNT.into_account_truncating(), 10 * UNIT).unwrap();
+		// fees on substrate chains are not subject to the dollar minimum withdrawal amount
+		pallet_ismp_relayer::Fees::<Test>::insert(
+			host_state_machine,
+			public_key.clone(),
+			U256::from(5 * UNIT),
+		);
+		let message = message(0, host_state_machine);
+		let signature = pair.sign(&message).0.to_vec();
+
+		let withdrawal_input = WithdrawalInputData {
+			signature: Signature::Sr25519 { public_key: public_key.clone(), signature },
+			dest_chain: host_state_machine,
+		};
+
+		pallet_ismp_relayer::Pallet::<Test>::withdraw_fees(
+			RuntimeOrigin::none(),
+			withdrawal_input.clone(),
+		)
+		.unwrap();
+
+		// paid out directly without dispatching a request
+		assert_eq!(Balances::balance(&account), 5 * UNIT);
+		assert_eq!(pallet_ismp::Nonce::<Test>::get(), 0);
+		assert_eq!(
+			pallet_ismp_relayer::Fees::<Test>::get(host_state_machine, public_key.clone()),
+			U256::zero()
+		);
+		assert_eq!(pallet_ismp_relayer::Nonce::<Test>::get(public_key, host_state_machine), 1);
+
+		assert!(pallet_ismp_relayer::Pallet::<Test>::withdraw_fees(
+			RuntimeOrigin::none(),
+			withdrawal_input.clone()
+		)
+		.is_err());
+	})
+}
+
 #[test]
 #[ignore]
 fn test_evm_accumulate_fees() {
@@ -0,0 +1,354 @@
+#![cfg(test)]
+
+use crate::runtime::{new_test_ext, AssetIdFactory, RuntimeOrigin, Test};
+use alloy_sol_types::SolValue;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungibles::{Inspect, Mutate},
+};
+use ismp::{
+	host::StateMachine,
+	module::IsmpModule,
+	router::{PostRequest, Request, Timeout},
+};
+use pallet_token_gateway::{AssetIdFactory as _, Body, TeleportParams};
+use pallet_token_governor::{
+	convert_to_erc20, SolAssetMetadata, SolChangeAssetAdmin, SolContractInstance,
+	SolDeregsiterAsset, SolTokenGatewayParams, TokenGatewayRequest, ERC20_DECIMALS,
+};
+use sp_core::{ByteArray, H160, H256};
+use staging_xcm::v3::MultiLocation;
+use xcm_simulator_example::ALICE;
+
+const SEND_AMOUNT: u128 = 1_000_000_000_000;
+
+fn governance_request(body: Vec<u8>) -> PostRequest {
+	PostRequest {
+		source: StateMachine::Polkadot(3367),
+		dest: StateMachine::Kusama(100),
+		nonce: 0,
+		from: pallet_token_governor::PALLET_ID.to_vec(),
+		to: H160::repeat_byte(1).0.to_vec(),
+		timeout_timestamp: 0,
+		body,
+	}
+}
+
+fn register_asset(symbol: &str) -> (MultiLocation, H256) {
+	let metadata =
+		SolAssetMetadata { name: symbol.into(), symbol: symbol.into(), ..Default::default() };
+	pallet_token_gateway::Pallet::<Test>::default()
+		.on_accept(governance_request(metadata.encode_request()))
+		.unwrap();
+
+	(
+		AssetIdFactory::asset_id(symbol.as_bytes()),
+		sp_io::hashing::keccak_256(symbol.as_bytes()).into(),
+	)
+}
+
+fn setup_asset() -> (MultiLocation, H256) {
+	assert_ok!(pallet_token_gateway::Pallet::<Test>::set_token_gateway_addresses(
+		RuntimeOrigin::root(),
+		vec![(StateMachine::Evm(1), H160::repeat_byte(1).0.to_vec())]
+			.into_iter()
+			.collect(),
+	));
+
+	register_asset("USDT")
+}
+
+fn incoming_request(asset_id: H256, amount: u128, decimals: u8) -> PostRequest {
+	let body = Body {
+		amount: {
+			let mut bytes = [0u8; 32];
+			convert_to_erc20(amount, decimals).unwrap().to_big_endian(&mut bytes);
+			alloy_primitives::U256::from_be_bytes(bytes)
+		},
+		asset_id: asset_id.0.into(),
+		redeem: false,
+		max_fee: Default::default(),
+		from: alloy_primitives::B256::from_slice(ALICE.as_slice()),
+		to: alloy_primitives::B256::from_slice(ALICE.as_slice()),
+	};
+	PostRequest {
+		source: StateMachine::Evm(1),
+		dest: StateMachine::Kusama(100),
+		nonce: 0,
+		from: H160::repeat_byte(1).0.to_vec(),
+		to: H160::repeat_byte(1).0.to_vec(),
+		timeout_timestamp: 0,
+		body: {
+			let mut encoded = Body::abi_encode(&body);
+			encoded.insert(0, 0);
+			encoded
+		},
+	}
+}
+
+#[test]
+fn should_create_assets_registered_on_the_token_governor() {
+	new_test_ext().execute_with(|| {
+		let (local_id, asset_id) = setup_asset();
+
+		assert!(<pallet_assets::Pallet<Test> as Inspect<_>>::asset_exists(local_id.clone()));
+		assert_eq!(
+			pallet_token_gateway::SupportedAssets::<Test>::get(local_id.clone()),
+			Some(asset_id)
+		);
+		assert_eq!(
+			pallet_token_gateway::LocalAssets::<Test>::get(asset_id),
+			Some(local_id.clone())
+		);
+		assert_eq!(
+			pallet_token_gateway::Precisions::<Test>::get(local_id.clone()),
+			Some(ERC20_DECIMALS)
+		);
+		assert!(!pallet_token_gateway::NativeAssets::<Test>::get(local_id.clone()));
+
+		// governance requests are only accepted from the token governor
+		let mut request = governance_request(
+			SolAssetMetadata { name: "DOT".into(), symbol: "DOT".into(), ..Default::default() }
+				.encode_request(),
+		);
+		request.from = H160::repeat_byte(1).0.to_vec();
+		assert!(pallet_token_gateway::Pallet::<Test>::default().on_accept(request).is_err());
+		assert!(pallet_token_gateway::LocalAssets::<Test>::get(H256::from(
+			sp_io::hashing::keccak_256(b"DOT")
+		))
+		.is_none());
+
+		// delisted assets can no longer be teleported
+		let delisted = SolDeregsiterAsset { assetIds: vec![asset_id.0.into()] };
+		pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(delisted.encode_request()))
+			.unwrap();
+		assert!(pallet_token_gateway::LocalAssets::<Test>::get(asset_id).is_none());
+		assert_noop!(
+			pallet_token_gateway::Pallet::<Test>::teleport(
+				RuntimeOrigin::signed(ALICE),
+				TeleportParams {
+					asset_id: local_id,
+					destination: StateMachine::Evm(1),
+					recipient: H256::repeat_byte(2),
+					amount: SEND_AMOUNT,
+					timeout: 0,
+					relayer_fee: 0,
+				}
+			),
+			pallet_token_gateway::Error::<Test>::UnregisteredAsset
+		);
+	})
+}
+
+#[test]
+fn should_register_existing_local_assets_as_native() {
+	new_test_ext().execute_with(|| {
+		let local_id = AssetIdFactory::asset_id(b"DOT");
+		assert_ok!(pallet_assets::Pallet::<Test>::force_create(
+			RuntimeOrigin::root(),
+			local_id.clone(),
+			ALICE,
+			true,
+			1
+		));
+		assert_ok!(pallet_assets::Pallet::<Test>::force_set_metadata(
+			RuntimeOrigin::root(),
+			local_id.clone(),
+			b"Polkadot".to_vec(),
+			b"DOT".to_vec(),
+			10,
+			false
+		));
+		setup_asset();
+
+		// existing local assets must be approved as native by the admin
+		let metadata =
+			SolAssetMetadata { name: "DOT".into(), symbol: "DOT".into(), ..Default::default() };
+		assert!(pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(metadata.encode_request()))
+			.is_err());
+		assert!(pallet_token_gateway::SupportedAssets::<Test>::get(local_id.clone()).is_none());
+		assert_noop!(
+			pallet_token_gateway::Pallet::<Test>::register_native_asset(
+				RuntimeOrigin::signed(ALICE),
+				local_id.clone()
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(pallet_token_gateway::Pallet::<Test>::register_native_asset(
+			RuntimeOrigin::root(),
+			local_id.clone()
+		));
+		assert_noop!(
+			pallet_token_gateway::Pallet::<Test>::register_native_asset(
+				RuntimeOrigin::root(),
+				local_id.clone()
+			),
+			pallet_token_gateway::Error::<Test>::AssetAlreadyRegistered
+		);
+		register_asset("DOT");
+
+		assert!(pallet_token_gateway::NativeAssets::<Test>::get(local_id.clone()));
+		assert_eq!(pallet_token_gateway::Precisions::<Test>::get(local_id.clone()), Some(10));
+
+		// native assets are locked in the pallet account
+		assert_ok!(<pallet_assets::Pallet<Test> as Mutate<_>>::mint_into(
+			local_id.clone(),
+			&ALICE,
+			SEND_AMOUNT
+		));
+		assert_ok!(pallet_token_gateway::Pallet::<Test>::teleport(
+			RuntimeOrigin::signed(ALICE),
+			TeleportParams {
+				asset_id: local_id.clone(),
+				destination: StateMachine::Evm(1),
+				recipient: H256::repeat_byte(2),
+				amount: SEND_AMOUNT,
+				timeout: 0,
+				relayer_fee: 0,
+			}
+		));
+		assert_eq!(
+			<pallet_assets::Pallet<Test> as Inspect<_>>::balance(
+				local_id.clone(),
+				&pallet_token_gateway::Pallet::<Test>::pallet_account()
+			),
+			SEND_AMOUNT
+		);
+
+		// and unlocked when received
+		let asset_id = sp_io::hashing::keccak_256(b"DOT").into();
+		pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(incoming_request(asset_id, SEND_AMOUNT, 10))
+			.unwrap();
+		assert_eq!(
+			<pallet_assets::Pallet<Test> as Inspect<_>>::balance(local_id, &ALICE),
+			SEND_AMOUNT
+		);
+	})
+}
+
+#[test]
+fn should_reject_native_assets_that_do_not_match_the_symbol() {
+	new_test_ext().execute_with(|| {
+		let local_id = AssetIdFactory::asset_id(b"DOT");
+		assert_ok!(pallet_assets::Pallet::<Test>::force_create(
+			RuntimeOrigin::root(),
+			local_id.clone(),
+			ALICE,
+			true,
+			1
+		));
+		assert_ok!(pallet_token_gateway::Pallet::<Test>::register_native_asset(
+			RuntimeOrigin::root(),
+			local_id.clone()
+		));
+
+		let metadata =
+			SolAssetMetadata { name: "DOT".into(), symbol: "DOT".into(), ..Default::default() };
+		assert!(pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(metadata.encode_request()))
+			.is_err());
+		assert!(pallet_token_gateway::SupportedAssets::<Test>::get(local_id).is_none());
+	})
+}
+
+#[test]
+fn should_track_token_gateway_instances_and_asset_admins() {
+	new_test_ext().execute_with(|| {
+		let instance = SolContractInstance {
+			chain: StateMachine::Evm(97).to_string().as_bytes().to_vec().into(),
+			moduleId: H160::repeat_byte(4).0.into(),
+		};
+		pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(instance.encode_request()))
+			.unwrap();
+		assert_eq!(
+			pallet_token_gateway::TokenGatewayAddresses::<Test>::get(StateMachine::Evm(97)),
+			Some(H160::repeat_byte(4).0.to_vec())
+		);
+
+		let (_, asset_id) = setup_asset();
+		let admin = SolChangeAssetAdmin {
+			assetId: asset_id.0.into(),
+			newAdmin: H160::repeat_byte(5).0.into(),
+		};
+		assert_ok!(pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(admin.encode_request())));
+
+		// gateway params only apply to EVM deployments
+		let params = SolTokenGatewayParams {
+			host: H160::repeat_byte(6).0.into(),
+			dispatcher: H160::repeat_byte(7).0.into(),
+		};
+		assert!(pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(params.encode_request()))
+			.is_err());
+
+		// unknown governance actions are rejected
+		assert!(pallet_token_gateway::Pallet::<Test>::default()
+			.on_accept(governance_request(vec![6]))
+			.is_err());
+	})
+}
+
+#[test]
+fn should_burn_assets_and_dispatch_request_on_teleport() {
+	new_test_ext().execute_with(|| {
+		let (local_id, _) = setup_asset();
+		assert_ok!(<pallet_assets::Pallet<Test> as Mutate<_>>::mint_into(
+			local_id.clone(),
+			&ALICE,
+			SEND_AMOUNT
+		));
+
+		assert_ok!(pallet_token_gateway::Pallet::<Test>::teleport(
+			RuntimeOrigin::signed(ALICE),
+			TeleportParams {
+				asset_id: local_id.clone(),
+				destination: StateMachine::Evm(1),
+				recipient: H256::repeat_byte(2),
+				amount: SEND_AMOUNT,
+				timeout: 0,
+				relayer_fee: 0,
+			}
+		));
+
+		assert_eq!(pallet_ismp::Nonce::<Test>::get(), 1);
+		assert_eq!(
+			<pallet_assets::Pallet<Test> as Inspect<_>>::total_issuance(local_id.clone()),
+			0
+		);
+	})
+}
+
+#[test]
+fn should_mint_on_accept_and_refund_on_timeout() {
+	new_test_ext().execute_with(|| {
+		let (local_id, asset_id) = setup_asset();
+		let module = pallet_token_gateway::Pallet::<Test>::default();
+
+		// requests from unknown token gateways are rejected
+		let mut unknown = incoming_request(asset_id, SEND_AMOUNT, ERC20_DECIMALS);
+		unknown.from = H160::repeat_byte(3).0.to_vec();
+		assert!(module.on_accept(unknown).is_err());
+
+		module
+			.on_accept(incoming_request(asset_id, SEND_AMOUNT, ERC20_DECIMALS))
+			.unwrap();
+		assert_eq!(
+			<pallet_assets::Pallet<Test> as Inspect<_>>::balance(local_id.clone(), &ALICE),
+			SEND_AMOUNT
+		);
+
+		let mut timed_out = incoming_request(asset_id, SEND_AMOUNT, ERC20_DECIMALS);
+		timed_out.source = StateMachine::Kusama(100);
+		timed_out.dest = StateMachine::Evm(1);
+		module.on_timeout(Timeout::Request(Request::Post(timed_out))).unwrap();
+		assert_eq!(
+			<pallet_assets::Pallet<Test> as Inspect<_>>::balance(local_id, &ALICE),
+			SEND_AMOUNT * 2
+		);
+	})
+}
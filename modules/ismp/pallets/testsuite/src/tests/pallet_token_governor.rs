@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use crate::runtime::{new_test_ext, RuntimeOrigin, Test};
+use alloy_sol_types::SolValue;
+use frame_support::{
+	assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use ismp::{host::StateMachine, module::IsmpModule, router::PostRequest};
+use pallet_token_governor::{
+	AssetRegistration, ChainAsset, ChainAssetDetails, ChainAssets, ChainWithSupply,
+	ERC20AssetRegistration, ERC6160AssetUpdate, GatewayParams, InitialSupply, RegistrarParams,
+	SolRequestBody, SupportedChains, TokenGatewayParams, TokenRegistrarParams,
+};
+use sp_core::{H160, H256, U256};
+
+fn setup_gateways(chains: &[StateMachine]) {
+	for chain in chains {
+		TokenGatewayParams::<Test>::insert(
+			chain,
+			GatewayParams { address: H160::repeat_byte(1), ..Default::default() },
+		);
+	}
+}
+
+fn register_erc20_asset(symbol: &str, chains: Vec<AssetRegistration>) -> H256 {
+	assert_ok!(pallet_token_governor::Pallet::<Test>::create_erc20_asset(
+		RuntimeOrigin::root(),
+		ERC20AssetRegistration {
+			name: symbol.as_bytes().to_vec().try_into().unwrap(),
+			symbol: symbol.as_bytes().to_vec().try_into().unwrap(),
+			logo: Default::default(),
+			chains,
+		}
+	));
+
+	sp_io::hashing::keccak_256(symbol.as_bytes()).into()
+}
+
+#[test]
+fn should_return_details_of_registered_assets() {
+	new_test_ext().execute_with(|| {
+		setup_gateways(&[StateMachine::Evm(1), StateMachine::Evm(56)]);
+		let asset_id = register_erc20_asset(
+			"USDC",
+			vec![
+				AssetRegistration {
+					chain: StateMachine::Evm(1),
+					erc20: Some(H160::repeat_byte(2)),
+					erc6160: None,
+				},
+				AssetRegistration {
+					chain: StateMachine::Evm(56),
+					erc20: None,
+					erc6160: Some(H160::repeat_byte(3)),
+				},
+			],
+		);
+
+		let details = pallet_token_governor::Pallet::<Test>::asset_details(asset_id).unwrap();
+		assert_eq!(details.asset_id, asset_id);
+		assert_eq!(details.metadata.symbol.to_vec(), b"USDC".to_vec());
+		assert!(details.owner.is_some());
+		assert_eq!(details.chains.len(), 2);
+		assert!(details.chains.contains(&ChainAssetDetails {
+			chain: StateMachine::Evm(1),
+			details: ChainAsset { erc20: Some(H160::repeat_byte(2)), ..Default::default() },
+		}));
+		assert!(details.chains.contains(&ChainAssetDetails {
+			chain: StateMachine::Evm(56),
+			details: ChainAsset { erc6160: Some(H160::repeat_byte(3)), ..Default::default() },
+		}));
+
+		let other = register_erc20_asset(
+			"USDT",
+			vec![AssetRegistration { chain: StateMachine::Evm(1), erc20: None, erc6160: None }],
+		);
+		let assets = pallet_token_governor::Pallet::<Test>::assets();
+		assert_eq!(assets.len(), 2);
+		assert!(assets.iter().any(|asset| asset.asset_id == other));
+		assert!(assets.contains(&details));
+
+		// unknown assets have no details
+		assert!(
+			pallet_token_governor::Pallet::<Test>::asset_details(H256::repeat_byte(1)).is_none()
+		);
+	})
+}
+
+#[test]
+fn should_merge_chain_asset_details_on_update() {
+	new_test_ext().execute_with(|| {
+		setup_gateways(&[StateMachine::Evm(1), StateMachine::Evm(56)]);
+		let asset_id = register_erc20_asset(
+			"USDC",
+			vec![AssetRegistration {
+				chain: StateMachine::Evm(1),
+				erc20: Some(H160::repeat_byte(2)),
+				erc6160: None,
+			}],
+		);
+
+		// contract addresses that are already known are not overwritten
+		ChainAssets::<Test>::insert(
+			asset_id,
+			StateMachine::Evm(56),
+			ChainAsset { erc6160: Some(H160::repeat_byte(3)), ..Default::default() },
+		);
+		let supply =
+			InitialSupply { beneficiary: H160::repeat_byte(4), initial_supply: U256::from(100) };
+		assert_ok!(pallet_token_governor::Pallet::<Test>::update_erc6160_asset(
+			RuntimeOrigin::root(),
+			ERC6160AssetUpdate {
+				asset_id,
+				add_chains: vec![ChainWithSupply {
+					chain: StateMachine::Evm(56),
+					supply: Some(supply.clone()),
+				}]
+				.try_into()
+				.unwrap(),
+				..Default::default()
+			},
+		));
+
+		assert_eq!(
+			ChainAssets::<Test>::get(asset_id, StateMachine::Evm(56)),
+			Some(ChainAsset {
+				supply: Some(supply),
+				erc20: None,
+				erc6160: Some(H160::repeat_byte(3))
+			})
+		);
+		assert_eq!(
+			ChainAssets::<Test>::get(asset_id, StateMachine::Evm(1)),
+			Some(ChainAsset { erc20: Some(H160::repeat_byte(2)), ..Default::default() })
+		);
+	})
+}
+
+#[test]
+fn should_return_pending_assets() {
+	new_test_ext().execute_with(|| {
+		assert!(pallet_token_governor::Pallet::<Test>::pending_assets().is_empty());
+
+		TokenRegistrarParams::<Test>::insert(
+			StateMachine::Evm(1),
+			RegistrarParams { address: H160::repeat_byte(1), ..Default::default() },
+		);
+		let asset_id = H256::repeat_byte(2);
+		let owner = H160::repeat_byte(3);
+		let body = SolRequestBody { owner: owner.0.into(), assetId: asset_id.0.into() };
+		pallet_token_governor::Pallet::<Test>::default()
+			.on_accept(PostRequest {
+				source: StateMachine::Evm(1),
+				dest: StateMachine::Kusama(100),
+				nonce: 0,
+				from: H160::repeat_byte(1).0.to_vec(),
+				to: pallet_token_governor::PALLET_ID.to_vec(),
+				timeout_timestamp: 0,
+				body: body.abi_encode(),
+			})
+			.unwrap();
+
+		assert_eq!(
+			pallet_token_governor::Pallet::<Test>::pending_assets(),
+			vec![(asset_id, owner)]
+		);
+	})
+}
+
+#[test]
+fn should_migrate_chain_assets_of_previously_supported_chains() {
+	new_test_ext().execute_with(|| {
+		let asset_id = H256::repeat_byte(1);
+		SupportedChains::<Test>::insert(asset_id, StateMachine::Evm(1), true);
+		ChainAssets::<Test>::insert(
+			asset_id,
+			StateMachine::Evm(56),
+			ChainAsset { erc20: Some(H160::repeat_byte(2)), ..Default::default() },
+		);
+		SupportedChains::<Test>::insert(asset_id, StateMachine::Evm(56), true);
+		StorageVersion::new(0).put::<pallet_token_governor::Pallet<Test>>();
+
+		pallet_token_governor::Pallet::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			pallet_token_governor::Pallet::<Test>::on_chain_storage_version(),
+			StorageVersion::new(1)
+		);
+		assert_eq!(
+			ChainAssets::<Test>::get(asset_id, StateMachine::Evm(1)),
+			Some(ChainAsset::default())
+		);
+		assert_eq!(
+			ChainAssets::<Test>::get(asset_id, StateMachine::Evm(56)),
+			Some(ChainAsset { erc20: Some(H160::repeat_byte(2)), ..Default::default() })
+		);
+	})
+}
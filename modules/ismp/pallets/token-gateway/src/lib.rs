@@ -0,0 +1,596 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The token gateway enables any ISMP-enabled substrate chain to send and receive multi-chain
+//! native tokens registered on the token governor. It speaks the same protocol as the
+//! `TokenGateway` contract deployed on EVM chains.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod types;
+use alloy_sol_types::SolType;
+use frame_support::{
+	ensure,
+	pallet_prelude::Weight,
+	traits::{
+		fungibles::{self, metadata::Inspect as MetadataInspect, Create, Inspect, Mutate},
+		tokens::{Fortitude, Precision, Preservation},
+	},
+	PalletId,
+};
+use ismp::{
+	events::Meta,
+	router::{PostRequest, Request, Response, Timeout},
+};
+use pallet_token_governor::{
+	convert_to_balance, convert_to_erc20, SolAssetMetadata, SolChangeAssetAdmin,
+	SolContractInstance, SolDeregsiterAsset, ERC20_DECIMALS, MAX_DECIMALS,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::AccountIdConversion;
+pub use types::*;
+
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use ismp::module::IsmpModule;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+/// The module id for this pallet, used to derive the custody account for native assets
+pub const PALLET_ID: PalletId = PalletId(*b"tokengtw");
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use alloc::collections::BTreeMap;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use ismp::{
+		dispatcher::{DispatchPost, DispatchRequest, FeeMetadata, IsmpDispatcher},
+		host::StateMachine,
+	};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The pallet's configuration trait.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_ismp::Config {
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The [`IsmpDispatcher`] for dispatching cross-chain requests
+		type Dispatcher: IsmpDispatcher<Account = Self::AccountId, Balance = Self::Balance>;
+
+		/// Fungible asset implementation
+		type Assets: fungibles::Mutate<Self::AccountId>
+			+ fungibles::Create<Self::AccountId>
+			+ fungibles::metadata::Inspect<Self::AccountId>;
+
+		/// Derives the local asset identifiers of assets registered on the token governor
+		type AssetIdFactory: AssetIdFactory<AssetId<Self>>;
+	}
+
+	/// Maps local asset identifiers to their ERC6160 asset identifier
+	#[pallet::storage]
+	pub type SupportedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetId<T>, H256, OptionQuery>;
+
+	/// Maps ERC6160 asset identifiers to their local asset identifier
+	#[pallet::storage]
+	pub type LocalAssets<T: Config> = StorageMap<_, Identity, H256, AssetId<T>, OptionQuery>;
+
+	/// Assets for which this chain is the reserve. These are locked in the pallet account rather
+	/// than burned when teleported.
+	#[pallet::storage]
+	pub type NativeAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetId<T>, bool, ValueQuery>;
+
+	/// The decimals of each supported asset. Assets created by this pallet use the decimals of
+	/// their ERC6160 representation.
+	#[pallet::storage]
+	pub type Precisions<T: Config> = StorageMap<_, Blake2_128Concat, AssetId<T>, u8, OptionQuery>;
+
+	/// The token gateway module on each connected chain
+	#[pallet::storage]
+	pub type TokenGatewayAddresses<T: Config> =
+		StorageMap<_, Twox64Concat, StateMachine, Vec<u8>, OptionQuery>;
+
+	/// Pallet events that functions in this pallet can emit.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An asset has been teleported to a remote chain
+		AssetTeleported {
+			/// Source account
+			from: T::AccountId,
+			/// Beneficiary account on destination
+			to: H256,
+			/// Amount transferred
+			amount: AssetBalance<T>,
+			/// Destination chain
+			dest: StateMachine,
+			/// Request commitment
+			commitment: H256,
+		},
+		/// An asset has been received from a remote chain
+		AssetReceived {
+			/// Beneficiary account
+			beneficiary: T::AccountId,
+			/// Amount transferred
+			amount: AssetBalance<T>,
+			/// Source chain
+			source: StateMachine,
+		},
+		/// A teleport has timed out and the asset has been refunded
+		AssetRefunded {
+			/// Beneficiary account
+			beneficiary: T::AccountId,
+			/// Amount refunded
+			amount: AssetBalance<T>,
+			/// Destination chain of the timed-out request
+			source: StateMachine,
+		},
+		/// A local asset has been mapped to an ERC6160 asset
+		AssetRegistered {
+			/// The ERC6160 asset identifier
+			asset_id: H256,
+		},
+		/// An asset has been delisted by the token governor
+		AssetDeregistered {
+			/// The ERC6160 asset identifier
+			asset_id: H256,
+		},
+		/// The token governor has changed the admin of an asset on EVM chains
+		AssetAdminChanged {
+			/// The ERC6160 asset identifier
+			asset_id: H256,
+			/// The new admin
+			admin: H160,
+		},
+		/// The token gateway address has been updated for a state machine
+		TokenGatewayAddressUpdated {
+			/// The state machine it was updated for
+			state_machine: StateMachine,
+		},
+		/// An existing local asset has been approved as native to this chain
+		NativeAssetApproved {
+			/// The local asset identifier
+			asset_id: AssetId<T>,
+		},
+	}
+
+	/// Errors that can be returned by this pallet.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The asset has not been registered on this gateway
+		UnregisteredAsset,
+		/// The symbol of the local asset does not match the asset registered on the token governor
+		InvalidAssetId,
+		/// Unknown token gateway instance on the destination
+		UnknownTokenGateway,
+		/// Failed to dispatch a request
+		DispatchFailed,
+		/// The amount can't be represented exactly in the ERC6160 denomination
+		InexactAmount,
+		/// The local asset does not exist
+		UnknownAsset,
+		/// The local asset has already been registered on this gateway
+		AssetAlreadyRegistered,
+		/// The asset decimals exceed the maximum supported decimals
+		InvalidDecimals,
+		/// The local asset exists but has not been approved as native by the admin
+		UnapprovedNativeAsset,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		T::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+		u128: From<AssetBalance<T>>,
+		AssetBalance<T>: From<u128>,
+	{
+		/// Teleports a registered asset to the token gateway on the destination chain. Native
+		/// assets are custodied by the pallet, every other asset is burned.
+		#[pallet::call_index(0)]
+		#[pallet::weight(weight())]
+		pub fn teleport(
+			origin: OriginFor<T>,
+			params: TeleportParams<AssetId<T>, AssetBalance<T>, RelayerFee<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let asset_id = SupportedAssets::<T>::get(params.asset_id.clone())
+				.ok_or_else(|| Error::<T>::UnregisteredAsset)?;
+			let decimals = Precisions::<T>::get(params.asset_id.clone())
+				.ok_or_else(|| Error::<T>::UnregisteredAsset)?;
+			let token_gateway = TokenGatewayAddresses::<T>::get(&params.destination)
+				.ok_or_else(|| Error::<T>::UnknownTokenGateway)?;
+
+			if NativeAssets::<T>::get(params.asset_id.clone()) {
+				T::Assets::transfer(
+					params.asset_id.clone(),
+					&who,
+					&Self::pallet_account(),
+					params.amount,
+					Preservation::Expendable,
+				)?;
+			} else {
+				T::Assets::burn_from(
+					params.asset_id.clone(),
+					&who,
+					params.amount,
+					Precision::Exact,
+					Fortitude::Polite,
+				)?;
+			}
+
+			let body = Body {
+				amount: {
+					let mut bytes = [0u8; 32];
+					convert_to_erc20(u128::from(params.amount), decimals)
+						.map_err(|_| Error::<T>::InexactAmount)?
+						.to_big_endian(&mut bytes);
+					alloy_primitives::U256::from_be_bytes(bytes)
+				},
+				max_fee: Default::default(),
+				asset_id: asset_id.0.into(),
+				redeem: false,
+				from: {
+					let from: [u8; 32] = who.clone().into();
+					from.into()
+				},
+				to: params.recipient.0.into(),
+			};
+
+			let dispatch_post = DispatchPost {
+				dest: params.destination,
+				from: token_gateway.clone(),
+				to: token_gateway,
+				timeout: params.timeout,
+				body: {
+					// Prefix with the handleIncomingAsset enum variant
+					let mut encoded = vec![0];
+					encoded.extend_from_slice(&Body::abi_encode(&body));
+					encoded
+				},
+			};
+
+			let dispatcher = T::Dispatcher::default();
+			let commitment = dispatcher
+				.dispatch_request(
+					DispatchRequest::Post(dispatch_post),
+					FeeMetadata { payer: who.clone(), fee: params.relayer_fee },
+				)
+				.map_err(|_| Error::<T>::DispatchFailed)?;
+
+			Self::deposit_event(Event::<T>::AssetTeleported {
+				from: who,
+				to: params.recipient,
+				dest: params.destination,
+				amount: params.amount,
+				commitment,
+			});
+
+			Ok(())
+		}
+
+		/// Sets the token gateway module for the provided chains
+		#[pallet::call_index(1)]
+		#[pallet::weight(weight())]
+		pub fn set_token_gateway_addresses(
+			origin: OriginFor<T>,
+			addresses: BTreeMap<StateMachine, Vec<u8>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			for (state_machine, address) in addresses {
+				TokenGatewayAddresses::<T>::insert(state_machine, address);
+				Self::deposit_event(Event::<T>::TokenGatewayAddressUpdated { state_machine });
+			}
+
+			Ok(())
+		}
+
+		/// Approves an existing local asset as native to this chain, so that it is locked rather
+		/// than burned when teleported once the token governor registers an asset with the same
+		/// symbol. Existing local assets that haven't been approved can't be registered.
+		#[pallet::call_index(2)]
+		#[pallet::weight(weight())]
+		pub fn register_native_asset(origin: OriginFor<T>, asset_id: AssetId<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(T::Assets::asset_exists(asset_id.clone()), Error::<T>::UnknownAsset);
+			ensure!(
+				!Precisions::<T>::contains_key(asset_id.clone()),
+				Error::<T>::AssetAlreadyRegistered
+			);
+			let decimals = T::Assets::decimals(asset_id.clone());
+			ensure!(decimals <= MAX_DECIMALS, Error::<T>::InvalidDecimals);
+
+			NativeAssets::<T>::insert(asset_id.clone(), true);
+			Precisions::<T>::insert(asset_id.clone(), decimals);
+			Self::deposit_event(Event::<T>::NativeAssetApproved { asset_id });
+
+			Ok(())
+		}
+	}
+
+	// Hack for implementing the [`Default`] bound needed for
+	// [`IsmpModule`](ismp::module::IsmpModule)
+	impl<T> Default for Pallet<T> {
+		fn default() -> Self {
+			Self(PhantomData)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account which custodies native assets that have been teleported
+	pub fn pallet_account() -> T::AccountId {
+		PALLET_ID.into_account_truncating()
+	}
+
+	/// Credits the beneficiary with the given amount of the asset, unlocking native assets from
+	/// the pallet account and minting every other asset.
+	fn credit(
+		asset_id: AssetId<T>,
+		beneficiary: &T::AccountId,
+		amount: AssetBalance<T>,
+	) -> Result<(), sp_runtime::DispatchError> {
+		if NativeAssets::<T>::get(asset_id.clone()) {
+			T::Assets::transfer(
+				asset_id,
+				&Self::pallet_account(),
+				beneficiary,
+				amount,
+				Preservation::Expendable,
+			)?;
+		} else {
+			T::Assets::mint_into(asset_id, beneficiary, amount)?;
+		}
+
+		Ok(())
+	}
+
+	/// Decodes the asset transfer body and resolves the local asset and amount
+	fn decode_body(
+		post: &PostRequest,
+	) -> Result<(Body, AssetId<T>, AssetBalance<T>), ismp::error::Error>
+	where
+		AssetBalance<T>: From<u128>,
+	{
+		let request = Request::Post(post.clone());
+		let meta = Meta {
+			source: request.source_chain(),
+			dest: request.dest_chain(),
+			nonce: request.nonce(),
+		};
+		let body = post
+			.body
+			.split_first()
+			.filter(|(variant, _)| **variant == 0)
+			.and_then(|(_, body)| Body::abi_decode(body, true).ok())
+			.ok_or_else(|| ismp::error::Error::ModuleDispatchError {
+				msg: "Token Gateway: Failed to decode request body".to_string(),
+				meta: meta.clone(),
+			})?;
+
+		let local_id = LocalAssets::<T>::get(H256::from(body.asset_id.0)).ok_or_else(|| {
+			ismp::error::Error::ModuleDispatchError {
+				msg: "Token Gateway: AssetId is unknown".to_string(),
+				meta: meta.clone(),
+			}
+		})?;
+
+		let decimals = Precisions::<T>::get(local_id.clone()).unwrap_or(ERC20_DECIMALS);
+		let amount =
+			convert_to_balance(U256::from_big_endian(&body.amount.to_be_bytes::<32>()), decimals)
+				.map_err(|_| ismp::error::Error::ModuleDispatchError {
+				msg: "Token Gateway: Trying to withdraw Invalid amount".to_string(),
+				meta,
+			})?;
+
+		Ok((body, local_id, amount.into()))
+	}
+}
+
+impl<T: Config> IsmpModule for Pallet<T>
+where
+	T::AccountId: From<[u8; 32]>,
+	AssetBalance<T>: From<u128>,
+{
+	fn on_accept(&self, post: PostRequest) -> Result<(), ismp::error::Error> {
+		// governance actions from the token governor on Hyperbridge
+		if Some(post.source) == T::Coprocessor::get() &&
+			post.from == pallet_token_governor::PALLET_ID.to_vec()
+		{
+			return Self::handle_governance(post);
+		}
+
+		let token_gateway = TokenGatewayAddresses::<T>::get(&post.source);
+		ensure!(
+			token_gateway.as_ref() == Some(&post.from),
+			ismp::error::Error::ModuleDispatchError {
+				msg: "Token Gateway: Unknown source contract address".to_string(),
+				meta: Meta { source: post.source, dest: post.dest, nonce: post.nonce },
+			}
+		);
+
+		let (body, local_id, amount) = Self::decode_body(&post)?;
+		let beneficiary: T::AccountId = body.to.0.into();
+		Self::credit(local_id, &beneficiary, amount).map_err(|err| {
+			ismp::error::Error::ModuleDispatchError {
+				msg: format!("Token Gateway: Failed to credit beneficiary: {err:?}"),
+				meta: Meta { source: post.source, dest: post.dest, nonce: post.nonce },
+			}
+		})?;
+
+		Self::deposit_event(Event::<T>::AssetReceived { beneficiary, amount, source: post.source });
+
+		Ok(())
+	}
+
+	fn on_response(&self, response: Response) -> Result<(), ismp::error::Error> {
+		Err(ismp::error::Error::ModuleDispatchError {
+			msg: "Token Gateway does not accept responses".to_string(),
+			meta: Meta {
+				source: response.source_chain(),
+				dest: response.dest_chain(),
+				nonce: response.nonce(),
+			},
+		})
+	}
+
+	fn on_timeout(&self, request: Timeout) -> Result<(), ismp::error::Error> {
+		match request {
+			Timeout::Request(Request::Post(post)) => {
+				let (body, local_id, amount) = Self::decode_body(&post)?;
+				// refund the sender
+				let beneficiary: T::AccountId = body.from.0.into();
+				Self::credit(local_id, &beneficiary, amount).map_err(|err| {
+					ismp::error::Error::ModuleDispatchError {
+						msg: format!("Token Gateway: Failed to refund sender: {err:?}"),
+						meta: Meta { source: post.source, dest: post.dest, nonce: post.nonce },
+					}
+				})?;
+
+				Self::deposit_event(Event::<T>::AssetRefunded {
+					beneficiary,
+					amount,
+					source: post.dest,
+				});
+
+				Ok(())
+			},
+			Timeout::Request(Request::Get(get)) => Err(ismp::error::Error::ModuleDispatchError {
+				msg: "Tried to timeout unsupported request type".to_string(),
+				meta: Meta { source: get.source, dest: get.dest, nonce: get.nonce },
+			}),
+			Timeout::Response(response) => Err(ismp::error::Error::ModuleDispatchError {
+				msg: "Tried to timeout unsupported request type".to_string(),
+				meta: Meta {
+					source: response.source_chain(),
+					dest: response.dest_chain(),
+					nonce: response.nonce(),
+				},
+			}),
+		}
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	AssetBalance<T>: From<u128>,
+{
+	/// Handles governance requests from the token governor, which tracks the assets and token
+	/// gateway instances of the token network.
+	fn handle_governance(post: PostRequest) -> Result<(), ismp::error::Error> {
+		let meta = Meta { source: post.source, dest: post.dest, nonce: post.nonce };
+		let error = |msg: String| ismp::error::Error::ModuleDispatchError {
+			msg: format!("Token Gateway: {msg}"),
+			meta: meta.clone(),
+		};
+
+		match post.body.split_first() {
+			// Token gateway params only configure the host and call dispatcher contracts of EVM
+			// deployments, so they can't be applied to this gateway
+			Some((1, _)) =>
+				Err(error("Gateway params are only supported on EVM chains".to_string())),
+			// New asset
+			Some((2, body)) => {
+				let metadata = SolAssetMetadata::abi_decode(body, true)
+					.map_err(|err| error(format!("Failed to decode asset metadata: {err}")))?;
+				Self::register_asset(metadata.symbol.as_bytes())
+					.map_err(|err| error(format!("Failed to register asset: {err:?}")))
+			},
+			// Delisted assets
+			Some((3, body)) => {
+				let SolDeregsiterAsset { assetIds } = SolDeregsiterAsset::abi_decode(body, true)
+					.map_err(|err| error(format!("Failed to decode delisted assets: {err}")))?;
+				for asset_id in assetIds {
+					let asset_id = H256::from(asset_id.0);
+					if let Some(local_id) = LocalAssets::<T>::take(asset_id) {
+						SupportedAssets::<T>::remove(local_id);
+						Self::deposit_event(Event::<T>::AssetDeregistered { asset_id });
+					}
+				}
+
+				Ok(())
+			},
+			// New asset admin. Assets created by this pallet are administered by the pallet
+			// account, so this only changes the admin of the asset on EVM chains.
+			Some((4, body)) => {
+				let SolChangeAssetAdmin { assetId, newAdmin } =
+					SolChangeAssetAdmin::abi_decode(body, true)
+						.map_err(|err| error(format!("Failed to decode asset admin: {err}")))?;
+				Self::deposit_event(Event::<T>::AssetAdminChanged {
+					asset_id: H256::from(assetId.0),
+					admin: H160::from(newAdmin.0 .0),
+				});
+
+				Ok(())
+			},
+			// New contract instance
+			Some((5, body)) => {
+				let instance = SolContractInstance::abi_decode(body, true)
+					.map_err(|err| error(format!("Failed to decode contract instance: {err}")))?;
+				let state_machine = core::str::from_utf8(&instance.chain)
+					.ok()
+					.and_then(|chain| chain.parse().ok())
+					.ok_or_else(|| error("Invalid state machine identifier".to_string()))?;
+
+				TokenGatewayAddresses::<T>::insert(state_machine, instance.moduleId.0.to_vec());
+				Self::deposit_event(Event::<T>::TokenGatewayAddressUpdated { state_machine });
+
+				Ok(())
+			},
+			_ => Err(error("Unsupported governance action".to_string())),
+		}
+	}
+
+	/// Maps an asset registered on the token governor to its local asset. Local assets which
+	/// don't exist yet are created and custodied by the pallet. Existing local assets must have
+	/// been approved as native by the admin, these are locked rather than burned when teleported.
+	fn register_asset(symbol: &[u8]) -> Result<(), sp_runtime::DispatchError> {
+		let asset_id: H256 = sp_io::hashing::keccak_256(symbol).into();
+		let local_id = T::AssetIdFactory::asset_id(symbol);
+
+		// assets which were delisted and are listed again keep their decimals and custody
+		if NativeAssets::<T>::get(local_id.clone()) {
+			ensure!(T::Assets::symbol(local_id.clone()) == symbol, Error::<T>::InvalidAssetId);
+		} else if !Precisions::<T>::contains_key(local_id.clone()) {
+			ensure!(!T::Assets::asset_exists(local_id.clone()), Error::<T>::UnapprovedNativeAsset);
+			T::Assets::create(local_id.clone(), Self::pallet_account(), true, 1u128.into())?;
+			Precisions::<T>::insert(local_id.clone(), ERC20_DECIMALS);
+		}
+
+		SupportedAssets::<T>::insert(local_id.clone(), asset_id);
+		LocalAssets::<T>::insert(asset_id, local_id);
+		Self::deposit_event(Event::<T>::AssetRegistered { asset_id });
+
+		Ok(())
+	}
+}
+
+/// Static weights because benchmarks suck, and we'll be getting PolkaVM soon anyways
+fn weight() -> Weight {
+	Weight::from_parts(300_000_000, 0)
+}
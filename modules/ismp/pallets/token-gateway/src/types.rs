@@ -0,0 +1,77 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pallet types
+
+use frame_support::{pallet_prelude::*, traits::fungibles};
+use ismp::host::StateMachine;
+use primitive_types::H256;
+
+use crate::Config;
+
+/// Asset balance type
+pub type AssetBalance<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Asset id type
+pub type AssetId<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+
+/// Relayer fee balance type
+pub type RelayerFee<T> = <T as pallet_ismp::Config>::Balance;
+
+/// Parameters for teleporting an asset to a remote chain
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct TeleportParams<AssetId, Balance, RelayerFee> {
+	/// Local asset identifier
+	pub asset_id: AssetId,
+	/// The destination state machine
+	pub destination: StateMachine,
+	/// Recipient address on the destination, EVM addresses should be left-padded with zeros
+	pub recipient: H256,
+	/// Amount of the asset to be sent
+	pub amount: Balance,
+	/// Request timeout in seconds
+	pub timeout: u64,
+	/// Relayer fee in the native token, paid by the sender
+	pub relayer_fee: RelayerFee,
+}
+
+/// Derives the local asset identifier of ERC6160 assets registered on the token governor
+pub trait AssetIdFactory<AssetId> {
+	/// Returns the local asset identifier for the asset with the given symbol. Assets that
+	/// already exist locally under this identifier must be approved as native to this chain with
+	/// `register_native_asset` before they can be registered.
+	fn asset_id(symbol: &[u8]) -> AssetId;
+}
+
+alloy_sol_macro::sol! {
+	#![sol(all_derives)]
+
+	struct Body {
+		// Amount of the asset to be sent
+		uint256 amount;
+		// Maximum amount to pay for liquidity fees
+		uint256 max_fee;
+		// The asset identifier
+		bytes32 asset_id;
+		// Flag to redeem the erc20 asset on the destination
+		bool redeem;
+		// Sender address
+		bytes32 from;
+		// Recipient address
+		bytes32 to;
+	}
+}
@@ -0,0 +1,257 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![deny(missing_docs)]
+
+//! RPC API Implementation for pallet-token-governor
+//!
+//! Exposes the assets registered on the token governor, along with the TokenGateway and
+//! TokenRegistrar parameters for every chain, so that frontends and indexers do not need to
+//! decode raw storage.
+
+use codec::Codec;
+use ismp::host::StateMachine;
+use jsonrpsee::{
+	core::{Error as RpcError, RpcResult},
+	proc_macros::rpc,
+	types::{error::CallError, ErrorObject},
+};
+use pallet_token_governor::{AssetDetails, ChainAssetDetails, GatewayParams, RegistrarParams};
+use pallet_token_governor_runtime_api::TokenGovernorRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(e: impl std::fmt::Display) -> RpcError {
+	RpcError::Call(CallError::Custom(ErrorObject::owned(
+		9877, // no real reason for this value
+		"Something wrong",
+		Some(format!("{}", e)),
+	)))
+}
+
+/// Deployment of an asset on a single chain
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ChainAsset {
+	/// The supported chain
+	pub chain: StateMachine,
+	/// Beneficiary of the initial supply on this chain
+	pub beneficiary: Option<H160>,
+	/// Initial supply minted on this chain
+	pub initial_supply: Option<U256>,
+	/// ERC20 contract address on this chain, if known
+	pub erc20: Option<H160>,
+	/// ERC6160 contract address on this chain, if known
+	pub erc6160: Option<H160>,
+}
+
+/// A registered multi-chain asset
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Asset<AccountId> {
+	/// The asset identifier
+	pub asset_id: H256,
+	/// The asset name
+	pub name: String,
+	/// The asset symbol
+	pub symbol: String,
+	/// The asset logo
+	pub logo: Bytes,
+	/// The account which owns this asset
+	pub owner: Option<AccountId>,
+	/// Chains the asset has been deployed to
+	pub chains: Vec<ChainAsset>,
+}
+
+/// An asset which has been paid for through a TokenRegistrar but not yet registered
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct PendingAsset {
+	/// The asset identifier
+	pub asset_id: H256,
+	/// The address which paid for the asset registration
+	pub owner: H160,
+}
+
+/// TokenGateway parameters on a chain
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct TokenGateway {
+	/// The chain
+	pub chain: StateMachine,
+	/// The Ismp host address
+	pub host: H160,
+	/// Contract for dispatching calls in `AssetWithCall`
+	pub call_dispatcher: H160,
+	/// Token gateway address
+	pub address: H160,
+}
+
+/// TokenRegistrar parameters on a chain
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct TokenRegistrar {
+	/// The chain
+	pub chain: StateMachine,
+	/// The Ismp host address
+	pub host: H160,
+	/// Registration base fee
+	pub base_fee: U256,
+	/// Token registrar address
+	pub address: H160,
+}
+
+impl<AccountId> From<AssetDetails<AccountId>> for Asset<AccountId> {
+	fn from(value: AssetDetails<AccountId>) -> Self {
+		Asset {
+			asset_id: value.asset_id,
+			name: String::from_utf8_lossy(&value.metadata.name).into_owned(),
+			symbol: String::from_utf8_lossy(&value.metadata.symbol).into_owned(),
+			logo: value.metadata.logo.into_inner().into(),
+			owner: value.owner,
+			chains: value
+				.chains
+				.into_iter()
+				.map(|ChainAssetDetails { chain, details }| ChainAsset {
+					chain,
+					beneficiary: details.supply.as_ref().map(|supply| supply.beneficiary),
+					initial_supply: details.supply.map(|supply| supply.initial_supply),
+					erc20: details.erc20,
+					erc6160: details.erc6160,
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Token governor RPC methods.
+#[rpc(client, server)]
+pub trait TokenGovernorApi<Hash, AccountId> {
+	/// Query all assets registered on the token governor
+	#[method(name = "tokenGovernor_queryAssets")]
+	fn query_assets(&self, at: Option<Hash>) -> RpcResult<Vec<Asset<AccountId>>>;
+
+	/// Query a single asset registered on the token governor
+	#[method(name = "tokenGovernor_queryAsset")]
+	fn query_asset(&self, asset_id: H256, at: Option<Hash>) -> RpcResult<Option<Asset<AccountId>>>;
+
+	/// Query asset registrations that were paid for through a TokenRegistrar but have not yet
+	/// been completed
+	#[method(name = "tokenGovernor_queryPendingAssets")]
+	fn query_pending_assets(&self, at: Option<Hash>) -> RpcResult<Vec<PendingAsset>>;
+
+	/// Query the TokenGateway parameters on all chains
+	#[method(name = "tokenGovernor_queryGatewayParams")]
+	fn query_gateway_params(&self, at: Option<Hash>) -> RpcResult<Vec<TokenGateway>>;
+
+	/// Query the TokenRegistrar parameters on all chains
+	#[method(name = "tokenGovernor_queryRegistrarParams")]
+	fn query_registrar_params(&self, at: Option<Hash>) -> RpcResult<Vec<TokenRegistrar>>;
+}
+
+/// An implementation of the token governor RPC methods.
+pub struct TokenGovernorRpcHandler<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> TokenGovernorRpcHandler<C, B> {
+	/// Create new `TokenGovernorRpcHandler` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> TokenGovernorApiServer<Block::Hash, AccountId>
+	for TokenGovernorRpcHandler<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TokenGovernorRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+	fn query_assets(&self, at: Option<Block::Hash>) -> RpcResult<Vec<Asset<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		let assets = api
+			.assets(at)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching assets"))?;
+
+		Ok(assets.into_iter().map(Into::into).collect())
+	}
+
+	fn query_asset(
+		&self,
+		asset_id: H256,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<Asset<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		let asset = api
+			.asset(at, asset_id)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching asset"))?;
+
+		Ok(asset.map(Into::into))
+	}
+
+	fn query_pending_assets(&self, at: Option<Block::Hash>) -> RpcResult<Vec<PendingAsset>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		let pending = api
+			.pending_assets(at)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching pending assets"))?;
+
+		Ok(pending
+			.into_iter()
+			.map(|(asset_id, owner)| PendingAsset { asset_id, owner })
+			.collect())
+	}
+
+	fn query_gateway_params(&self, at: Option<Block::Hash>) -> RpcResult<Vec<TokenGateway>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		let params = api
+			.gateway_params(at)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching gateway params"))?;
+
+		Ok(params
+			.into_iter()
+			.map(|(chain, GatewayParams { host, call_dispatcher, address })| TokenGateway {
+				chain,
+				host,
+				call_dispatcher,
+				address,
+			})
+			.collect())
+	}
+
+	fn query_registrar_params(&self, at: Option<Block::Hash>) -> RpcResult<Vec<TokenRegistrar>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(self.client.info().best_hash);
+		let params = api
+			.registrar_params(at)
+			.map_err(|_| runtime_error_into_rpc_error("Error fetching registrar params"))?;
+
+		Ok(params
+			.into_iter()
+			.map(|(chain, RegistrarParams { host, base_fee, address })| TokenRegistrar {
+				chain,
+				host,
+				base_fee,
+				address,
+			})
+			.collect())
+	}
+}
@@ -0,0 +1,47 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pallet-token-governor runtime Apis
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![deny(missing_docs)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use ismp::host::StateMachine;
+use pallet_token_governor::{AssetDetails, GatewayParams, RegistrarParams};
+use primitive_types::{H160, H256};
+
+sp_api::decl_runtime_apis! {
+	/// Runtime APIs for discovering assets registered on the token governor
+	pub trait TokenGovernorRuntimeApi<AccountId: codec::Codec> {
+		/// Return the details of all registered assets
+		fn assets() -> Vec<AssetDetails<AccountId>>;
+
+		/// Return the details of the asset with the given identifier
+		fn asset(asset_id: H256) -> Option<AssetDetails<AccountId>>;
+
+		/// Return all pending unsigned asset registrations and their owners
+		fn pending_assets() -> Vec<(H256, H160)>;
+
+		/// Return the TokenGateway parameters for all chains
+		fn gateway_params() -> Vec<(StateMachine, GatewayParams)>;
+
+		/// Return the TokenRegistrar parameters for all chains
+		fn registrar_params() -> Vec<(StateMachine, RegistrarParams)>;
+	}
+}
@@ -15,7 +15,7 @@
 
 // Pallet Implementations
 
-use alloc::{collections::BTreeMap, vec};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
 use alloy_sol_types::SolValue;
 use frame_support::{ensure, PalletId};
 use frame_system::{pallet_prelude::OriginFor, RawOrigin};
@@ -27,9 +27,10 @@ use sp_core::{H160, H256};
 use sp_runtime::traits::AccountIdConversion;
 
 use crate::{
-	AssetMetadata, AssetMetadatas, AssetOwners, AssetRegistration, ChainWithSupply, Config,
-	ContractInstance, ERC20AssetRegistration, ERC6160AssetRegistration, ERC6160AssetUpdate, Error,
-	Event, GatewayParams, Pallet, PendingAsset, RegistrarParamsUpdate, SolAssetMetadata,
+	AssetDetails, AssetMetadata, AssetMetadatas, AssetOwners, AssetRegistration, ChainAsset,
+	ChainAssetDetails, ChainAssets, ChainWithSupply, Config, ContractInstance,
+	ERC20AssetRegistration, ERC6160AssetRegistration, ERC6160AssetUpdate, Error, Event,
+	GatewayParams, Pallet, PendingAsset, RegistrarParams, RegistrarParamsUpdate, SolAssetMetadata,
 	SolChangeAssetAdmin, SolContractInstance, SolDeregsiterAsset, SolRegistrarParams,
 	SolTokenGatewayParams, SupportedChains, TokenGatewayParams, TokenGatewayParamsUpdate,
 	TokenGatewayRequest, TokenRegistrarParams, UnsignedERC6160AssetRegistration, PALLET_ID,
@@ -76,7 +77,7 @@ where
 			let mut body: SolAssetMetadata =
 				metadata.clone().try_into().map_err(|_| Error::<T>::InvalidUtf8)?;
 
-			if let Some(supply) = supply {
+			if let Some(ref supply) = supply {
 				body.beneficiary = supply.beneficiary.0.into();
 				body.initialSupply = alloy_primitives::U256::from_limbs(supply.initial_supply.0);
 			}
@@ -98,7 +99,15 @@ where
 				)
 				.map_err(|_| Error::<T>::DispatchFailed)?;
 			// tracks which chains the asset is deployed on
-			SupportedChains::<T>::insert(asset_id, chain, true);
+			SupportedChains::<T>::insert(asset_id, chain.clone(), true);
+			ChainAssets::<T>::mutate(asset_id, chain, |details| {
+				*details = Some(
+					details
+						.clone()
+						.unwrap_or_default()
+						.update(ChainAsset { supply, ..Default::default() }),
+				)
+			});
 		}
 
 		AssetMetadatas::<T>::insert(asset_id, metadata);
@@ -168,7 +177,7 @@ where
 			let mut body: SolAssetMetadata =
 				metadata.clone().try_into().map_err(|_| Error::<T>::InvalidUtf8)?;
 
-			if let Some(supply) = supply {
+			if let Some(ref supply) = supply {
 				body.beneficiary = supply.beneficiary.0.into();
 				body.initialSupply = alloy_primitives::U256::from_limbs(supply.initial_supply.0);
 			}
@@ -186,7 +195,15 @@ where
 				)
 				.map_err(|_| Error::<T>::DispatchFailed)?;
 			// tracks which chains the asset is deployed on
-			SupportedChains::<T>::insert(update.asset_id, chain, true);
+			SupportedChains::<T>::insert(update.asset_id, chain.clone(), true);
+			ChainAssets::<T>::mutate(update.asset_id, chain, |details| {
+				*details = Some(
+					details
+						.clone()
+						.unwrap_or_default()
+						.update(ChainAsset { supply, ..Default::default() }),
+				)
+			});
 		}
 
 		for chain in update.remove_chains {
@@ -376,11 +393,11 @@ where
 			let mut body: SolAssetMetadata =
 				metadata.clone().try_into().map_err(|_| Error::<T>::InvalidUtf8)?;
 
-			if let Some(erc20) = erc20 {
+			if let Some(ref erc20) = erc20 {
 				body.erc20 = erc20.0.into();
 			}
 
-			if let Some(erc6160) = erc6160 {
+			if let Some(ref erc6160) = erc6160 {
 				body.erc6160 = erc6160.0.into();
 			}
 
@@ -401,7 +418,14 @@ where
 				)
 				.map_err(|_| Error::<T>::DispatchFailed)?;
 			// tracks which chains the asset is deployed on
-			SupportedChains::<T>::insert(asset_id, chain, true);
+			SupportedChains::<T>::insert(asset_id, chain.clone(), true);
+			ChainAssets::<T>::mutate(asset_id, chain, |details| {
+				*details = Some(details.clone().unwrap_or_default().update(ChainAsset {
+					supply: None,
+					erc20,
+					erc6160,
+				}))
+			});
 		}
 
 		AssetMetadatas::<T>::insert(asset_id, metadata);
@@ -412,3 +436,39 @@ where
 		Ok(())
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// Returns the details of a registered asset across all the chains it has been deployed to
+	pub fn asset_details(asset_id: H256) -> Option<AssetDetails<T::AccountId>> {
+		let metadata = AssetMetadatas::<T>::get(&asset_id)?;
+		let chains = SupportedChains::<T>::iter_prefix(&asset_id)
+			.map(|(chain, _)| ChainAssetDetails {
+				details: ChainAssets::<T>::get(&asset_id, &chain).unwrap_or_default(),
+				chain,
+			})
+			.collect();
+
+		Some(AssetDetails { asset_id, metadata, owner: AssetOwners::<T>::get(&asset_id), chains })
+	}
+
+	/// Returns the details of all registered assets
+	pub fn assets() -> Vec<AssetDetails<T::AccountId>> {
+		AssetMetadatas::<T>::iter_keys().filter_map(Self::asset_details).collect()
+	}
+
+	/// Returns all pending asset registrations paid for through the TokenRegistrar, along with
+	/// their owners
+	pub fn pending_assets() -> Vec<(H256, H160)> {
+		PendingAsset::<T>::iter().collect()
+	}
+
+	/// Returns the TokenGateway parameters for all chains
+	pub fn gateway_params() -> Vec<(StateMachine, GatewayParams)> {
+		TokenGatewayParams::<T>::iter().collect()
+	}
+
+	/// Returns the TokenRegistrar parameters for all chains
+	pub fn registrar_params() -> Vec<(StateMachine, RegistrarParams)> {
+		TokenRegistrarParams::<T>::iter().collect()
+	}
+}
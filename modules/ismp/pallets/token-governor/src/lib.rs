@@ -20,6 +20,7 @@
 extern crate alloc;
 
 mod impls;
+mod migration;
 mod types;
 use alloy_sol_types::SolValue;
 use frame_support::pallet_prelude::Weight;
@@ -49,7 +50,11 @@ pub mod pallet {
 	use ismp::{dispatcher::IsmpDispatcher, host::StateMachine};
 	use sp_runtime::traits::AccountIdConversion;
 
+	use migration::StorageV0;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -79,6 +84,12 @@ pub mod pallet {
 	pub type SupportedChains<T: Config> =
 		StorageDoubleMap<_, Identity, H256, Twox64Concat, StateMachine, bool, OptionQuery>;
 
+	/// Tracks the initial supply and token contract addresses of assets on each chain they have
+	/// been deployed to
+	#[pallet::storage]
+	pub type ChainAssets<T: Config> =
+		StorageDoubleMap<_, Identity, H256, Twox64Concat, StateMachine, ChainAsset, OptionQuery>;
+
 	/// Mapping of AssetId to their metadata
 	#[pallet::storage]
 	pub type AssetMetadatas<T: Config> = StorageMap<_, Identity, H256, AssetMetadata, OptionQuery>;
@@ -167,6 +178,13 @@ pub mod pallet {
 		UnknownTokenGateway,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			StorageV0::migrate_to_v1::<T>()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T>
 	where
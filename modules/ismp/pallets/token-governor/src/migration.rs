@@ -0,0 +1,51 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+pub use storage_v0::*;
+pub mod storage_v0 {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::{Get, GetStorageVersion, StorageVersion},
+		weights::Weight,
+	};
+
+	pub struct StorageV0 {}
+
+	impl StorageV0 {
+		/// Tracks the deployment details of assets which were dispatched to chains before
+		/// [`ChainAssets`] was introduced. Their initial supply and contract addresses were never
+		/// stored, so they are left empty.
+		pub fn migrate_to_v1<T: Config>() -> Weight {
+			return if Pallet::<T>::on_chain_storage_version() == 0 {
+				let mut reads = 0u64;
+				let mut writes = 0u64;
+				for (asset_id, chain, _) in SupportedChains::<T>::iter() {
+					reads += 2;
+					if !ChainAssets::<T>::contains_key(&asset_id, &chain) {
+						ChainAssets::<T>::insert(asset_id, chain, ChainAsset::default());
+						writes += 1;
+					}
+				}
+				log::info!(target: "pallet_token_governor", "Migrated ChainAssets storage on {} keys", writes);
+				StorageVersion::new(1).put::<Pallet<T>>();
+				T::DbWeight::get().reads_writes(reads, writes + 1)
+			} else {
+				Weight::zero()
+			};
+		}
+	}
+}
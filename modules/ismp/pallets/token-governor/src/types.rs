@@ -73,6 +73,39 @@ pub struct ChainWithSupply {
 	pub supply: Option<InitialSupply>,
 }
 
+/// Tracks the deployment of an asset on a single chain
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq, Default)]
+pub struct ChainAsset {
+	/// Initial supply minted on this chain when the asset was created
+	pub supply: Option<InitialSupply>,
+	/// ERC20 contract address on this chain, if known
+	pub erc20: Option<H160>,
+	/// ERC6160 contract address on this chain, if known
+	pub erc6160: Option<H160>,
+}
+
+/// Describes a registered asset on a single chain, as returned by the runtime API
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct ChainAssetDetails {
+	/// The supported chain
+	pub chain: StateMachine,
+	/// Deployment details on this chain
+	pub details: ChainAsset,
+}
+
+/// Describes a registered asset across all chains, as returned by the runtime API
+#[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq)]
+pub struct AssetDetails<AccountId> {
+	/// The asset identifier
+	pub asset_id: H256,
+	/// The asset metadata
+	pub metadata: AssetMetadata,
+	/// The account which owns this asset
+	pub owner: Option<AccountId>,
+	/// Chains the asset has been deployed to
+	pub chains: Vec<ChainAssetDetails>,
+}
+
 /// Protocol parameters
 #[derive(Debug, Clone, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq, Default)]
 pub struct Params<Balance> {
@@ -200,6 +233,28 @@ impl<B: Clone> Params<B> {
 	}
 }
 
+impl ChainAsset {
+	/// Convenience method for updating the deployment details, fields which are not provided in
+	/// the update are left untouched
+	pub fn update(&self, update: ChainAsset) -> ChainAsset {
+		let mut asset = self.clone();
+
+		if let Some(supply) = update.supply {
+			asset.supply = Some(supply);
+		}
+
+		if let Some(erc20) = update.erc20 {
+			asset.erc20 = Some(erc20);
+		}
+
+		if let Some(erc6160) = update.erc6160 {
+			asset.erc6160 = Some(erc6160);
+		}
+
+		asset
+	}
+}
+
 impl RegistrarParams {
 	/// Convenience method for updating protocol params
 	pub fn update<T: crate::Config>(
@@ -414,3 +469,105 @@ impl TokenGatewayRequest for SolContractInstance {
 		[variant, encoded].concat()
 	}
 }
+
+/// Decimals of the ERC6160 representation of all assets
+pub const ERC20_DECIMALS: u8 = 18;
+
+/// The maximum decimals an asset may be registered with, so that the scaling factor to and from
+/// [`ERC20_DECIMALS`] always fits in a u128
+pub const MAX_DECIMALS: u8 = 36;
+
+/// Returns the factor between the given decimals and [`ERC20_DECIMALS`]
+fn scaling_factor(decimals: u8) -> Result<U256, anyhow::Error> {
+	if decimals > MAX_DECIMALS {
+		Err(anyhow!("Decimals {decimals} exceed the maximum of {MAX_DECIMALS}"))?
+	}
+	Ok(U256::exp10(decimals.abs_diff(ERC20_DECIMALS) as usize))
+}
+
+/// Converts an ERC20 U256 to a u128 balance with the given decimals
+pub fn convert_to_balance(value: U256, decimals: u8) -> Result<u128, anyhow::Error> {
+	let factor = scaling_factor(decimals)?;
+	let value = if decimals <= ERC20_DECIMALS {
+		value / factor
+	} else {
+		value.checked_mul(factor).ok_or_else(|| anyhow!("Balance overflow"))?
+	};
+	let dec_str = value.to_string();
+	dec_str.parse().map_err(|e| anyhow!("{e:?}"))
+}
+
+/// Converts a u128 balance with the given decimals to an Erc20 denomination.
+/// Balances with more than [`ERC20_DECIMALS`] must be exactly representable in the Erc20
+/// denomination, otherwise the truncated remainder would be lost.
+pub fn convert_to_erc20(value: u128, decimals: u8) -> Result<U256, anyhow::Error> {
+	let factor = scaling_factor(decimals)?;
+	if decimals <= ERC20_DECIMALS {
+		U256::from(value).checked_mul(factor).ok_or_else(|| anyhow!("Balance overflow"))
+	} else {
+		let (value, remainder) = U256::from(value).div_mod(factor);
+		if !remainder.is_zero() {
+			Err(anyhow!("Balance {remainder} is not representable with {ERC20_DECIMALS} decimals"))?
+		}
+		Ok(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{convert_to_balance, convert_to_erc20, MAX_DECIMALS};
+	use primitive_types::U256;
+
+	#[test]
+	fn balance_conversions() {
+		let supposedly_small_u256 = U256::from_dec_str("1000000000000000000").unwrap();
+		// convert erc20 value to dot value
+		let converted_balance = convert_to_balance(supposedly_small_u256, 10).unwrap();
+		let dot = 10_000_000_000u128;
+		assert_eq!(converted_balance, dot);
+
+		// Convert 1 dot to erc20
+		let erc_20_val = convert_to_erc20(dot, 10).unwrap();
+		assert_eq!(erc_20_val, U256::from_dec_str("1000000000000000000").unwrap());
+	}
+
+	#[test]
+	fn max_value_check() {
+		let max = U256::MAX;
+
+		let converted_balance = convert_to_balance(max, 10);
+		assert!(converted_balance.is_err())
+	}
+
+	#[test]
+	fn min_value_check() {
+		let min = U256::from(1u128);
+
+		let converted_balance = convert_to_balance(min, 10).unwrap();
+		assert_eq!(converted_balance, 0);
+	}
+
+	#[test]
+	fn balance_conversions_with_decimals() {
+		let one_token = U256::from_dec_str("1000000000000000000").unwrap();
+
+		assert_eq!(convert_to_balance(one_token, 6).unwrap(), 1_000_000);
+		assert_eq!(convert_to_balance(one_token, 18).unwrap(), 1_000_000_000_000_000_000);
+		assert_eq!(convert_to_balance(one_token, 20).unwrap(), 100_000_000_000_000_000_000);
+
+		assert_eq!(convert_to_erc20(1_000_000, 6).unwrap(), one_token);
+		assert_eq!(convert_to_erc20(100_000_000_000_000_000_000, 20).unwrap(), one_token);
+	}
+
+	#[test]
+	fn rejects_inexact_and_out_of_bounds_conversions() {
+		// the remainder of balances with more than 18 decimals can't be represented
+		assert!(convert_to_erc20(100_000_000_000_000_000_001, 20).is_err());
+
+		// decimals are bounded, rather than panicking on the scaling factor
+		assert!(convert_to_erc20(1, MAX_DECIMALS).is_ok());
+		assert!(convert_to_erc20(1, MAX_DECIMALS + 1).is_err());
+		assert!(convert_to_erc20(1, u8::MAX).is_err());
+		assert!(convert_to_balance(U256::one(), u8::MAX).is_err());
+	}
+}
@@ -23,10 +23,12 @@ use codec::{Decode, Encode};
 use log::{info, trace};
 use sc_client_api::backend::AuxStore;
 use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_mmr_primitives::LeafIndex;
 use sp_runtime::traits::{Block, NumberFor};
 
 const VERSION_KEY: &[u8] = b"mmr_auxschema_version";
 const GADGET_STATE: &[u8] = b"mmr_gadget_state";
+const PRUNED_LEAVES: &[u8] = b"mmr_gadget_pruned_leaves";
 
 const CURRENT_VERSION: u32 = 1;
 pub(crate) type PersistedState<B> = NumberFor<B>;
@@ -45,6 +47,20 @@ pub(crate) fn write_gadget_state<B: Block, BE: AuxStore>(
 	backend.insert_aux(&[(GADGET_STATE, state.encode().as_slice())], &[])
 }
 
+/// Write the number of leaves whose canonical nodes have been pruned.
+pub(crate) fn write_pruned_leaves<BE: AuxStore>(
+	backend: &BE,
+	pruned: LeafIndex,
+) -> ClientResult<()> {
+	trace!(target: LOG_TARGET, "Storing pruned leaf count {:?}", pruned);
+	backend.insert_aux(&[(PRUNED_LEAVES, pruned.encode().as_slice())], &[])
+}
+
+/// Load the number of leaves whose canonical nodes have been pruned.
+pub(crate) fn load_pruned_leaves<BE: AuxStore>(backend: &BE) -> ClientResult<LeafIndex> {
+	Ok(load_decode(backend, PRUNED_LEAVES)?.unwrap_or_default())
+}
+
 fn load_decode<B: AuxStore, T: Decode>(backend: &B, key: &[u8]) -> ClientResult<Option<T>> {
 	match backend.get_aux(key)? {
 		None => Ok(None),
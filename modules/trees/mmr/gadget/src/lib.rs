@@ -34,6 +34,9 @@
 //! This gadget is driven by block finality and in responsible for pruning stale forks from
 //! offchain db, and moving finalized forks under a "canonical" key based solely on node `pos`
 //! in the MMR.
+//!
+//! Canonical nodes are kept forever by default, see [`MmrPruning`] for bounding their disk usage.
+//! A corrupted or pruned offchain MMR can be restored with [`rebuild_offchain_mmr`].
 
 #![warn(missing_docs)]
 
@@ -42,8 +45,10 @@ extern crate sp_runtime;
 
 mod aux_schema;
 mod offchain_mmr;
+mod rebuild;
 
 use crate::offchain_mmr::OffchainMmr;
+pub use crate::rebuild::rebuild_offchain_mmr;
 use futures::StreamExt;
 use log::{error, trace, warn};
 use pallet_ismp::mmr::Leaf;
@@ -54,7 +59,7 @@ use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_mmr_primitives::LeafIndex;
 use sp_runtime::traits::{Block, Header, NumberFor};
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, str::FromStr, sync::Arc};
 
 /// Logging target for the mmr gadget.
 pub const LOG_TARGET: &str = "mmr";
@@ -62,6 +67,33 @@ pub const LOG_TARGET: &str = "mmr";
 /// Hashing Output
 pub type HashFor<B> = <<B as Block>::Header as Header>::Hash;
 
+/// Retention policy for the canonical MMR nodes in the offchain db.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MmrPruning {
+	/// Keep every canonical node, so that proofs can be generated for any leaf.
+	#[default]
+	Archive,
+	/// Only keep the nodes needed to prove the latest `n` leaves. Proofs for older leaves can no
+	/// longer be generated and their content can no longer be queried.
+	KeepLeaves(u64),
+}
+
+impl FromStr for MmrPruning {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"archive" => Ok(MmrPruning::Archive),
+			leaves => match leaves.parse::<u64>() {
+				Ok(leaves) if leaves > 0 => Ok(MmrPruning::KeepLeaves(leaves)),
+				_ => Err(format!(
+					"Invalid MMR pruning mode {s:?}, expected `archive` or a number of leaves"
+				)),
+			},
+		}
+	}
+}
+
 /// A convenience MMR client trait that defines all the type bounds a MMR client
 /// has to satisfy and defines some helper methods.
 pub trait MmrClient<B, BE>:
@@ -102,6 +134,7 @@ struct OffchainMmrBuilder<B: Block, BE: Backend<B>, C> {
 	client: Arc<C>,
 	offchain_db: OffchainDb<BE::OffchainStorage>,
 	indexing_prefix: Vec<u8>,
+	pruning: MmrPruning,
 	_phantom: PhantomData<B>,
 }
 
@@ -124,6 +157,7 @@ where
 					self.offchain_db,
 					self.indexing_prefix,
 					first_mmr_block_num,
+					self.pruning,
 				)?;
 
 				// We need to make sure all blocks leading up to current notification
@@ -173,7 +207,12 @@ where
 	}
 
 	/// Create and run the MMR gadget.
-	pub async fn start(client: Arc<C>, backend: Arc<BE>, indexing_prefix: Vec<u8>) {
+	pub async fn start(
+		client: Arc<C>,
+		backend: Arc<BE>,
+		indexing_prefix: Vec<u8>,
+		pruning: MmrPruning,
+	) {
 		let offchain_db = match backend.offchain_storage() {
 			Some(offchain_storage) => OffchainDb::new(offchain_storage),
 			None => {
@@ -196,8 +235,24 @@ where
 				client,
 				offchain_db,
 				indexing_prefix,
+				pruning,
 				_phantom: Default::default(),
 			})
 			.await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_parse_mmr_pruning_modes() {
+		assert_eq!("archive".parse::<MmrPruning>(), Ok(MmrPruning::Archive));
+		assert_eq!("1000".parse::<MmrPruning>(), Ok(MmrPruning::KeepLeaves(1000)));
+		assert!("0".parse::<MmrPruning>().is_err());
+		assert!("-1".parse::<MmrPruning>().is_err());
+		assert!("all".parse::<MmrPruning>().is_err());
+		assert_eq!(MmrPruning::default(), MmrPruning::Archive);
+	}
+}
@@ -21,7 +21,7 @@
 
 #![warn(missing_docs)]
 
-use crate::{aux_schema, HashFor, MmrClient, LOG_TARGET};
+use crate::{aux_schema, HashFor, MmrClient, MmrPruning, LOG_TARGET};
 use log::{debug, error, info, trace, warn};
 use pallet_ismp::mmr::Leaf;
 use pallet_mmr_runtime_api::MmrRuntimeApi;
@@ -29,7 +29,7 @@ use sc_client_api::{Backend, FinalityNotification};
 use sc_offchain::OffchainDb;
 use sp_blockchain::{CachedHeaderMetadata, ForkBackend};
 use sp_core::offchain::{DbExternalities, StorageKind};
-use sp_mmr_primitives::{utils::NodesUtils, LeafIndex, NodeIndex};
+use sp_mmr_primitives::{mmr_lib::helper::get_peaks, utils::NodesUtils, LeafIndex, NodeIndex};
 use sp_runtime::{
 	traits::{Block, Header, NumberFor, One},
 	Saturating,
@@ -44,6 +44,9 @@ pub struct OffchainMmr<B: Block, BE: Backend<B>, C> {
 	indexing_prefix: Vec<u8>,
 	first_mmr_block: NumberFor<B>,
 	best_canonicalized: NumberFor<B>,
+	pruning: MmrPruning,
+	/// Number of leaves whose canonical nodes have been pruned, apart from the peaks
+	pruned_leaves: LeafIndex,
 }
 
 impl<B, BE, C> OffchainMmr<B, BE, C>
@@ -59,11 +62,15 @@ where
 		offchain_db: OffchainDb<BE::OffchainStorage>,
 		indexing_prefix: Vec<u8>,
 		first_mmr_block: NumberFor<B>,
+		pruning: MmrPruning,
 	) -> Option<Self> {
 		let mut best_canonicalized = first_mmr_block.saturating_sub(One::one());
 		best_canonicalized = aux_schema::load_or_init_state::<B, BE>(&*backend, best_canonicalized)
 			.map_err(|e| error!(target: LOG_TARGET, "Error loading state from aux db: {:?}", e))
 			.ok()?;
+		let pruned_leaves = aux_schema::load_pruned_leaves(&*backend)
+			.map_err(|e| error!(target: LOG_TARGET, "Error loading state from aux db: {:?}", e))
+			.ok()?;
 
 		Some(Self {
 			backend,
@@ -72,9 +79,18 @@ where
 			indexing_prefix,
 			first_mmr_block,
 			best_canonicalized,
+			pruning,
+			pruned_leaves,
 		})
 	}
 
+	/// Canonicalize every block after `best_canonicalized` again, eg. when rebuilding the offchain
+	/// MMR. Pruning also restarts from the first leaf.
+	pub(crate) fn reset(&mut self, best_canonicalized: NumberFor<B>) {
+		self.best_canonicalized = best_canonicalized;
+		self.pruned_leaves = 0;
+	}
+
 	fn node_temp_offchain_key(&self, pos: NodeIndex, fork_identifier: B::Hash) -> Vec<u8> {
 		NodesUtils::node_temp_offchain_key::<B::Header>(&self.indexing_prefix, pos, fork_identifier)
 	}
@@ -83,12 +99,15 @@ where
 		NodesUtils::node_canon_offchain_key(&self.indexing_prefix, pos)
 	}
 
-	fn write_gadget_state_or_log(&self) {
+	pub(crate) fn write_gadget_state_or_log(&self) {
 		if let Err(e) =
 			aux_schema::write_gadget_state::<B, BE>(&*self.backend, &self.best_canonicalized)
 		{
 			debug!(target: LOG_TARGET, "error saving state: {:?}", e);
 		}
+		if let Err(e) = aux_schema::write_pruned_leaves(&*self.backend, self.pruned_leaves) {
+			debug!(target: LOG_TARGET, "error saving state: {:?}", e);
+		}
 	}
 
 	fn header_metadata_or_log(
@@ -180,7 +199,7 @@ where
 		}
 	}
 
-	fn canonicalize_branch(&mut self, block_hash: B::Hash) {
+	pub(crate) fn canonicalize_branch(&mut self, block_hash: B::Hash) {
 		let action = "canonicalize";
 		let header = match self.header_metadata_or_log(block_hash, action) {
 			Some(header) => header,
@@ -262,6 +281,29 @@ where
 			self.canonicalize_catch_up(header.parent);
 		}
 		self.best_canonicalized = header.number;
+		self.prune_canonical(current_leaf_count);
+	}
+
+	/// Removes the canonical nodes which are only needed to prove leaves outside the retention
+	/// window. Nodes covering only older leaves are never siblings on the path of a newer leaf,
+	/// unless they are peaks of the MMR made up of the older leaves, so those peaks are kept.
+	fn prune_canonical(&mut self, leaf_count: LeafIndex) {
+		let MmrPruning::KeepLeaves(keep) = self.pruning else { return };
+		let prunable = leaf_count.saturating_sub(keep);
+		if prunable <= self.pruned_leaves {
+			return;
+		}
+
+		for pos in prunable_nodes(self.pruned_leaves, prunable) {
+			let canon_key = self.node_canon_offchain_key(pos);
+			self.offchain_db.local_storage_clear(StorageKind::PERSISTENT, &canon_key);
+		}
+
+		debug!(
+			target: LOG_TARGET,
+			"Pruned canonical nodes for leaves {} to {}", self.pruned_leaves, prunable
+		);
+		self.pruned_leaves = prunable;
 	}
 
 	/// In case of missed finality notifications (node restarts for example),
@@ -298,7 +340,7 @@ where
 					first_mmr_block_num
 				);
 				self.first_mmr_block = first_mmr_block_num;
-				self.best_canonicalized = first_mmr_block_num.saturating_sub(One::one());
+				self.reset(first_mmr_block_num.saturating_sub(One::one()));
 				self.write_gadget_state_or_log();
 			}
 		}
@@ -344,3 +386,52 @@ where
 		}
 	}
 }
+
+/// Positions of the nodes which may be removed once the first `prunable` leaves no longer need to
+/// be proven, given that the nodes of the first `pruned_leaves` leaves were already removed. The
+/// peaks of the MMR made up of the pruned leaves are siblings on the path of every newer leaf, so
+/// they are kept.
+fn prunable_nodes(pruned_leaves: LeafIndex, prunable: LeafIndex) -> Vec<NodeIndex> {
+	let old_size = NodesUtils::new(pruned_leaves).size();
+	let new_size = NodesUtils::new(prunable).size();
+	let old_peaks = if old_size == 0 { vec![] } else { get_peaks(old_size) };
+	let new_peaks = get_peaks(new_size);
+
+	old_peaks
+		.into_iter()
+		.chain(old_size..new_size)
+		.filter(|pos| !new_peaks.contains(pos))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	#[test]
+	fn should_keep_the_peaks_of_pruned_leaves() {
+		// leaves 0..3 are at positions 0, 1 and 3 with their parent at 2
+		assert_eq!(prunable_nodes(0, 3), vec![0, 1]);
+		// the fourth leaf completes the subtree rooted at 6
+		assert_eq!(prunable_nodes(3, 4), vec![2, 3, 4, 5]);
+		assert_eq!(prunable_nodes(0, 4), vec![0, 1, 2, 3, 4, 5]);
+		assert!(prunable_nodes(4, 4).is_empty());
+	}
+
+	#[test]
+	fn should_prune_the_same_nodes_incrementally() {
+		for total in 1..64 {
+			let at_once = prunable_nodes(0, total).into_iter().collect::<BTreeSet<_>>();
+			let incremental = (0..total)
+				.flat_map(|pruned| prunable_nodes(pruned, pruned + 1))
+				.collect::<BTreeSet<_>>();
+			assert_eq!(at_once, incremental);
+
+			// only the peaks of the pruned leaves remain
+			let size = NodesUtils::new(total).size();
+			let remaining = (0..size).filter(|pos| !at_once.contains(pos)).collect::<Vec<_>>();
+			assert_eq!(remaining, get_peaks(size));
+		}
+	}
+}
@@ -0,0 +1,127 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rebuilding the offchain MMR from the finalized chain.
+//!
+//! MMR nodes are only written to the offchain db through the Indexing API while blocks are
+//! imported, so they are recovered by re-executing every finalized block on top of its parent and
+//! applying the offchain changes it produced, before canonicalizing them as the gadget would.
+
+use crate::{offchain_mmr::OffchainMmr, HashFor, MmrClient, MmrPruning, LOG_TARGET};
+use log::info;
+use pallet_ismp::mmr::Leaf;
+use pallet_mmr_runtime_api::MmrRuntimeApi;
+use sc_client_api::{Backend, BlockBackend};
+use sc_offchain::OffchainDb;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_core::{
+	offchain::{OffchainOverlayedChange, OffchainStorage},
+	traits::CallContext,
+};
+use sp_runtime::{
+	traits::{Block, Header, NumberFor, One},
+	DigestItem, Saturating,
+};
+use std::sync::Arc;
+
+/// Rebuilds the offchain MMR by re-executing every finalized block from `from`, or from the block
+/// `pallet-mmr` was initialized at, and canonicalizing the nodes they add. The state of those
+/// blocks must still be available, so this should be run against an archive node's database.
+pub fn rebuild_offchain_mmr<B, BE, C>(
+	client: Arc<C>,
+	backend: Arc<BE>,
+	indexing_prefix: Vec<u8>,
+	from: Option<NumberFor<B>>,
+	pruning: MmrPruning,
+) -> Result<(), String>
+where
+	B: Block,
+	BE: Backend<B>,
+	C: MmrClient<B, BE> + BlockBackend<B>,
+	C::Api: MmrRuntimeApi<B, HashFor<B>, NumberFor<B>, Leaf> + Core<B>,
+{
+	let mut offchain_storage = backend
+		.offchain_storage()
+		.ok_or_else(|| "Can't rebuild the MMR for a node without offchain storage".to_string())?;
+
+	let info = client.info();
+	let first_mmr_block = match client.runtime_api().pallet_genesis(info.finalized_hash) {
+		Ok(Ok(Some(number))) => number,
+		_ => Err("pallet-mmr has not been initialized at the latest finalized block".to_string())?,
+	};
+	let start = from.map_or(first_mmr_block, |from| from.max(first_mmr_block));
+
+	let mut offchain_mmr = OffchainMmr::new(
+		backend.clone(),
+		client.clone(),
+		OffchainDb::new(offchain_storage.clone()),
+		indexing_prefix,
+		first_mmr_block,
+		pruning,
+	)
+	.ok_or_else(|| "Failed to load the MMR gadget state".to_string())?;
+	offchain_mmr.reset(start.saturating_sub(One::one()));
+
+	info!(
+		target: LOG_TARGET,
+		"Rebuilding the offchain MMR from block {:?} to {:?}", start, info.finalized_number
+	);
+
+	let mut number = start;
+	while number <= info.finalized_number {
+		let hash = client
+			.hash(number)
+			.map_err(|e| format!("{e:?}"))?
+			.ok_or_else(|| format!("Hash for finalized block {number:?} not found"))?;
+		let (mut header, extrinsics) = client
+			.block(hash)
+			.map_err(|e| format!("{e:?}"))?
+			.ok_or_else(|| format!("Finalized block {hash:?} not found"))?
+			.block
+			.deconstruct();
+		// The seal is removed before blocks are executed on import
+		if let Some(DigestItem::Seal(..)) = header.digest().logs().last() {
+			header.digest_mut().pop();
+		}
+
+		let parent_hash = *header.parent_hash();
+		let mut api = client.runtime_api();
+		api.set_call_context(CallContext::Onchain);
+		api.execute_block(parent_hash, B::new(header, extrinsics))
+			.map_err(|e| format!("Failed to execute block {hash:?}: {e:?}"))?;
+		let state = backend.state_at(parent_hash).map_err(|e| format!("{e:?}"))?;
+		let changes = api.into_storage_changes(&state, parent_hash)?;
+
+		for ((prefix, key), change) in changes.offchain_storage_changes {
+			match change {
+				OffchainOverlayedChange::SetValue(value) =>
+					offchain_storage.set(&prefix, &key, &value),
+				OffchainOverlayedChange::Remove => offchain_storage.remove(&prefix, &key),
+			}
+		}
+
+		offchain_mmr.canonicalize_branch(hash);
+		if number % 1000u32.into() == 0u32.into() {
+			info!(target: LOG_TARGET, "Rebuilt the offchain MMR up to block {:?}", number);
+			offchain_mmr.write_gadget_state_or_log();
+		}
+		number = number.saturating_add(One::one());
+	}
+
+	offchain_mmr.write_gadget_state_or_log();
+	info!(target: LOG_TARGET, "Finished rebuilding the offchain MMR");
+
+	Ok(())
+}
@@ -238,9 +238,10 @@ fn testnet_genesis(
 
 	// sibling parachain for tests
 	let sibling = match id {
-		2000 => ParachainData { id: 2001, slot_duration: 6000 },
-		2001 => ParachainData { id: 2000, slot_duration: 6000 },
-		_ => ParachainData { id: 1000, slot_duration: 6000 }, /* default to assethub */
+		2000 => ParachainData { id: 2001, slot_duration: 6000, header_format: Default::default() },
+		2001 => ParachainData { id: 2000, slot_duration: 6000, header_format: Default::default() },
+		// default to assethub
+		_ => ParachainData { id: 1000, slot_duration: 6000, header_format: Default::default() },
 	};
 
 	serde_json::json!({
@@ -60,6 +60,51 @@ pub enum Subcommand {
 
 	/// Runs the node with signature verification override and manual seal.
 	Simnode(sc_simnode::cli::SimnodeCli),
+
+	/// Rebuild the offchain MMR by re-executing finalized blocks.
+	RebuildMmr(RebuildMmrCmd),
+}
+
+/// The `rebuild-mmr` command, used to recover a corrupted offchain MMR. The state of every block
+/// being re-executed must still be available, so this should be run against an archive node's
+/// database while the node is stopped.
+#[derive(Debug, clap::Parser)]
+pub struct RebuildMmrCmd {
+	/// Block number to start rebuilding from, defaults to the block at which pallet-mmr was
+	/// initialized.
+	#[arg(long)]
+	pub from: Option<u32>,
+
+	/// Pruning mode to apply to the rebuilt offchain MMR, either `archive` or the number of
+	/// latest leaves that should remain provable.
+	#[arg(long, default_value = "archive")]
+	pub mmr_pruning: mmr_gadget::MmrPruning,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for RebuildMmrCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
 }
 
 #[derive(Debug, clap::Parser)]
@@ -91,6 +136,17 @@ pub struct Cli {
 	#[arg(long)]
 	pub async_backing: bool,
 
+	/// Maintain an index of the ISMP events in finalized blocks, which serves paginated event
+	/// history through the `ismp_queryIndexedEvents` RPC.
+	#[arg(long)]
+	pub enable_ismp_event_index: bool,
+
+	/// Pruning mode for the canonical nodes of the offchain MMR, either `archive` to keep every
+	/// node, or the number of latest leaves that should remain provable. Proofs and queries for
+	/// older requests and responses will fail once their nodes are pruned.
+	#[arg(long, default_value = "archive")]
+	pub mmr_pruning: mmr_gadget::MmrPruning,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
@@ -19,6 +19,7 @@ use cumulus_primitives_core::ParaId;
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
 use gargantua_runtime::Block;
 use log::info;
+use mmr_gadget::MmrPruning;
 use polkadot_cli::service;
 use sc_cli::{
 	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
@@ -225,6 +226,21 @@ pub fn run() -> Result<()> {
 				cmd.run(components.client, components.backend, None)
 			})
 		},
+		Some(Subcommand::RebuildMmr(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| {
+				let (client, backend) = (components.client, components.backend);
+				async move {
+					mmr_gadget::rebuild_offchain_mmr(
+						client,
+						backend,
+						sp_mmr_primitives::INDEXING_PREFIX.to_vec(),
+						cmd.from,
+						cmd.mmr_pruning,
+					)
+					.map_err(|e| sc_cli::Error::Application(e.into()))
+				}
+			})
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 
@@ -391,6 +407,12 @@ pub fn run() -> Result<()> {
 					let executor = sc_simnode::new_wasm_executor(config);
 					let components =
 						new_partial::<gargantua_runtime::RuntimeApi, _>(&config, executor)?;
+					crate::service::spawn_mmr_gadget(
+						&components.task_manager,
+						components.client.clone(),
+						components.backend.clone(),
+						MmrPruning::Archive,
+					);
 					runner.run_node_until_exit(move |config| async move {
 						let client = components.client.clone();
 						let pool = components.transaction_pool.clone();
@@ -406,12 +428,17 @@ pub fn run() -> Result<()> {
 							components,
 							config,
 							instant: false,
-							rpc_builder: Box::new(move |deny_unsafe, _| {
+							rpc_builder: Box::new(move |deny_unsafe, subscription_executor| {
 								let client = client.clone();
 								let pool = pool.clone();
 								let backend = backend.clone();
-								let full_deps =
-									rpc::FullDeps { client, pool, deny_unsafe, backend };
+								let full_deps = rpc::FullDeps {
+									client,
+									pool,
+									deny_unsafe,
+									backend,
+									subscription_executor,
+								};
 								let io =
 									rpc::create_full(full_deps).expect("Rpc to be initialized");
 
@@ -469,6 +496,8 @@ pub fn run() -> Result<()> {
 					collator_options,
 					id,
 					hwbench,
+					cli.enable_ismp_event_index,
+					cli.mmr_pruning,
 				)
 				.await
 				.map_err(Into::into)
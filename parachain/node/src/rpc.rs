@@ -25,8 +25,8 @@ use std::sync::Arc;
 use gargantua_runtime::{opaque::Block, AccountId, Balance, Index as Nonce};
 
 use crate::runtime_api::opaque;
-use sc_client_api::{AuxStore, BlockBackend, ProofProvider};
-pub use sc_rpc::DenyUnsafe;
+use sc_client_api::{AuxStore, BlockBackend, BlockchainEvents, ProofProvider};
+pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -46,6 +46,8 @@ pub struct FullDeps<C, P, B> {
 	pub deny_unsafe: DenyUnsafe,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// Executor for RPC subscriptions
+	pub subscription_executor: SubscriptionTaskExecutor,
 }
 
 /// Instantiate all RPC extensions.
@@ -58,6 +60,7 @@ where
 		+ AuxStore
 		+ BlockBackend<Block>
 		+ ProofProvider<Block>
+		+ BlockchainEvents<Block>
 		+ HeaderMetadata<Block, Error = BlockChainError>
 		+ Send
 		+ Sync
@@ -66,20 +69,26 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
 	C::Api: pallet_ismp_runtime_api::IsmpRuntimeApi<opaque::Block, H256>,
+	C::Api: pallet_token_governor_runtime_api::TokenGovernorRuntimeApi<opaque::Block, AccountId>,
+	C::Api: pallet_ismp_host_executive_runtime_api::HostExecutiveRuntimeApi<opaque::Block>,
 	P: TransactionPool + Sync + Send + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashingFor<Block>>,
 {
+	use pallet_ismp_host_executive_rpc::{HostExecutiveApiServer, HostExecutiveRpcHandler};
 	use pallet_ismp_rpc::{IsmpApiServer, IsmpRpcHandler};
+	use pallet_token_governor_rpc::{TokenGovernorApiServer, TokenGovernorRpcHandler};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool, deny_unsafe, backend } = deps;
+	let FullDeps { client, pool, deny_unsafe, backend, subscription_executor } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
-	module.merge(IsmpRpcHandler::new(client, backend.clone())?.into_rpc())?;
+	module.merge(TokenGovernorRpcHandler::new(client.clone()).into_rpc())?;
+	module.merge(HostExecutiveRpcHandler::new(client.clone()).into_rpc())?;
+	module.merge(IsmpRpcHandler::new(client, backend, subscription_executor)?.into_rpc())?;
 
 	Ok(module)
 }
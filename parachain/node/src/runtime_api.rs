@@ -81,6 +81,8 @@ pub trait BaseHostRuntimeApis:
 	+ TransactionPaymentRuntimeApi<opaque::Block, opaque::Balance>
 	+ ismp_parachain_runtime_api::IsmpParachainApi<opaque::Block>
 	+ pallet_ismp_runtime_api::IsmpRuntimeApi<opaque::Block, H256>
+	+ pallet_token_governor_runtime_api::TokenGovernorRuntimeApi<opaque::Block, opaque::AccountId>
+	+ pallet_ismp_host_executive_runtime_api::HostExecutiveRuntimeApi<opaque::Block>
 	+ cumulus_primitives_aura::AuraUnincludedSegmentApi<opaque::Block>
 	+ pallet_mmr_runtime_api::MmrRuntimeApi<
 		opaque::Block,
@@ -108,6 +110,8 @@ impl<Api> BaseHostRuntimeApis for Api where
 		+ TransactionPaymentRuntimeApi<opaque::Block, opaque::Balance>
 		+ ismp_parachain_runtime_api::IsmpParachainApi<opaque::Block>
 		+ pallet_ismp_runtime_api::IsmpRuntimeApi<opaque::Block, H256>
+		+ pallet_token_governor_runtime_api::TokenGovernorRuntimeApi<opaque::Block, opaque::AccountId>
+		+ pallet_ismp_host_executive_runtime_api::HostExecutiveRuntimeApi<opaque::Block>
 		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<opaque::Block>
 		+ pallet_mmr_runtime_api::MmrRuntimeApi<
 			opaque::Block,
@@ -36,6 +36,7 @@ use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
 use polkadot_primitives::ValidationCode;
 // Substrate Imports
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
+use mmr_gadget::MmrPruning;
 use sc_client_api::Backend;
 use sc_consensus::ImportQueue;
 use sc_executor::{RuntimeVersionOf, WasmExecutor};
@@ -112,17 +113,6 @@ where
 	});
 	let select_chain = ParachainSelectChain::new(client.clone());
 
-	// Spawn mmr canonicalizing task
-	task_manager.spawn_handle().spawn(
-		"mmr-canonicalizing-gadget",
-		"mmr-gadget",
-		mmr_gadget::MmrGadget::start(
-			client.clone(),
-			backend.clone(),
-			sp_mmr_primitives::INDEXING_PREFIX.to_vec(),
-		),
-	);
-
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
@@ -154,6 +144,32 @@ where
 	})
 }
 
+/// Spawns the task which canonicalizes, and optionally prunes, the offchain MMR.
+pub fn spawn_mmr_gadget<Runtime, Executor>(
+	task_manager: &TaskManager,
+	client: Arc<FullClient<Runtime, Executor>>,
+	backend: Arc<FullBackend>,
+	pruning: MmrPruning,
+) where
+	Runtime:
+		ConstructRuntimeApi<opaque::Block, FullClient<Runtime, Executor>> + Send + Sync + 'static,
+	Runtime::RuntimeApi: BaseHostRuntimeApis,
+	sc_client_api::StateBackendFor<FullBackend, opaque::Block>:
+		sc_client_api::StateBackend<Keccak256>,
+	Executor: CodeExecutor + RuntimeVersionOf + 'static,
+{
+	task_manager.spawn_handle().spawn(
+		"mmr-canonicalizing-gadget",
+		"mmr-gadget",
+		mmr_gadget::MmrGadget::start(
+			client,
+			backend,
+			sp_mmr_primitives::INDEXING_PREFIX.to_vec(),
+			pruning,
+		),
+	);
+}
+
 /// Start a node with the given parachain `Configuration` and relay chain `Configuration`.
 ///
 /// This is the actual implementation that is abstract over the executor and the runtime api.
@@ -164,6 +180,8 @@ async fn start_node_impl<Runtime>(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	enable_event_index: bool,
+	mmr_pruning: MmrPruning,
 ) -> sc_service::error::Result<TaskManager>
 where
 	Runtime: ConstructRuntimeApi<opaque::Block, FullClient<Runtime>> + Send + Sync + 'static,
@@ -182,6 +200,8 @@ where
 	let backend = params.backend.clone();
 	let mut task_manager = params.task_manager;
 
+	spawn_mmr_gadget(&task_manager, client.clone(), backend.clone(), mmr_pruning);
+
 	let (relay_chain_interface, collator_key) = build_relay_chain_interface(
 		polkadot_config,
 		&parachain_config,
@@ -235,17 +255,29 @@ where
 		);
 	}
 
+	if enable_event_index {
+		task_manager.spawn_handle().spawn(
+			"ismp-event-index",
+			"ismp",
+			pallet_ismp_rpc::index::EventIndexer::<opaque::Block, _, _>::start(
+				client.clone(),
+				backend.clone(),
+			),
+		);
+	}
+
 	let rpc_builder = {
 		let client = client.clone();
 		let backend = backend.clone();
 		let transaction_pool = transaction_pool.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
 				backend: backend.clone(),
+				subscription_executor,
 			};
 
 			crate::rpc::create_full(deps).map_err(Into::into)
@@ -501,6 +533,8 @@ pub async fn start_parachain_node(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	enable_event_index: bool,
+	mmr_pruning: MmrPruning,
 ) -> sc_service::error::Result<TaskManager> {
 	match parachain_config.chain_spec.id() {
 		chain if chain.contains("gargantua") =>
@@ -510,6 +544,8 @@ pub async fn start_parachain_node(
 				collator_options,
 				para_id,
 				hwbench,
+				enable_event_index,
+				mmr_pruning,
 			)
 			.await,
 		chain if chain.contains("messier") =>
@@ -519,6 +555,8 @@ pub async fn start_parachain_node(
 				collator_options,
 				para_id,
 				hwbench,
+				enable_event_index,
+				mmr_pruning,
 			)
 			.await,
 		chain if chain.contains("nexus") =>
@@ -528,6 +566,8 @@ pub async fn start_parachain_node(
 				collator_options,
 				para_id,
 				hwbench,
+				enable_event_index,
+				mmr_pruning,
 			)
 			.await,
 		chain => panic!("Unknown chain with id: {}", chain),
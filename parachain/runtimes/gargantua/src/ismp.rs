@@ -16,12 +16,14 @@
 use crate::{
 	alloc::{boxed::Box, string::ToString},
 	weights, AccountId, Assets, Balance, Balances, Gateway, Ismp, IsmpParachain, Mmr,
-	ParachainInfo, Runtime, RuntimeEvent, Timestamp, EXISTENTIAL_DEPOSIT,
+	ParachainInfo, Runtime, RuntimeCall, RuntimeEvent, Timestamp, EXISTENTIAL_DEPOSIT, HOURS,
+	MAXIMUM_BLOCK_WEIGHT, UNIT,
 };
 use frame_support::{
-	pallet_prelude::{ConstU32, Get},
+	pallet_prelude::{ConstU32, ConstU64, Get},
 	parameter_types,
-	traits::AsEnsureOriginWithArg,
+	traits::{AsEnsureOriginWithArg, Contains},
+	weights::Weight,
 	PalletId,
 };
 use frame_system::EnsureRoot;
@@ -35,7 +37,7 @@ use pallet_asset_gateway::AssetGatewayParams;
 #[cfg(feature = "runtime-benchmarks")]
 use pallet_assets::BenchmarkHelper;
 use sp_core::crypto::AccountId32;
-use sp_runtime::Permill;
+use sp_runtime::{traits::AccountIdConversion, Perbill, Permill};
 
 use hyperbridge_client_machine::HyperbridgeClientMachine;
 use ismp::router::Timeout;
@@ -112,13 +114,61 @@ impl pallet_ismp_relayer::Config for Runtime {
 	type IsmpHost = Ismp;
 }
 
+parameter_types! {
+	pub const FishermanBond: Balance = 100 * UNIT;
+	pub const VetoReward: Balance = 10 * UNIT;
+	pub FishermenProtocolAccount: AccountId = ProtocolAccount::get().into_account_truncating();
+}
+
+impl pallet_fishermen::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type IsmpHost = Ismp;
+	type MinimumBond = FishermanBond;
+	type DisputePeriod = ConstU32<{ 2 * HOURS }>;
+	type VetoReward = VetoReward;
+	type ProtocolAccount = FishermenProtocolAccount;
+}
+
 impl pallet_ismp_host_executive::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IsmpHost = Ismp;
+	// 3 days
+	type HostParamsUpdateTimeout = ConstU64<259_200>;
+}
+
+/// Runtime calls which may be submitted through the call decompressor
+pub struct CompressedCalls;
+
+impl Contains<RuntimeCall> for CompressedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		use pallet_state_coprocessor::Call as StateCoprocessorCall;
+
+		matches!(
+			call,
+			RuntimeCall::Ismp(pallet_ismp::Call::handle_unsigned { .. }) |
+				RuntimeCall::Relayer(pallet_ismp_relayer::Call::accumulate_fees { .. }) |
+				RuntimeCall::StateCoprocessor(StateCoprocessorCall::handle_unsigned { .. }) |
+				RuntimeCall::TokenGovernor(
+					pallet_token_governor::Call::create_erc6160_asset_unsigned { .. }
+				)
+		)
+	}
+}
+
+parameter_types! {
+	// Upper bound of the weight of compressed calls, the unused weight is refunded after dispatch
+	pub MaxCompressedCallWeight: Weight = Perbill::from_percent(50) * MAXIMUM_BLOCK_WEIGHT;
 }
 
 impl pallet_call_decompressor::Config for Runtime {
+	type RuntimeCall = RuntimeCall;
 	type MaxCallSize = ConstU32<2>;
+	type AllowedCalls = CompressedCalls;
+	type UnsignedValidator = Runtime;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxDictionarySize = ConstU32<{ 128 * 1024 }>;
+	type MaxCallWeight = MaxCompressedCallWeight;
+	type WeightInfo = ();
 }
 
 impl ismp_parachain::Config for Runtime {
@@ -226,6 +276,8 @@ impl IsmpModule for ProxyModule {
 		match pallet_id {
 			pallet_ismp_demo::PALLET_ID =>
 				pallet_ismp_demo::IsmpModuleCallback::<Runtime>::default().on_response(response),
+			pallet_ismp_host_executive::PALLET_ID =>
+				pallet_ismp_host_executive::Pallet::<Runtime>::default().on_response(response),
 			_ => Err(Error::Custom("Destination module not found".to_string())),
 		}
 	}
@@ -242,6 +294,8 @@ impl IsmpModule for ProxyModule {
 		match pallet_id {
 			pallet_ismp_demo::PALLET_ID =>
 				pallet_ismp_demo::IsmpModuleCallback::<Runtime>::default().on_timeout(timeout),
+			pallet_ismp_host_executive::PALLET_ID =>
+				pallet_ismp_host_executive::Pallet::<Runtime>::default().on_timeout(timeout),
 			id if id == token_gateway =>
 				pallet_asset_gateway::Module::<Runtime>::default().on_timeout(timeout),
 			// instead of returning an error, do nothing. The timeout is for a connected chain.
@@ -214,7 +214,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("gargantua"),
 	impl_name: create_runtime_str!("gargantua"),
 	authoring_version: 1,
-	spec_version: 382,
+	spec_version: 383,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -596,6 +596,7 @@ construct_runtime!(
 		Assets: pallet_assets = 58,
 		TokenGovernor: pallet_token_governor = 59,
 		StateCoprocessor: pallet_state_coprocessor = 60,
+		Fishermen: pallet_fishermen = 61,
 	}
 );
 
@@ -621,6 +622,7 @@ mod benches {
 		[pallet_utility, Utility]
 		[cumulus_pallet_parachain_system, ParachainSystem]
 		[pallet_session, SessionBench::<Runtime>]
+		[pallet_call_decompressor, CallDecompressor]
 	);
 }
 
@@ -792,6 +794,39 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_token_governor_runtime_api::TokenGovernorRuntimeApi<Block, AccountId> for Runtime {
+		fn assets() -> Vec<pallet_token_governor::AssetDetails<AccountId>> {
+			TokenGovernor::assets()
+		}
+
+		fn asset(asset_id: H256) -> Option<pallet_token_governor::AssetDetails<AccountId>> {
+			TokenGovernor::asset_details(asset_id)
+		}
+
+		fn pending_assets() -> Vec<(H256, sp_core::H160)> {
+			TokenGovernor::pending_assets()
+		}
+
+		fn gateway_params() -> Vec<(StateMachine, pallet_token_governor::GatewayParams)> {
+			TokenGovernor::gateway_params()
+		}
+
+		fn registrar_params() -> Vec<(StateMachine, pallet_token_governor::RegistrarParams)> {
+			TokenGovernor::registrar_params()
+		}
+	}
+
+	impl pallet_ismp_host_executive_runtime_api::HostExecutiveRuntimeApi<Block> for Runtime {
+		fn per_byte_fee(state_machine: StateMachine) -> Option<sp_core::U256> {
+			HostExecutive::per_byte_fee(state_machine)
+		}
+
+		fn request_protocol_fee(request: ::ismp::router::PostRequest) -> Option<sp_core::U256> {
+			HostExecutive::request_protocol_fee(&request)
+		}
+	}
+
+	#[api_version(2)]
 	impl pallet_ismp_runtime_api::IsmpRuntimeApi<Block, <Block as BlockT>::Hash> for Runtime {
 		fn host_state_machine() -> StateMachine {
 			<Runtime as pallet_ismp::Config>::HostStateMachine::get()
@@ -843,13 +878,22 @@ impl_runtime_apis! {
 		fn responses(commitments: Vec<H256>) -> Vec<Response> {
 			Ismp::responses(commitments)
 		}
+
+		fn message_status(commitment: H256) -> pallet_ismp::MessageStatus {
+			Ismp::message_status(commitment)
+		}
 	}
 
+	#[api_version(2)]
 	impl ismp_parachain_runtime_api::IsmpParachainApi<Block> for Runtime {
 		fn para_ids() -> Vec<u32> {
 			IsmpParachain::para_ids()
 		}
 
+		fn parachains() -> Vec<ismp_parachain::ParachainData> {
+			IsmpParachain::parachains()
+		}
+
 		fn current_relay_chain_state() -> RelayChainState {
 			IsmpParachain::current_relay_chain_state()
 		}
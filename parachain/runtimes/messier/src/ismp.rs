@@ -15,13 +15,14 @@
 
 use crate::{
 	alloc::{boxed::Box, string::ToString},
-	AccountId, Assets, Balance, Balances, Gateway, Ismp, Mmr, ParachainInfo, Runtime, RuntimeEvent,
-	Timestamp, EXISTENTIAL_DEPOSIT,
+	AccountId, Assets, Balance, Balances, Gateway, Ismp, Mmr, ParachainInfo, Runtime, RuntimeCall,
+	RuntimeEvent, Timestamp, EXISTENTIAL_DEPOSIT, MAXIMUM_BLOCK_WEIGHT,
 };
 use frame_support::{
-	pallet_prelude::{ConstU32, Get},
+	pallet_prelude::{ConstU32, ConstU64, Get},
 	parameter_types,
-	traits::AsEnsureOriginWithArg,
+	traits::{AsEnsureOriginWithArg, Contains},
+	weights::Weight,
 	PalletId,
 };
 use frame_system::EnsureRoot;
@@ -35,7 +36,7 @@ use pallet_asset_gateway::AssetGatewayParams;
 #[cfg(feature = "runtime-benchmarks")]
 use pallet_assets::BenchmarkHelper;
 use sp_core::crypto::AccountId32;
-use sp_runtime::Permill;
+use sp_runtime::{Perbill, Permill};
 
 use ismp::router::Timeout;
 use ismp_sync_committee::constants::mainnet::Mainnet;
@@ -92,6 +93,8 @@ impl pallet_ismp_relayer::Config for Runtime {
 impl pallet_ismp_host_executive::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IsmpHost = Ismp;
+	// 3 days
+	type HostParamsUpdateTimeout = ConstU64<259_200>;
 }
 
 impl ismp_parachain::Config for Runtime {
@@ -99,8 +102,36 @@ impl ismp_parachain::Config for Runtime {
 	type IsmpHost = Ismp;
 }
 
+/// Runtime calls which may be submitted through the call decompressor
+pub struct CompressedCalls;
+
+impl Contains<RuntimeCall> for CompressedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Ismp(pallet_ismp::Call::handle_unsigned { .. }) |
+				RuntimeCall::Relayer(pallet_ismp_relayer::Call::accumulate_fees { .. }) |
+				RuntimeCall::TokenGovernor(
+					pallet_token_governor::Call::create_erc6160_asset_unsigned { .. }
+				)
+		)
+	}
+}
+
+parameter_types! {
+	// Upper bound of the weight of compressed calls, the unused weight is refunded after dispatch
+	pub MaxCompressedCallWeight: Weight = Perbill::from_percent(50) * MAXIMUM_BLOCK_WEIGHT;
+}
+
 impl pallet_call_decompressor::Config for Runtime {
+	type RuntimeCall = RuntimeCall;
 	type MaxCallSize = ConstU32<3>;
+	type AllowedCalls = CompressedCalls;
+	type UnsignedValidator = Runtime;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxDictionarySize = ConstU32<{ 128 * 1024 }>;
+	type MaxCallWeight = MaxCompressedCallWeight;
+	type WeightInfo = ();
 }
 
 // todo: set corrrect Token Gateway parameters
@@ -195,7 +226,19 @@ impl IsmpModule for ProxyModule {
 			return Ok(());
 		}
 
-		Err(Error::Custom("Destination module not found".to_string()))
+		let request = &response.request();
+		let from = match &request {
+			Request::Post(post) => &post.from,
+			Request::Get(get) => &get.from,
+		};
+
+		let pallet_id = ModuleId::from_bytes(from).map_err(|err| Error::Custom(err.to_string()))?;
+
+		match pallet_id {
+			pallet_ismp_host_executive::PALLET_ID =>
+				pallet_ismp_host_executive::Pallet::<Runtime>::default().on_response(response),
+			_ => Err(Error::Custom("Destination module not found".to_string())),
+		}
 	}
 
 	fn on_timeout(&self, timeout: Timeout) -> Result<(), Error> {
@@ -210,6 +253,8 @@ impl IsmpModule for ProxyModule {
 		match pallet_id {
 			id if id == token_gateway =>
 				pallet_asset_gateway::Module::<Runtime>::default().on_timeout(timeout),
+			pallet_ismp_host_executive::PALLET_ID =>
+				pallet_ismp_host_executive::Pallet::<Runtime>::default().on_timeout(timeout),
 			// instead of returning an error, do nothing. The timeout is for a connected chain.
 			_ => Ok(()),
 		}
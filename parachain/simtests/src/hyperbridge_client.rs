@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use crate::pallet_ismp::Keccak256;
+use crate::{pallet_ismp::Keccak256, parachain_data};
 use anyhow::anyhow;
 use codec::Encode;
 use ismp::{
@@ -31,7 +31,6 @@ use subxt_utils::{
 					consensus::{StateCommitment, StateMachineHeight, StateMachineId},
 					messaging::{Message, Proof, RequestMessage},
 				},
-				ismp_parachain::ParachainData,
 			},
 		},
 	},
@@ -58,7 +57,7 @@ async fn test_will_accept_paid_requests() -> Result<(), anyhow::Error> {
 		let calls = vec![
 			RuntimeCall::IsmpParachain(
 				runtime_types::ismp_parachain::pallet::Call::add_parachain {
-					para_ids: vec![ParachainData { id: para_id, slot_duration }],
+					para_ids: vec![parachain_data(para_id, slot_duration)],
 				},
 			),
 			// init the host executive
@@ -251,7 +250,7 @@ async fn test_will_reject_unpaid_requests() -> Result<(), anyhow::Error> {
 		let calls = vec![
 			RuntimeCall::IsmpParachain(
 				runtime_types::ismp_parachain::pallet::Call::add_parachain {
-					para_ids: vec![ParachainData { id: para_id, slot_duration }],
+					para_ids: vec![parachain_data(para_id, slot_duration)],
 				},
 			),
 			// init the host executive
@@ -436,7 +435,7 @@ async fn test_will_reject_partially_paid_requests() -> Result<(), anyhow::Error>
 		let calls = vec![
 			RuntimeCall::IsmpParachain(
 				runtime_types::ismp_parachain::pallet::Call::add_parachain {
-					para_ids: vec![ParachainData { id: para_id, slot_duration }],
+					para_ids: vec![parachain_data(para_id, slot_duration)],
 				},
 			),
 			// init the host executive
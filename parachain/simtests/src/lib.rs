@@ -1,3 +1,25 @@
 mod hyperbridge_client;
+mod pallet_fishermen;
 mod pallet_ismp;
 mod pallet_mmr;
+
+#[cfg(test)]
+use subxt_utils::gargantua::api::runtime_types::ismp_parachain::{
+	BlockNumberWidth, DigestLayout, HeaderFormat, ParachainData, TimestampDigest,
+};
+
+/// Registration for a parachain whose headers are in the default substrate format
+#[cfg(test)]
+pub(crate) fn parachain_data(id: u32, slot_duration: u64) -> ParachainData {
+	ParachainData {
+		id,
+		slot_duration,
+		header_format: HeaderFormat {
+			block_number: BlockNumberWidth::U32,
+			digest: DigestLayout {
+				timestamp: TimestampDigest::Slot(*b"aura"),
+				ismp_engine_id: *b"ISMP",
+			},
+		},
+	}
+}
@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use std::{
+	env,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::anyhow;
+use codec::Encode;
+use sc_consensus_manual_seal::CreatedBlock;
+use sp_core::{crypto::Ss58Codec, Bytes};
+use sp_keyring::sr25519::Keyring;
+use subxt::{rpc_params, tx::SubmittableExtrinsic};
+
+use ismp::host::StateMachine;
+use primitive_types::H256;
+use subxt_utils::{
+	gargantua::{
+		api,
+		api::runtime_types::{
+			self,
+			gargantua_runtime::RuntimeCall,
+			ismp::consensus::{StateCommitment, StateMachineHeight, StateMachineId},
+		},
+	},
+	Extrinsic, Hyperbridge,
+};
+
+/// Authors `call` from `signer`, then creates and finalizes the block which includes it.
+async fn author_and_finalize(
+	client: &subxt::OnlineClient<Hyperbridge>,
+	call: Vec<u8>,
+	signer: Keyring,
+) -> Result<(), anyhow::Error> {
+	let extrinsic: Bytes = client
+		.rpc()
+		.request(
+			"simnode_authorExtrinsic",
+			rpc_params![Bytes::from(call), signer.to_account_id().to_ss58check()],
+		)
+		.await?;
+	let submittable = SubmittableExtrinsic::from_bytes(client.clone(), extrinsic.0);
+	let progress = submittable.submit_and_watch().await?;
+	let block = client
+		.rpc()
+		.request::<CreatedBlock<H256>>("engine_createBlock", rpc_params![true, false])
+		.await?;
+
+	let finalized = client
+		.rpc()
+		.request::<bool>("engine_finalizeBlock", rpc_params![block.hash])
+		.await?;
+	assert!(finalized);
+	progress.wait_for_finalized_success().await?;
+
+	Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_fishermen_can_veto_state_commitments() -> Result<(), anyhow::Error> {
+	let port = env::var("PORT").unwrap_or("9990".into());
+	let client = subxt_utils::client::ws_client::<Hyperbridge>(
+		&format!("ws://127.0.0.1:{}", port),
+		u32::MAX,
+	)
+	.await?;
+
+	// the fishermen pallet isn't in the static metadata, so its calls are resolved at runtime
+	client
+		.metadata()
+		.pallet_by_name("Fishermen")
+		.ok_or_else(|| anyhow!("Fishermen pallet isn't in the runtime"))?;
+
+	// 1. store a state commitment which is still in its challenge period
+	let state_commitment = StateCommitment {
+		timestamp: 0,
+		overlay_root: Some(H256::random()),
+		state_root: H256::random(),
+	};
+	let height = StateMachineHeight {
+		id: StateMachineId {
+			state_id: StateMachine::Polkadot(3000).into(),
+			consensus_state_id: *b"PARA",
+		},
+		height: 200,
+	};
+	let commitment_address = api::storage().ismp().state_commitments(&height);
+	let update_time_address = api::storage().ismp().state_machine_update_time(&height);
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards");
+	let call = RuntimeCall::System(runtime_types::frame_system::pallet::Call::set_storage {
+		items: vec![
+			(client.storage().address_bytes(&commitment_address)?, state_commitment.encode()),
+			(client.storage().address_bytes(&update_time_address)?, now.as_secs().encode()),
+		],
+	});
+	let call = client.tx().call_data(&api::tx().sudo().sudo(call))?;
+	author_and_finalize(&client, call, Keyring::Alice).await?;
+
+	let item = client.storage().at_latest().await?.fetch(&commitment_address).await?;
+	assert_eq!(item, Some(state_commitment));
+
+	// 2. bond bob as a fisherman, this is the minimum bond in gargantua
+	let bond = 100 * 1_000_000_000_000u128;
+	let call = client.tx().call_data(&Extrinsic::new("Fishermen", "bond", bond.encode()))?;
+	author_and_finalize(&client, call, Keyring::Bob).await?;
+
+	// 3. veto the state commitment the same way tesseract does
+	let call = client.tx().call_data(&Extrinsic::new(
+		"Fishermen",
+		"veto_state_commitment",
+		height.encode(),
+	))?;
+	author_and_finalize(&client, call, Keyring::Bob).await?;
+
+	// the vetoed commitment is deleted
+	let item = client.storage().at_latest().await?.fetch(&commitment_address).await?;
+	assert_eq!(item, None);
+
+	let events = client.events().at_latest().await?;
+	let vetoed = events.iter().filter_map(|event| event.ok()).any(|event| {
+		event.pallet_name() == "Fishermen" && event.variant_name() == "StateCommitmentVetoed"
+	});
+	assert!(vetoed);
+
+	// 4. a second veto of the same height fails
+	let call = client.tx().call_data(&Extrinsic::new(
+		"Fishermen",
+		"veto_state_commitment",
+		height.encode(),
+	))?;
+	assert!(author_and_finalize(&client, call, Keyring::Bob).await.is_err());
+
+	Ok(())
+}
@@ -1,5 +1,6 @@
 #![cfg(test)]
 
+use crate::parachain_data;
 use std::{
 	collections::HashSet,
 	env,
@@ -34,7 +35,6 @@ use subxt_utils::{
 					consensus::{StateCommitment, StateMachineHeight, StateMachineId},
 					messaging::{Message, Proof, RequestMessage},
 				},
-				ismp_parachain::ParachainData,
 			},
 		},
 	},
@@ -71,10 +71,7 @@ async fn test_txpool_should_reject_duplicate_requests() -> Result<(), anyhow::Er
 		let calls = vec![
             RuntimeCall::IsmpParachain(
 				runtime_types::ismp_parachain::pallet::Call::add_parachain {
-					para_ids: vec![ParachainData{
-						id: para_id,
-						slot_duration,
-					}],
+					para_ids: vec![parachain_data(para_id, slot_duration)],
 				},
             ),
             // init the host executive
@@ -402,14 +402,26 @@ async fn dispatch_requests() -> Result<(), anyhow::Error> {
 	let at = client.rpc().header(Some(finalized_hash)).await?.unwrap().number;
 
 	// Fetch mmr proof from finalized branch
-	let keys = ProofKeys::Requests(
-		chain_a_commitments.into_iter().map(|(.., commitment)| commitment).collect(),
-	);
+	let commitments = chain_a_commitments
+		.into_iter()
+		.map(|(.., commitment)| commitment)
+		.collect::<Vec<_>>();
+	let keys = ProofKeys::Requests(commitments.clone());
 	let params = rpc_params![at, keys];
 	let response: pallet_ismp_rpc::Proof =
 		client.rpc().request("ismp_queryMmrProof", params).await?;
 	let proof: pallet_ismp::mmr::Proof<H256> = Decode::decode(&mut &*response.proof)?;
 
+	// No leaves were added since, so the proof from the commitments alone at the latest finalized
+	// height should be identical
+	let response: pallet_ismp_rpc::Proof = client
+		.rpc()
+		.request("ismp_queryMmrProofByCommitments", rpc_params![commitments])
+		.await?;
+	let proof_by_commitments: pallet_ismp::mmr::Proof<H256> =
+		Decode::decode(&mut &*response.proof)?;
+	assert_eq!(proof_by_commitments, proof);
+
 	let merkle_proof = MerkleProof::<DataOrHash<Keccak256, Leaf>, MmrHasher<Keccak256, Leaf>>::new(
 		mmr.mmr_size(),
 		proof.items.into_iter().map(DataOrHash::Hash).collect(),
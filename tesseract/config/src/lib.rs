@@ -16,7 +16,7 @@
 use std::sync::Arc;
 use substrate_state_machine::HashAlgorithm;
 use tesseract_evm::{EvmClient, EvmConfig};
-use tesseract_primitives::IsmpProvider;
+use tesseract_primitives::{ByzantineHandler, IsmpProvider};
 use tesseract_substrate::{
 	config::{Blake2SubstrateChain, KeccakSubstrateChain},
 	SubstrateClient, SubstrateConfig,
@@ -73,4 +73,47 @@ impl AnyConfig {
 
 		Ok(client)
 	}
+
+	/// Convert the [`AnyConfig`] into an implementation of an [`IsmpProvider`], alongside its
+	/// [`ByzantineHandler`] for policing the state commitments which are held for it on its
+	/// counterparties.
+	pub async fn into_byzantine_handler(
+		self,
+		hyperbridge: Arc<dyn IsmpProvider>,
+	) -> Result<(Arc<dyn IsmpProvider>, Arc<dyn ByzantineHandler>), anyhow::Error> {
+		let client = match self {
+			AnyConfig::Substrate(config) => {
+				match config.hashing.clone().unwrap_or(HashAlgorithm::Keccak) {
+					HashAlgorithm::Keccak => {
+						let mut client =
+							SubstrateClient::<KeccakSubstrateChain>::new(config).await?;
+						client.set_latest_finalized_height(hyperbridge).await?;
+						(
+							Arc::new(client.clone()) as Arc<dyn IsmpProvider>,
+							Arc::new(client) as Arc<dyn ByzantineHandler>,
+						)
+					},
+					HashAlgorithm::Blake2 => {
+						let mut client =
+							SubstrateClient::<Blake2SubstrateChain>::new(config).await?;
+						client.set_latest_finalized_height(hyperbridge).await?;
+						(
+							Arc::new(client.clone()) as Arc<dyn IsmpProvider>,
+							Arc::new(client) as Arc<dyn ByzantineHandler>,
+						)
+					},
+				}
+			},
+			AnyConfig::Evm(config) => {
+				let mut client = EvmClient::new(config).await?;
+				client.set_latest_finalized_height(hyperbridge).await?;
+				(
+					Arc::new(client.clone()) as Arc<dyn IsmpProvider>,
+					Arc::new(client) as Arc<dyn ByzantineHandler>,
+				)
+			},
+		};
+
+		Ok(client)
+	}
 }
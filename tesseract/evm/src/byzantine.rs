@@ -0,0 +1,59 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`ByzantineHandler`] implementation
+
+use crate::EvmClient;
+use ethers::providers::Middleware;
+use ismp::{consensus::StateMachineHeight, events::StateMachineUpdated, host::StateMachine};
+use std::sync::Arc;
+use tesseract_primitives::{BlockNotAvailable, ByzantineHandler, IsmpProvider};
+
+#[async_trait::async_trait]
+impl ByzantineHandler for EvmClient {
+	async fn check_for_byzantine_attack(
+		&self,
+		_coprocessor: StateMachine,
+		counterparty: Arc<dyn IsmpProvider>,
+		challenge_event: StateMachineUpdated,
+	) -> Result<Option<StateMachineHeight>, anyhow::Error> {
+		let height = StateMachineHeight {
+			id: challenge_event.state_machine_id,
+			height: challenge_event.latest_height,
+		};
+		let commitment = counterparty.query_state_machine_commitment(height).await?;
+
+		// Hyperbridge only commits to finalized blocks, so the rpc node must be lagging behind
+		let block = self.client.get_block(height.height).await?.ok_or_else(|| {
+			BlockNotAvailable { state_machine: self.state_machine, height: height.height }
+		})?;
+		let state_root = block.state_root.0;
+
+		if commitment.state_root.0 != state_root {
+			log::info!(
+				"Vetoing state commitment for {:?} at {} on {}, expected state root {:?} found {:?}",
+				self.state_machine,
+				height.height,
+				counterparty.name(),
+				sp_core::H256(state_root),
+				commitment.state_root
+			);
+			counterparty.veto_state_commitment(height).await?;
+			return Ok(Some(height));
+		}
+
+		Ok(None)
+	}
+}
@@ -22,6 +22,7 @@ use std::{sync::Arc, time::Duration};
 use tesseract_primitives::IsmpProvider;
 
 pub mod abi;
+mod byzantine;
 mod gas_oracle;
 pub mod provider;
 
@@ -684,21 +684,37 @@ impl IsmpProvider for EvmClient {
 	}
 
 	async fn veto_state_commitment(&self, height: StateMachineHeight) -> Result<(), Error> {
-		let contract = EvmHost::new(self.config.ismp_host, self.client.clone());
-		if let Some(_) = contract
-			.veto_state_commitment(ismp_solidity_abi::beefy::StateMachineHeight {
-				state_machine_id: match height.id.state_id {
-					StateMachine::Kusama(id) | StateMachine::Polkadot(id) => id.into(),
-					_ => Err(anyhow!("Unexpected State machine"))?,
-				},
-				height: height.height.into(),
-			})
-			.send()
-			.await?
-			.await?
-		{
-			log::info!("Frozen consensus client on {:?}", self.state_machine);
+		let contract = EvmHost::new(self.config.ismp_host, self.signer.clone());
+		let call = contract.veto_state_commitment(height.try_into()?);
+
+		// dry run the veto first, so we don't pay for vetoes which would be rejected
+		call.call().await.map_err(|err| {
+			anyhow!(
+				"Veto for {:?} at {} on {:?} failed dry run: {err:?}",
+				height.id.state_id,
+				height.height,
+				self.state_machine
+			)
+		})?;
+
+		let gas = call.estimate_gas().await?;
+		let receipt = call.gas(gas).send().await?.await?.ok_or_else(|| {
+			anyhow!("No receipt for veto transaction on {:?}", self.state_machine)
+		})?;
+		if !matches!(receipt.status.map(|status| status.low_u64()), Some(1)) {
+			Err(anyhow!(
+				"Veto transaction {:?} on {:?} reverted",
+				receipt.transaction_hash,
+				self.state_machine
+			))?
 		}
+
+		log::info!(
+			"Vetoed state commitment for {:?} at {} on {:?}",
+			height.id.state_id,
+			height.height,
+			self.state_machine
+		);
 		Ok(())
 	}
 
@@ -14,7 +14,7 @@ use ismp::{
 use ismp_solidity_abi::evm_host::EvmHost;
 use ismp_testsuite::mocks::{Host, Keccak256Hasher};
 use primitive_types::H160;
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 use tesseract_primitives::{IsmpProvider, Query};
 
 // source :
@@ -160,3 +160,31 @@ async fn fetch_state_commitment() -> anyhow::Result<()> {
 	assert_eq!(state_commitment.timestamp, 3443504784000);
 	Ok(())
 }
+
+#[test]
+fn encodes_state_commitment_vetoes() {
+	use ethers::{abi::AbiDecode, providers::Provider};
+	use ismp_solidity_abi::evm_host::VetoStateCommitmentCall;
+
+	// the client is never connected to, we only inspect the calldata of the veto
+	let provider = Arc::new(Provider::try_from("http://127.0.0.1:8545").unwrap());
+	let contract = EvmHost::new(NEW_HOST, provider);
+
+	let height = StateMachineHeight {
+		id: StateMachineId { state_id: StateMachine::Kusama(4009), consensus_state_id: *b"PARA" },
+		height: 899092,
+	};
+	let call = contract.veto_state_commitment(height.try_into().unwrap());
+
+	let calldata = call.calldata().expect("veto has calldata");
+	let decoded = VetoStateCommitmentCall::decode(&calldata).unwrap();
+	assert_eq!(decoded.height.state_machine_id, 4009.into());
+	assert_eq!(decoded.height.height, 899092.into());
+
+	// only parachain commitments can be vetoed on the evm host
+	let height = StateMachineHeight {
+		id: StateMachineId { state_id: StateMachine::Evm(1), consensus_state_id: *b"ETH0" },
+		height: 899092,
+	};
+	assert!(ismp_solidity_abi::evm_host::StateMachineHeight::try_from(height).is_err());
+}
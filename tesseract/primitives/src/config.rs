@@ -36,3 +36,13 @@ pub struct RelayerConfig {
 	/// Flag to tell the messsaging process to deliver failed transactions
 	pub deliver_failed: Option<bool>,
 }
+
+/// Configuration options for the fisherman.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FishermanConfig {
+	/// State machines whose commitments should be policed, defaults to all configured chains
+	pub state_machines: Option<Vec<String>>,
+	/// Port on which to expose prometheus metrics, metrics are not exposed if this is not
+	/// supplied
+	pub prometheus_port: Option<u16>,
+}
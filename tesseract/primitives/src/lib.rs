@@ -334,15 +334,37 @@ pub trait IsmpProvider: Send + Sync {
 /// Provides an interface for handling byzantine behaviour. Implementations of this should watch for
 /// eclipse attacks, as well as invalid state transitions.
 #[async_trait::async_trait]
-pub trait ByzantineHandler {
+pub trait ByzantineHandler: Send + Sync {
 	/// Check the state machine update event for byzantine behaviour and challenge it.
+	/// Should return the vetoed height if the state commitment on the counterparty was found to be
+	/// misrepresentative. The `coprocessor`'s state commitments hold the root of its ismp child
+	/// trie rather than its state root.
 	async fn check_for_byzantine_attack(
 		&self,
+		coprocessor: StateMachine,
 		counterparty: Arc<dyn IsmpProvider>,
 		challenge_event: StateMachineUpdated,
-	) -> Result<(), anyhow::Error>;
+	) -> Result<Option<StateMachineHeight>, anyhow::Error>;
+}
+
+/// Returned by a [`ByzantineHandler`] when the finalized block for a state machine update is not
+/// yet available on its rpc node, the check should be retried later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockNotAvailable {
+	/// The state machine whose node is lagging behind
+	pub state_machine: StateMachine,
+	/// The height of the block that is not yet available
+	pub height: u64,
+}
+
+impl Display for BlockNotAvailable {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "Block {} is not yet available on {:?}", self.height, self.state_machine)
+	}
 }
 
+impl std::error::Error for BlockNotAvailable {}
+
 /// Provides an interface for the chain to the relayer core for submitting Ismp messages as well as
 #[async_trait::async_trait]
 pub trait IsmpHost: ByzantineHandler + Send + Sync {
@@ -21,6 +21,7 @@ use std::{
 pub struct MockHost<C> {
 	pub consensus_state: Arc<Mutex<C>>,
 	pub latest_height: Arc<Mutex<u64>>,
+	pub timestamp: Arc<Mutex<Duration>>,
 	pub state_machine: StateMachine,
 }
 
@@ -29,6 +30,7 @@ impl<C> MockHost<C> {
 		Self {
 			consensus_state: Arc::new(Mutex::new(consensus_state)),
 			latest_height: Arc::new(Mutex::new(latest_height)),
+			timestamp: Arc::new(Mutex::new(Duration::from_secs(0))),
 			state_machine,
 		}
 	}
@@ -57,9 +59,10 @@ impl<T: Codec + Send + Sync> HyperbridgeClaim for MockHost<T> {
 impl<C: Codec + Send + Sync> ByzantineHandler for MockHost<C> {
 	async fn check_for_byzantine_attack(
 		&self,
+		_coprocessor: StateMachine,
 		_counterparty: Arc<dyn IsmpProvider>,
 		_challenge_event: StateMachineUpdated,
-	) -> Result<(), Error> {
+	) -> Result<Option<StateMachineHeight>, Error> {
 		Err(anyhow!("No byzantine faults"))
 	}
 }
@@ -116,7 +119,7 @@ impl<C: Codec + Send + Sync> IsmpProvider for MockHost<C> {
 	}
 
 	async fn query_timestamp(&self) -> Result<Duration, Error> {
-		Ok(Duration::from_secs(0))
+		Ok(*self.timestamp.lock().unwrap())
 	}
 
 	async fn query_requests_proof(
@@ -258,6 +261,7 @@ impl<C: Send + Sync> Clone for MockHost<C> {
 		Self {
 			consensus_state: self.consensus_state.clone(),
 			latest_height: self.latest_height.clone(),
+			timestamp: self.timestamp.clone(),
 			state_machine: self.state_machine.clone(),
 		}
 	}
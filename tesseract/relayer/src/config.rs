@@ -20,7 +20,7 @@ use ismp::host::StateMachine;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tesseract_config::AnyConfig;
-use tesseract_primitives::config::RelayerConfig;
+use tesseract_primitives::config::{FishermanConfig, RelayerConfig};
 use tesseract_substrate::SubstrateConfig;
 use toml::Table;
 
@@ -33,19 +33,32 @@ pub struct HyperbridgeConfig {
 	pub chains: HashMap<StateMachine, AnyConfig>,
 	/// Relayer config
 	pub relayer: RelayerConfig,
+	/// Fisherman config
+	pub fisherman: Option<FishermanConfig>,
 }
 const HYPERRIDGE: &'static str = "hyperbridge";
 const RELAYER: &'static str = "relayer";
+const FISHERMAN: &'static str = "fisherman";
 
 impl HyperbridgeConfig {
+	/// Parse the config for relaying messages, the relayer config must be present.
 	pub async fn parse_conf(config: &str) -> Result<Self, anyhow::Error> {
-		let toml = tokio::fs::read_to_string(config)
-			.await
-			.map_err(|err| anyhow!("Error occured while reading config file: {err:?}"))?;
+		Self::parse(&read_conf(config).await?, true)
+	}
+
+	/// Parse the config for the fisherman, which doesn't require the relayer config.
+	pub async fn parse_fisherman_conf(config: &str) -> Result<Self, anyhow::Error> {
+		Self::parse(&read_conf(config).await?, false)
+	}
+
+	fn parse(toml: &str, relayer_required: bool) -> Result<Self, anyhow::Error> {
 		let table = toml.parse::<Table>()?;
 		let mut chains: HashMap<StateMachine, AnyConfig> = HashMap::new();
-		if !table.contains_key(HYPERRIDGE) || !table.contains_key(RELAYER) {
-			Err(anyhow!("Missing Hyperbridge or Relayer Config, Check your toml file"))?
+		if !table.contains_key(HYPERRIDGE) {
+			Err(anyhow!("Missing Hyperbridge Config, Check your toml file"))?
+		}
+		if relayer_required && !table.contains_key(RELAYER) {
+			Err(anyhow!("Missing Relayer Config, Check your toml file"))?
 		}
 
 		let hyperbridge: SubstrateConfig = table
@@ -57,21 +70,45 @@ impl HyperbridgeConfig {
 		let relayer: RelayerConfig = table
 			.get(RELAYER)
 			.cloned()
-			.expect("Relayer Config is Present")
-			.try_into()
-			.expect("Failed to parse relayer config");
+			.map(|relayer| relayer.try_into().expect("Failed to parse relayer config"))
+			.unwrap_or_default();
+		let fisherman: Option<FishermanConfig> = table
+			.get(FISHERMAN)
+			.cloned()
+			.map(|fisherman| fisherman.try_into().expect("Failed to parse fisherman config"));
 		for (key, val) in table {
-			if &key != HYPERRIDGE && key != RELAYER {
+			if &key != HYPERRIDGE && key != RELAYER && key != FISHERMAN {
 				let any_conf: AnyConfig = val.try_into().unwrap();
 				chains.insert(any_conf.state_machine(), any_conf);
 			}
 		}
-		Ok(Self { hyperbridge, chains, relayer })
+		Ok(Self { hyperbridge, chains, relayer, fisherman })
 	}
 }
 
+async fn read_conf(config: &str) -> Result<String, anyhow::Error> {
+	tokio::fs::read_to_string(config)
+		.await
+		.map_err(|err| anyhow!("Error occured while reading config file: {err:?}"))
+}
+
 #[tokio::test]
 async fn test_parsing() {
 	let config = HyperbridgeConfig::parse_conf("../test-config.toml").await.unwrap();
 	dbg!(config);
 }
+
+#[test]
+fn relayer_config_is_only_optional_for_the_fisherman() {
+	let toml = r#"
+[hyperbridge]
+state_machine = "KUSAMA-4009"
+rpc_ws = "ws://127.0.0.1:9944"
+
+[fisherman]
+prometheus_port = 9090
+"#;
+	assert!(HyperbridgeConfig::parse(toml, true).is_err());
+	let config = HyperbridgeConfig::parse(toml, false).unwrap();
+	assert_eq!(config.fisherman.unwrap().prometheus_port, Some(9090));
+}
@@ -1,4 +1,4 @@
-use crate::{config::HyperbridgeConfig, create_client_map, logging};
+use crate::{config::HyperbridgeConfig, create_client_map, fisherman::Fisherman, logging};
 use anyhow::anyhow;
 use ethers::providers::interval;
 use futures::StreamExt;
@@ -23,6 +23,8 @@ use transaction_fees::TransactionPayment;
 pub enum Subcommand {
 	/// Withdraw fees on hyperbridge
 	AccumulateFees(AccumulateFees),
+	/// Police state commitments on hyperbridge and its counterparties
+	Fisherman(Fisherman),
 }
 
 #[derive(Debug, clap::Parser)]
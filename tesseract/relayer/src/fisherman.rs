@@ -0,0 +1,390 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tesseract fisherman, polices the state commitments which are written to Hyperbridge and the
+//! hosts that Hyperbridge writes to.
+
+use crate::{config::HyperbridgeConfig, logging};
+use anyhow::Context;
+use futures::{FutureExt, StreamExt};
+use ismp::{consensus::StateMachineHeight, host::StateMachine};
+use sc_service::TaskManager;
+use std::{collections::VecDeque, net::SocketAddr, sync::Arc, time::Duration};
+use substrate_prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+use tesseract_primitives::{
+	BlockNotAvailable, ByzantineHandler, IsmpProvider, StateMachineUpdated,
+};
+use tesseract_substrate::{config::KeccakSubstrateChain, SubstrateClient};
+use tokio::time::MissedTickBehavior;
+
+/// Run the fisherman, independently of message relaying
+#[derive(Debug, clap::Parser)]
+pub struct Fisherman {}
+
+/// Prometheus metrics for the fisherman
+#[derive(Clone)]
+pub struct Metrics {
+	/// State machine updates which have been checked
+	checked: CounterVec<U64>,
+	/// State commitments which have been vetoed
+	vetoes: CounterVec<U64>,
+	/// Errors encountered while checking state machine updates
+	errors: CounterVec<U64>,
+}
+
+impl Metrics {
+	/// Register the fisherman metrics with the prometheus registry
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let labels = &["state_machine", "host"];
+		Ok(Self {
+			checked: register(
+				CounterVec::new(
+					Opts::new("state_machine_updates_checked", "State machine updates checked"),
+					labels,
+				)?,
+				registry,
+			)?,
+			vetoes: register(
+				CounterVec::new(
+					Opts::new("state_commitments_vetoed", "State commitments vetoed"),
+					labels,
+				)?,
+				registry,
+			)?,
+			errors: register(
+				CounterVec::new(
+					Opts::new(
+						"check_errors",
+						"Errors encountered while checking state machine updates",
+					),
+					labels,
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+impl Fisherman {
+	/// Watch for state machine updates on hyperbridge and the configured chains, vetoing any
+	/// misrepresentative state commitments.
+	pub async fn fish(&self, config_path: String) -> anyhow::Result<()> {
+		logging::setup()?;
+		log::info!("🎣 Initializing tesseract fisherman");
+		let config = HyperbridgeConfig::parse_fisherman_conf(&config_path).await?;
+		let HyperbridgeConfig { hyperbridge: hyperbridge_config, chains, fisherman, .. } = config;
+		let fisherman = fisherman.unwrap_or_default();
+
+		let tokio_handle = tokio::runtime::Handle::current();
+		let task_manager = TaskManager::new(tokio_handle, None)?;
+
+		let registry = Registry::new_custom(Some("tesseract_fisherman".into()), None)?;
+		let metrics = Metrics::register(&registry)?;
+		if let Some(port) = fisherman.prometheus_port {
+			task_manager.spawn_essential_handle().spawn(
+				"prometheus",
+				"fisherman",
+				async move {
+					let address = SocketAddr::from(([0, 0, 0, 0], port));
+					if let Err(err) =
+						substrate_prometheus_endpoint::init_prometheus(address, registry).await
+					{
+						log::error!("Prometheus endpoint exited with {err:?}");
+					}
+				}
+				.boxed(),
+			);
+		}
+
+		let coprocessor = hyperbridge_config.state_machine;
+		let hyperbridge = SubstrateClient::<KeccakSubstrateChain>::new(hyperbridge_config).await?;
+		let hyperbridge_provider = Arc::new(hyperbridge.clone()) as Arc<dyn IsmpProvider>;
+		let hyperbridge_handler = Arc::new(hyperbridge) as Arc<dyn ByzantineHandler>;
+
+		for (state_machine, config) in chains {
+			if let Some(ref state_machines) = fisherman.state_machines {
+				if !state_machines.contains(&state_machine.to_string()) {
+					continue;
+				}
+			}
+
+			let (client, handler) = config
+				.into_byzantine_handler(hyperbridge_provider.clone())
+				.await
+				.context(format!("Failed to create client for {state_machine:?}"))?;
+
+			// Commitments for this chain on hyperbridge
+			task_manager.spawn_essential_handle().spawn(
+				"fisherman",
+				"fisherman",
+				police(
+					coprocessor,
+					client.clone(),
+					handler,
+					hyperbridge_provider.clone(),
+					metrics.clone(),
+				)
+				.boxed(),
+			);
+
+			// Commitments for hyperbridge on this chain
+			task_manager.spawn_essential_handle().spawn(
+				"fisherman",
+				"fisherman",
+				police(
+					coprocessor,
+					hyperbridge_provider.clone(),
+					hyperbridge_handler.clone(),
+					client,
+					metrics.clone(),
+				)
+				.boxed(),
+			);
+		}
+
+		log::info!("🎣 Initialized fisherman tasks");
+
+		task_manager.future().await?;
+
+		Ok(())
+	}
+}
+
+/// How often updates whose blocks were not yet available on the chain's rpc node are checked again
+const RETRY_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Check every state machine update for `chain` on `host`, vetoing it if the state commitment does
+/// not match what is finalized on `chain`. Updates for blocks that the chain's rpc node hasn't
+/// seen yet are retried until the block is available or their challenge period elapses.
+async fn police(
+	coprocessor: StateMachine,
+	chain: Arc<dyn IsmpProvider>,
+	handler: Arc<dyn ByzantineHandler>,
+	host: Arc<dyn IsmpProvider>,
+	metrics: Metrics,
+) {
+	let (chain_name, host_name) = (chain.name(), host.name());
+	let mut stream = match host.state_machine_update_notification(chain.state_machine_id()).await {
+		Ok(stream) => stream,
+		Err(err) => {
+			log::error!(
+				"Failed to create state machine update stream for {}->{}: {err:?}",
+				chain_name,
+				host_name
+			);
+			return;
+		},
+	};
+
+	let mut pending = VecDeque::new();
+	let mut retry = tokio::time::interval(RETRY_INTERVAL);
+	retry.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		let events = tokio::select! {
+			res = stream.next() => match res {
+				Some(Ok(event)) => vec![event],
+				Some(Err(err)) => {
+					log::error!("State machine update stream returned an error {err:?}");
+					continue;
+				},
+				None => break,
+			},
+			_ = retry.tick(), if !pending.is_empty() => pending.drain(..).collect(),
+		};
+
+		for event in events {
+			if let Some(event) =
+				check_update(coprocessor, &chain_name, &handler, &host, event, &metrics).await
+			{
+				pending.push_back(event);
+			}
+		}
+	}
+
+	log::error!("State machine update stream for {}->{} returned None", chain_name, host_name);
+}
+
+/// Check a single state machine update for `chain_name` on `host`, returns the update if it
+/// should be checked again later.
+async fn check_update(
+	coprocessor: StateMachine,
+	chain_name: &str,
+	handler: &Arc<dyn ByzantineHandler>,
+	host: &Arc<dyn IsmpProvider>,
+	event: StateMachineUpdated,
+	metrics: &Metrics,
+) -> Option<StateMachineUpdated> {
+	let host_name = host.name();
+	let labels = [chain_name, host_name.as_str()];
+	let result = handler
+		.check_for_byzantine_attack(coprocessor, host.clone(), event.clone())
+		.await;
+	if result.is_ok() {
+		metrics.checked.with_label_values(&labels).inc();
+	}
+
+	match result {
+		Ok(Some(height)) => {
+			log::info!(
+				"Vetoed state commitment for {} at {} on {}",
+				chain_name,
+				height.height,
+				host_name
+			);
+			metrics.vetoes.with_label_values(&labels).inc();
+		},
+		Ok(None) => {},
+		Err(err) if err.is::<BlockNotAvailable>() =>
+			match challenge_period_elapsed(host, &event).await {
+				Ok(false) => {
+					log::trace!("{err}, retrying the check for {host_name} later");
+					return Some(event);
+				},
+				Ok(true) => {
+					log::error!(
+						"Challenge period for {} at {} on {} elapsed before it could be checked: {err}",
+						chain_name,
+						event.latest_height,
+						host_name
+					);
+					metrics.errors.with_label_values(&labels).inc();
+				},
+				Err(err) => {
+					log::error!(
+						"Failed to query the challenge period for {} at {} on {}, retrying later: {err:?}",
+						chain_name,
+						event.latest_height,
+						host_name
+					);
+					return Some(event);
+				},
+			},
+		Err(err) => {
+			log::error!(
+				"Failed to check state machine update for {} on {}: {err:?}",
+				chain_name,
+				host_name
+			);
+			metrics.errors.with_label_values(&labels).inc();
+		},
+	}
+
+	None
+}
+
+/// Returns true if the state commitment from this update can no longer be vetoed on `host`
+async fn challenge_period_elapsed(
+	host: &Arc<dyn IsmpProvider>,
+	event: &StateMachineUpdated,
+) -> anyhow::Result<bool> {
+	let height = StateMachineHeight { id: event.state_machine_id, height: event.latest_height };
+	let challenge_period =
+		host.query_challenge_period(event.state_machine_id.consensus_state_id).await?;
+	let update_time = host.query_state_machine_update_time(height).await?;
+	let now = host.query_timestamp().await?;
+	Ok(now.saturating_sub(update_time) > challenge_period)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ismp::consensus::StateMachineId;
+	use tesseract_primitives::mocks::MockHost;
+
+	/// Returns the configured result for every state machine update
+	struct MockHandler(fn(StateMachineHeight) -> anyhow::Result<Option<StateMachineHeight>>);
+
+	#[async_trait::async_trait]
+	impl ByzantineHandler for MockHandler {
+		async fn check_for_byzantine_attack(
+			&self,
+			_coprocessor: StateMachine,
+			_counterparty: Arc<dyn IsmpProvider>,
+			challenge_event: StateMachineUpdated,
+		) -> anyhow::Result<Option<StateMachineHeight>> {
+			(self.0)(StateMachineHeight {
+				id: challenge_event.state_machine_id,
+				height: challenge_event.latest_height,
+			})
+		}
+	}
+
+	/// Checks an update on a host whose challenge period is zero and whose clock is `now` seconds
+	/// past the update, returning the update if it should be retried and the metric counts.
+	async fn check(handler: MockHandler, now: u64) -> (Option<StateMachineUpdated>, [u64; 3]) {
+		let metrics = Metrics::register(&Registry::new()).unwrap();
+		let host = MockHost::new((), 0, StateMachine::Polkadot(3367));
+		*host.timestamp.lock().unwrap() = Duration::from_secs(now);
+		let host = Arc::new(host) as Arc<dyn IsmpProvider>;
+		let event = StateMachineUpdated {
+			state_machine_id: StateMachineId {
+				state_id: StateMachine::Evm(1),
+				consensus_state_id: *b"ETH0",
+			},
+			latest_height: 100,
+		};
+		let handler = Arc::new(handler) as Arc<dyn ByzantineHandler>;
+		let retry =
+			check_update(StateMachine::Polkadot(3367), "EVM-1", &handler, &host, event, &metrics)
+				.await;
+
+		let labels = ["EVM-1", "Mock"];
+		let counts = [
+			metrics.checked.with_label_values(&labels).get(),
+			metrics.vetoes.with_label_values(&labels).get(),
+			metrics.errors.with_label_values(&labels).get(),
+		];
+		(retry, counts)
+	}
+
+	#[tokio::test]
+	async fn counts_checked_updates() {
+		let (retry, counts) = check(MockHandler(|_| Ok(None)), 0).await;
+		assert!(retry.is_none());
+		assert_eq!(counts, [1, 0, 0]);
+	}
+
+	#[tokio::test]
+	async fn counts_vetoed_updates() {
+		let (retry, counts) = check(MockHandler(|height| Ok(Some(height))), 0).await;
+		assert!(retry.is_none());
+		assert_eq!(counts, [1, 1, 0]);
+	}
+
+	#[tokio::test]
+	async fn does_not_count_failed_checks() {
+		let (retry, counts) = check(MockHandler(|_| Err(anyhow::anyhow!("rpc error"))), 0).await;
+		assert!(retry.is_none());
+		assert_eq!(counts, [0, 0, 1]);
+	}
+
+	#[tokio::test]
+	async fn retries_updates_whose_blocks_are_not_available() {
+		let not_available =
+			|height: StateMachineHeight| -> anyhow::Result<Option<StateMachineHeight>> {
+				Err(BlockNotAvailable { state_machine: height.id.state_id, height: height.height }
+					.into())
+			};
+		let (retry, counts) = check(MockHandler(not_available), 0).await;
+		assert_eq!(retry.map(|event| event.latest_height), Some(100));
+		assert_eq!(counts, [0, 0, 0]);
+
+		// the update can no longer be vetoed once the challenge period elapses
+		let (retry, counts) = check(MockHandler(not_available), 1).await;
+		assert!(retry.is_none());
+		assert_eq!(counts, [0, 0, 1]);
+	}
+}
@@ -3,5 +3,6 @@ mod config;
 pub mod logging;
 
 pub mod fees;
+pub mod fisherman;
 
 pub use cli::*;
@@ -23,6 +23,7 @@ async fn main() -> Result<(), anyhow::Error> {
 		match command {
 			Subcommand::AccumulateFees(cmd) =>
 				cmd.accumulate_fees(cli.config.clone(), cli.db.clone()).await?,
+			Subcommand::Fisherman(cmd) => cmd.fish(cli.config.clone()).await?,
 		}
 		return Ok(());
 	}
@@ -0,0 +1,192 @@
+// Copyright (C) Polytope Labs Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`ByzantineHandler`] implementation
+
+use crate::SubstrateClient;
+use anyhow::anyhow;
+use codec::Decode;
+use ismp::{
+	consensus::{StateCommitment, StateMachineHeight},
+	events::StateMachineUpdated,
+	host::StateMachine,
+};
+use pallet_ismp::{ConsensusDigest, ISMP_ID};
+use std::sync::Arc;
+use subxt::{
+	config::{
+		extrinsic_params::BaseExtrinsicParamsBuilder,
+		polkadot::PlainTip,
+		substrate::{DigestItem, SubstrateHeader},
+		ExtrinsicParams, Hasher,
+	},
+	ext::{
+		sp_core::{crypto::AccountId32, H256, U256},
+		sp_runtime::MultiSignature,
+	},
+};
+use tesseract_primitives::{BlockNotAvailable, ByzantineHandler, IsmpProvider};
+
+#[async_trait::async_trait]
+impl<C, N, H> ByzantineHandler for SubstrateClient<C>
+where
+	C: subxt::Config<Header = SubstrateHeader<N, H>> + Send + Sync + Clone,
+	N: Copy + Into<U256> + TryFrom<U256> + Send + Sync,
+	H: Hasher<Output = H256> + Send + Sync,
+	<C::ExtrinsicParams as ExtrinsicParams<C::Hash>>::OtherParams:
+		Default + Send + Sync + From<BaseExtrinsicParamsBuilder<C, PlainTip>>,
+	C::AccountId: From<AccountId32> + Into<C::Address> + Clone + Send + Sync,
+	C::Signature: From<MultiSignature> + Send + Sync,
+{
+	async fn check_for_byzantine_attack(
+		&self,
+		coprocessor: StateMachine,
+		counterparty: Arc<dyn IsmpProvider>,
+		challenge_event: StateMachineUpdated,
+	) -> Result<Option<StateMachineHeight>, anyhow::Error> {
+		let height = StateMachineHeight {
+			id: challenge_event.state_machine_id,
+			height: challenge_event.latest_height,
+		};
+		let commitment = counterparty.query_state_machine_commitment(height).await?;
+
+		// Hyperbridge only commits to finalized blocks, so the rpc node must be lagging behind
+		let hash =
+			self.client.rpc().block_hash(Some(height.height.into())).await?.ok_or_else(|| {
+				BlockNotAvailable { state_machine: self.state_machine, height: height.height }
+			})?;
+		let header = self
+			.client
+			.rpc()
+			.header(Some(hash))
+			.await?
+			.ok_or_else(|| anyhow!("Header with hash: {hash:?} not found!"))?;
+
+		let is_coprocessor = self.state_machine == coprocessor;
+		if let Some(state_root) = verify_state_commitment(&commitment, &header, is_coprocessor)? {
+			log::info!(
+				"Vetoing state commitment for {:?} at {} on {}, expected state root {:?} found {:?}",
+				self.state_machine,
+				height.height,
+				counterparty.name(),
+				state_root,
+				commitment.state_root
+			);
+			counterparty.veto_state_commitment(height).await?;
+			return Ok(Some(height));
+		}
+
+		Ok(None)
+	}
+}
+
+/// Checks the state commitment against the finalized header, returning the state root that should
+/// have been committed to if they do not match. State commitments for the coprocessor hold the
+/// root of its ismp child trie, which is found in the header's [`ConsensusDigest`].
+fn verify_state_commitment<N, H>(
+	commitment: &StateCommitment,
+	header: &SubstrateHeader<N, H>,
+	is_coprocessor: bool,
+) -> Result<Option<H256>, anyhow::Error>
+where
+	N: Copy + Into<U256> + TryFrom<U256>,
+	H: Hasher<Output = H256>,
+{
+	let state_root = if is_coprocessor {
+		let digest = header
+			.digest
+			.logs
+			.iter()
+			.find_map(|item| match item {
+				DigestItem::Consensus(id, value) if *id == ISMP_ID => Some(value),
+				_ => None,
+			})
+			.ok_or_else(|| anyhow!("Header is missing the ismp consensus digest"))?;
+		ConsensusDigest::decode(&mut &digest[..])?.child_trie_root
+	} else {
+		header.state_root
+	};
+
+	Ok((commitment.state_root != state_root).then_some(state_root))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::Blake2Hasher;
+	use codec::Encode;
+	use subxt::config::substrate::Digest;
+
+	fn finalized_header<N>(number: N) -> SubstrateHeader<N, Blake2Hasher>
+	where
+		N: Copy + Into<U256> + TryFrom<U256>,
+	{
+		let digest = ConsensusDigest {
+			mmr_root: H256::repeat_byte(3),
+			child_trie_root: H256::repeat_byte(2),
+		};
+		SubstrateHeader {
+			parent_hash: Default::default(),
+			number,
+			state_root: H256::repeat_byte(1),
+			extrinsics_root: Default::default(),
+			digest: Digest { logs: vec![DigestItem::Consensus(ISMP_ID, digest.encode())] },
+		}
+	}
+
+	fn commitment(state_root: H256) -> StateCommitment {
+		StateCommitment { timestamp: 0, overlay_root: None, state_root }
+	}
+
+	#[test]
+	fn accepts_correct_state_commitments() {
+		let header = finalized_header(100u32);
+		assert_eq!(
+			verify_state_commitment(&commitment(H256::repeat_byte(1)), &header, false).unwrap(),
+			None
+		);
+		assert_eq!(
+			verify_state_commitment(&commitment(H256::repeat_byte(2)), &header, true).unwrap(),
+			None
+		);
+
+		let header = finalized_header(u64::from(u32::MAX) + 1);
+		assert_eq!(
+			verify_state_commitment(&commitment(H256::repeat_byte(1)), &header, false).unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	fn rejects_misrepresentative_state_commitments() {
+		let header = finalized_header(100u32);
+		assert_eq!(
+			verify_state_commitment(&commitment(H256::repeat_byte(2)), &header, false).unwrap(),
+			Some(H256::repeat_byte(1))
+		);
+		// the coprocessor commits to its child trie root, not the header's state root
+		assert_eq!(
+			verify_state_commitment(&commitment(H256::repeat_byte(1)), &header, true).unwrap(),
+			Some(H256::repeat_byte(2))
+		);
+	}
+
+	#[test]
+	fn requires_the_consensus_digest_for_the_coprocessor() {
+		let mut header = finalized_header(100u32);
+		header.digest.logs.clear();
+		assert!(verify_state_commitment(&commitment(H256::repeat_byte(2)), &header, true).is_err());
+	}
+}
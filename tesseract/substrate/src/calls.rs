@@ -15,7 +15,7 @@ use pallet_ismp::{child_trie::CHILD_TRIE_PREFIX, mmr::LeafIndexAndPos};
 use pallet_ismp_host_executive::HostParam;
 use pallet_ismp_relayer::{
 	message,
-	withdrawal::{Key, WithdrawalInputData, WithdrawalParams, WithdrawalProof},
+	withdrawal::{Key, WithdrawalInputData, WithdrawalProof},
 };
 use pallet_state_coprocessor::impls::GetRequestsWithProof;
 use sp_core::{
@@ -185,11 +185,12 @@ where
 						StateMachine::Polkadot(_) |
 						StateMachine::Grandpa(_) |
 						StateMachine::Beefy(_) => {
-							if let Ok(decoded_data) = WithdrawalParams::decode(&mut &*post.body) {
-								decoded_data.beneficiary_address == counterparty.address() &&
-									condition
-							} else {
-								false
+							match pallet_hyperbridge::Message::<[u8; 32], u128>::decode(
+								&mut &*post.body,
+							) {
+								Ok(pallet_hyperbridge::Message::WithdrawRelayerFees(request)) =>
+									request.account.to_vec() == counterparty.address() && condition,
+								_ => false,
 							}
 						},
 						StateMachine::Evm(_) => {
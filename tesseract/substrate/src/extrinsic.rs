@@ -91,3 +91,20 @@ where
 	let result = ext.dry_run(None).await?;
 	Ok(result)
 }
+
+/// Dry run signed extrinsic
+pub async fn system_dry_run_signed<T: subxt::Config, Tx: TxPayload>(
+	client: &OnlineClient<T>,
+	signer: InMemorySigner<T>,
+	payload: Tx,
+) -> Result<DryRunResult, anyhow::Error>
+where
+	<T::ExtrinsicParams as ExtrinsicParams<T::Hash>>::OtherParams:
+		Default + Send + Sync + From<BaseExtrinsicParamsBuilder<T, PlainTip>>,
+	T::Signature: From<MultiSignature> + Send + Sync,
+{
+	let other_params = BaseExtrinsicParamsBuilder::new();
+	let ext = client.tx().create_signed(&payload, &signer, other_params.into()).await?;
+	let result = ext.dry_run(None).await?;
+	Ok(result)
+}
@@ -35,6 +35,7 @@ use subxt::{
 	OnlineClient,
 };
 
+mod byzantine;
 pub mod calls;
 pub mod config;
 pub mod extrinsic;
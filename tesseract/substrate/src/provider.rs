@@ -27,7 +27,8 @@ use ismp::{
 	},
 	events::Event,
 	host::StateMachine,
-	messaging::{CreateConsensusState, Message},
+	messaging::{hash_request, hash_response, CreateConsensusState, Message, ResponseMessage},
+	router::{Request, RequestResponse},
 };
 use pallet_ismp::{
 	child_trie::{
@@ -59,13 +60,16 @@ use subxt::{
 
 use subxt_utils::{host_params_storage_key, send_extrinsic, state_machine_update_time_storage_key};
 use tesseract_primitives::{
-	BoxStream, EstimateGasReturnParams, IsmpProvider, Query, StateMachineUpdated,
+	BoxStream, EstimateGasReturnParams, Hasher, IsmpProvider, Query, StateMachineUpdated,
 	StateProofQueryType, TxReceipt,
 };
 
 use crate::{
 	calls::RequestMetadata,
-	extrinsic::{send_unsigned_extrinsic, system_dry_run_unsigned, Extrinsic, InMemorySigner},
+	extrinsic::{
+		send_unsigned_extrinsic, system_dry_run_signed, system_dry_run_unsigned, Extrinsic,
+		InMemorySigner,
+	},
 	SubstrateClient,
 };
 
@@ -505,8 +509,8 @@ where
 
 	async fn submit(&self, messages: Vec<Message>) -> Result<Vec<TxReceipt>, anyhow::Error> {
 		let mut futs = vec![];
-		for msg in messages {
-			let is_consensus_message = matches!(&msg, Message::Consensus(_));
+		for msg in messages.iter() {
+			let is_consensus_message = matches!(msg, Message::Consensus(_));
 			let call = vec![msg].encode();
 			let extrinsic = Extrinsic::new("Ismp", "handle_unsigned", call);
 			// We don't compress consensus messages
@@ -534,11 +538,44 @@ where
 				futs.push(send_unsigned_extrinsic(&self.client, extrinsic, false))
 			}
 		}
-		futures::future::join_all(futs)
+		let block_hashes = futures::future::join_all(futs)
 			.await
 			.into_iter()
 			.collect::<Result<Vec<_>, _>>()?;
-		Ok(Default::default())
+
+		let mut receipts = vec![];
+		for (msg, block_hash) in messages.iter().zip(block_hashes) {
+			if !matches!(msg, Message::Request(_) | Message::Response(_)) {
+				continue;
+			}
+			// The deliveries are recorded at the block that included the extrinsic
+			let block_hash =
+				block_hash.ok_or_else(|| anyhow!("Extrinsic should have been included"))?;
+			let height: u64 = self
+				.client
+				.rpc()
+				.header(Some(block_hash))
+				.await?
+				.ok_or_else(|| anyhow!("Header for block {block_hash:?} should exist"))?
+				.number()
+				.into();
+
+			// Only messages whose receipts were written by this relayer can be claimed
+			for delivery in delivery_receipts(msg, height) {
+				let relayer = match delivery {
+					TxReceipt::Request { query, .. } =>
+						self.query_request_receipt(query.commitment).await?,
+					TxReceipt::Response { request_commitment, .. } =>
+						self.query_response_receipt(request_commitment).await?,
+				};
+
+				if relayer == self.address {
+					receipts.push(delivery);
+				}
+			}
+		}
+
+		Ok(receipts)
 	}
 
 	async fn query_challenge_period(
@@ -629,9 +666,38 @@ where
 			signer: self.signer.clone(),
 		};
 
-		let call = height.encode();
-		let call = Extrinsic::new("Fishermen", "veto_state_commitment", call);
+		// Only runtimes which include the fishermen pallet can veto state commitments
+		let metadata = self.client.metadata();
+		metadata
+			.pallet_by_name("Fishermen")
+			.and_then(|pallet| pallet.call_variant_by_name("veto_state_commitment"))
+			.ok_or_else(|| {
+				anyhow!(
+					"{:?} can't veto state commitments without the fishermen pallet",
+					self.state_machine
+				)
+			})?;
+
+		// dry run the veto first, so we don't pay for vetoes which would be rejected
+		let call = Extrinsic::new("Fishermen", "veto_state_commitment", height.encode());
+		match system_dry_run_signed(&self.client, signer.clone(), call).await? {
+			DryRunResult::Success => {},
+			result => Err(anyhow!(
+				"Veto for {:?} at {} on {:?} failed dry run: {result:?}",
+				height.id.state_id,
+				height.height,
+				self.state_machine
+			))?,
+		}
+
+		let call = Extrinsic::new("Fishermen", "veto_state_commitment", height.encode());
 		send_extrinsic(&self.client, signer, call).await?;
+		log::info!(
+			"Vetoed state commitment for {:?} at {} on {:?}",
+			height.id.state_id,
+			height.height,
+			self.state_machine
+		);
 		Ok(())
 	}
 
@@ -664,3 +730,132 @@ pub fn system_events_key() -> StorageKey {
 	storage_key.extend(sp_core::twox_128(b"Events").to_vec());
 	StorageKey(storage_key)
 }
+
+/// Receipts for the post requests and post responses delivered by a message that was included in
+/// the block at the given height
+fn delivery_receipts(msg: &Message, height: u64) -> Vec<TxReceipt> {
+	match msg {
+		Message::Request(req_msg) => req_msg
+			.requests
+			.iter()
+			.map(|post| {
+				let req = Request::Post(post.clone());
+				TxReceipt::Request {
+					query: Query {
+						source_chain: req.source_chain(),
+						dest_chain: req.dest_chain(),
+						nonce: req.nonce(),
+						commitment: hash_request::<Hasher>(&req),
+					},
+					height,
+				}
+			})
+			.collect(),
+		Message::Response(ResponseMessage {
+			datagram: RequestResponse::Response(responses),
+			..
+		}) => responses
+			.iter()
+			.map(|res| TxReceipt::Response {
+				query: Query {
+					source_chain: res.source_chain(),
+					dest_chain: res.dest_chain(),
+					nonce: res.nonce(),
+					commitment: hash_response::<Hasher>(res),
+				},
+				request_commitment: hash_request::<Hasher>(&res.request()),
+				height,
+			})
+			.collect(),
+		_ => vec![],
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ismp::{
+		messaging::{ConsensusMessage, Proof, RequestMessage},
+		router::{PostRequest, PostResponse, Response},
+	};
+
+	fn post(nonce: u64) -> PostRequest {
+		PostRequest {
+			source: StateMachine::Evm(1),
+			dest: StateMachine::Kusama(2000),
+			nonce,
+			from: vec![1; 20],
+			to: vec![2; 32],
+			timeout_timestamp: 0,
+			body: vec![],
+		}
+	}
+
+	fn proof() -> Proof {
+		Proof {
+			height: StateMachineHeight {
+				id: StateMachineId { state_id: StateMachine::Evm(1), consensus_state_id: *b"ETH0" },
+				height: 10,
+			},
+			proof: vec![],
+		}
+	}
+
+	#[test]
+	fn records_requests_at_the_inclusion_height() {
+		let msg = Message::Request(RequestMessage {
+			requests: vec![post(0), post(1)],
+			proof: proof(),
+			signer: vec![],
+		});
+
+		let receipts = delivery_receipts(&msg, 42);
+		assert_eq!(receipts.len(), 2);
+		for (nonce, receipt) in receipts.into_iter().enumerate() {
+			let TxReceipt::Request { query, height } = receipt else {
+				panic!("Expected a request receipt")
+			};
+			assert_eq!(height, 42);
+			assert_eq!(query.nonce, nonce as u64);
+			assert_eq!(
+				query.commitment,
+				hash_request::<Hasher>(&Request::Post(post(nonce as u64)))
+			);
+		}
+	}
+
+	#[test]
+	fn records_responses_at_the_inclusion_height() {
+		let response = PostResponse { post: post(0), response: vec![1], timeout_timestamp: 0 };
+		let msg = Message::Response(ResponseMessage {
+			datagram: RequestResponse::Response(vec![Response::Post(response.clone())]),
+			proof: proof(),
+			signer: vec![],
+		});
+
+		let receipts = delivery_receipts(&msg, 42);
+		let [TxReceipt::Response { query, request_commitment, height }] = receipts[..] else {
+			panic!("Expected a single response receipt")
+		};
+		assert_eq!(height, 42);
+		assert_eq!(query.commitment, hash_response::<Hasher>(&Response::Post(response)));
+		assert_eq!(request_commitment, hash_request::<Hasher>(&Request::Post(post(0))));
+	}
+
+	#[test]
+	fn ignores_messages_without_deliveries() {
+		let msg = Message::Consensus(ConsensusMessage {
+			consensus_proof: vec![],
+			consensus_state_id: *b"ETH0",
+			signer: vec![],
+		});
+		assert!(delivery_receipts(&msg, 42).is_empty());
+
+		let msg = Message::Response(ResponseMessage {
+			datagram: RequestResponse::Request(vec![Request::Post(post(0))]),
+			proof: proof(),
+			signer: vec![],
+		});
+		assert!(delivery_receipts(&msg, 42).is_empty());
+	}
+}